@@ -60,7 +60,7 @@ impl AttachmentFormatterHook<DatabaseQuery> for DatabaseQueryFormatter {
         match formatting_function {
             // for display printing we want the full verbosity level and
             // put it in an appendix
-            FormattingFunction::Display => AttachmentFormattingStyle {
+            FormattingFunction::Display | FormattingFunction::Json => AttachmentFormattingStyle {
                 placement: AttachmentFormattingPlacement::Appendix {
                     appendix_name: "Database Query",
                 },
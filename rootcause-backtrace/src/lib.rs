@@ -77,6 +77,21 @@
 //! let result = operation().attach_backtrace();
 //! ```
 //!
+//! Resolving the symbols of a backtrace is the expensive part of capturing it.
+//! Use [`attach_backtrace_lazy`](BacktraceExt::attach_backtrace_lazy) to
+//! capture only the frames, and resolve them when the report is formatted:
+//!
+//! ```
+//! use rootcause::{Report, report};
+//! use rootcause_backtrace::BacktraceExt;
+//!
+//! fn operation() -> Result<(), Report> {
+//!     Err(report!("operation failed"))
+//! }
+//!
+//! let result = operation().attach_backtrace_lazy();
+//! ```
+//!
 //! # Environment Variables
 //!
 //! - `RUST_BACKTRACE=full` - Disables filtering and shows full paths
@@ -122,30 +137,52 @@
 //! ```
 //! use rootcause_backtrace::{BacktraceCollector, BacktraceFilter};
 //!
-//! let collector = BacktraceCollector {
-//!     filter: BacktraceFilter {
-//!         skipped_initial_crates: &["rootcause", "rootcause-backtrace"],  // Skip frames from rootcause at start
-//!         skipped_middle_crates: &["tokio"],     // Skip tokio frames in middle
-//!         skipped_final_crates: &["std"],        // Skip std frames at end
-//!         max_entry_count: 15,                   // Limit to 15 frames
-//!         show_full_path: false,                 // Show shortened paths
-//!     },
-//!     capture_backtrace_for_reports_with_children: false,  // Only leaf errors
-//! };
+//! let collector = BacktraceCollector::new(BacktraceFilter {
+//!     skipped_initial_crates: &["rootcause", "rootcause-backtrace"],  // Skip frames from rootcause at start
+//!     skipped_middle_crates: &["tokio"],     // Skip tokio frames in middle
+//!     skipped_final_crates: &["std"],        // Skip std frames at end
+//!     max_entry_count: 15,                   // Limit to 15 frames
+//!     show_full_path: false,                 // Show shortened paths
+//! })
+//! .with_capture_for_reports_with_children(false);  // Only leaf errors
+//! ```
+//!
+//! The crate lists of [`BacktraceFilter`] are `&'static` slices. To build a
+//! filter from runtime configuration, such as a config file listing crates to
+//! hide, use [`BacktraceFilterOwned`] instead:
+//!
+//! ```
+//! use rootcause_backtrace::{BacktraceCollector, BacktraceFilterOwned};
+//!
+//! let hidden_crates: Vec<String> = "tokio,hyper".split(',').map(String::from).collect();
+//!
+//! let collector = BacktraceCollector::new(BacktraceFilterOwned {
+//!     skipped_middle_crates: hidden_crates,
+//!     ..BacktraceFilterOwned::default()
+//! })
+//! .with_capture_for_reports_with_children(false);
 //! ```
 
-use std::{borrow::Cow, fmt, panic::Location, sync::OnceLock};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+    fmt,
+    panic::Location,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
 
 use backtrace::BytesOrWideString;
 use rootcause::{
     Report, ReportMut,
     handlers::{
         AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler,
-        FormattingFunction,
+        FormattingFunction, JsonSink, StackFrame,
     },
     hooks::report_creation::ReportCreationHook,
     markers::{self, Dynamic, ObjectMarkerFor},
     report_attachment::ReportAttachment,
+    report_collection::ReportCollection,
 };
 
 /// Stack backtrace information.
@@ -165,12 +202,20 @@ use rootcause::{
 ///     println!("Captured {} frames", bt.entries.len());
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Backtrace {
     /// The entries in the backtrace, ordered from most recent to oldest.
     pub entries: Vec<BacktraceEntry>,
     /// Total number of frames that were omitted due to filtering.
     pub total_omitted_frames: usize,
+    /// Number of frames removed from the end of the backtrace because they are
+    /// identical to the end of the backtrace of a child report.
+    ///
+    /// This is only non-zero when the backtrace was captured by a
+    /// [`BacktraceCollector`] with
+    /// [`deduplicate_with_children`](BacktraceCollector::deduplicate_with_children)
+    /// enabled.
+    pub frames_shared_with_child: usize,
 }
 
 /// A single entry in a stack backtrace.
@@ -191,7 +236,7 @@ pub struct Backtrace {
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BacktraceEntry {
     /// A normal stack frame.
     Frame(Frame),
@@ -200,7 +245,7 @@ pub enum BacktraceEntry {
         /// Number of omitted frames.
         count: usize,
         /// The name of the crate whose frames were omitted.
-        skipped_crate: &'static str,
+        skipped_crate: Cow<'static, str>,
     },
 }
 
@@ -222,7 +267,7 @@ pub enum BacktraceEntry {
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Frame {
     /// The demangled symbol name for this frame.
     pub sym_demangled: String,
@@ -232,6 +277,34 @@ pub struct Frame {
     pub lineno: Option<u32>,
 }
 
+/// The source lines around the line of a stack frame, as returned by
+/// [`Frame::read_source`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_backtrace::{Backtrace, BacktraceEntry, BacktraceFilter};
+///
+/// if let Some(bt) = Backtrace::capture(&BacktraceFilter::DEFAULT) {
+///     for entry in &bt.entries {
+///         if let BacktraceEntry::Frame(frame) = entry
+///             && let Some(source) = frame.read_source()
+///         {
+///             for (lineno, line) in (source.first_lineno..).zip(&source.lines) {
+///                 println!("{lineno:>5} | {line}");
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameSource {
+    /// The line number of the first line in [`lines`](Self::lines).
+    pub first_lineno: u32,
+    /// The source lines, without line terminators.
+    pub lines: Vec<String>,
+}
+
 /// File path information for a stack frame.
 ///
 /// Contains the raw path and processed components for better display
@@ -252,7 +325,7 @@ pub struct Frame {
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FramePath {
     /// The raw file path from the debug information.
     pub raw_path: String,
@@ -286,7 +359,7 @@ pub struct FramePath {
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FramePrefix {
     /// The kind of prefix used to identify this prefix.
     ///
@@ -305,10 +378,151 @@ pub struct FramePrefix {
     pub suffix: String,
 }
 
+/// A stack backtrace whose symbols are resolved on first use.
+///
+/// Capturing the frames of a backtrace is cheap, but resolving their symbol
+/// names and source locations is not. A [`LazyBacktrace`] only records the
+/// frames when it is captured, and resolves them into a [`Backtrace`] the
+/// first time it is needed, which is usually when the report it is attached
+/// to is formatted. Reports that are handled without ever being printed never
+/// pay for the resolution.
+///
+/// The filter is applied when the backtrace is resolved.
+///
+/// A [`BacktraceCollector`] attaches lazy backtraces when
+/// [`resolve_lazily`](BacktraceCollector::resolve_lazily) is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_backtrace::{BacktraceFilter, LazyBacktrace};
+///
+/// let backtrace = LazyBacktrace::capture(BacktraceFilter::DEFAULT);
+/// assert!(!backtrace.is_resolved());
+///
+/// if let Some(bt) = backtrace.resolve() {
+///     println!("Resolved {} frames", bt.entries.len());
+/// }
+/// assert!(backtrace.is_resolved());
+/// ```
+#[derive(Clone)]
+pub struct LazyBacktrace {
+    frames: Vec<backtrace::Frame>,
+    /// Number of frames at the end of `frames` that are shared with the
+    /// backtrace of a child report, and are left out when resolving
+    shared_frames: usize,
+    filter: Arc<dyn BacktraceFilterConfig + Send + Sync>,
+    resolved: OnceLock<Option<Backtrace>>,
+}
+
+impl LazyBacktrace {
+    /// Captures the frames of the current stack backtrace without resolving
+    /// them.
+    ///
+    /// The `filter` is stored and applied when the backtrace is resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceFilterOwned, LazyBacktrace};
+    ///
+    /// let filter = BacktraceFilterOwned {
+    ///     skipped_middle_crates: vec!["tokio".to_string()],
+    ///     ..BacktraceFilterOwned::default()
+    /// };
+    /// let backtrace = LazyBacktrace::capture(filter);
+    /// ```
+    pub fn capture<F>(filter: F) -> Self
+    where
+        F: BacktraceFilterConfig + Send + Sync + 'static,
+    {
+        let mut frames = Vec::new();
+        backtrace::trace(|frame| {
+            frames.push(frame.clone());
+            true
+        });
+        Self {
+            frames,
+            shared_frames: 0,
+            filter: Arc::new(filter),
+            resolved: OnceLock::new(),
+        }
+    }
+
+    /// Resolves the captured frames and returns the filtered [`Backtrace`],
+    /// or `None` if no frames could be resolved.
+    ///
+    /// The frames are only resolved the first time this is called; later calls
+    /// return the same result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceFilter, LazyBacktrace};
+    ///
+    /// let backtrace = LazyBacktrace::capture(BacktraceFilter::DEFAULT);
+    /// let first = backtrace.resolve().map(|bt| bt.entries.len());
+    /// let second = backtrace.resolve().map(|bt| bt.entries.len());
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn resolve(&self) -> Option<&Backtrace> {
+        self.resolved
+            .get_or_init(|| {
+                let unshared_frames = &self.frames[..self.frames.len() - self.shared_frames];
+                let mut backtrace = Backtrace::resolve_with(&*self.filter, |resolve_frame| {
+                    unshared_frames.iter().for_each(resolve_frame);
+                })?;
+                backtrace.frames_shared_with_child = self.shared_frames;
+                Some(backtrace)
+            })
+            .as_ref()
+    }
+
+    /// Returns `true` if the frames have already been resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceFilter, LazyBacktrace};
+    ///
+    /// let backtrace = LazyBacktrace::capture(BacktraceFilter::DEFAULT);
+    /// assert!(!backtrace.is_resolved());
+    /// ```
+    pub fn is_resolved(&self) -> bool {
+        self.resolved.get().is_some()
+    }
+
+    /// Returns the number of unresolved frames at the end of this backtrace
+    /// that are identical to the frames at the end of `other`.
+    fn common_suffix_len(&self, other: &LazyBacktrace) -> usize {
+        self.frames
+            .iter()
+            .rev()
+            .zip(other.frames.iter().rev())
+            .take_while(|(frame, other_frame)| frame.ip() == other_frame.ip())
+            .count()
+    }
+}
+
+impl fmt::Debug for LazyBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.resolved.get() {
+            Some(resolved) => fmt::Debug::fmt(resolved, f),
+            None => f
+                .debug_struct("LazyBacktrace")
+                .field("unresolved_frames", &self.frames.len())
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
 /// Handler for formatting [`Backtrace`] attachments.
 ///
 /// The const generic `SHOW_FULL_PATH` controls whether file paths are shown
-/// in full or with common prefixes shortened.
+/// in full or with common prefixes shortened. The const generic
+/// `SHOW_SOURCE_SNIPPETS` controls whether the source lines around each
+/// frame are shown below it, as returned by [`Frame::read_source`]. The
+/// source files are read when the backtrace is formatted.
 ///
 /// # Examples
 ///
@@ -317,13 +531,16 @@ pub struct FramePrefix {
 /// use rootcause_backtrace::{Backtrace, BacktraceFilter, BacktraceHandler};
 ///
 /// let backtrace = Backtrace::capture(&BacktraceFilter::DEFAULT)
-///     .unwrap_or(Backtrace { entries: Vec::new(), total_omitted_frames: 0 });
+///     .unwrap_or(Backtrace { entries: Vec::new(), total_omitted_frames: 0, frames_shared_with_child: 0 });
 ///
 /// // SHOW_FULL_PATH = false: shortened paths
-/// let _ = ReportAttachment::new_sendsync_custom::<BacktraceHandler<false>>(backtrace);
+/// let _ = ReportAttachment::new_sendsync_custom::<BacktraceHandler<false>>(backtrace.clone());
+///
+/// // SHOW_SOURCE_SNIPPETS = true: source lines below each frame
+/// let _ = ReportAttachment::new_sendsync_custom::<BacktraceHandler<false, true>>(backtrace);
 /// ```
 #[derive(Copy, Clone)]
-pub struct BacktraceHandler<const SHOW_FULL_PATH: bool>;
+pub struct BacktraceHandler<const SHOW_FULL_PATH: bool, const SHOW_SOURCE_SNIPPETS: bool = false>;
 
 fn get_function_name(s: &str) -> &str {
     let mut word_start = 0usize;
@@ -374,7 +591,24 @@ fn get_function_name(s: &str) -> &str {
     }
 }
 
-impl<const SHOW_FULL_PATH: bool> AttachmentHandler<Backtrace> for BacktraceHandler<SHOW_FULL_PATH> {
+/// Writes the source lines of a frame, marking the line of the frame.
+fn write_frame_source(
+    f: &mut fmt::Formatter<'_>,
+    source: &FrameSource,
+    lineno: Option<u32>,
+) -> fmt::Result {
+    let last_lineno = source.first_lineno as usize + source.lines.len().saturating_sub(1);
+    let width = last_lineno.to_string().len();
+    for (current, line) in (source.first_lineno..).zip(&source.lines) {
+        let marker = if Some(current) == lineno { '>' } else { ' ' };
+        writeln!(f, "   {marker} {current:>width$} | {line}")?;
+    }
+    Ok(())
+}
+
+impl<const SHOW_FULL_PATH: bool, const SHOW_SOURCE_SNIPPETS: bool> AttachmentHandler<Backtrace>
+    for BacktraceHandler<SHOW_FULL_PATH, SHOW_SOURCE_SNIPPETS>
+{
     fn display(value: &Backtrace, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         const MAX_UNWRAPPED_SYM_LENGTH: usize = 25;
         let mut max_seen_length = 0;
@@ -422,10 +656,22 @@ impl<const SHOW_FULL_PATH: bool> AttachmentHandler<Backtrace> for BacktraceHandl
                         }
                     }
                     writeln!(f)?;
+
+                    if SHOW_SOURCE_SNIPPETS && let Some(source) = frame.read_source() {
+                        write_frame_source(f, &source, frame.lineno)?;
+                    }
                 }
             }
         }
 
+        if value.frames_shared_with_child > 0 {
+            writeln!(
+                f,
+                "... {} frame(s) shared with the child backtrace, see child backtrace ...",
+                value.frames_shared_with_child
+            )?;
+        }
+
         if value.total_omitted_frames > 0 {
             writeln!(
                 f,
@@ -441,6 +687,70 @@ impl<const SHOW_FULL_PATH: bool> AttachmentHandler<Backtrace> for BacktraceHandl
         std::fmt::Debug::fmt(value, formatter)
     }
 
+    /// Emits the backtrace as an array of entries. Frames are objects with
+    /// `function`, `file` and `line` fields, and omitted frames are objects
+    /// with `omitted_count` and `skipped_crate` fields. Frames shared with a
+    /// child backtrace are represented by a final object with a
+    /// `shared_with_child_count` field.
+    fn json(value: &Backtrace, sink: &mut dyn JsonSink) -> fmt::Result {
+        sink.begin_array()?;
+        for entry in &value.entries {
+            sink.begin_object()?;
+            match entry {
+                BacktraceEntry::Frame(frame) => {
+                    sink.key("function")?;
+                    sink.str(&frame.sym_demangled)?;
+                    sink.key("file")?;
+                    match &frame.frame_path {
+                        Some(frame_path) => sink.str(&frame_path.raw_path)?,
+                        None => sink.null()?,
+                    }
+                    sink.key("line")?;
+                    match frame.lineno {
+                        Some(lineno) => sink.u64(lineno.into())?,
+                        None => sink.null()?,
+                    }
+                }
+                BacktraceEntry::OmittedFrames {
+                    count,
+                    skipped_crate,
+                } => {
+                    sink.key("omitted_count")?;
+                    sink.u64(*count as u64)?;
+                    sink.key("skipped_crate")?;
+                    sink.str(skipped_crate)?;
+                }
+            }
+            sink.end_object()?;
+        }
+        if value.frames_shared_with_child > 0 {
+            sink.begin_object()?;
+            sink.key("shared_with_child_count")?;
+            sink.u64(value.frames_shared_with_child as u64)?;
+            sink.end_object()?;
+        }
+        sink.end_array()
+    }
+
+    fn stack_frames(value: &Backtrace) -> Option<Vec<StackFrame<'_>>> {
+        let frames = value
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                BacktraceEntry::Frame(frame) => Some(StackFrame::new(
+                    &frame.sym_demangled,
+                    frame
+                        .frame_path
+                        .as_ref()
+                        .map(|frame_path| frame_path.raw_path.as_str()),
+                    frame.lineno,
+                )),
+                BacktraceEntry::OmittedFrames { .. } => None,
+            })
+            .collect();
+        Some(frames)
+    }
+
     fn preferred_formatting_style(
         backtrace: &Backtrace,
         _report_formatting_function: FormattingFunction,
@@ -461,6 +771,56 @@ impl<const SHOW_FULL_PATH: bool> AttachmentHandler<Backtrace> for BacktraceHandl
     }
 }
 
+/// Resolves the backtrace the first time it is formatted, and then formats it
+/// in the same way as a [`Backtrace`]. A backtrace that cannot be resolved is
+/// hidden.
+impl<const SHOW_FULL_PATH: bool, const SHOW_SOURCE_SNIPPETS: bool> AttachmentHandler<LazyBacktrace>
+    for BacktraceHandler<SHOW_FULL_PATH, SHOW_SOURCE_SNIPPETS>
+{
+    fn display(value: &LazyBacktrace, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match value.resolve() {
+            Some(backtrace) => <Self as AttachmentHandler<Backtrace>>::display(backtrace, f),
+            None => Ok(()),
+        }
+    }
+
+    fn debug(value: &LazyBacktrace, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        value.resolve();
+        fmt::Debug::fmt(value, formatter)
+    }
+
+    fn json(value: &LazyBacktrace, sink: &mut dyn JsonSink) -> fmt::Result {
+        match value.resolve() {
+            Some(backtrace) => <Self as AttachmentHandler<Backtrace>>::json(backtrace, sink),
+            None => {
+                sink.begin_array()?;
+                sink.end_array()
+            }
+        }
+    }
+
+    fn stack_frames(value: &LazyBacktrace) -> Option<Vec<StackFrame<'_>>> {
+        <Self as AttachmentHandler<Backtrace>>::stack_frames(value.resolve()?)
+    }
+
+    fn preferred_formatting_style(
+        value: &LazyBacktrace,
+        report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        match value.resolve() {
+            Some(backtrace) => <Self as AttachmentHandler<Backtrace>>::preferred_formatting_style(
+                backtrace,
+                report_formatting_function,
+            ),
+            None => AttachmentFormattingStyle {
+                placement: AttachmentFormattingPlacement::Hidden,
+                function: FormattingFunction::Display,
+                priority: 10,
+            },
+        }
+    }
+}
+
 /// Attachment collector for capturing stack backtraces.
 ///
 /// When registered as a report creation hook, this collector automatically
@@ -487,26 +847,35 @@ impl<const SHOW_FULL_PATH: bool> AttachmentHandler<Backtrace> for BacktraceHandl
 /// use rootcause::hooks::Hooks;
 /// use rootcause_backtrace::{BacktraceCollector, BacktraceFilter};
 ///
-/// let collector = BacktraceCollector {
-///     filter: BacktraceFilter {
-///         skipped_initial_crates: &[],
-///         skipped_middle_crates: &[],
-///         skipped_final_crates: &[],
-///         max_entry_count: 30,
-///         show_full_path: true,
-///     },
-///     capture_backtrace_for_reports_with_children: true,
-/// };
+/// let collector = BacktraceCollector::new(BacktraceFilter {
+///     skipped_initial_crates: &[],
+///     skipped_middle_crates: &[],
+///     skipped_final_crates: &[],
+///     max_entry_count: 30,
+///     show_full_path: true,
+/// })
+/// .with_deduplicate_with_children(true)
+/// .with_source_snippets(true);
 ///
 /// Hooks::new()
 ///     .report_creation_hook(collector)
 ///     .install()
 ///     .expect("failed to install hooks");
 /// ```
+///
+/// New configuration options may be added in future versions, so a
+/// [`BacktraceCollector`] is created using [`BacktraceCollector::new`] or
+/// [`BacktraceCollector::new_from_env`] and configured using its `with_*`
+/// methods rather than a struct literal.
 #[derive(Copy, Clone)]
-pub struct BacktraceCollector {
+#[non_exhaustive]
+pub struct BacktraceCollector<F = BacktraceFilter> {
     /// Configuration for filtering and formatting backtrace frames.
-    pub filter: BacktraceFilter,
+    ///
+    /// This is usually a [`BacktraceFilter`], but any
+    /// [`BacktraceFilterConfig`] can be used, such as a
+    /// [`BacktraceFilterOwned`] built from runtime configuration.
+    pub filter: F,
 
     /// If set to true, a backtrace is captured for every report creation,
     /// including reports that have child reports (i.e., reports created with
@@ -514,6 +883,37 @@ pub struct BacktraceCollector {
     /// for reports created without any children. Reports created without
     /// children always receive a backtrace regardless of this setting.
     pub capture_backtrace_for_reports_with_children: bool,
+
+    /// If set to true, a newly captured backtrace is compared against the
+    /// backtraces attached to the direct children of the report. When it
+    /// shares a common suffix of frames with one of them, only the differing
+    /// prefix is kept, followed by a `see child backtrace` marker. This
+    /// avoids printing nearly identical backtraces for every report in a
+    /// chain, which is common in async code.
+    ///
+    /// Both [`Backtrace`] and [`LazyBacktrace`] attachments of the children
+    /// are recognized. When [`resolve_lazily`](Self::resolve_lazily) is
+    /// enabled, the unresolved frames are compared with those of the
+    /// [`LazyBacktrace`] attachments of the children, so that nothing has to
+    /// be resolved when the report is created.
+    ///
+    /// This only has an effect if
+    /// [`capture_backtrace_for_reports_with_children`](Self::capture_backtrace_for_reports_with_children)
+    /// is also enabled, since otherwise reports with children never receive a
+    /// backtrace.
+    pub deduplicate_with_children: bool,
+
+    /// If set to true, a [`LazyBacktrace`] is attached instead of a
+    /// [`Backtrace`], so that the symbols of the frames are only resolved
+    /// when the report is formatted.
+    ///
+    /// The filter is cloned into every captured backtrace.
+    pub resolve_lazily: bool,
+
+    /// If set to true, the source lines around each frame are shown below
+    /// it when the backtrace is formatted. See [`Frame::read_source`] for
+    /// which files are read.
+    pub show_source_snippets: bool,
 }
 
 /// Configuration for filtering frames from certain crates in a backtrace.
@@ -587,6 +987,216 @@ impl Default for BacktraceFilter {
     }
 }
 
+/// Configuration for filtering frames from certain crates in a backtrace,
+/// using owned crate lists.
+///
+/// This is the runtime-configurable counterpart of [`BacktraceFilter`]. Where
+/// [`BacktraceFilter`] requires `&'static` crate lists, this type stores
+/// them as [`Vec<String>`], so it can be built from a config file or
+/// environment variables without recompiling.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_backtrace::{Backtrace, BacktraceFilter, BacktraceFilterOwned};
+///
+/// // Start from the default settings and extend them at runtime
+/// let mut filter = BacktraceFilterOwned::from(BacktraceFilter::DEFAULT);
+/// filter.skipped_middle_crates.push("hyper".to_string());
+/// filter.max_entry_count = 10;
+///
+/// let backtrace = Backtrace::capture(&filter);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BacktraceFilterOwned {
+    /// Set of crate names whose frames should be hidden when they appear
+    /// at the beginning of a backtrace.
+    pub skipped_initial_crates: Vec<String>,
+    /// Set of crate names whose frames should be hidden when they appear
+    /// in the middle of a backtrace.
+    pub skipped_middle_crates: Vec<String>,
+    /// Set of crate names whose frames should be hidden when they appear
+    /// at the end of a backtrace.
+    pub skipped_final_crates: Vec<String>,
+    /// Maximum number of entries to include in the backtrace.
+    pub max_entry_count: usize,
+    /// Whether to show full file paths in the backtrace frames.
+    pub show_full_path: bool,
+}
+
+impl Default for BacktraceFilterOwned {
+    fn default() -> Self {
+        Self::from(BacktraceFilter::DEFAULT)
+    }
+}
+
+impl From<BacktraceFilter> for BacktraceFilterOwned {
+    fn from(filter: BacktraceFilter) -> Self {
+        fn to_owned(crates: &[&str]) -> Vec<String> {
+            crates
+                .iter()
+                .map(|&crate_name| crate_name.to_owned())
+                .collect()
+        }
+
+        Self {
+            skipped_initial_crates: to_owned(filter.skipped_initial_crates),
+            skipped_middle_crates: to_owned(filter.skipped_middle_crates),
+            skipped_final_crates: to_owned(filter.skipped_final_crates),
+            max_entry_count: filter.max_entry_count,
+            show_full_path: filter.show_full_path,
+        }
+    }
+}
+
+/// Filtering configuration used by [`Backtrace::capture`].
+///
+/// This trait is implemented by both [`BacktraceFilter`] and
+/// [`BacktraceFilterOwned`], and is object safe, so either can be passed to
+/// [`Backtrace::capture`] as a `&dyn BacktraceFilterConfig`. You can also
+/// implement it yourself to decide which crates to hide in some other way.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_backtrace::{Backtrace, BacktraceFilterConfig};
+///
+/// /// Hides the frames of rootcause at the start of the backtrace, the frames
+/// /// of crates whose name starts with `my-framework` in the middle, and the
+/// /// frames of `std` at the end.
+/// struct HideFramework;
+///
+/// impl BacktraceFilterConfig for HideFramework {
+///     fn skips_initial_crate(&self, crate_name: &str) -> bool {
+///         crate_name.starts_with("rootcause")
+///     }
+///
+///     fn skips_middle_crate(&self, crate_name: &str) -> bool {
+///         crate_name.starts_with("my-framework")
+///     }
+///
+///     fn skips_final_crate(&self, crate_name: &str) -> bool {
+///         crate_name == "std"
+///     }
+///
+///     fn max_entry_count(&self) -> usize {
+///         20
+///     }
+///
+///     fn show_full_path(&self) -> bool {
+///         false
+///     }
+/// }
+///
+/// let backtrace = Backtrace::capture(&HideFramework);
+/// ```
+pub trait BacktraceFilterConfig {
+    /// Returns whether frames from the given crate should be hidden when they
+    /// appear at the beginning of a backtrace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceFilter, BacktraceFilterConfig};
+    ///
+    /// assert!(BacktraceFilter::DEFAULT.skips_initial_crate("rootcause"));
+    /// assert!(!BacktraceFilter::DEFAULT.skips_initial_crate("my-app"));
+    /// ```
+    fn skips_initial_crate(&self, crate_name: &str) -> bool;
+
+    /// Returns whether frames from the given crate should be hidden when they
+    /// appear in the middle of a backtrace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceFilter, BacktraceFilterConfig};
+    ///
+    /// assert!(BacktraceFilter::DEFAULT.skips_middle_crate("tokio"));
+    /// assert!(!BacktraceFilter::DEFAULT.skips_middle_crate("my-app"));
+    /// ```
+    fn skips_middle_crate(&self, crate_name: &str) -> bool;
+
+    /// Returns whether frames from the given crate should be hidden when they
+    /// appear at the end of a backtrace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceFilter, BacktraceFilterConfig};
+    ///
+    /// assert!(BacktraceFilter::DEFAULT.skips_final_crate("std"));
+    /// assert!(!BacktraceFilter::DEFAULT.skips_final_crate("my-app"));
+    /// ```
+    fn skips_final_crate(&self, crate_name: &str) -> bool;
+
+    /// Returns the maximum number of entries to include in the backtrace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceFilter, BacktraceFilterConfig};
+    ///
+    /// assert_eq!(BacktraceFilterConfig::max_entry_count(&BacktraceFilter::DEFAULT), 20);
+    /// ```
+    fn max_entry_count(&self) -> usize;
+
+    /// Returns whether to show full file paths in the backtrace frames.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceFilter, BacktraceFilterConfig};
+    ///
+    /// assert!(!BacktraceFilterConfig::show_full_path(&BacktraceFilter::DEFAULT));
+    /// ```
+    fn show_full_path(&self) -> bool;
+}
+
+impl BacktraceFilterConfig for BacktraceFilter {
+    fn skips_initial_crate(&self, crate_name: &str) -> bool {
+        self.skipped_initial_crates.contains(&crate_name)
+    }
+
+    fn skips_middle_crate(&self, crate_name: &str) -> bool {
+        self.skipped_middle_crates.contains(&crate_name)
+    }
+
+    fn skips_final_crate(&self, crate_name: &str) -> bool {
+        self.skipped_final_crates.contains(&crate_name)
+    }
+
+    fn max_entry_count(&self) -> usize {
+        self.max_entry_count
+    }
+
+    fn show_full_path(&self) -> bool {
+        self.show_full_path
+    }
+}
+
+impl BacktraceFilterConfig for BacktraceFilterOwned {
+    fn skips_initial_crate(&self, crate_name: &str) -> bool {
+        self.skipped_initial_crates.iter().any(|c| c == crate_name)
+    }
+
+    fn skips_middle_crate(&self, crate_name: &str) -> bool {
+        self.skipped_middle_crates.iter().any(|c| c == crate_name)
+    }
+
+    fn skips_final_crate(&self, crate_name: &str) -> bool {
+        self.skipped_final_crates.iter().any(|c| c == crate_name)
+    }
+
+    fn max_entry_count(&self) -> usize {
+        self.max_entry_count
+    }
+
+    fn show_full_path(&self) -> bool {
+        self.show_full_path
+    }
+}
+
 #[derive(Debug)]
 struct RootcauseEnvOptions {
     rust_backtrace_full: bool,
@@ -643,62 +1253,229 @@ impl BacktraceCollector {
     ///
     /// ```
     /// use rootcause::hooks::Hooks;
-    /// use rootcause_backtrace::BacktraceCollector;
+    /// use rootcause_backtrace::BacktraceCollector;
+    ///
+    /// // Respects RUST_BACKTRACE and ROOTCAUSE_BACKTRACE environment variables
+    /// Hooks::new()
+    ///     .report_creation_hook(BacktraceCollector::new_from_env())
+    ///     .install()
+    ///     .expect("failed to install hooks");
+    /// ```
+    pub fn new_from_env() -> Self {
+        let env_options = RootcauseEnvOptions::get();
+        let capture_backtrace_for_reports_with_children = !env_options.backtrace_leafs_only;
+
+        Self {
+            filter: if env_options.rust_backtrace_full {
+                BacktraceFilter {
+                    skipped_initial_crates: &[],
+                    skipped_middle_crates: &[],
+                    skipped_final_crates: &[],
+                    max_entry_count: usize::MAX,
+                    show_full_path: env_options.show_full_path,
+                }
+            } else {
+                BacktraceFilter {
+                    show_full_path: env_options.show_full_path,
+                    ..BacktraceFilter::DEFAULT
+                }
+            },
+            capture_backtrace_for_reports_with_children,
+            deduplicate_with_children: false,
+            resolve_lazily: false,
+            show_source_snippets: false,
+        }
+    }
+}
+
+impl<F> BacktraceCollector<F> {
+    /// Creates a new [`BacktraceCollector`] using the given filter.
+    ///
+    /// Backtraces are captured for all reports, including reports with
+    /// children, and are not deduplicated against the backtraces of the
+    /// children. Unlike [`new_from_env`](BacktraceCollector::new_from_env),
+    /// this does not read any environment variables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceCollector, BacktraceFilterOwned};
+    ///
+    /// let collector = BacktraceCollector::new(BacktraceFilterOwned::default());
+    /// assert!(collector.capture_backtrace_for_reports_with_children);
+    /// assert!(!collector.deduplicate_with_children);
+    /// ```
+    pub const fn new(filter: F) -> Self {
+        Self {
+            filter,
+            capture_backtrace_for_reports_with_children: true,
+            deduplicate_with_children: false,
+            resolve_lazily: false,
+            show_source_snippets: false,
+        }
+    }
+
+    /// Sets [`capture_backtrace_for_reports_with_children`](Self::capture_backtrace_for_reports_with_children).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceCollector, BacktraceFilter};
+    ///
+    /// // Only capture backtraces for leaf errors
+    /// let collector = BacktraceCollector::new(BacktraceFilter::DEFAULT)
+    ///     .with_capture_for_reports_with_children(false);
+    /// ```
+    #[must_use]
+    pub const fn with_capture_for_reports_with_children(mut self, capture: bool) -> Self {
+        self.capture_backtrace_for_reports_with_children = capture;
+        self
+    }
+
+    /// Sets [`deduplicate_with_children`](Self::deduplicate_with_children).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceCollector, BacktraceFilter};
+    ///
+    /// let collector =
+    ///     BacktraceCollector::new(BacktraceFilter::DEFAULT).with_deduplicate_with_children(true);
+    /// ```
+    #[must_use]
+    pub const fn with_deduplicate_with_children(mut self, deduplicate: bool) -> Self {
+        self.deduplicate_with_children = deduplicate;
+        self
+    }
+
+    /// Sets [`resolve_lazily`](Self::resolve_lazily).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::Hooks;
+    /// use rootcause_backtrace::{BacktraceCollector, BacktraceFilter};
     ///
-    /// // Respects RUST_BACKTRACE and ROOTCAUSE_BACKTRACE environment variables
+    /// // Only resolve the backtraces of reports that are formatted
     /// Hooks::new()
-    ///     .report_creation_hook(BacktraceCollector::new_from_env())
+    ///     .report_creation_hook(
+    ///         BacktraceCollector::new(BacktraceFilter::DEFAULT).with_lazy_resolution(true),
+    ///     )
     ///     .install()
     ///     .expect("failed to install hooks");
     /// ```
-    pub fn new_from_env() -> Self {
-        let env_options = RootcauseEnvOptions::get();
-        let capture_backtrace_for_reports_with_children = !env_options.backtrace_leafs_only;
+    #[must_use]
+    pub const fn with_lazy_resolution(mut self, resolve_lazily: bool) -> Self {
+        self.resolve_lazily = resolve_lazily;
+        self
+    }
 
-        Self {
-            filter: if env_options.rust_backtrace_full {
-                BacktraceFilter {
-                    skipped_initial_crates: &[],
-                    skipped_middle_crates: &[],
-                    skipped_final_crates: &[],
-                    max_entry_count: usize::MAX,
-                    show_full_path: env_options.show_full_path,
-                }
-            } else {
-                BacktraceFilter {
-                    show_full_path: env_options.show_full_path,
-                    ..BacktraceFilter::DEFAULT
-                }
-            },
-            capture_backtrace_for_reports_with_children,
+    /// Sets [`show_source_snippets`](Self::show_source_snippets).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{BacktraceCollector, BacktraceFilter};
+    ///
+    /// let collector = BacktraceCollector::new(BacktraceFilter::DEFAULT).with_source_snippets(true);
+    /// ```
+    #[must_use]
+    pub const fn with_source_snippets(mut self, show_source_snippets: bool) -> Self {
+        self.show_source_snippets = show_source_snippets;
+        self
+    }
+}
+
+impl<F> BacktraceCollector<F>
+where
+    F: BacktraceFilterConfig + Clone + Send + Sync + 'static,
+{
+    /// Captures the backtrace attachment for a newly created report with the
+    /// given children, or returns `None` if no backtrace should be attached.
+    ///
+    /// The attachment is `Send + Sync`, and is recorded as such, so that
+    /// `Report::try_into_sendsync` succeeds for local reports.
+    fn capture_for<T>(
+        &self,
+        children: &ReportCollection<Dynamic, T>,
+    ) -> Option<ReportAttachment<Dynamic, markers::SendSync>> {
+        if !self.capture_backtrace_for_reports_with_children && !children.is_empty() {
+            return None;
+        }
+
+        let child_attachments = || children.iter().flat_map(|child| child.attachments().iter());
+
+        if self.resolve_lazily {
+            let mut backtrace = LazyBacktrace::capture(self.filter.clone());
+            if self.deduplicate_with_children {
+                backtrace.shared_frames = child_attachments()
+                    .filter_map(|attachment| attachment.downcast_inner::<LazyBacktrace>())
+                    .map(|child_backtrace| backtrace.common_suffix_len(child_backtrace))
+                    .max()
+                    .unwrap_or(0);
+            }
+            return Some(self.attachment(backtrace));
+        }
+
+        let mut backtrace = Backtrace::capture(&self.filter)?;
+        if self.deduplicate_with_children {
+            let shared_entries = child_attachments()
+                .filter_map(|attachment| {
+                    attachment
+                        .downcast_inner::<Backtrace>()
+                        .or_else(|| attachment.downcast_inner::<LazyBacktrace>()?.resolve())
+                })
+                .map(|child_backtrace| backtrace.common_suffix_len(child_backtrace))
+                .max()
+                .unwrap_or(0);
+            backtrace.remove_shared_suffix(shared_entries);
         }
+        Some(self.attachment(backtrace))
+    }
+
+    /// Creates an attachment for a backtrace, using the handler matching the
+    /// [`show_full_path`](BacktraceFilterConfig::show_full_path) setting of
+    /// the filter and the
+    /// [`show_source_snippets`](Self::show_source_snippets) setting.
+    fn attachment<A>(&self, backtrace: A) -> ReportAttachment<Dynamic, markers::SendSync>
+    where
+        A: Send + Sync + 'static,
+        BacktraceHandler<false, false>: AttachmentHandler<A>,
+        BacktraceHandler<false, true>: AttachmentHandler<A>,
+        BacktraceHandler<true, false>: AttachmentHandler<A>,
+        BacktraceHandler<true, true>: AttachmentHandler<A>,
+    {
+        let attachment = match (self.filter.show_full_path(), self.show_source_snippets) {
+            (false, false) => {
+                ReportAttachment::new_sendsync_custom::<BacktraceHandler<false, false>>(backtrace)
+            }
+            (false, true) => {
+                ReportAttachment::new_sendsync_custom::<BacktraceHandler<false, true>>(backtrace)
+            }
+            (true, false) => {
+                ReportAttachment::new_sendsync_custom::<BacktraceHandler<true, false>>(backtrace)
+            }
+            (true, true) => {
+                ReportAttachment::new_sendsync_custom::<BacktraceHandler<true, true>>(backtrace)
+            }
+        };
+        attachment.into_dynamic()
     }
 }
 
-impl ReportCreationHook for BacktraceCollector {
+impl<F> ReportCreationHook for BacktraceCollector<F>
+where
+    F: BacktraceFilterConfig + Clone + Send + Sync + 'static,
+{
     fn on_local_creation(&self, mut report: ReportMut<'_, Dynamic, markers::Local>) {
-        let do_capture =
-            self.capture_backtrace_for_reports_with_children || report.children().is_empty();
-        if do_capture && let Some(backtrace) = Backtrace::capture(&self.filter) {
-            let attachment = if self.filter.show_full_path {
-                ReportAttachment::new_custom::<BacktraceHandler<true>>(backtrace)
-            } else {
-                ReportAttachment::new_custom::<BacktraceHandler<false>>(backtrace)
-            };
-            report.attachments_mut().push(attachment.into_dynamic());
+        if let Some(attachment) = self.capture_for(report.children()) {
+            report.attachments_mut().push(attachment.into_local());
         }
     }
 
     fn on_sendsync_creation(&self, mut report: ReportMut<'_, Dynamic, markers::SendSync>) {
-        let do_capture =
-            self.capture_backtrace_for_reports_with_children || report.children().is_empty();
-        if do_capture && let Some(backtrace) = Backtrace::capture(&self.filter) {
-            let attachment = if self.filter.show_full_path {
-                ReportAttachment::new_custom::<BacktraceHandler<true>>(backtrace)
-            } else {
-                ReportAttachment::new_custom::<BacktraceHandler<false>>(backtrace)
-            };
-            report.attachments_mut().push(attachment.into_dynamic());
+        if let Some(attachment) = self.capture_for(report.children()) {
+            report.attachments_mut().push(attachment);
         }
     }
 }
@@ -758,23 +1535,51 @@ impl Backtrace {
     ///     println!("Captured {} frames", bt.entries.len());
     /// }
     /// ```
-    pub fn capture(filter: &BacktraceFilter) -> Option<Self> {
-        let mut initial_filtering = !filter.skipped_initial_crates.is_empty();
+    ///
+    /// Both [`BacktraceFilter`] and [`BacktraceFilterOwned`] can be used as
+    /// the filter:
+    ///
+    /// ```
+    /// use rootcause_backtrace::{Backtrace, BacktraceFilterOwned};
+    ///
+    /// let filter = BacktraceFilterOwned {
+    ///     skipped_middle_crates: vec!["tokio".to_string()],
+    ///     ..BacktraceFilterOwned::default()
+    /// };
+    /// let backtrace = Backtrace::capture(&filter);
+    /// ```
+    pub fn capture(filter: &dyn BacktraceFilterConfig) -> Option<Self> {
+        Self::resolve_with(filter, |resolve_frame| {
+            backtrace::trace(|frame| {
+                resolve_frame(frame);
+                true
+            });
+        })
+    }
+
+    /// Resolves the frames passed to `resolve_frame` by `trace`, ordered from
+    /// most recent to oldest, and applies the filter to them.
+    fn resolve_with(
+        filter: &dyn BacktraceFilterConfig,
+        trace: impl FnOnce(&mut dyn FnMut(&backtrace::Frame)),
+    ) -> Option<Self> {
+        let mut initial_filtering = true;
+        let max_entry_count = filter.max_entry_count();
         let mut entries: Vec<BacktraceEntry> = Vec::new();
         let mut total_omitted_frames = 0;
 
         let mut delayed_omitted_frame: Option<Frame> = None;
-        let mut currently_omitted_crate_name: Option<&'static str> = None;
+        let mut currently_omitted_crate_name: Option<Cow<'static, str>> = None;
         let mut currently_omitted_frames = 0;
 
-        backtrace::trace(|frame| {
+        trace(&mut |frame| {
             backtrace::resolve_frame(frame, |symbol| {
                 // Don't consider frames without symbol names or filenames.
                 let (Some(sym), Some(filename_raw)) = (symbol.name(), symbol.filename_raw()) else {
                     return;
                 };
 
-                if entries.len() >= filter.max_entry_count {
+                if entries.len() >= max_entry_count {
                     total_omitted_frames += 1;
                     return;
                 }
@@ -783,7 +1588,7 @@ impl Backtrace {
 
                 if initial_filtering {
                     if let Some(cur_crate_name) = &frame_path.crate_name
-                        && filter.skipped_initial_crates.contains(&&**cur_crate_name)
+                        && filter.skips_initial_crate(cur_crate_name)
                     {
                         total_omitted_frames += 1;
                         return;
@@ -815,30 +1620,22 @@ impl Backtrace {
                 }
 
                 if let Some(cur_crate_name) = &frame_path.crate_name
-                    && let Some(skipped_crate) = filter
-                        .skipped_middle_crates
-                        .iter()
-                        .find(|&crate_name| crate_name == cur_crate_name)
+                    && filter.skips_middle_crate(cur_crate_name)
                 {
-                    currently_omitted_crate_name = Some(skipped_crate);
+                    currently_omitted_crate_name = Some(cur_crate_name.clone());
                     currently_omitted_frames = 1;
                     total_omitted_frames += 1;
-                    delayed_omitted_frame = Some(Frame {
-                        sym_demangled: format!("{sym:#}"),
-                        frame_path: Some(frame_path),
-                        lineno: symbol.lineno(),
-                    });
+                    delayed_omitted_frame =
+                        Some(Frame::new(format!("{sym:#}"), frame_path, symbol.lineno()));
                     return;
                 }
 
-                entries.push(BacktraceEntry::Frame(Frame {
-                    sym_demangled: format!("{sym:#}"),
-                    frame_path: Some(frame_path),
-                    lineno: symbol.lineno(),
-                }));
+                entries.push(BacktraceEntry::Frame(Frame::new(
+                    format!("{sym:#}"),
+                    frame_path,
+                    symbol.lineno(),
+                )));
             });
-
-            true
         });
 
         if let Some(currently_omitted_crate_name) = currently_omitted_crate_name.take() {
@@ -858,7 +1655,7 @@ impl Backtrace {
                     let mut skip = false;
                     if let Some(frame_path) = &frame.frame_path
                         && let Some(crate_name) = &frame_path.crate_name
-                        && filter.skipped_final_crates.contains(&&**crate_name)
+                        && filter.skips_final_crate(crate_name)
                     {
                         skip = true;
                     } else if frame.sym_demangled == "__libc_start_call_main"
@@ -883,7 +1680,7 @@ impl Backtrace {
                     skipped_crate,
                     count,
                 } => {
-                    if filter.skipped_final_crates.contains(skipped_crate) {
+                    if filter.skips_final_crate(skipped_crate) {
                         total_omitted_frames += count;
                         entries.pop();
                     } else {
@@ -899,9 +1696,40 @@ impl Backtrace {
             Some(Self {
                 entries,
                 total_omitted_frames,
+                frames_shared_with_child: 0,
             })
         }
     }
+
+    /// Returns the number of entries at the end of this backtrace that are
+    /// identical to the entries at the end of `other`.
+    fn common_suffix_len(&self, other: &Backtrace) -> usize {
+        self.entries
+            .iter()
+            .rev()
+            .zip(other.entries.iter().rev())
+            .take_while(|(entry, other_entry)| entry == other_entry)
+            .count()
+    }
+
+    /// Removes the last `entry_count` entries, recording the number of frames
+    /// they represented in [`Backtrace::frames_shared_with_child`].
+    ///
+    /// Omitted frames among the removed entries are moved from
+    /// [`Backtrace::total_omitted_frames`] to the shared frames, so that every
+    /// frame is counted once.
+    fn remove_shared_suffix(&mut self, entry_count: usize) {
+        let shared_start = self.entries.len() - entry_count;
+        for entry in self.entries.drain(shared_start..) {
+            match entry {
+                BacktraceEntry::Frame(_) => self.frames_shared_with_child += 1,
+                BacktraceEntry::OmittedFrames { count, .. } => {
+                    self.total_omitted_frames -= count;
+                    self.frames_shared_with_child += count;
+                }
+            }
+        }
+    }
 }
 
 /// Matches Rust standard library source paths and returns the crate name and byte offset.
@@ -962,6 +1790,145 @@ fn match_cargo_registry_path(path: &str) -> Option<(&str, usize)> {
     Some((crate_name, crate_start_abs))
 }
 
+impl Frame {
+    fn new(sym_demangled: String, frame_path: FramePath, lineno: Option<u32>) -> Self {
+        Self {
+            sym_demangled,
+            frame_path: Some(frame_path),
+            lineno,
+        }
+    }
+
+    /// Reads the source lines around [`lineno`](Self::lineno) from the file
+    /// of this frame.
+    ///
+    /// Source lines are only read for files of the current project, that is
+    /// files which do not belong to the standard library, to a crate from the
+    /// Cargo registry or to rootcause, and whose path is relative or inside
+    /// the current working directory. `None` is returned for other files and
+    /// for files that cannot be read.
+    ///
+    /// The lines are read when this is called, which is usually when a
+    /// backtrace is formatted by a [`BacktraceHandler`] with
+    /// `SHOW_SOURCE_SNIPPETS` enabled, and not when it is captured. The lines
+    /// around the most recently read frame locations are cached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_backtrace::{Backtrace, BacktraceEntry, BacktraceFilter};
+    ///
+    /// if let Some(bt) = Backtrace::capture(&BacktraceFilter::DEFAULT) {
+    ///     for entry in &bt.entries {
+    ///         if let BacktraceEntry::Frame(frame) = entry {
+    ///             let has_source = frame.read_source().is_some();
+    ///             println!("{}: {has_source}", frame.sym_demangled);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn read_source(&self) -> Option<FrameSource> {
+        FrameSource::read(self.frame_path.as_ref()?, self.lineno?)
+    }
+}
+
+/// A cache of the source lines around frame locations, which evicts the
+/// oldest entry once it holds [`SourceCache::CAPACITY`] entries.
+#[derive(Default)]
+struct SourceCache {
+    sources: HashMap<(PathBuf, u32), Option<FrameSource>>,
+    insertion_order: VecDeque<(PathBuf, u32)>,
+}
+
+impl SourceCache {
+    /// Maximum number of frame locations kept in the cache.
+    const CAPACITY: usize = 256;
+
+    fn get(&self, key: &(PathBuf, u32)) -> Option<Option<FrameSource>> {
+        self.sources.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: (PathBuf, u32), source: Option<FrameSource>) {
+        if self.sources.contains_key(&key) {
+            return;
+        }
+        if self.insertion_order.len() >= Self::CAPACITY
+            && let Some(oldest) = self.insertion_order.pop_front()
+        {
+            self.sources.remove(&oldest);
+        }
+        self.insertion_order.push_back(key.clone());
+        self.sources.insert(key, source);
+    }
+}
+
+impl FrameSource {
+    /// Number of lines shown before and after the line of the frame.
+    const CONTEXT_LINES: u32 = 2;
+
+    /// Reads the lines around `lineno` from the file of a frame, if it is a
+    /// readable file of the current project.
+    ///
+    /// The result is kept in a bounded cache, so formatting the same
+    /// backtrace again does not read the file again.
+    fn read(frame_path: &FramePath, lineno: u32) -> Option<Self> {
+        static SOURCE_CACHE: OnceLock<Mutex<SourceCache>> = OnceLock::new();
+
+        if frame_path.split_path.is_some() || lineno == 0 {
+            return None;
+        }
+
+        let cache = SOURCE_CACHE.get_or_init(Default::default);
+        let key = (PathBuf::from(&frame_path.raw_path), lineno);
+        if let Some(source) = cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&key)
+        {
+            return source;
+        }
+
+        // The file is read without holding the lock, so that threads formatting
+        // backtraces at the same time do not wait for each other's reads
+        let source = Self::read_uncached(&key.0, lineno);
+        cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(key, source.clone());
+        source
+    }
+
+    fn read_uncached(path: &Path, lineno: u32) -> Option<Self> {
+        let path = project_source_path(path)?;
+        let first_lineno = lineno.saturating_sub(Self::CONTEXT_LINES).max(1);
+        let file = std::fs::File::open(path).ok()?;
+        let lines = std::io::BufRead::lines(std::io::BufReader::new(file))
+            .skip(first_lineno as usize - 1)
+            .take((lineno - first_lineno + Self::CONTEXT_LINES + 1) as usize)
+            .collect::<Result<Vec<String>, _>>()
+            .ok()?;
+        if lines.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            first_lineno,
+            lines,
+        })
+    }
+}
+
+/// Returns the canonical path of a source file, or `None` if the file does
+/// not exist or is not inside the current working directory.
+///
+/// Relative paths are resolved against the current working directory, so a
+/// relative path leaving it, such as `../secret.rs`, is rejected as well.
+fn project_source_path(path: &Path) -> Option<PathBuf> {
+    let current_dir = std::env::current_dir().ok()?.canonicalize().ok()?;
+    let path = current_dir.join(path).canonicalize().ok()?;
+    path.starts_with(&current_dir).then_some(path)
+}
+
 impl FramePath {
     fn new(path: BytesOrWideString<'_>) -> Self {
         let path_str = path.to_string();
@@ -1124,16 +2091,77 @@ pub trait BacktraceExt: Sized {
     ///
     /// let report = report!(io::Error::other("error")).attach_backtrace_with_filter(&filter);
     /// ```
-    fn attach_backtrace_with_filter(self, filter: &BacktraceFilter) -> Self;
+    ///
+    /// Any [`BacktraceFilterConfig`] can be used, including a
+    /// [`BacktraceFilterOwned`] built at runtime:
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use rootcause::report;
+    /// use rootcause_backtrace::{BacktraceExt, BacktraceFilterOwned};
+    ///
+    /// let filter = BacktraceFilterOwned {
+    ///     skipped_middle_crates: vec!["tokio".to_string()],
+    ///     ..BacktraceFilterOwned::default()
+    /// };
+    ///
+    /// let report = report!(io::Error::other("error")).attach_backtrace_with_filter(&filter);
+    /// ```
+    fn attach_backtrace_with_filter(self, filter: &dyn BacktraceFilterConfig) -> Self;
+
+    /// Attaches a [`LazyBacktrace`] to the report using the default filter.
+    ///
+    /// Only the frames are captured when this is called. Their symbols are
+    /// resolved when the report is formatted, so reports that are handled
+    /// without being printed stay cheap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use rootcause::report;
+    /// use rootcause_backtrace::BacktraceExt;
+    ///
+    /// let report = report!(io::Error::other("error")).attach_backtrace_lazy();
+    /// ```
+    fn attach_backtrace_lazy(self) -> Self {
+        self.attach_backtrace_lazy_with_filter(BacktraceFilter::DEFAULT)
+    }
+
+    /// Attaches a [`LazyBacktrace`] to the report using the specified filter.
+    ///
+    /// The filter is applied when the backtrace is resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use rootcause::report;
+    /// use rootcause_backtrace::{BacktraceExt, BacktraceFilter};
+    ///
+    /// let filter = BacktraceFilter {
+    ///     max_entry_count: 10,
+    ///     ..BacktraceFilter::DEFAULT
+    /// };
+    ///
+    /// let report = report!(io::Error::other("error")).attach_backtrace_lazy_with_filter(filter);
+    /// ```
+    fn attach_backtrace_lazy_with_filter<F>(self, filter: F) -> Self
+    where
+        F: BacktraceFilterConfig + Send + Sync + 'static;
 }
 
 impl<C: ?Sized, T> BacktraceExt for Report<C, markers::Mutable, T>
 where
     Backtrace: ObjectMarkerFor<T>,
+    LazyBacktrace: ObjectMarkerFor<T>,
 {
-    fn attach_backtrace_with_filter(mut self, filter: &BacktraceFilter) -> Self {
+    fn attach_backtrace_with_filter(mut self, filter: &dyn BacktraceFilterConfig) -> Self {
         if let Some(backtrace) = Backtrace::capture(filter) {
-            if filter.show_full_path {
+            if filter.show_full_path() {
                 self = self.attach_custom::<BacktraceHandler<true>, _>(backtrace);
             } else {
                 self = self.attach_custom::<BacktraceHandler<false>, _>(backtrace);
@@ -1141,24 +2169,198 @@ where
         }
         self
     }
+
+    fn attach_backtrace_lazy_with_filter<F>(self, filter: F) -> Self
+    where
+        F: BacktraceFilterConfig + Send + Sync + 'static,
+    {
+        let show_full_path = filter.show_full_path();
+        let backtrace = LazyBacktrace::capture(filter);
+        if show_full_path {
+            self.attach_custom::<BacktraceHandler<true>, _>(backtrace)
+        } else {
+            self.attach_custom::<BacktraceHandler<false>, _>(backtrace)
+        }
+    }
 }
 
 impl<C: ?Sized, V, T> BacktraceExt for Result<V, Report<C, markers::Mutable, T>>
 where
     Backtrace: ObjectMarkerFor<T>,
+    LazyBacktrace: ObjectMarkerFor<T>,
 {
-    fn attach_backtrace_with_filter(self, filter: &BacktraceFilter) -> Self {
+    fn attach_backtrace_with_filter(self, filter: &dyn BacktraceFilterConfig) -> Self {
         match self {
             Ok(v) => Ok(v),
             Err(report) => Err(report.attach_backtrace_with_filter(filter)),
         }
     }
+
+    fn attach_backtrace_lazy_with_filter<F>(self, filter: F) -> Self
+    where
+        F: BacktraceFilterConfig + Send + Sync + 'static,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(report) => Err(report.attach_backtrace_lazy_with_filter(filter)),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // ── json ──────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn backtrace_json() {
+        let backtrace = Backtrace {
+            entries: vec![
+                BacktraceEntry::Frame(Frame {
+                    sym_demangled: "app::main".to_string(),
+                    frame_path: None,
+                    lineno: Some(12),
+                }),
+                BacktraceEntry::OmittedFrames {
+                    count: 3,
+                    skipped_crate: Cow::Borrowed("std"),
+                },
+            ],
+            total_omitted_frames: 3,
+            frames_shared_with_child: 0,
+        };
+        let mut writer = rootcause::handlers::JsonWriter::new(String::new());
+        BacktraceHandler::<false>::json(&backtrace, &mut writer).unwrap();
+        assert_eq!(
+            writer.into_inner(),
+            r#"[{"function":"app::main","file":null,"line":12},{"omitted_count":3,"skipped_crate":"std"}]"#
+        );
+    }
+
+    #[test]
+    fn backtrace_stack_frames() {
+        let backtrace = Backtrace {
+            entries: vec![
+                frame("app::inner", 3),
+                BacktraceEntry::OmittedFrames {
+                    count: 3,
+                    skipped_crate: Cow::Borrowed("std"),
+                },
+                frame("app::main", 12),
+            ],
+            total_omitted_frames: 3,
+            frames_shared_with_child: 0,
+        };
+        let frames = BacktraceHandler::<false>::stack_frames(&backtrace).unwrap();
+        assert_eq!(
+            frames,
+            [
+                StackFrame::new("app::inner", None, Some(3)),
+                StackFrame::new("app::main", None, Some(12)),
+            ]
+        );
+    }
+
+    // ── deduplicate_with_children ─────────────────────────────────────────────
+
+    fn frame(sym_demangled: &str, lineno: u32) -> BacktraceEntry {
+        BacktraceEntry::Frame(Frame {
+            sym_demangled: sym_demangled.to_string(),
+            frame_path: None,
+            lineno: Some(lineno),
+        })
+    }
+
+    #[test]
+    fn backtrace_remove_suffix_shared_with_child() {
+        let child = Backtrace {
+            entries: vec![
+                frame("app::read_config", 40),
+                frame("app::load", 20),
+                frame("app::main", 10),
+                BacktraceEntry::OmittedFrames {
+                    count: 4,
+                    skipped_crate: Cow::Borrowed("tokio"),
+                },
+            ],
+            total_omitted_frames: 4,
+            frames_shared_with_child: 0,
+        };
+        let mut parent = Backtrace {
+            entries: vec![
+                frame("app::load", 22),
+                frame("app::main", 10),
+                BacktraceEntry::OmittedFrames {
+                    count: 4,
+                    skipped_crate: Cow::Borrowed("tokio"),
+                },
+            ],
+            total_omitted_frames: 4,
+            frames_shared_with_child: 0,
+        };
+
+        let shared_entries = parent.common_suffix_len(&child);
+        assert_eq!(shared_entries, 2);
+
+        parent.remove_shared_suffix(shared_entries);
+        assert_eq!(parent.entries, [frame("app::load", 22)]);
+        assert_eq!(parent.frames_shared_with_child, 5);
+        assert_eq!(parent.total_omitted_frames, 0);
+
+        let output = format!("{}", FormatBacktrace(&parent));
+        assert!(output.contains("... 5 frame(s) shared with the child backtrace"));
+        assert!(!output.contains("frame(s) omitted"));
+    }
+
+    #[test]
+    fn collector_deduplicate_with_children_counts_frames_once() {
+        // Every frame is either an entry, omitted or shared with the child
+        fn frame_count(backtrace: &Backtrace) -> usize {
+            let entries = backtrace
+                .entries
+                .iter()
+                .filter(|entry| matches!(entry, BacktraceEntry::Frame(_)))
+                .count();
+            entries + backtrace.total_omitted_frames + backtrace.frames_shared_with_child
+        }
+
+        let filter = BacktraceFilterOwned {
+            skipped_initial_crates: Vec::new(),
+            skipped_middle_crates: vec!["std".to_string(), "core".to_string()],
+            skipped_final_crates: Vec::new(),
+            max_entry_count: 1000,
+            show_full_path: false,
+        };
+        let child = rootcause::report!("child").attach_backtrace_with_filter(&filter);
+        let children: ReportCollection = core::iter::once(child.into_cloneable()).collect();
+
+        let mut attachments = Vec::new();
+        for deduplicate in [false, true] {
+            let collector =
+                BacktraceCollector::new(filter.clone()).with_deduplicate_with_children(deduplicate);
+            attachments.push(collector.capture_for(&children).unwrap());
+        }
+        let [full, deduplicated] = &attachments[..] else {
+            unreachable!()
+        };
+        let full = full.downcast_inner::<Backtrace>().unwrap();
+        let deduplicated = deduplicated.downcast_inner::<Backtrace>().unwrap();
+
+        assert_eq!(full.frames_shared_with_child, 0);
+        assert!(deduplicated.frames_shared_with_child > 0);
+        assert!(deduplicated.entries.len() < full.entries.len());
+        assert_eq!(frame_count(deduplicated), frame_count(full));
+    }
+
+    struct FormatBacktrace<'a>(&'a Backtrace);
+
+    impl fmt::Display for FormatBacktrace<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            BacktraceHandler::<false>::display(self.0, f)
+        }
+    }
+
     // ── match_std_library_path ────────────────────────────────────────────────
 
     #[test]
@@ -1290,4 +2492,237 @@ mod tests {
         let path = "/lib/rustlib/src/rust/library/std/src/io/mod.rs";
         assert!(match_cargo_registry_path(path).is_none());
     }
+
+    // ── BacktraceFilterOwned ──────────────────────────────────────────────────
+
+    #[test]
+    fn owned_filter_from_static_filter() {
+        let filter = BacktraceFilterOwned::from(BacktraceFilter::DEFAULT);
+        for crate_name in ["backtrace", "rootcause", "tokio", "std", "my-app"] {
+            assert_eq!(
+                filter.skips_initial_crate(crate_name),
+                BacktraceFilter::DEFAULT.skips_initial_crate(crate_name)
+            );
+            assert_eq!(
+                filter.skips_middle_crate(crate_name),
+                BacktraceFilter::DEFAULT.skips_middle_crate(crate_name)
+            );
+            assert_eq!(
+                filter.skips_final_crate(crate_name),
+                BacktraceFilter::DEFAULT.skips_final_crate(crate_name)
+            );
+        }
+        assert_eq!(
+            filter.max_entry_count,
+            BacktraceFilter::DEFAULT.max_entry_count
+        );
+        assert_eq!(
+            filter.show_full_path,
+            BacktraceFilter::DEFAULT.show_full_path
+        );
+    }
+
+    #[test]
+    fn owned_filter_runtime_crate_list() {
+        let crates = String::from("hyper,tower");
+        let filter = BacktraceFilterOwned {
+            skipped_middle_crates: crates.split(',').map(String::from).collect(),
+            ..BacktraceFilterOwned::default()
+        };
+        assert!(filter.skips_middle_crate("hyper"));
+        assert!(filter.skips_middle_crate("tower"));
+        assert!(!filter.skips_middle_crate("tokio"));
+    }
+
+    #[test]
+    fn owned_filter_limits_entry_count() {
+        let filter = BacktraceFilterOwned {
+            skipped_initial_crates: Vec::new(),
+            skipped_middle_crates: Vec::new(),
+            skipped_final_crates: Vec::new(),
+            max_entry_count: 1,
+            show_full_path: false,
+        };
+        let backtrace = Backtrace::capture(&filter).expect("failed to capture backtrace");
+        assert_eq!(backtrace.entries.len(), 1);
+        assert!(backtrace.total_omitted_frames > 0);
+    }
+
+    // ── source snippets ───────────────────────────────────────────────────────
+
+    fn project_frame_path(raw_path: String) -> FramePath {
+        FramePath {
+            raw_path,
+            crate_name: None,
+            split_path: None,
+        }
+    }
+
+    /// A file with the lines `line 1` to `line 12`
+    const FIXTURE_PATH: &str = "tests/fixtures/frame_source.txt";
+
+    #[test]
+    fn frame_source_reads_context_lines() {
+        let path = project_frame_path(format!("{}/{FIXTURE_PATH}", env!("CARGO_MANIFEST_DIR")));
+        let source = FrameSource::read(&path, 1).unwrap();
+        assert_eq!(source.first_lineno, 1);
+        assert_eq!(source.lines, ["line 1", "line 2", "line 3"]);
+
+        let source = FrameSource::read(&path, 10).unwrap();
+        assert_eq!(source.first_lineno, 8);
+        assert_eq!(
+            source.lines,
+            ["line 8", "line 9", "line 10", "line 11", "line 12"]
+        );
+
+        let source = FrameSource::read(&path, 12).unwrap();
+        assert_eq!(source.lines, ["line 10", "line 11", "line 12"]);
+    }
+
+    #[test]
+    fn frame_source_omitted_when_unreadable() {
+        let path = project_frame_path("src/does_not_exist.rs".to_string());
+        assert_eq!(FrameSource::read(&path, 1), None);
+
+        let path = project_frame_path("/outside/the/project.rs".to_string());
+        assert_eq!(FrameSource::read(&path, 1), None);
+
+        // Relative paths must stay inside the project as well
+        let path = project_frame_path("../Cargo.toml".to_string());
+        assert!(Path::new("../Cargo.toml").exists());
+        assert_eq!(FrameSource::read(&path, 1), None);
+    }
+
+    #[test]
+    fn source_cache_evicts_oldest_entry() {
+        let mut cache = SourceCache::default();
+        for lineno in 0..=SourceCache::CAPACITY as u32 {
+            cache.insert((PathBuf::from("src/main.rs"), lineno), None);
+        }
+        assert_eq!(cache.sources.len(), SourceCache::CAPACITY);
+        assert_eq!(cache.get(&(PathBuf::from("src/main.rs"), 0)), None);
+        assert_eq!(cache.get(&(PathBuf::from("src/main.rs"), 1)), Some(None));
+    }
+
+    #[test]
+    fn frame_source_display() {
+        let backtrace = Backtrace {
+            entries: vec![BacktraceEntry::Frame(Frame {
+                sym_demangled: "app::main".to_string(),
+                frame_path: Some(project_frame_path(FIXTURE_PATH.to_string())),
+                lineno: Some(10),
+            })],
+            total_omitted_frames: 0,
+            frames_shared_with_child: 0,
+        };
+
+        let output = rootcause::report!("error")
+            .attach_custom::<BacktraceHandler<false, true>, _>(backtrace.clone())
+            .to_string();
+        assert!(output.contains("     9 | line 9"));
+        assert!(output.contains("   > 10 | line 10"));
+
+        let output = rootcause::report!("error")
+            .attach_custom::<BacktraceHandler<false>, _>(backtrace)
+            .to_string();
+        assert!(!output.contains("line 10"));
+    }
+
+    // ── lazy backtraces ───────────────────────────────────────────────────────
+
+    #[test]
+    fn lazy_backtrace_resolves_when_formatted() {
+        let report = rootcause::report!("error").attach_backtrace_lazy();
+        let lazy_backtrace = || {
+            report
+                .attachments()
+                .iter()
+                .find_map(|attachment| attachment.downcast_inner::<LazyBacktrace>())
+                .unwrap()
+        };
+        assert!(!lazy_backtrace().is_resolved());
+
+        let _ = report.to_string();
+        assert!(lazy_backtrace().is_resolved());
+    }
+
+    #[test]
+    fn collector_resolves_lazily() {
+        let collector =
+            BacktraceCollector::new(BacktraceFilter::DEFAULT).with_lazy_resolution(true);
+        let attachment = collector
+            .capture_for(&ReportCollection::<Dynamic>::new())
+            .unwrap();
+        let backtrace = attachment.downcast_inner::<LazyBacktrace>().unwrap();
+        assert!(!backtrace.is_resolved());
+    }
+
+    #[test]
+    fn collector_deduplicates_with_lazy_children() {
+        let filter = BacktraceFilterOwned {
+            skipped_initial_crates: Vec::new(),
+            skipped_middle_crates: Vec::new(),
+            skipped_final_crates: Vec::new(),
+            max_entry_count: 1000,
+            show_full_path: false,
+        };
+        let child = rootcause::report!("child").attach_backtrace_lazy_with_filter(filter.clone());
+        let children: ReportCollection = core::iter::once(child.into_cloneable()).collect();
+
+        for resolve_lazily in [false, true] {
+            let collector = BacktraceCollector::new(filter.clone())
+                .with_deduplicate_with_children(true)
+                .with_lazy_resolution(resolve_lazily);
+            let attachment = collector.capture_for(&children).unwrap();
+            let backtrace = match attachment.downcast_inner::<Backtrace>() {
+                Some(backtrace) => backtrace,
+                None => {
+                    let lazy = attachment.downcast_inner::<LazyBacktrace>().unwrap();
+                    assert!(!lazy.is_resolved());
+                    lazy.resolve().unwrap()
+                }
+            };
+            assert!(backtrace.frames_shared_with_child > 0);
+        }
+    }
+
+    #[test]
+    fn lazy_backtrace_matches_eager_filtering() {
+        // Describes the entries from the frame of this test onwards, as the
+        // frames above it differ between the two captures
+        fn entries_from_test(backtrace: &Backtrace) -> Vec<String> {
+            backtrace
+                .entries
+                .iter()
+                .map(|entry| match entry {
+                    BacktraceEntry::Frame(frame) => frame.sym_demangled.clone(),
+                    BacktraceEntry::OmittedFrames {
+                        count,
+                        skipped_crate,
+                    } => format!("{count} frames of {skipped_crate}"),
+                })
+                .skip_while(|entry| !entry.contains("lazy_backtrace_matches_eager_filtering"))
+                .collect()
+        }
+
+        let filter = BacktraceFilterOwned {
+            skipped_initial_crates: Vec::new(),
+            skipped_middle_crates: vec!["std".to_string(), "core".to_string()],
+            skipped_final_crates: Vec::new(),
+            max_entry_count: 1000,
+            show_full_path: false,
+        };
+        let lazy = LazyBacktrace::capture(filter.clone());
+        let eager = Backtrace::capture(&filter).expect("failed to capture backtrace");
+        let lazy = lazy.resolve().expect("failed to resolve backtrace");
+
+        let lazy_entries = entries_from_test(lazy);
+        assert!(!lazy_entries.is_empty());
+        assert_eq!(lazy_entries, entries_from_test(&eager));
+        assert!(
+            lazy_entries
+                .iter()
+                .any(|entry| entry.ends_with("frames of std") || entry.ends_with("frames of core"))
+        );
+    }
 }
@@ -0,0 +1,136 @@
+#![deny(
+    missing_docs,
+    elided_lifetimes_in_paths,
+    unsafe_code,
+    rustdoc::invalid_rust_codeblocks,
+    rustdoc::broken_intra_doc_links,
+    unused_doc_comments
+)]
+
+//! Derive macros for the [rootcause](https://docs.rs/rootcause) error
+//! reporting library.
+//!
+//! This crate should not be used directly. Enable the `derive` feature of
+//! `rootcause` instead, which re-exports [`macro@RootcauseContext`] as
+//! `rootcause::handlers::RootcauseContext`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, Path, parse_macro_input, parse_quote};
+
+/// Derives `rootcause::handlers::RootcauseContext`, which selects the handler
+/// used by the `report!` macro for the type.
+///
+/// By default, the `Error` handler is used. The handler can be changed with
+/// one of these attributes:
+///
+/// - `#[rootcause(handler = path::to::Handler)]` uses the given handler.
+/// - `#[rootcause(display = "...")]` formats the context with the given
+///   format string, in which the fields of a struct with named fields can be
+///   referred to by name.
+#[proc_macro_derive(RootcauseContext, attributes(rootcause))]
+pub fn derive_rootcause_context(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The handler selected by the `#[rootcause(...)]` attributes.
+enum HandlerChoice {
+    Default,
+    Handler(Path),
+    Display(LitStr),
+}
+
+fn parse_attributes(input: &DeriveInput) -> syn::Result<HandlerChoice> {
+    let mut choice = HandlerChoice::Default;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("rootcause") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if !matches!(choice, HandlerChoice::Default) {
+                return Err(meta.error("only one of `handler` and `display` can be used"));
+            }
+            if meta.path.is_ident("handler") {
+                choice = HandlerChoice::Handler(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("display") {
+                choice = HandlerChoice::Display(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `handler` or `display`"))
+            }
+        })?;
+    }
+    Ok(choice)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (handler, display_impl): (Path, TokenStream2) = match parse_attributes(input)? {
+        HandlerChoice::Default => (parse_quote!(::rootcause::handlers::Error), quote!()),
+        HandlerChoice::Handler(handler) => (handler, quote!()),
+        HandlerChoice::Display(format) => {
+            let bindings = display_bindings(input)?;
+            let display_impl = quote! {
+                impl #impl_generics ::rootcause::__private::derive::ContextDisplay
+                    for #name #ty_generics #where_clause
+                {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        #[allow(unused_variables)]
+                        let #bindings = self;
+                        ::core::write!(f, #format)
+                    }
+                }
+            };
+            (
+                parse_quote!(::rootcause::__private::derive::DisplayHandler),
+                display_impl,
+            )
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#handler: ::rootcause::handlers::ContextHandler<Self>));
+    let (_, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::rootcause::handlers::RootcauseContext
+            for #name #ty_generics #where_clause
+        {
+            type Handler = #handler;
+        }
+
+        #display_impl
+    })
+}
+
+/// Returns a pattern binding the fields of the type to local variables, so
+/// they can be captured by name in the format string.
+fn display_bindings(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`#[rootcause(display = \"...\")]` is only supported on structs",
+        ));
+    };
+    match &data.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            Ok(quote!(Self { #(#names),* }))
+        }
+        Fields::Unit => Ok(quote!(Self)),
+        Fields::Unnamed(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "`#[rootcause(display = \"...\")]` is not supported on tuple structs",
+        )),
+    }
+}
@@ -54,11 +54,12 @@ impl<A: 'static> AttachmentData<A> {
     /// attachment.
     ///
     /// This method creates the vtable for type-erased dispatch and pairs it
-    /// with the attachment data.
+    /// with the attachment data. `SEND_SYNC` must only be `true` if `A` is
+    /// `Send + Sync`.
     #[inline]
-    pub(super) fn new<H: AttachmentHandler<A>>(attachment: A) -> Self {
+    pub(super) fn new<H: AttachmentHandler<A>, const SEND_SYNC: bool>(attachment: A) -> Self {
         Self {
-            vtable: AttachmentVtable::new::<A, H>(),
+            vtable: AttachmentVtable::new::<A, H, SEND_SYNC>(),
             attachment,
         }
     }
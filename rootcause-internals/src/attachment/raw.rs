@@ -22,7 +22,7 @@
 //! provides the runtime type information needed to safely downcast and format
 //! attachments.
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::{
     any::{Any, TypeId},
     ptr::NonNull,
@@ -30,7 +30,9 @@ use core::{
 
 use crate::{
     attachment::data::AttachmentData,
-    handlers::{AttachmentFormattingStyle, AttachmentHandler, FormattingFunction},
+    handlers::{
+        AttachmentFormattingStyle, AttachmentHandler, FormattingFunction, JsonSink, StackFrame,
+    },
     util::Erased,
 };
 
@@ -69,14 +71,45 @@ impl RawAttachment {
     /// attachment.
     ///
     /// The returned attachment will embed the specified attachment and use the
-    /// specified handler for all operations.
+    /// specified handler for all operations. The attachment is not recorded as
+    /// `Send + Sync`, see [`RawAttachment::new_send_sync`].
     #[inline]
     pub fn new<A, H>(attachment: A) -> Self
     where
         A: 'static,
         H: AttachmentHandler<A>,
     {
-        let ptr = Box::new(AttachmentData::new::<H>(attachment));
+        let ptr = Box::new(AttachmentData::new::<H, false>(attachment));
+        let ptr: *mut AttachmentData<A> = Box::into_raw(ptr);
+        let ptr: *mut AttachmentData<Erased> = ptr.cast::<AttachmentData<Erased>>();
+
+        // SAFETY: `Box::into_raw` returns a non-null pointer
+        let ptr: NonNull<AttachmentData<Erased>> = unsafe {
+            // @add-unsafe-context: Erased
+            NonNull::new_unchecked(ptr)
+        };
+
+        Self {
+            // SAFETY:
+            // 1. See above
+            // 2. N/A
+            // 3. N/A
+            // 4. The Box is consumed, so we are the sole owner
+            ptr,
+        }
+    }
+
+    /// Creates a new [`RawAttachment`] like [`RawAttachment::new`],
+    /// additionally recording that the attachment is `Send + Sync`.
+    ///
+    /// The recorded flag can be read with [`RawAttachmentRef::is_send_sync`].
+    #[inline]
+    pub fn new_send_sync<A, H>(attachment: A) -> Self
+    where
+        A: Send + Sync + 'static,
+        H: AttachmentHandler<A>,
+    {
+        let ptr = Box::new(AttachmentData::new::<H, true>(attachment));
         let ptr: *mut AttachmentData<A> = Box::into_raw(ptr);
         let ptr: *mut AttachmentData<Erased> = ptr.cast::<AttachmentData<Erased>>();
 
@@ -229,6 +262,19 @@ impl<'a> RawAttachmentRef<'a> {
         self.vtable().handler_type_id()
     }
 
+    /// Returns the [`core::any::type_name`] of the attachment handler.
+    #[inline]
+    pub fn attachment_handler_type_name(self) -> &'static str {
+        self.vtable().handler_type_name()
+    }
+
+    /// Returns whether the attachment was recorded as `Send + Sync` when it
+    /// was created using [`RawAttachment::new_send_sync`].
+    #[inline]
+    pub fn is_send_sync(self) -> bool {
+        self.vtable().is_send_sync()
+    }
+
     /// Formats the attachment by using the [`AttachmentHandler::display`]
     /// method specified by the handler used to create the
     /// [`AttachmentData`].
@@ -259,6 +305,38 @@ impl<'a> RawAttachmentRef<'a> {
         }
     }
 
+    /// Emits the structured representation of the attachment by using the
+    /// [`AttachmentHandler::json`] method specified by the handler used to
+    /// create the [`AttachmentData`].
+    #[inline]
+    pub fn attachment_json(self, sink: &mut dyn JsonSink) -> core::fmt::Result {
+        let vtable = self.vtable();
+
+        // SAFETY:
+        // 1. The vtable returned by `self.vtable()` is guaranteed to match the data in
+        //    the `AttachmentData`.
+        unsafe {
+            // @add-unsafe-context: AttachmentData
+            vtable.json(self, sink)
+        }
+    }
+
+    /// Gets the stack frames of the attachment by using the
+    /// [`AttachmentHandler::stack_frames`] method specified by the handler
+    /// used to create the [`AttachmentData`].
+    #[inline]
+    pub fn attachment_stack_frames(self) -> Option<Vec<StackFrame<'a>>> {
+        let vtable = self.vtable();
+
+        // SAFETY:
+        // 1. The vtable returned by `self.vtable()` is guaranteed to match the data in
+        //    the `AttachmentData`.
+        unsafe {
+            // @add-unsafe-context: AttachmentData
+            vtable.stack_frames(self)
+        }
+    }
+
     /// Returns a [`&dyn Any`](Any) view of the attachment.
     ///
     /// The returned reference can be downcast using
@@ -538,6 +616,15 @@ mod tests {
         assert_eq!(ptr1, ptr2);
     }
 
+    #[test]
+    fn test_raw_attachment_is_send_sync() {
+        let attachment = RawAttachment::new::<i32, HandlerI32>(1);
+        assert!(!attachment.as_ref().is_send_sync());
+
+        let attachment = RawAttachment::new_send_sync::<i32, HandlerI32>(2);
+        assert!(attachment.as_ref().is_send_sync());
+    }
+
     #[test]
     fn test_raw_attachment_downcast() {
         let int_attachment = RawAttachment::new::<i32, HandlerI32>(42);
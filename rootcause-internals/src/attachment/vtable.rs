@@ -16,7 +16,7 @@
 //! references via [`AttachmentVtable::new`], which pairs the function pointers
 //! with specific types `A` and `H` at compile time.
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::{
     any::{self, Any, TypeId},
     ptr::NonNull,
@@ -27,7 +27,9 @@ use crate::{
         data::AttachmentData,
         raw::{RawAttachmentMut, RawAttachmentRef},
     },
-    handlers::{AttachmentFormattingStyle, AttachmentHandler, FormattingFunction},
+    handlers::{
+        AttachmentFormattingStyle, AttachmentHandler, FormattingFunction, JsonSink, StackFrame,
+    },
     util::Erased,
 };
 
@@ -38,7 +40,8 @@ use crate::{
 ///
 /// # Safety Invariant
 ///
-/// The fields `drop`, `display`, `debug`, and `preferred_formatting_style` are
+/// The fields `drop`, `display`, `debug`, `json`, `stack_frames` and
+/// `preferred_formatting_style` are
 /// guaranteed to point to the functions defined below instantiated with the
 /// attachment type `A` and handler type `H` that were used to create this
 /// [`AttachmentVtable`].
@@ -52,6 +55,14 @@ pub(crate) struct AttachmentVtable {
     /// Gets the [`TypeId`] of the handler that was used to create this
     /// [`AttachmentVtable`].
     handler_type_id: fn() -> TypeId,
+    /// Gets the [`any::type_name`] of the handler that was used to create
+    /// this [`AttachmentVtable`].
+    handler_type_name: fn() -> &'static str,
+    /// Whether the attachment type is known to be `Send + Sync`.
+    ///
+    /// This is only `true` if the attachment type was required to be
+    /// `Send + Sync` when this [`AttachmentVtable`] was created.
+    send_sync: bool,
     /// Drops the [`Box<AttachmentData<A>>`] instance pointed to by this
     /// pointer.
     drop: unsafe fn(NonNull<AttachmentData<Erased>>),
@@ -59,6 +70,12 @@ pub(crate) struct AttachmentVtable {
     display: unsafe fn(RawAttachmentRef<'_>, &mut core::fmt::Formatter<'_>) -> core::fmt::Result,
     /// Formats the attachment using the `debug` method on the handler.
     debug: unsafe fn(RawAttachmentRef<'_>, &mut core::fmt::Formatter<'_>) -> core::fmt::Result,
+    /// Emits the structured representation of the attachment using the `json`
+    /// method on the handler.
+    json: unsafe fn(RawAttachmentRef<'_>, &mut dyn JsonSink) -> core::fmt::Result,
+    /// Gets the stack frames of the attachment using the `stack_frames`
+    /// method on the handler.
+    stack_frames: unsafe fn(RawAttachmentRef<'_>) -> Option<Vec<StackFrame<'_>>>,
     /// Get the formatting style preferred by the attachment when formatted as
     /// part of a report.
     preferred_formatting_style:
@@ -72,15 +89,22 @@ pub(crate) struct AttachmentVtable {
 impl AttachmentVtable {
     /// Creates a new [`AttachmentVtable`] for the attachment type `A` and the
     /// handler type `H`.
-    pub(super) const fn new<A: 'static, H: AttachmentHandler<A>>() -> &'static Self {
+    ///
+    /// `SEND_SYNC` must only be `true` if `A` is `Send + Sync`.
+    pub(super) const fn new<A: 'static, H: AttachmentHandler<A>, const SEND_SYNC: bool>()
+    -> &'static Self {
         const {
             &Self {
                 type_id: TypeId::of::<A>,
                 type_name: any::type_name::<A>,
                 handler_type_id: TypeId::of::<H>,
+                handler_type_name: any::type_name::<H>,
+                send_sync: SEND_SYNC,
                 drop: drop::<A>,
                 display: display::<A, H>,
                 debug: debug::<A, H>,
+                json: json::<A, H>,
+                stack_frames: stack_frames::<A, H>,
                 preferred_formatting_style: preferred_formatting_style::<A, H>,
                 attachment_as_any: attachment_as_any::<A>,
                 attachment_as_any_mut: attachment_as_any_mut::<A>,
@@ -109,6 +133,20 @@ impl AttachmentVtable {
         (self.handler_type_id)()
     }
 
+    /// Gets the [`any::type_name`] of the handler that was used to create
+    /// this [`AttachmentVtable`].
+    #[inline]
+    pub(super) fn handler_type_name(&self) -> &'static str {
+        (self.handler_type_name)()
+    }
+
+    /// Returns whether the attachment type that was used to create this
+    /// [`AttachmentVtable`] is known to be `Send + Sync`.
+    #[inline]
+    pub(super) fn is_send_sync(&self) -> bool {
+        self.send_sync
+    }
+
     /// Drops the `Box<AttachmentData<A>>` instance pointed to by this pointer.
     ///
     /// # Safety
@@ -190,6 +228,62 @@ impl AttachmentVtable {
         }
     }
 
+    /// Emits the structured representation of the attachment using the
+    /// [`H::json`] function used when creating this [`AttachmentVtable`].
+    ///
+    /// [`H::json`]: AttachmentHandler::json
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    ///
+    /// 1. This [`AttachmentVtable`] must be a vtable for the attachment type
+    ///    stored in the [`RawAttachmentRef`].
+    #[inline]
+    pub(super) unsafe fn json(
+        &self,
+        ptr: RawAttachmentRef<'_>,
+        sink: &mut dyn JsonSink,
+    ) -> core::fmt::Result {
+        // SAFETY: We know that the `self.json` field points to the function
+        // `json::<A, H>` below. That function's safety requirements are upheld:
+        // 1. Guaranteed by the caller
+        unsafe {
+            // @add-unsafe-context: json
+            // @add-unsafe-context: RawAttachmentRef
+            // @add-unsafe-context: AttachmentData
+            (self.json)(ptr, sink)
+        }
+    }
+
+    /// Gets the stack frames of the attachment using the [`H::stack_frames`]
+    /// function used when creating this [`AttachmentVtable`].
+    ///
+    /// [`H::stack_frames`]: AttachmentHandler::stack_frames
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    ///
+    /// 1. This [`AttachmentVtable`] must be a vtable for the attachment type
+    ///    stored in the [`RawAttachmentRef`].
+    #[inline]
+    pub(super) unsafe fn stack_frames<'a>(
+        &self,
+        ptr: RawAttachmentRef<'a>,
+    ) -> Option<Vec<StackFrame<'a>>> {
+        // SAFETY: We know that the `self.stack_frames` field points to the function
+        // `stack_frames::<A, H>` below. That function's safety requirements are
+        // upheld:
+        // 1. Guaranteed by the caller
+        unsafe {
+            // @add-unsafe-context: stack_frames
+            // @add-unsafe-context: RawAttachmentRef
+            // @add-unsafe-context: AttachmentData
+            (self.stack_frames)(ptr)
+        }
+    }
+
     /// Gets the preferred formatting style using the
     /// [`H::preferred_formatting_style`] function used when creating this
     /// [`AttachmentVtable`].
@@ -334,6 +428,43 @@ unsafe fn debug<A: 'static, H: AttachmentHandler<A>>(
     H::debug(attachment, formatter)
 }
 
+/// Emits the structured representation of an attachment using its handler's
+/// json implementation.
+///
+/// # Safety
+///
+/// The caller must ensure:
+///
+/// 1. The type `A` matches the actual attachment type stored in the
+///    [`AttachmentData`]
+unsafe fn json<A: 'static, H: AttachmentHandler<A>>(
+    ptr: RawAttachmentRef<'_>,
+    sink: &mut dyn JsonSink,
+) -> core::fmt::Result {
+    // SAFETY:
+    // 1. Guaranteed by the caller
+    let attachment: &A = unsafe { ptr.attachment_downcast_unchecked::<A>() };
+    H::json(attachment, sink)
+}
+
+/// Gets the stack frames of an attachment using its handler's stack_frames
+/// implementation.
+///
+/// # Safety
+///
+/// The caller must ensure:
+///
+/// 1. The type `A` matches the actual attachment type stored in the
+///    [`AttachmentData`]
+unsafe fn stack_frames<'a, A: 'static, H: AttachmentHandler<A>>(
+    ptr: RawAttachmentRef<'a>,
+) -> Option<Vec<StackFrame<'a>>> {
+    // SAFETY:
+    // 1. Guaranteed by the caller
+    let attachment: &A = unsafe { ptr.attachment_downcast_unchecked::<A>() };
+    H::stack_frames(attachment)
+}
+
 /// Gets the preferred formatting style using the
 /// [`H::preferred_formatting_style`] function.
 ///
@@ -406,8 +537,8 @@ mod tests {
     #[test]
     fn test_attachment_vtable_eq() {
         // Test that vtables have proper static lifetime and can be safely shared
-        let vtable1 = AttachmentVtable::new::<i32, HandlerI32>();
-        let vtable2 = AttachmentVtable::new::<i32, HandlerI32>();
+        let vtable1 = AttachmentVtable::new::<i32, HandlerI32, false>();
+        let vtable2 = AttachmentVtable::new::<i32, HandlerI32, false>();
 
         // Both should be the exact same static instance
         assert!(core::ptr::eq(vtable1, vtable2));
@@ -415,13 +546,22 @@ mod tests {
 
     #[test]
     fn test_attachment_type_id() {
-        let vtable = AttachmentVtable::new::<i32, HandlerI32>();
+        let vtable = AttachmentVtable::new::<i32, HandlerI32, false>();
         assert_eq!(vtable.type_id(), TypeId::of::<i32>());
     }
 
     #[test]
     fn test_attachment_type_name() {
-        let vtable = AttachmentVtable::new::<i32, HandlerI32>();
+        let vtable = AttachmentVtable::new::<i32, HandlerI32, false>();
         assert_eq!(vtable.type_name(), core::any::type_name::<i32>());
     }
+
+    #[test]
+    fn test_attachment_handler_type_name() {
+        let vtable = AttachmentVtable::new::<i32, HandlerI32, false>();
+        assert_eq!(
+            vtable.handler_type_name(),
+            core::any::type_name::<HandlerI32>()
+        );
+    }
 }
@@ -5,6 +5,9 @@
 //! handlers that control how context objects and attachments are formatted and
 //! displayed in error reports.
 
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
 /// Trait for implementing custom formatting and error-chaining behavior for
 /// report contexts.
 ///
@@ -39,6 +42,9 @@
 /// - [`preferred_formatting_style`](ContextHandler::preferred_formatting_style):
 ///   Specifies whether to use display or debug formatting when embedded in a report.
 ///   The default implementation matches the formatting of the report itself.
+/// - [`json`](ContextHandler::json): Emits a structured representation of the
+///   context. The default implementation emits the output of
+///   [`display`](ContextHandler::display) as a JSON string.
 ///
 /// # Examples
 ///
@@ -171,6 +177,55 @@ pub trait ContextHandler<C>: 'static {
     /// ```
     fn debug(value: &C, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result;
 
+    /// Emits a structured representation of the context to a [`JsonSink`].
+    ///
+    /// This method is used by formatters that produce structured output, and
+    /// when the report is formatted using [`FormattingFunction::Json`].
+    ///
+    /// The default implementation emits the output of
+    /// [`display`](ContextHandler::display) as a single JSON string, so
+    /// handlers only need to override this method if they want to provide a
+    /// more detailed structure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_internals::handlers::{ContextHandler, JsonSink};
+    ///
+    /// struct HttpError {
+    ///     status: u16,
+    ///     url: String,
+    /// }
+    ///
+    /// struct HttpErrorHandler;
+    ///
+    /// impl ContextHandler<HttpError> for HttpErrorHandler {
+    ///     fn source(_context: &HttpError) -> Option<&(dyn std::error::Error + 'static)> {
+    ///         None
+    ///     }
+    ///
+    ///     fn display(context: &HttpError, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "HTTP {} from {}", context.status, context.url)
+    ///     }
+    ///
+    ///     fn debug(context: &HttpError, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "HttpError({}, {:?})", context.status, context.url)
+    ///     }
+    ///
+    ///     fn json(context: &HttpError, sink: &mut dyn JsonSink) -> std::fmt::Result {
+    ///         sink.begin_object()?;
+    ///         sink.key("status")?;
+    ///         sink.u64(context.status.into())?;
+    ///         sink.key("url")?;
+    ///         sink.str(&context.url)?;
+    ///         sink.end_object()
+    ///     }
+    /// }
+    /// ```
+    fn json(value: &C, sink: &mut dyn JsonSink) -> core::fmt::Result {
+        sink.display(&ContextDisplay::<C, Self>(value, PhantomData))
+    }
+
     /// Specifies the preferred formatting style when this context is embedded
     /// in a report.
     ///
@@ -260,6 +315,12 @@ pub trait ContextHandler<C>: 'static {
 ///   Specifies formatting preferences including placement (inline/appendix) and
 ///   whether to use display or debug formatting. The default implementation uses
 ///   inline placement with the same formatting function as the report itself.
+/// - [`json`](AttachmentHandler::json): Emits a structured representation of
+///   the attachment. The default implementation emits the output of
+///   [`display`](AttachmentHandler::display) as a JSON string.
+/// - [`stack_frames`](AttachmentHandler::stack_frames): Returns the frames of
+///   the attachment if it is a backtrace. The default implementation returns
+///   `None`.
 ///
 /// # Examples
 ///
@@ -342,6 +403,95 @@ pub trait AttachmentHandler<A>: 'static {
     /// It should produce detailed output suitable for developers.
     fn debug(value: &A, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result;
 
+    /// Emits a structured representation of the attachment to a [`JsonSink`].
+    ///
+    /// This method is used by formatters that produce structured output, and
+    /// when the attachment is formatted using [`FormattingFunction::Json`].
+    ///
+    /// The default implementation emits the output of
+    /// [`display`](AttachmentHandler::display) as a single JSON string, so
+    /// handlers only need to override this method if they want to provide a
+    /// more detailed structure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_internals::handlers::{AttachmentHandler, JsonSink};
+    ///
+    /// struct Retries {
+    ///     attempts: u32,
+    ///     max_attempts: u32,
+    /// }
+    ///
+    /// struct RetriesHandler;
+    ///
+    /// impl AttachmentHandler<Retries> for RetriesHandler {
+    ///     fn display(value: &Retries, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "attempt {} of {}", value.attempts, value.max_attempts)
+    ///     }
+    ///
+    ///     fn debug(value: &Retries, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "Retries({}, {})", value.attempts, value.max_attempts)
+    ///     }
+    ///
+    ///     fn json(value: &Retries, sink: &mut dyn JsonSink) -> std::fmt::Result {
+    ///         sink.begin_object()?;
+    ///         sink.key("attempts")?;
+    ///         sink.u64(value.attempts.into())?;
+    ///         sink.key("max_attempts")?;
+    ///         sink.u64(value.max_attempts.into())?;
+    ///         sink.end_object()
+    ///     }
+    /// }
+    /// ```
+    fn json(value: &A, sink: &mut dyn JsonSink) -> core::fmt::Result {
+        sink.display(&AttachmentDisplay::<A, Self>(value, PhantomData))
+    }
+
+    /// Returns the stack frames of the attachment, if it is a backtrace.
+    ///
+    /// Integrations with error trackers use this to turn backtrace
+    /// attachments into native stack traces. The frames are ordered from the
+    /// innermost frame, the one that was executing when the backtrace was
+    /// captured, to the outermost one. Frames that were omitted from the
+    /// backtrace are not included.
+    ///
+    /// The default implementation returns `None`, so handlers only need to
+    /// override this method for backtrace types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_internals::handlers::{AttachmentHandler, StackFrame};
+    ///
+    /// struct Trace(Vec<(String, u32)>);
+    ///
+    /// struct TraceHandler;
+    ///
+    /// impl AttachmentHandler<Trace> for TraceHandler {
+    ///     fn display(value: &Trace, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "{} frames", value.0.len())
+    ///     }
+    ///
+    ///     fn debug(value: &Trace, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "Trace({} frames)", value.0.len())
+    ///     }
+    ///
+    ///     fn stack_frames(value: &Trace) -> Option<Vec<StackFrame<'_>>> {
+    ///         let frames = value
+    ///             .0
+    ///             .iter()
+    ///             .map(|(function, line)| StackFrame::new(function, None, Some(*line)))
+    ///             .collect();
+    ///         Some(frames)
+    ///     }
+    /// }
+    /// ```
+    fn stack_frames(value: &A) -> Option<Vec<StackFrame<'_>>> {
+        let _ = value;
+        None
+    }
+
     /// Specifies the preferred formatting style and placement for this
     /// attachment.
     ///
@@ -403,6 +553,51 @@ pub trait AttachmentHandler<A>: 'static {
     }
 }
 
+/// A frame of a backtrace attachment, as returned by
+/// [`AttachmentHandler::stack_frames`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_internals::handlers::StackFrame;
+///
+/// let frame = StackFrame::new("app::main", Some("src/main.rs"), Some(12));
+/// assert_eq!(frame.function, "app::main");
+/// assert_eq!(frame.file, Some("src/main.rs"));
+/// assert_eq!(frame.line, Some(12));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub struct StackFrame<'a> {
+    /// The name of the function of the frame
+    pub function: &'a str,
+    /// The path of the source file of the frame, if known
+    pub file: Option<&'a str>,
+    /// The line number in the source file, if known
+    pub line: Option<u32>,
+}
+
+impl<'a> StackFrame<'a> {
+    /// Creates a new [`StackFrame`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_internals::handlers::StackFrame;
+    ///
+    /// let frame = StackFrame::new("app::main", None, None);
+    /// assert_eq!(frame.file, None);
+    /// ```
+    #[must_use]
+    pub const fn new(function: &'a str, file: Option<&'a str>, line: Option<u32>) -> Self {
+        Self {
+            function,
+            file,
+            line,
+        }
+    }
+}
+
 /// Formatting preferences for a context when displayed in a report.
 ///
 /// This struct allows a [`ContextHandler`] to specify how it prefers to be
@@ -517,6 +712,8 @@ pub struct AttachmentFormattingStyle {
 /// - **[`Display`](FormattingFunction::Display)** (default): Use the
 ///   `display` method
 /// - **[`Debug`](FormattingFunction::Debug)**: Use the `debug` method
+/// - **[`Json`](FormattingFunction::Json)**: Use the `json` method to emit a
+///   structured representation
 ///
 /// # Examples
 ///
@@ -536,6 +733,147 @@ pub enum FormattingFunction {
     Display,
     /// Prefer debug formatting via the `debug` method.
     Debug,
+    /// Prefer structured formatting via the `json` method.
+    Json,
+}
+
+/// A receiver for the structured representation of a context or attachment.
+///
+/// Handlers emit their structure to a [`JsonSink`] in
+/// [`ContextHandler::json`] and [`AttachmentHandler::json`] by calling the
+/// methods in the same order as the values would appear in a JSON document.
+/// Inside an object, every value must be preceded by a call to
+/// [`key`](JsonSink::key).
+///
+/// The methods return a [`core::fmt::Result`] so that sinks writing to a
+/// [`core::fmt::Write`] can propagate errors.
+///
+/// # Examples
+///
+/// ```
+/// use core::fmt;
+///
+/// use rootcause_internals::handlers::JsonSink;
+///
+/// /// A sink that only collects the strings it receives
+/// #[derive(Default)]
+/// struct StringCollector(Vec<String>);
+///
+/// impl JsonSink for StringCollector {
+///     fn null(&mut self) -> fmt::Result {
+///         Ok(())
+///     }
+///
+///     fn bool(&mut self, _value: bool) -> fmt::Result {
+///         Ok(())
+///     }
+///
+///     fn i64(&mut self, _value: i64) -> fmt::Result {
+///         Ok(())
+///     }
+///
+///     fn u64(&mut self, _value: u64) -> fmt::Result {
+///         Ok(())
+///     }
+///
+///     fn f64(&mut self, _value: f64) -> fmt::Result {
+///         Ok(())
+///     }
+///
+///     fn str(&mut self, value: &str) -> fmt::Result {
+///         self.0.push(value.to_string());
+///         Ok(())
+///     }
+///
+///     fn begin_object(&mut self) -> fmt::Result {
+///         Ok(())
+///     }
+///
+///     fn key(&mut self, _key: &str) -> fmt::Result {
+///         Ok(())
+///     }
+///
+///     fn end_object(&mut self) -> fmt::Result {
+///         Ok(())
+///     }
+///
+///     fn begin_array(&mut self) -> fmt::Result {
+///         Ok(())
+///     }
+///
+///     fn end_array(&mut self) -> fmt::Result {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut collector = StringCollector::default();
+/// collector.display(&format_args!("{} + {}", 1, 2)).unwrap();
+/// assert_eq!(collector.0, ["1 + 2"]);
+/// ```
+pub trait JsonSink {
+    /// Emits a JSON `null`.
+    fn null(&mut self) -> core::fmt::Result;
+
+    /// Emits a JSON boolean.
+    fn bool(&mut self, value: bool) -> core::fmt::Result;
+
+    /// Emits a signed integer as a JSON number.
+    fn i64(&mut self, value: i64) -> core::fmt::Result;
+
+    /// Emits an unsigned integer as a JSON number.
+    fn u64(&mut self, value: u64) -> core::fmt::Result;
+
+    /// Emits a floating point value as a JSON number.
+    fn f64(&mut self, value: f64) -> core::fmt::Result;
+
+    /// Emits a JSON string.
+    fn str(&mut self, value: &str) -> core::fmt::Result;
+
+    /// Emits the [`Display`](core::fmt::Display) output of `value` as a JSON
+    /// string.
+    ///
+    /// The default implementation formats `value` into a temporary
+    /// [`String`](alloc::string::String) and passes it to
+    /// [`str`](JsonSink::str). Sinks can override this to avoid the
+    /// allocation.
+    fn display(&mut self, value: &dyn core::fmt::Display) -> core::fmt::Result {
+        self.str(&alloc::format!("{value}"))
+    }
+
+    /// Starts a JSON object.
+    fn begin_object(&mut self) -> core::fmt::Result;
+
+    /// Emits the key of the next value in the current object.
+    fn key(&mut self, key: &str) -> core::fmt::Result;
+
+    /// Ends the current JSON object.
+    fn end_object(&mut self) -> core::fmt::Result;
+
+    /// Starts a JSON array.
+    fn begin_array(&mut self) -> core::fmt::Result;
+
+    /// Ends the current JSON array.
+    fn end_array(&mut self) -> core::fmt::Result;
+}
+
+/// Adapter to use [`ContextHandler::display`] as a [`core::fmt::Display`]
+/// implementation.
+struct ContextDisplay<'a, C, H: ?Sized>(&'a C, PhantomData<H>);
+
+impl<C, H: ContextHandler<C> + ?Sized> core::fmt::Display for ContextDisplay<'_, C, H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        H::display(self.0, f)
+    }
+}
+
+/// Adapter to use [`AttachmentHandler::display`] as a [`core::fmt::Display`]
+/// implementation.
+struct AttachmentDisplay<'a, A, H: ?Sized>(&'a A, PhantomData<H>);
+
+impl<A, H: AttachmentHandler<A> + ?Sized> core::fmt::Display for AttachmentDisplay<'_, A, H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        H::display(self.0, f)
+    }
 }
 
 /// Specifies where an attachment should be placed when displayed in a report.
@@ -548,12 +886,19 @@ pub enum FormattingFunction {
 /// - **InlineWithHeader**: Multi-line content that needs a header for clarity
 /// - **Appendix**: Large or detailed content better suited to a separate
 ///   section
+/// - **Footer**: Help text, such as suggestions, shown after the report
+/// - **Collapsed**: Large content shown as a one-line summary unless the
+///   alternate flag is used
 /// - **Opaque**: Content that shouldn't be shown but should be counted
 /// - **Hidden**: Content that shouldn't appear at all
 ///
 /// The actual formatting system may or may not respect these preferences
 /// depending on the implementation.
 ///
+/// New placements may be added in future versions, so matches on this enum
+/// outside of rootcause need a wildcard arm. Formatters should treat unknown
+/// placements like [`Inline`](Self::Inline).
+///
 /// # Examples
 ///
 /// ```
@@ -573,10 +918,22 @@ pub enum FormattingFunction {
 ///     appendix_name: "Full Stack Trace",
 /// };
 ///
+/// // Help text shown after the report
+/// let footer = AttachmentFormattingPlacement::Footer {
+///     label: "Suggestion",
+/// };
+///
+/// // Large content that is only shown with the alternate flag
+/// let collapsed = AttachmentFormattingPlacement::Collapsed {
+///     header: "Request Body",
+///     summary: "large JSON document",
+/// };
+///
 /// // Sensitive data that should be hidden
 /// let hidden = AttachmentFormattingPlacement::Hidden;
 /// ```
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[non_exhaustive]
 pub enum AttachmentFormattingPlacement {
     /// Display the attachment inline with the error message.
     ///
@@ -604,6 +961,40 @@ pub enum AttachmentFormattingPlacement {
         appendix_name: &'static str,
     },
 
+    /// Display the attachment in a footer section after the report, preceded
+    /// by a label.
+    ///
+    /// Suitable for help text that the reader should act on, such as
+    /// suggestions for how to fix the error or notes about what went wrong.
+    Footer {
+        /// The label to display in front of the attachment, such as
+        /// `"Suggestion"` or `"Note"`
+        label: &'static str,
+    },
+
+    /// Display a one-line summary of the attachment, preceded by a header,
+    /// unless the report is formatted with the alternate flag.
+    ///
+    /// Suitable for large content that is rarely needed, such as backtraces or
+    /// big data dumps. Formatters that support this placement show
+    /// `header: summary` together with a hint on how to expand it, and show
+    /// the attachment like [`InlineWithHeader`](Self::InlineWithHeader) when
+    /// the alternate flag is set, for instance with `{:#?}`.
+    Collapsed {
+        /// The header text to display in front of the summary, and above the
+        /// attachment when it is expanded
+        header: &'static str,
+        /// The short summary to display instead of the attachment
+        ///
+        /// The summary is a static string, so it cannot be computed from the
+        /// attachment, as in `"4 KiB of JSON"`. Handlers can still choose
+        /// between several summaries depending on the attachment in
+        /// [`AttachmentHandler::preferred_formatting_style`], such as
+        /// `"small"` and `"large"`, and put details that are needed when the
+        /// attachment is collapsed in the header.
+        summary: &'static str,
+    },
+
     /// Don't display the attachment, but count it in a summary.
     ///
     /// The attachment won't be shown directly, but may appear in a message like
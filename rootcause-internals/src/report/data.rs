@@ -10,7 +10,9 @@
 //! Since [`ReportData`] can only be constructed via [`ReportData::new`] (which
 //! creates matching vtable and context), and fields cannot be modified after
 //! construction (no `pub` or `pub(crate)` fields), the types remain in sync
-//! throughout the value's lifetime.
+//! throughout the value's lifetime. The only exception is
+//! [`RawReportMut::set_context_handler`], which replaces the vtable with one
+//! created for the same context type, but a different handler.
 //!
 //! # `#[repr(C)]` Layout
 //!
@@ -63,15 +65,16 @@ impl<C: 'static> ReportData<C> {
     /// children and attachments.
     ///
     /// This method creates the vtable for type-erased dispatch and pairs it
-    /// with the report data.
+    /// with the report data. `SEND_SYNC` must only be `true` if `C` is
+    /// `Send + Sync`.
     #[inline]
-    pub(super) fn new<H: ContextHandler<C>>(
+    pub(super) fn new<H: ContextHandler<C>, const SEND_SYNC: bool>(
         context: C,
         children: Vec<RawReport>,
         attachments: Vec<RawAttachment>,
     ) -> Self {
         Self {
-            vtable: ReportVtable::new::<C, H>(),
+            vtable: ReportVtable::new::<C, H, SEND_SYNC>(),
             children,
             attachments,
             context,
@@ -141,8 +144,9 @@ impl<'a> RawReportRef<'a> {
         // SAFETY: The safety requirements for dereferencing `vtable_ptr` are upheld:
         // 1. The pointer is valid and properly aligned because it points to the first
         //    field of a valid `ReportData<C>` instance
-        // 2. The `vtable` field is initialized in `ReportData::new` and never modified,
-        //    so it contains a valid `&'static ReportVtable` value
+        // 2. The `vtable` field is initialized in `ReportData::new` and only ever
+        //    replaced by another valid vtable, so it contains a valid `&'static
+        //    ReportVtable` value
         unsafe { *vtable_ptr }
     }
 
@@ -210,6 +214,40 @@ impl<'a> RawReportRef<'a> {
         let this = unsafe { self.cast_inner::<C>() };
         &this.context
     }
+
+    /// Creates a new [`RawReport`] with a clone of the context of this report
+    /// and the given children and attachments.
+    ///
+    /// The new report uses the same vtable as this report, so it keeps the
+    /// handler and the `Send + Sync` flag of the context. It has a strong
+    /// count of 1.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    ///
+    /// 1. The type `C` matches the actual context type stored in the
+    ///    [`ReportData`]
+    #[inline]
+    pub unsafe fn clone_with_parts<C: Clone + 'static>(
+        self,
+        children: Vec<RawReport>,
+        attachments: Vec<RawAttachment>,
+    ) -> RawReport {
+        // SAFETY:
+        // 1. Guaranteed by the caller
+        let this = unsafe { self.cast_inner::<C>() };
+        // The vtable was created for the context type `C` of this report, and
+        // the new report stores a context of the same type, so the invariant on
+        // the `vtable` field is upheld.
+        let data = ReportData {
+            vtable: this.vtable,
+            children,
+            attachments,
+            context: this.context.clone(),
+        };
+        RawReport::from_arc(triomphe::Arc::new(data))
+    }
 }
 
 impl<'a> RawReportMut<'a> {
@@ -294,11 +332,66 @@ impl<'a> RawReportMut<'a> {
         let this = unsafe { self.cast_inner::<C>() };
         &mut this.context
     }
+
+    /// Replaces the vtable of the report with one that uses the handler `H`
+    /// to format and inspect the context.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    ///
+    /// 1. The type `C` matches the actual context type stored in the
+    ///    [`ReportData`]
+    #[inline]
+    pub unsafe fn set_context_handler<C: 'static, H: ContextHandler<C>>(self) {
+        // SAFETY:
+        // 1. Guaranteed by the caller
+        let this = unsafe { self.cast_inner::<C>() };
+        // The new vtable is created for the context type `C`, which the caller
+        // guarantees to be the actual context type, so the invariant on the
+        // `vtable` field is upheld. The `Send + Sync` flag only depends on the
+        // context type, so it is carried over from the previous vtable.
+        this.vtable = if this.vtable.is_send_sync() {
+            ReportVtable::new::<C, H, true>()
+        } else {
+            ReportVtable::new::<C, H, false>()
+        };
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::{string::String, vec};
+    use core::{any::TypeId, error::Error, fmt};
+
     use super::*;
+    use crate::handlers::AttachmentHandler;
+
+    struct HandlerString;
+    impl ContextHandler<String> for HandlerString {
+        fn source(_value: &String) -> Option<&(dyn Error + 'static)> {
+            None
+        }
+
+        fn display(value: &String, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(value, formatter)
+        }
+
+        fn debug(value: &String, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(value, formatter)
+        }
+    }
+
+    struct AttachmentHandlerI32;
+    impl AttachmentHandler<i32> for AttachmentHandlerI32 {
+        fn display(value: &i32, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(value, formatter)
+        }
+
+        fn debug(value: &i32, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(value, formatter)
+        }
+    }
 
     #[test]
     fn test_report_data_field_offsets() {
@@ -335,4 +428,63 @@ mod tests {
         check::<i32>();
         check::<LargeAlignment>();
     }
+
+    #[test]
+    fn test_clone_with_parts() {
+        let child = RawReport::new::<String, HandlerString>(String::from("child"), vec![], vec![]);
+        let attachment = RawAttachment::new::<i32, AttachmentHandlerI32>(1);
+        let original = RawReport::new::<String, HandlerString>(
+            String::from("context"),
+            vec![child],
+            vec![attachment],
+        );
+
+        let new_child =
+            RawReport::new::<String, HandlerString>(String::from("new child"), vec![], vec![]);
+        let new_attachments = vec![
+            RawAttachment::new::<i32, AttachmentHandlerI32>(2),
+            RawAttachment::new::<i32, AttachmentHandlerI32>(3),
+        ];
+
+        // SAFETY: The context of `original` is a `String`
+        let copy = unsafe {
+            original
+                .as_ref()
+                .clone_with_parts::<String>(vec![new_child], new_attachments)
+        };
+        // All reports in this test have `String` contexts and `i32` attachments
+        fn context(report: RawReportRef<'_>) -> &str {
+            // SAFETY: The context is a `String`
+            unsafe { report.context_downcast_unchecked::<String>() }
+        }
+        fn attachment_values(report: RawReportRef<'_>) -> Vec<i32> {
+            report
+                .attachments()
+                .iter()
+                .map(|attachment| {
+                    // SAFETY: The attachment is an `i32`
+                    unsafe { *attachment.as_ref().attachment_downcast_unchecked::<i32>() }
+                })
+                .collect()
+        }
+
+        let original_ref = original.as_ref();
+        let copy_ref = copy.as_ref();
+
+        // The copy is a new report with the same context and vtable
+        assert_eq!(original_ref.strong_count(), 1);
+        assert_eq!(copy_ref.strong_count(), 1);
+        assert!(core::ptr::eq(original_ref.vtable(), copy_ref.vtable()));
+        assert_eq!(copy_ref.context_type_id(), TypeId::of::<String>());
+        assert_eq!(context(copy_ref), "context");
+
+        // The copy uses the given parts, and the original keeps its own
+        assert_eq!(copy_ref.children().len(), 1);
+        assert_eq!(context(copy_ref.children()[0].as_ref()), "new child");
+        assert_eq!(attachment_values(copy_ref), [2, 3]);
+
+        assert_eq!(original_ref.children().len(), 1);
+        assert_eq!(context(original_ref.children()[0].as_ref()), "child");
+        assert_eq!(attachment_values(original_ref), [1]);
+    }
 }
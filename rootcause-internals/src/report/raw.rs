@@ -37,7 +37,7 @@ use core::{
 
 use crate::{
     attachment::RawAttachment,
-    handlers::{ContextFormattingStyle, ContextHandler, FormattingFunction},
+    handlers::{ContextFormattingStyle, ContextHandler, FormattingFunction, JsonSink},
     report::data::ReportData,
     util::Erased,
 };
@@ -107,13 +107,34 @@ impl RawReport {
     ///
     /// The created report will have the supplied context type and handler type.
     /// It will also have a strong count of 1.
+    ///
+    /// The context is not recorded as `Send + Sync`, see
+    /// [`RawReport::new_send_sync`].
     #[inline]
     pub fn new<C, H>(context: C, children: Vec<RawReport>, attachments: Vec<RawAttachment>) -> Self
     where
         C: 'static,
         H: ContextHandler<C>,
     {
-        let data = triomphe::Arc::new(ReportData::new::<H>(context, children, attachments));
+        let data = triomphe::Arc::new(ReportData::new::<H, false>(context, children, attachments));
+        Self::from_arc(data)
+    }
+
+    /// Creates a new [`RawReport`] like [`RawReport::new`], additionally
+    /// recording that the context is `Send + Sync`.
+    ///
+    /// The recorded flag can be read with [`RawReportRef::is_send_sync`].
+    #[inline]
+    pub fn new_send_sync<C, H>(
+        context: C,
+        children: Vec<RawReport>,
+        attachments: Vec<RawAttachment>,
+    ) -> Self
+    where
+        C: Send + Sync + 'static,
+        H: ContextHandler<C>,
+    {
+        let data = triomphe::Arc::new(ReportData::new::<H, true>(context, children, attachments));
         Self::from_arc(data)
     }
 
@@ -260,6 +281,19 @@ impl<'a> RawReportRef<'a> {
         self.vtable().handler_type_id()
     }
 
+    /// Returns the [`core::any::type_name`] of the context handler.
+    #[inline]
+    pub fn context_handler_type_name(self) -> &'static str {
+        self.vtable().handler_type_name()
+    }
+
+    /// Returns whether the context was recorded as `Send + Sync` when the
+    /// report was created using [`RawReport::new_send_sync`].
+    #[inline]
+    pub fn is_send_sync(self) -> bool {
+        self.vtable().is_send_sync()
+    }
+
     /// Returns the source of the context using the [`ContextHandler::source`]
     /// method specified when the [`ReportData`] was created.
     #[inline]
@@ -293,6 +327,18 @@ impl<'a> RawReportRef<'a> {
         unsafe { vtable.debug(self, formatter) }
     }
 
+    /// Emits the structured representation of the context by using the
+    /// [`ContextHandler::json`] method specified by the handler used to create
+    /// the [`ReportData`].
+    #[inline]
+    pub fn context_json(self, sink: &mut dyn JsonSink) -> core::fmt::Result {
+        let vtable = self.vtable();
+        // SAFETY:
+        // 1. The vtable returned by `self.vtable()` is guaranteed to match the data in
+        //    the `ReportData`.
+        unsafe { vtable.json(self, sink) }
+    }
+
     /// The formatting style preferred by the context when formatted as part of
     /// a report.
     ///
@@ -517,7 +563,10 @@ impl<'a> RawReportMut<'a> {
 
 #[cfg(test)]
 mod tests {
-    use alloc::{string::String, vec};
+    use alloc::{
+        string::{String, ToString},
+        vec,
+    };
     use core::{error::Error, fmt};
 
     use super::*;
@@ -630,6 +679,15 @@ mod tests {
         assert_eq!(ptr1, ptr2);
     }
 
+    #[test]
+    fn test_raw_report_is_send_sync() {
+        let report = RawReport::new::<i32, HandlerI32>(1, vec![], vec![]);
+        assert!(!report.as_ref().is_send_sync());
+
+        let report = RawReport::new_send_sync::<i32, HandlerI32>(2, vec![], vec![]);
+        assert!(report.as_ref().is_send_sync());
+    }
+
     #[test]
     fn test_raw_report_clone_arc() {
         // Test that Arc cloning maintains safety
@@ -926,4 +984,44 @@ mod tests {
             &42
         );
     }
+
+    #[test]
+    fn test_raw_report_set_context_handler() {
+        struct HandlerI32Hex;
+        impl ContextHandler<i32> for HandlerI32Hex {
+            fn source(_value: &i32) -> Option<&(dyn Error + 'static)> {
+                None
+            }
+
+            fn display(value: &i32, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "{value:#x}")
+            }
+
+            fn debug(value: &i32, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(value, formatter)
+            }
+        }
+
+        struct DisplayContext<'a>(RawReportRef<'a>);
+        impl fmt::Display for DisplayContext<'_> {
+            fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.context_display(formatter)
+            }
+        }
+
+        let mut report = RawReport::new::<i32, HandlerI32>(255, vec![], vec![]);
+        assert_eq!(DisplayContext(report.as_ref()).to_string(), "255");
+
+        // SAFETY: We have unique ownership of the report
+        let report_mut = unsafe { report.as_mut() };
+        // SAFETY: The context is an `i32`
+        unsafe { report_mut.set_context_handler::<i32, HandlerI32Hex>() };
+
+        assert_eq!(DisplayContext(report.as_ref()).to_string(), "0xff");
+        assert_eq!(
+            report.as_ref().context_handler_type_id(),
+            TypeId::of::<HandlerI32Hex>()
+        );
+        assert_eq!(report.as_ref().context_type_id(), TypeId::of::<i32>());
+    }
 }
@@ -22,7 +22,7 @@ use core::{
 };
 
 use crate::{
-    handlers::{ContextFormattingStyle, ContextHandler, FormattingFunction},
+    handlers::{ContextFormattingStyle, ContextHandler, FormattingFunction, JsonSink},
     report::{
         data::ReportData,
         raw::{RawReport, RawReportMut, RawReportRef},
@@ -41,8 +41,8 @@ use crate::{
 /// struct exists:
 ///
 /// * The fields `drop`, `clone_arc`, `strong_count`, `source`, `display`,
-///   `debug`, and `preferred_context_formatting_style` all point to the
-///   functions defined below
+///   `debug`, `json`, and `preferred_context_formatting_style` all point to
+///   the functions defined below
 /// * The concrete pointers are all instantiated with the same context type `C`
 ///   and handler type `H` that were used to create this `ReportVtable`.
 pub(crate) struct ReportVtable {
@@ -55,6 +55,14 @@ pub(crate) struct ReportVtable {
     /// Gets the [`TypeId`] of the handler that was used to create this
     /// [`ReportVtable`].
     handler_type_id: fn() -> TypeId,
+    /// Gets the [`any::type_name`] of the handler that was used to create
+    /// this [`ReportVtable`].
+    handler_type_name: fn() -> &'static str,
+    /// Whether the context type is known to be `Send + Sync`.
+    ///
+    /// This is only `true` if the context type was required to be
+    /// `Send + Sync` when this [`ReportVtable`] was created.
+    send_sync: bool,
     /// Method to drop the [`triomphe::Arc<ReportData<C>>`] instance pointed to
     /// by this pointer.
     drop: unsafe fn(NonNull<ReportData<Erased>>),
@@ -70,6 +78,9 @@ pub(crate) struct ReportVtable {
     display: unsafe fn(RawReportRef<'_>, &mut core::fmt::Formatter<'_>) -> core::fmt::Result,
     /// Formats the report using the `debug` method on the handler.
     debug: unsafe fn(RawReportRef<'_>, &mut core::fmt::Formatter<'_>) -> core::fmt::Result,
+    /// Emits the structured representation of the context using the `json`
+    /// method on the handler.
+    json: unsafe fn(RawReportRef<'_>, &mut dyn JsonSink) -> core::fmt::Result,
     /// Get the formatting style preferred by the context when formatted as part
     /// of a report.
     preferred_context_formatting_style:
@@ -83,18 +94,24 @@ pub(crate) struct ReportVtable {
 impl ReportVtable {
     /// Creates a new [`ReportVtable`] for the context type `C` and the handler
     /// type `H`.
-    pub(super) const fn new<C: 'static, H: ContextHandler<C>>() -> &'static Self {
+    ///
+    /// `SEND_SYNC` must only be `true` if `C` is `Send + Sync`.
+    pub(super) const fn new<C: 'static, H: ContextHandler<C>, const SEND_SYNC: bool>()
+    -> &'static Self {
         const {
             &Self {
                 type_id: TypeId::of::<C>,
                 type_name: any::type_name::<C>,
                 handler_type_id: TypeId::of::<H>,
+                handler_type_name: any::type_name::<H>,
+                send_sync: SEND_SYNC,
                 drop: drop::<C>,
                 clone_arc: clone_arc::<C>,
                 strong_count: strong_count::<C>,
                 source: source::<C, H>,
                 display: display::<C, H>,
                 debug: debug::<C, H>,
+                json: json::<C, H>,
                 preferred_context_formatting_style: preferred_context_formatting_style::<C, H>,
                 context_as_any: context_as_any::<C>,
                 context_as_any_mut: context_as_any_mut::<C>,
@@ -123,6 +140,20 @@ impl ReportVtable {
         (self.handler_type_id)()
     }
 
+    /// Gets the [`any::type_name`] of the handler that was used to create
+    /// this [`ReportVtable`].
+    #[inline]
+    pub(super) fn handler_type_name(&self) -> &'static str {
+        (self.handler_type_name)()
+    }
+
+    /// Returns whether the context type that was used to create this
+    /// [`ReportVtable`] is known to be `Send + Sync`.
+    #[inline]
+    pub(super) fn is_send_sync(&self) -> bool {
+        self.send_sync
+    }
+
     /// Drops the `triomphe::Arc<ReportData<C>>` instance pointed to by this
     /// pointer.
     ///
@@ -290,6 +321,35 @@ impl ReportVtable {
         }
     }
 
+    /// Emits the structured representation of the context of the given
+    /// `RawReportRef` using the [`H::json`] function used when creating this
+    /// [`ReportVtable`].
+    ///
+    /// [`H::json`]: ContextHandler::json
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    ///
+    /// 1. This [`ReportVtable`] must be a vtable for the context type stored in
+    ///    the [`RawReportRef`].
+    #[inline]
+    pub(super) unsafe fn json(
+        &self,
+        ptr: RawReportRef<'_>,
+        sink: &mut dyn JsonSink,
+    ) -> core::fmt::Result {
+        // SAFETY: We know that `self.json` points to the function `json::<C, H>`
+        // below. That function's safety requirements are upheld:
+        // 1. Guaranteed by the caller
+        unsafe {
+            // @add-unsafe-context: json
+            // @add-unsafe-context: RawReportRef
+            // @add-unsafe-context: ReportData
+            (self.json)(ptr, sink)
+        }
+    }
+
     /// Calls the [`H::preferred_formatting_style`] function to get the
     /// formatting style preferred by the context when formatted as part of
     /// a report.
@@ -504,6 +564,24 @@ unsafe fn debug<C: 'static, H: ContextHandler<C>>(
     H::debug(context, formatter)
 }
 
+/// Emits the structured representation of a report's context using its
+/// handler's json implementation.
+///
+/// # Safety
+///
+/// The caller must ensure:
+///
+/// 1. The type `C` matches the actual context type stored in the [`ReportData`]
+unsafe fn json<C: 'static, H: ContextHandler<C>>(
+    ptr: RawReportRef<'_>,
+    sink: &mut dyn JsonSink,
+) -> core::fmt::Result {
+    // SAFETY:
+    // 1. Guaranteed by the caller
+    let context: &C = unsafe { ptr.context_downcast_unchecked::<C>() };
+    H::json(context, sink)
+}
+
 /// Gets the preferred formatting style using the
 /// [`H::preferred_formatting_style`] function.
 ///
@@ -578,8 +656,8 @@ mod tests {
     #[test]
     fn test_report_vtable_eq() {
         // Test that vtables have proper static lifetime and can be safely shared
-        let vtable1 = ReportVtable::new::<i32, HandlerI32>();
-        let vtable2 = ReportVtable::new::<i32, HandlerI32>();
+        let vtable1 = ReportVtable::new::<i32, HandlerI32, false>();
+        let vtable2 = ReportVtable::new::<i32, HandlerI32, false>();
 
         // Both should be the exact same static instance
         assert!(core::ptr::eq(vtable1, vtable2));
@@ -587,16 +665,25 @@ mod tests {
 
     #[test]
     fn test_report_type_id() {
-        let vtable = ReportVtable::new::<i32, HandlerI32>();
+        let vtable = ReportVtable::new::<i32, HandlerI32, false>();
         assert_eq!(vtable.type_id(), TypeId::of::<i32>());
     }
 
     #[test]
     fn test_report_type_name() {
-        let vtable = ReportVtable::new::<i32, HandlerI32>();
+        let vtable = ReportVtable::new::<i32, HandlerI32, false>();
         assert_eq!(vtable.type_name(), core::any::type_name::<i32>());
     }
 
+    #[test]
+    fn test_report_handler_type_name() {
+        let vtable = ReportVtable::new::<i32, HandlerI32, false>();
+        assert_eq!(
+            vtable.handler_type_name(),
+            core::any::type_name::<HandlerI32>()
+        );
+    }
+
     #[test]
     fn test_report_clone_eq() {
         let report = RawReport::new::<_, HandlerI32>(42, vec![], vec![]);
@@ -25,6 +25,8 @@
 //!   return a preformatted report alongside it.
 //! - [`ContextTransformNestedExt::context_transform_nested`] — transform the
 //!   root context while nesting the original report as a preformatted child.
+//! - [`SnapshotReportExt::to_bytes`] and [`from_bytes`] — save a preformatted
+//!   report as a versioned binary snapshot and load it again later.
 //!
 //! # Why preformat?
 //!
@@ -56,6 +58,8 @@
 //! [`Cloneable`]: rootcause::markers::Cloneable
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 use rootcause::{
     Report, ReportMut, ReportRef, handlers,
@@ -64,8 +68,10 @@ use rootcause::{
 };
 
 mod preformatted;
+mod snapshot;
 
 pub use preformatted::{PreformattedAttachment, PreformattedContext};
+pub use snapshot::{SnapshotError, SnapshotReportExt, from_bytes};
 
 /// Extension trait providing [`preformat`](Self::preformat) on [`Report`],
 /// [`ReportRef`], and [`ReportMut`].
@@ -59,6 +59,7 @@ use rootcause::{
 /// # Stored Information
 ///
 /// - The original type's [`TypeId`] (accessible via [`original_type_id`])
+/// - The original type's name (accessible via [`original_type_name`])
 /// - Preformatted [`Display`](core::fmt::Display) output as a `String`
 /// - Preformatted [`Debug`](core::fmt::Debug) output as a `String`
 /// - Preferred formatting styles for both [`Display`](core::fmt::Display)
@@ -94,19 +95,22 @@ use rootcause::{
 ///
 /// [`PreformatReportExt::preformat`]: crate::PreformatReportExt::preformat
 /// [`original_type_id`]: PreformattedContext::original_type_id
+/// [`original_type_name`]: PreformattedContext::original_type_name
 /// [`TypeId`]: core::any::TypeId
 pub struct PreformattedContext {
-    original_type_id: TypeId,
-    display: String,
-    debug: String,
-    display_preferred_formatting_style: ContextFormattingStyle,
-    debug_preferred_formatting_style: ContextFormattingStyle,
+    pub(crate) original_type_id: TypeId,
+    pub(crate) original_type_name: &'static str,
+    pub(crate) display: String,
+    pub(crate) debug: String,
+    pub(crate) display_preferred_formatting_style: ContextFormattingStyle,
+    pub(crate) debug_preferred_formatting_style: ContextFormattingStyle,
 }
 
 impl PreformattedContext {
     pub(crate) fn new_from_context<C: ?Sized, O, T>(report: ReportRef<'_, C, O, T>) -> Self {
         Self {
             original_type_id: report.current_context_type_id(),
+            original_type_name: report.current_context_type_name(),
             display: format!("{}", report.format_current_context()),
             debug: format!("{:?}", report.format_current_context()),
             display_preferred_formatting_style: report.preferred_context_formatting_style(
@@ -157,6 +161,68 @@ impl PreformattedContext {
     pub fn original_type_id(&self) -> TypeId {
         self.original_type_id
     }
+
+    /// Get the [`type_name`] of the original context type before it was
+    /// preformatted.
+    ///
+    /// Unlike the [`TypeId`], the name is human readable, which makes it useful
+    /// for classifying errors in logs after the report has been preformatted.
+    /// As with [`type_name`], the exact contents of the name are not
+    /// guaranteed to be stable between compiler versions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    /// use rootcause_preformat::{PreformatReportExt, PreformattedContext};
+    ///
+    /// #[derive(Debug)]
+    /// struct TimeoutError;
+    ///
+    /// impl core::fmt::Display for TimeoutError {
+    ///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         write!(f, "operation timed out")
+    ///     }
+    /// }
+    ///
+    /// let report: Report<TimeoutError> = report!(TimeoutError);
+    /// let preformatted: Report<PreformattedContext> = report.preformat();
+    ///
+    /// assert_eq!(
+    ///     preformatted.current_context().original_type_name(),
+    ///     core::any::type_name::<TimeoutError>()
+    /// );
+    /// ```
+    ///
+    /// [`type_name`]: core::any::type_name
+    /// [`TypeId`]: core::any::TypeId
+    pub fn original_type_name(&self) -> &'static str {
+        self.original_type_name
+    }
+
+    /// Get the preformatted [`Display`](core::fmt::Display) output of the
+    /// original context.
+    ///
+    /// This borrows the stored string, so the message of a preformatted
+    /// report can be read without formatting it again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    /// use rootcause_preformat::{PreformatReportExt, PreformattedContext};
+    ///
+    /// let report = report!("connection refused");
+    /// let preformatted: Report<PreformattedContext> = report.preformat();
+    ///
+    /// assert_eq!(
+    ///     preformatted.current_context().display_str(),
+    ///     "connection refused"
+    /// );
+    /// ```
+    pub fn display_str(&self) -> &str {
+        &self.display
+    }
 }
 
 /// An attachment that has been preformatted into `String`s for both
@@ -170,6 +236,7 @@ impl PreformattedContext {
 /// # Stored Information
 ///
 /// - The original type's [`TypeId`] (accessible via [`original_type_id`])
+/// - The original type's name (accessible via [`original_type_name`])
 /// - Preformatted [`Display`](core::fmt::Display) output as a `String`
 /// - Preformatted [`Debug`](core::fmt::Debug) output as a `String`
 /// - Preferred formatting styles for both [`Display`](core::fmt::Display)
@@ -195,13 +262,15 @@ impl PreformattedContext {
 ///
 /// [`PreformatReportExt::preformat`]: crate::PreformatReportExt::preformat
 /// [`original_type_id`]: PreformattedAttachment::original_type_id
+/// [`original_type_name`]: PreformattedAttachment::original_type_name
 /// [`TypeId`]: core::any::TypeId
 pub struct PreformattedAttachment {
-    original_type_id: TypeId,
-    display: String,
-    debug: String,
-    display_preferred_formatting_style: AttachmentFormattingStyle,
-    debug_preferred_formatting_style: AttachmentFormattingStyle,
+    pub(crate) original_type_id: TypeId,
+    pub(crate) original_type_name: &'static str,
+    pub(crate) display: String,
+    pub(crate) debug: String,
+    pub(crate) display_preferred_formatting_style: AttachmentFormattingStyle,
+    pub(crate) debug_preferred_formatting_style: AttachmentFormattingStyle,
 }
 
 impl PreformattedAttachment {
@@ -211,6 +280,7 @@ impl PreformattedAttachment {
     {
         Self {
             original_type_id: attachment.inner_type_id(),
+            original_type_name: attachment.inner_type_name(),
             display: format!("{}", attachment.format_inner()),
             debug: format!("{:?}", attachment.format_inner()),
             display_preferred_formatting_style: attachment
@@ -252,6 +322,35 @@ impl PreformattedAttachment {
     pub fn original_type_id(&self) -> TypeId {
         self.original_type_id
     }
+
+    /// Get the [`type_name`] of the original attachment type before it was
+    /// preformatted.
+    ///
+    /// As with [`type_name`], the exact contents of the name are not
+    /// guaranteed to be stable between compiler versions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    /// use rootcause_preformat::{PreformatReportExt, PreformattedAttachment};
+    ///
+    /// let report: Report = report!("error").attach(42u32);
+    /// let preformatted = report.preformat();
+    ///
+    /// let type_names: Vec<&str> = preformatted
+    ///     .attachments()
+    ///     .iter()
+    ///     .filter_map(|attachment| attachment.downcast_inner::<PreformattedAttachment>())
+    ///     .map(|attachment| attachment.original_type_name())
+    ///     .collect();
+    /// assert!(type_names.contains(&"u32"));
+    /// ```
+    ///
+    /// [`type_name`]: core::any::type_name
+    pub fn original_type_name(&self) -> &'static str {
+        self.original_type_name
+    }
 }
 
 /// Internal handler for preformatted contexts and attachments.
@@ -287,7 +386,8 @@ impl ContextHandler<PreformattedContext> for PreformattedHandler {
         report_formatting_function: rootcause::handlers::FormattingFunction,
     ) -> ContextFormattingStyle {
         match report_formatting_function {
-            rootcause::handlers::FormattingFunction::Display => {
+            rootcause::handlers::FormattingFunction::Display
+            | rootcause::handlers::FormattingFunction::Json => {
                 value.display_preferred_formatting_style
             }
             rootcause::handlers::FormattingFunction::Debug => {
@@ -317,7 +417,8 @@ impl AttachmentHandler<PreformattedAttachment> for PreformattedHandler {
         report_formatting_function: rootcause::handlers::FormattingFunction,
     ) -> AttachmentFormattingStyle {
         match report_formatting_function {
-            rootcause::handlers::FormattingFunction::Display => {
+            rootcause::handlers::FormattingFunction::Display
+            | rootcause::handlers::FormattingFunction::Json => {
                 value.display_preferred_formatting_style
             }
             rootcause::handlers::FormattingFunction::Debug => {
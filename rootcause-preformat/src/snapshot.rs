@@ -0,0 +1,771 @@
+//! A binary snapshot format for preformatted reports.
+//!
+//! See [`SnapshotReportExt::to_bytes`] and [`from_bytes`] for an overview.
+//!
+//! # Format
+//!
+//! A snapshot starts with the magic bytes `RCSNAP` followed by a single
+//! version byte. The rest of the snapshot is the root report, encoded as:
+//!
+//! - the [`Display`](core::fmt::Display) and [`Debug`](core::fmt::Debug)
+//!   output of the context
+//! - the preferred [`Display`](core::fmt::Display) and
+//!   [`Debug`](core::fmt::Debug) formatting styles of the context
+//! - the number of attachments, followed by each attachment (its two strings
+//!   and two formatting styles)
+//! - the number of children, followed by each child report
+//!
+//! Reports are nested at most 128 levels deep. When a deeper report is
+//! written, the children of the reports at the deepest level are replaced by
+//! an attachment saying how many nested reports were omitted.
+//! Strings longer than `u32::MAX` bytes are cut off and end in
+//! ` [truncated]`.
+//!
+//! Strings are encoded as a little-endian `u32` length followed by the UTF-8
+//! bytes, counts are little-endian `u32`s and priorities are little-endian
+//! `i32`s.
+
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
+use core::any::{TypeId, type_name};
+
+use rootcause::{
+    Report, ReportMut, ReportRef,
+    handlers::{
+        AttachmentFormattingPlacement, AttachmentFormattingStyle, ContextFormattingStyle,
+        FormattingFunction,
+    },
+    markers::{Dynamic, Mutable, ReportOwnershipMarker, SendSync},
+    report_attachment::ReportAttachment,
+};
+
+use crate::preformatted::{PreformattedAttachment, PreformattedContext, PreformattedHandler};
+
+const MAGIC: &[u8; 6] = b"RCSNAP";
+const VERSION: u8 = 1;
+
+/// The maximum nesting depth of the reports in a snapshot, to avoid
+/// overflowing the stack when decoding a corrupted or crafted snapshot.
+const MAX_DEPTH: usize = 128;
+
+/// The maximum total length of the strings leaked by [`Interner::leak`], so
+/// that decoding crafted snapshots cannot leak memory without bound.
+const MAX_LEAKED_BYTES: usize = 64 * 1024;
+
+/// The string used in place of the headers and labels that do not fit in
+/// [`MAX_LEAKED_BYTES`].
+const OMITTED_LABEL: &str = "<omitted>";
+
+/// The maximum length of a string and the maximum number of attachments or
+/// children of a report in a snapshot, since lengths are stored as `u32`s.
+const MAX_LEN: usize = u32::MAX as usize;
+
+/// Appended to the strings that were cut off at [`MAX_LEN`].
+const TRUNCATED_SUFFIX: &str = " [truncated]";
+
+/// Extension trait providing [`to_bytes`](Self::to_bytes) on [`Report`],
+/// [`ReportRef`], and [`ReportMut`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::prelude::*;
+/// use rootcause_preformat::{SnapshotReportExt, from_bytes};
+///
+/// let report: Report = report!("database connection failed");
+/// let restored = from_bytes(&report.to_bytes()).unwrap();
+/// assert_eq!(format!("{report}"), format!("{restored}"));
+/// ```
+pub trait SnapshotReportExt {
+    /// Encodes the preformatted version of the report into a self-describing
+    /// binary snapshot.
+    ///
+    /// The snapshot contains the same information as
+    /// [`preformat`](crate::PreformatReportExt::preformat): the formatted
+    /// strings and preferred formatting styles of every context and
+    /// attachment in the tree. It can be decoded again with [`from_bytes`],
+    /// for example in a separate tool that renders reports saved to disk.
+    ///
+    /// The original [`TypeId`]s and type names are not part of the snapshot,
+    /// since they are not stable between builds. Reports nested more than
+    /// 128 levels deep are truncated, so that the snapshot can always be
+    /// decoded again. Likewise, strings longer than 4 GiB are cut off and
+    /// end in ` [truncated]`.
+    ///
+    /// See the [module documentation](self) for a description of the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    /// use rootcause_preformat::SnapshotReportExt;
+    ///
+    /// let report = report!("connection refused").attach("host: example.com");
+    /// let bytes: Vec<u8> = report.to_bytes();
+    /// assert!(bytes.starts_with(b"RCSNAP"));
+    /// ```
+    #[must_use]
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+impl<C: ?Sized, O, T> SnapshotReportExt for Report<C, O, T>
+where
+    O: ReportOwnershipMarker,
+{
+    fn to_bytes(&self) -> Vec<u8> {
+        self.as_ref().to_bytes()
+    }
+}
+
+impl<'a, C: ?Sized, T> SnapshotReportExt for ReportMut<'a, C, T> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.as_ref().to_bytes()
+    }
+}
+
+impl<'a, C: ?Sized, O, T> SnapshotReportExt for ReportRef<'a, C, O, T> {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        write_report(&mut out, self.into_dynamic(), 0);
+        out
+    }
+}
+
+/// Decodes a snapshot created by [`SnapshotReportExt::to_bytes`] back into a
+/// preformatted report.
+///
+/// The decoded report displays the same way as the report the snapshot was
+/// taken from. Since type information is not part of the snapshot, the
+/// [`original_type_id`](PreformattedContext::original_type_id) and
+/// [`original_type_name`](PreformattedContext::original_type_name) of the
+/// decoded contexts and attachments refer to [`PreformattedContext`] and
+/// [`PreformattedAttachment`] themselves.
+///
+/// Attachment headers, appendix names and footer labels are `&'static str`
+/// in [`AttachmentFormattingPlacement`], so they are leaked when decoding.
+/// Each distinct string is only leaked once per snapshot, or only once in
+/// total with the `std` feature. At most 64 KiB of strings are leaked per
+/// snapshot, or in total with the `std` feature. Once that budget is used up,
+/// new headers and labels are decoded as `<omitted>` instead.
+///
+/// # Errors
+///
+/// Returns a [`SnapshotError`] if the bytes are not a snapshot, were written
+/// by an unsupported version of the format, are truncated or corrupted, or
+/// contain reports nested more than 128 levels deep.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::prelude::*;
+/// use rootcause_preformat::{SnapshotError, SnapshotReportExt, from_bytes};
+///
+/// let report: Report = report!("connection refused")
+///     .context("failed to load user")
+///     .into_dynamic();
+/// let restored = from_bytes(&report.to_bytes()).unwrap();
+/// assert_eq!(format!("{report:?}"), format!("{restored:?}"));
+///
+/// assert_eq!(from_bytes(b"not a snapshot").unwrap_err(), SnapshotError::InvalidMagic);
+/// ```
+pub fn from_bytes(
+    bytes: &[u8],
+) -> Result<Report<PreformattedContext, Mutable, SendSync>, SnapshotError> {
+    // The lock is only held while leaking a single string, so that
+    // concurrent decodes do not wait for each other
+    #[cfg(feature = "std")]
+    {
+        decode(bytes, &mut |value| {
+            INTERNED
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .leak(value)
+        })
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let mut interner = Interner::new();
+        decode(bytes, &mut |value| interner.leak(value))
+    }
+}
+
+/// Decodes a snapshot, leaking its headers and labels using `leak`.
+fn decode(
+    bytes: &[u8],
+    leak: &mut dyn FnMut(String) -> &'static str,
+) -> Result<Report<PreformattedContext, Mutable, SendSync>, SnapshotError> {
+    let mut reader = Reader { bytes, leak };
+    if reader.take(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+        return Err(SnapshotError::InvalidMagic);
+    }
+    let version = reader.u8()?;
+    if version != VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+    let report = reader.report(0)?;
+    if !reader.bytes.is_empty() {
+        return Err(SnapshotError::TrailingBytes);
+    }
+    Ok(report)
+}
+
+/// Error returned by [`from_bytes`] when a snapshot cannot be decoded.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_preformat::{SnapshotError, from_bytes};
+///
+/// let error = from_bytes(b"RCSNAP\xff").unwrap_err();
+/// assert_eq!(error, SnapshotError::UnsupportedVersion(0xff));
+/// assert_eq!(format!("{error}"), "unsupported snapshot version 255");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// The bytes do not start with the snapshot magic header.
+    InvalidMagic,
+    /// The snapshot was written with a version of the format that is not
+    /// supported by this version of the crate.
+    UnsupportedVersion(u8),
+    /// The snapshot ended in the middle of a value.
+    UnexpectedEnd,
+    /// A string in the snapshot was not valid UTF-8.
+    InvalidUtf8,
+    /// An enum tag or flag in the snapshot had an unknown value.
+    InvalidTag(u8),
+    /// The snapshot contained more bytes after the root report.
+    TrailingBytes,
+    /// The reports in the snapshot were nested too deeply.
+    TooDeep,
+}
+
+impl core::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SnapshotError::InvalidMagic => write!(f, "missing snapshot magic header"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {version}")
+            }
+            SnapshotError::UnexpectedEnd => write!(f, "unexpected end of snapshot"),
+            SnapshotError::InvalidUtf8 => write!(f, "invalid UTF-8 in snapshot"),
+            SnapshotError::InvalidTag(tag) => write!(f, "invalid tag {tag} in snapshot"),
+            SnapshotError::TrailingBytes => write!(f, "trailing bytes after snapshot"),
+            SnapshotError::TooDeep => write!(f, "reports nested too deeply in snapshot"),
+        }
+    }
+}
+
+impl core::error::Error for SnapshotError {}
+
+fn write_report<O, T>(out: &mut Vec<u8>, report: ReportRef<'_, Dynamic, O, T>, depth: usize) {
+    let context = PreformattedContext::new_from_context(report);
+    write_str(out, &context.display);
+    write_str(out, &context.debug);
+    write_context_style(out, context.display_preferred_formatting_style);
+    write_context_style(out, context.debug_preferred_formatting_style);
+
+    // The children of the reports at the deepest level are replaced by an
+    // attachment, so that the snapshot can be decoded again
+    let truncated = depth + 1 >= MAX_DEPTH && !report.children().is_empty();
+
+    // Attachments and children beyond what a `u32` can count are left out
+    let attachment_count = report
+        .attachments()
+        .len()
+        .min(MAX_LEN - usize::from(truncated));
+    write_len(out, attachment_count + usize::from(truncated));
+    for attachment in report.attachments().iter().take(attachment_count) {
+        let attachment = PreformattedAttachment::new_from_attachment(attachment);
+        write_str(out, &attachment.display);
+        write_str(out, &attachment.debug);
+        write_attachment_style(out, attachment.display_preferred_formatting_style);
+        write_attachment_style(out, attachment.debug_preferred_formatting_style);
+    }
+
+    if truncated {
+        let omitted = report.iter_reports().count() - 1;
+        let marker = format!("{omitted} nested report(s) omitted from the snapshot");
+        write_str(out, &marker);
+        write_str(out, &marker);
+        write_attachment_style(out, AttachmentFormattingStyle::default());
+        write_attachment_style(out, AttachmentFormattingStyle::default());
+        write_len(out, 0);
+        return;
+    }
+
+    let child_count = report.children().len().min(MAX_LEN);
+    write_len(out, child_count);
+    for child in report.children().iter().take(child_count) {
+        write_report(out, child, depth + 1);
+    }
+}
+
+/// Writes a length, which the caller must have limited to [`MAX_LEN`].
+fn write_len(out: &mut Vec<u8>, len: usize) {
+    debug_assert!(len <= MAX_LEN);
+    out.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+fn write_str(out: &mut Vec<u8>, value: &str) {
+    let value = truncate_str(value, MAX_LEN);
+    write_len(out, value.len());
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// Cuts off a string that is longer than `max_len` bytes at a character
+/// boundary, and appends [`TRUNCATED_SUFFIX`] so that the result is at most
+/// `max_len` bytes long.
+fn truncate_str(value: &str, max_len: usize) -> Cow<'_, str> {
+    if value.len() <= max_len {
+        return Cow::Borrowed(value);
+    }
+    let mut end = max_len.saturating_sub(TRUNCATED_SUFFIX.len());
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    Cow::Owned(format!("{}{TRUNCATED_SUFFIX}", &value[..end]))
+}
+
+fn write_function(out: &mut Vec<u8>, function: FormattingFunction) {
+    out.push(match function {
+        FormattingFunction::Display => 0,
+        FormattingFunction::Debug => 1,
+        FormattingFunction::Json => 2,
+    });
+}
+
+fn write_context_style(out: &mut Vec<u8>, style: ContextFormattingStyle) {
+    write_function(out, style.function);
+    out.push(u8::from(style.follow_source));
+    match style.follow_source_depth {
+        None => out.push(0),
+        Some(depth) => {
+            out.push(1);
+            out.extend_from_slice(&(depth as u64).to_le_bytes());
+        }
+    }
+}
+
+fn write_attachment_style(out: &mut Vec<u8>, style: AttachmentFormattingStyle) {
+    match style.placement {
+        AttachmentFormattingPlacement::Inline => out.push(0),
+        AttachmentFormattingPlacement::InlineWithHeader { header } => {
+            out.push(1);
+            write_str(out, header);
+        }
+        AttachmentFormattingPlacement::Appendix { appendix_name } => {
+            out.push(2);
+            write_str(out, appendix_name);
+        }
+        AttachmentFormattingPlacement::Footer { label } => {
+            out.push(3);
+            write_str(out, label);
+        }
+        AttachmentFormattingPlacement::Opaque => out.push(4),
+        AttachmentFormattingPlacement::Hidden => out.push(5),
+        AttachmentFormattingPlacement::Collapsed { header, summary } => {
+            out.push(6);
+            write_str(out, header);
+            write_str(out, summary);
+        }
+        // Placements added in future versions are stored as inline, which is
+        // how formatters display placements they do not know
+        _ => out.push(0),
+    }
+    write_function(out, style.function);
+    out.extend_from_slice(&style.priority.to_le_bytes());
+}
+
+/// The strings leaked while decoding snapshots, so that each distinct string
+/// is only leaked once.
+struct Interner {
+    strings: BTreeSet<&'static str>,
+    /// The total length of the leaked strings
+    leaked_bytes: usize,
+}
+
+impl Interner {
+    const fn new() -> Self {
+        Self {
+            strings: BTreeSet::new(),
+            leaked_bytes: 0,
+        }
+    }
+
+    /// Leaks a string, unless an equal string has already been leaked.
+    ///
+    /// Returns [`OMITTED_LABEL`] instead if the total length of the leaked
+    /// strings would exceed [`MAX_LEAKED_BYTES`].
+    fn leak(&mut self, value: String) -> &'static str {
+        if let Some(&leaked) = self.strings.get(value.as_str()) {
+            return leaked;
+        }
+        let leaked_bytes = self.leaked_bytes + value.len();
+        if leaked_bytes > MAX_LEAKED_BYTES {
+            return OMITTED_LABEL;
+        }
+        self.leaked_bytes = leaked_bytes;
+        let leaked: &'static str = Box::leak(value.into_boxed_str());
+        self.strings.insert(leaked);
+        leaked
+    }
+}
+
+/// The strings leaked by [`from_bytes`] in all decoded snapshots.
+#[cfg(feature = "std")]
+static INTERNED: std::sync::Mutex<Interner> = std::sync::Mutex::new(Interner::new());
+
+struct Reader<'a, 'l> {
+    bytes: &'a [u8],
+    /// Leaks the headers and labels, using the interner shared between all
+    /// snapshots with the `std` feature.
+    leak: &'l mut dyn FnMut(String) -> &'static str,
+}
+
+impl<'a> Reader<'a, '_> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.bytes.len() < len {
+            return Err(SnapshotError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, SnapshotError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(SnapshotError::InvalidTag(tag)),
+        }
+    }
+
+    fn len(&mut self) -> Result<usize, SnapshotError> {
+        Ok(u32::from_le_bytes(self.array()?) as usize)
+    }
+
+    fn string(&mut self) -> Result<String, SnapshotError> {
+        let len = self.len()?;
+        let bytes = self.take(len)?;
+        core::str::from_utf8(bytes)
+            .map(String::from)
+            .map_err(|_| SnapshotError::InvalidUtf8)
+    }
+
+    /// Reads a string and leaks it, unless an equal string has already been
+    /// leaked.
+    ///
+    /// With the `std` feature, the leaked strings are shared between all
+    /// decoded snapshots. Without it, they are only shared within this
+    /// snapshot.
+    fn leaked_str(&mut self) -> Result<&'static str, SnapshotError> {
+        let value = self.string()?;
+        Ok((self.leak)(value))
+    }
+
+    fn function(&mut self) -> Result<FormattingFunction, SnapshotError> {
+        match self.u8()? {
+            0 => Ok(FormattingFunction::Display),
+            1 => Ok(FormattingFunction::Debug),
+            2 => Ok(FormattingFunction::Json),
+            tag => Err(SnapshotError::InvalidTag(tag)),
+        }
+    }
+
+    fn context_style(&mut self) -> Result<ContextFormattingStyle, SnapshotError> {
+        let function = self.function()?;
+        let follow_source = self.bool()?;
+        let follow_source_depth = if self.bool()? {
+            let depth = u64::from_le_bytes(self.array()?);
+            Some(usize::try_from(depth).unwrap_or(usize::MAX))
+        } else {
+            None
+        };
+        Ok(ContextFormattingStyle {
+            function,
+            follow_source,
+            follow_source_depth,
+        })
+    }
+
+    fn attachment_style(&mut self) -> Result<AttachmentFormattingStyle, SnapshotError> {
+        let placement = match self.u8()? {
+            0 => AttachmentFormattingPlacement::Inline,
+            1 => AttachmentFormattingPlacement::InlineWithHeader {
+                header: self.leaked_str()?,
+            },
+            2 => AttachmentFormattingPlacement::Appendix {
+                appendix_name: self.leaked_str()?,
+            },
+            3 => AttachmentFormattingPlacement::Footer {
+                label: self.leaked_str()?,
+            },
+            4 => AttachmentFormattingPlacement::Opaque,
+            5 => AttachmentFormattingPlacement::Hidden,
+            6 => AttachmentFormattingPlacement::Collapsed {
+                header: self.leaked_str()?,
+                summary: self.leaked_str()?,
+            },
+            tag => return Err(SnapshotError::InvalidTag(tag)),
+        };
+        let function = self.function()?;
+        let priority = i32::from_le_bytes(self.array()?);
+        Ok(AttachmentFormattingStyle {
+            placement,
+            function,
+            priority,
+        })
+    }
+
+    fn attachment(
+        &mut self,
+    ) -> Result<ReportAttachment<PreformattedAttachment, SendSync>, SnapshotError> {
+        let attachment = PreformattedAttachment {
+            original_type_id: TypeId::of::<PreformattedAttachment>(),
+            original_type_name: type_name::<PreformattedAttachment>(),
+            display: self.string()?,
+            debug: self.string()?,
+            display_preferred_formatting_style: self.attachment_style()?,
+            debug_preferred_formatting_style: self.attachment_style()?,
+        };
+        Ok(ReportAttachment::new_custom::<PreformattedHandler>(
+            attachment,
+        ))
+    }
+
+    fn report(
+        &mut self,
+        depth: usize,
+    ) -> Result<Report<PreformattedContext, Mutable, SendSync>, SnapshotError> {
+        if depth >= MAX_DEPTH {
+            return Err(SnapshotError::TooDeep);
+        }
+        let context = PreformattedContext {
+            original_type_id: TypeId::of::<PreformattedContext>(),
+            original_type_name: type_name::<PreformattedContext>(),
+            display: self.string()?,
+            debug: self.string()?,
+            display_preferred_formatting_style: self.context_style()?,
+            debug_preferred_formatting_style: self.context_style()?,
+        };
+        let attachments = (0..self.len()?)
+            .map(|_| self.attachment().map(ReportAttachment::into_dynamic))
+            .collect::<Result<_, _>>()?;
+        let children = (0..self.len()?)
+            .map(|_| self.report(depth + 1))
+            .collect::<Result<_, _>>()?;
+        Ok(Report::from_parts_unhooked::<PreformattedHandler>(
+            context,
+            children,
+            attachments,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use rootcause::{handlers, prelude::*};
+
+    use super::*;
+
+    struct Help;
+
+    impl<A: core::fmt::Display> handlers::AttachmentHandler<A> for Help {
+        fn display(value: &A, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            core::fmt::Display::fmt(value, formatter)
+        }
+
+        fn debug(value: &A, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            core::fmt::Display::fmt(value, formatter)
+        }
+
+        fn preferred_formatting_style(
+            _value: &A,
+            _report_formatting_function: FormattingFunction,
+        ) -> AttachmentFormattingStyle {
+            AttachmentFormattingStyle {
+                placement: AttachmentFormattingPlacement::Footer { label: "Help" },
+                function: FormattingFunction::Display,
+                priority: -3,
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_preserves_output() {
+        let report: Report = report!("connection refused")
+            .attach("host: example.com")
+            .attach_custom::<Help, _>("check the firewall")
+            .context("failed to load user")
+            .into_dynamic();
+        let report = report.join(report!("cache unavailable"), "request failed");
+
+        let restored = from_bytes(&report.to_bytes()).unwrap();
+
+        assert_eq!(format!("{report}"), format!("{restored}"));
+        assert_eq!(format!("{report:?}"), format!("{restored:?}"));
+        assert_eq!(restored.children().len(), 2);
+    }
+
+    #[test]
+    fn test_snapshot_of_preformatted_report_is_identical() {
+        let report: Report = report!("boom").attach(42u32).into_dynamic();
+        assert_eq!(
+            report.to_bytes(),
+            crate::PreformatReportExt::preformat(&report).to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_rejects_corrupted_snapshots() {
+        let bytes = report!("boom").attach("detail").to_bytes();
+
+        assert_eq!(from_bytes(b"").unwrap_err(), SnapshotError::InvalidMagic);
+        assert_eq!(
+            from_bytes(b"RCSNAP\x02").unwrap_err(),
+            SnapshotError::UnsupportedVersion(2)
+        );
+        assert_eq!(
+            from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            SnapshotError::UnexpectedEnd
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            from_bytes(&trailing).unwrap_err(),
+            SnapshotError::TrailingBytes
+        );
+
+        let mut invalid_utf8 = bytes;
+        invalid_utf8[MAGIC.len() + 5] = 0xff;
+        assert_eq!(
+            from_bytes(&invalid_utf8).unwrap_err(),
+            SnapshotError::InvalidUtf8
+        );
+    }
+
+    #[test]
+    fn test_rejects_deeply_nested_snapshots() {
+        // A snapshot of `depth` reports nested in each other, without
+        // attachments
+        let nested = |depth: usize| {
+            let mut bytes = Vec::from(*MAGIC);
+            bytes.push(VERSION);
+            for level in 0..depth {
+                write_str(&mut bytes, "context");
+                write_str(&mut bytes, "context");
+                write_context_style(&mut bytes, ContextFormattingStyle::default());
+                write_context_style(&mut bytes, ContextFormattingStyle::default());
+                write_len(&mut bytes, 0);
+                write_len(&mut bytes, usize::from(level + 1 < depth));
+            }
+            bytes
+        };
+
+        assert_eq!(
+            from_bytes(&nested(MAX_DEPTH + 1)).unwrap_err(),
+            SnapshotError::TooDeep
+        );
+        assert!(from_bytes(&nested(MAX_DEPTH)).is_ok());
+    }
+
+    #[test]
+    fn test_truncates_deeply_nested_reports() {
+        let mut report = report!("root").into_dynamic();
+        for _ in 0..MAX_DEPTH + 2 {
+            report = report.context("context").into_dynamic();
+        }
+
+        let restored = from_bytes(&report.to_bytes()).unwrap();
+        // The reports form a single chain, so this is the nesting depth
+        assert_eq!(restored.iter_reports().count(), MAX_DEPTH);
+        assert!(format!("{restored}").contains("3 nested report(s) omitted from the snapshot"));
+    }
+
+    #[test]
+    fn test_truncates_long_strings() {
+        assert_eq!(truncate_str("short", 16), "short");
+        assert_eq!(truncate_str("a long context value", 16), "a lo [truncated]");
+        // The string is cut off at a character boundary
+        assert_eq!(truncate_str("éééééééééé", 17), "éé [truncated]");
+    }
+
+    #[test]
+    fn test_limits_leaked_strings() {
+        let mut interner = Interner::new();
+        let long = "x".repeat(MAX_LEAKED_BYTES / 2);
+        let first = interner.leak(long.clone());
+        assert!(core::ptr::eq(first, interner.leak(long)));
+        interner.leak("y".repeat(MAX_LEAKED_BYTES / 2));
+        assert_eq!(interner.leak(String::from("z")), OMITTED_LABEL);
+        assert_eq!(interner.leaked_bytes, MAX_LEAKED_BYTES);
+    }
+
+    #[test]
+    fn test_decodes_after_leak_budget_is_exhausted() {
+        let mut interner = Interner::new();
+        interner.leak("x".repeat(MAX_LEAKED_BYTES));
+
+        let report = report!("boom").attach_custom::<Help, _>("first");
+        let restored = decode(&report.to_bytes(), &mut |value| interner.leak(value)).unwrap();
+        let attachment = restored.attachments().iter().next_back().unwrap();
+        assert_eq!(
+            attachment
+                .preferred_formatting_style_unhooked(FormattingFunction::Display)
+                .placement,
+            AttachmentFormattingPlacement::Footer {
+                label: OMITTED_LABEL
+            }
+        );
+        assert_eq!(format!("{}", attachment.format_inner_unhooked()), "first");
+        assert_eq!(interner.leaked_bytes, MAX_LEAKED_BYTES);
+    }
+
+    #[test]
+    fn test_leaks_equal_strings_once() {
+        let bytes = report!("boom")
+            .attach_custom::<Help, _>("first")
+            .attach_custom::<Help, _>("second")
+            .to_bytes();
+        let labels = |report: &Report<PreformattedContext, Mutable, SendSync>| {
+            report
+                .attachments()
+                .iter()
+                .filter_map(|attachment| {
+                    match attachment
+                        .preferred_formatting_style_unhooked(FormattingFunction::Display)
+                        .placement
+                    {
+                        AttachmentFormattingPlacement::Footer { label } => Some(label),
+                        _ => None,
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let restored = labels(&from_bytes(&bytes).unwrap());
+        assert_eq!(restored.len(), 2);
+        assert!(core::ptr::eq(restored[0], restored[1]));
+
+        #[cfg(feature = "std")]
+        {
+            let restored_again = labels(&from_bytes(&bytes).unwrap());
+            assert!(core::ptr::eq(restored[0], restored_again[0]));
+        }
+    }
+}
@@ -77,21 +77,64 @@
 //!
 //! **Note:** [`RootcauseLayer`] must be in your subscriber setup either way.
 //!
+//! ## Snapshots
+//!
+//! A [`Span`] attachment holds a handle to the span, which keeps the span open
+//! for as long as the report exists, and the span is looked up when the
+//! report is formatted. A [`SpanSnapshot`] instead copies the names and field
+//! values of the current span and its ancestors when it is captured, so the
+//! spans can close as usual.
+//! Use [`SpanExt::attach_span_tree`] to attach one, or install a
+//! [`SpanSnapshotCollector`], created with
+//! [`SpanCollector::with_snapshots`], to capture snapshots automatically.
+//!
+//! # Logging Reports
+//!
+//! To log errors as [`tracing`] events as soon as they are created, install a
+//! [`TracingEmitter`] hook. It emits an `ERROR` event for either the leaf or
+//! the root reports, so each error is not logged once per context:
+//!
+//! ```
+//! use rootcause::hooks::Hooks;
+//! use rootcause_tracing::{EmitFor, TracingEmitter};
+//!
+//! Hooks::new()
+//!     .report_creation_hook(TracingEmitter::new(EmitFor::Leaves))
+//!     .install()
+//!     .expect("failed to install hooks");
+//! ```
+//!
+//! # Sensitive Fields
+//!
+//! Spans often carry sensitive fields, such as tokens or passwords, next to
+//! useful ones. Use [`RootcauseLayer::with_field_filter`] to mask the values
+//! of such fields before they are captured:
+//!
+//! ```
+//! use rootcause_tracing::{FieldFilter, RootcauseLayer};
+//! use tracing_subscriber::{Registry, layer::SubscriberExt};
+//!
+//! let subscriber = Registry::default()
+//!     .with(RootcauseLayer.with_field_filter(FieldFilter::deny(["password", "token"])));
+//! # let _ = subscriber;
+//! ```
+//!
 //! # Environment Variables
 //!
 //! - `ROOTCAUSE_TRACING` - Comma-separated options:
 //!   - `leafs` - Only capture tracing spans for leaf errors (errors without
 //!     children)
+//!   - `snapshot` - Capture tracing spans as [`SpanSnapshot`]s
 
-use std::{fmt, sync::OnceLock};
+use std::{borrow::Cow, fmt, sync::OnceLock};
 
 use rootcause::{
-    Report, ReportMut,
+    Report, ReportMut, ReportRef,
     handlers::{
         AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler,
         FormattingFunction,
     },
-    hooks::report_creation::ReportCreationHook,
+    hooks::{builtin_hooks::location::Location, report_creation::ReportCreationHook},
     markers::{self, Dynamic, ObjectMarkerFor},
     report_attachment::ReportAttachment,
 };
@@ -122,6 +165,101 @@ pub struct SpanHandler;
 /// Captured field values for a span.
 struct CapturedFields(String);
 
+/// Placeholder written in place of the values of masked span fields.
+const MASKED_FIELD_VALUE: &str = "<masked>";
+
+/// Allow-list or deny-list of span field names.
+///
+/// Used with [`RootcauseLayer::with_field_filter`] to keep sensitive span
+/// fields out of error reports. The names of masked fields are still shown,
+/// but their values are replaced with `<masked>` when they are captured, so
+/// the original values never end up in a report.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_tracing::FieldFilter;
+///
+/// // Mask only the listed fields
+/// let deny = FieldFilter::deny(["password", "token"]);
+/// assert!(deny.is_masked("password"));
+/// assert!(!deny.is_masked("user_id"));
+///
+/// // Mask every field except the listed ones
+/// let allow = FieldFilter::allow(["user_id"]);
+/// assert!(!allow.is_masked("user_id"));
+/// assert!(allow.is_masked("password"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub enum FieldFilter {
+    /// Capture the values of all fields.
+    #[default]
+    AllowAll,
+    /// Capture the values of the listed fields only, and mask all others.
+    Allow(Vec<Cow<'static, str>>),
+    /// Mask the values of the listed fields, and capture all others.
+    Deny(Vec<Cow<'static, str>>),
+}
+
+impl FieldFilter {
+    /// Creates a [`FieldFilter::Allow`] from a list of field names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_tracing::FieldFilter;
+    ///
+    /// // The names can also be loaded at runtime
+    /// let fields: Vec<String> = vec!["user_id".to_string(), "request_id".to_string()];
+    /// let filter = FieldFilter::allow(fields);
+    /// assert!(filter.is_masked("session"));
+    /// ```
+    pub fn allow<I>(fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Cow<'static, str>>,
+    {
+        Self::Allow(fields.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates a [`FieldFilter::Deny`] from a list of field names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_tracing::FieldFilter;
+    ///
+    /// let filter = FieldFilter::deny(["password"]);
+    /// assert!(filter.is_masked("password"));
+    /// ```
+    pub fn deny<I>(fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Cow<'static, str>>,
+    {
+        Self::Deny(fields.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns whether the value of the field with the given name should be
+    /// masked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_tracing::FieldFilter;
+    ///
+    /// assert!(!FieldFilter::AllowAll.is_masked("password"));
+    /// assert!(FieldFilter::deny(["password"]).is_masked("password"));
+    /// ```
+    pub fn is_masked(&self, field_name: &str) -> bool {
+        match self {
+            FieldFilter::AllowAll => false,
+            FieldFilter::Allow(fields) => !fields.iter().any(|f| f == field_name),
+            FieldFilter::Deny(fields) => fields.iter().any(|f| f == field_name),
+        }
+    }
+}
+
 impl AttachmentHandler<Span> for SpanHandler {
     fn display(value: &Span, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         match value
@@ -188,10 +326,23 @@ fn display_span(
     span: SpanRef<'_, Registry>,
     formatter: &mut fmt::Formatter<'_>,
 ) -> Result<(), fmt::Error> {
-    write!(formatter, "{}", span.name())?;
-
     let extensions = span.extensions();
-    let Some(captured_fields) = extensions.get::<CapturedFields>() else {
+    let captured_fields = extensions.get::<CapturedFields>();
+    write_span(
+        span.name(),
+        captured_fields.map(|fields| fields.0.as_str()),
+        formatter,
+    )
+}
+
+fn write_span(
+    name: &str,
+    captured_fields: Option<&str>,
+    formatter: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    write!(formatter, "{name}")?;
+
+    let Some(captured_fields) = captured_fields else {
         write!(
             formatter,
             "{{ Span values missing. Was the RootcauseLayer installed correctly? }}"
@@ -199,10 +350,175 @@ fn display_span(
         return Ok(());
     };
 
-    if captured_fields.0.is_empty() {
+    if captured_fields.is_empty() {
         Ok(())
     } else {
-        write!(formatter, "{{{}}}", captured_fields.0)
+        write!(formatter, "{{{captured_fields}}}")
+    }
+}
+
+/// A copy of a span and its ancestors, taken when the snapshot is captured.
+///
+/// Unlike a [`Span`] attachment, which keeps the span open and is looked up
+/// when the report is formatted, a snapshot owns the names and field values
+/// of the spans. It does not keep the spans open, and can still be displayed
+/// after they have closed. Field values are copied as they were
+/// captured by [`RootcauseLayer`], so masked fields stay masked.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_tracing::{RootcauseLayer, SpanSnapshot};
+/// use tracing_subscriber::{Registry, layer::SubscriberExt};
+///
+/// let subscriber = Registry::default().with(RootcauseLayer);
+/// let snapshot = tracing::subscriber::with_default(subscriber, || {
+///     let outer = tracing::info_span!("request", id = 7);
+///     let _outer = outer.enter();
+///     let inner = tracing::info_span!("query");
+///     let _inner = inner.enter();
+///     SpanSnapshot::capture()
+/// });
+///
+/// // The spans are closed, but the snapshot can still be displayed
+/// assert_eq!(snapshot.to_string(), "query\nrequest{id=7}");
+/// assert_eq!(snapshot.spans[1].name, "request");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpanSnapshot {
+    /// The captured spans, starting with the innermost span.
+    pub spans: Vec<CapturedSpan>,
+}
+
+/// A single span of a [`SpanSnapshot`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_tracing::{CapturedSpan, SpanSnapshot};
+///
+/// let snapshot = SpanSnapshot {
+///     spans: vec![CapturedSpan {
+///         name: "request",
+///         fields: Some("id=7".to_string()),
+///     }],
+/// };
+/// assert_eq!(snapshot.to_string(), "request{id=7}");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedSpan {
+    /// The name of the span.
+    pub name: &'static str,
+    /// The field values of the span, formatted as `name=value` pairs
+    /// separated by spaces, or `None` if [`RootcauseLayer`] was not installed
+    /// when the span was created.
+    pub fields: Option<String>,
+}
+
+impl SpanSnapshot {
+    /// Captures a snapshot of the current span and its ancestors.
+    ///
+    /// The snapshot is empty if there is no current span, or if the
+    /// subscriber is not a [`Registry`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_tracing::SpanSnapshot;
+    ///
+    /// // There is no current span here
+    /// assert!(SpanSnapshot::capture().spans.is_empty());
+    /// ```
+    pub fn capture() -> Self {
+        Self::of(&Span::current())
+    }
+
+    /// Captures a snapshot of the given span and its ancestors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_tracing::SpanSnapshot;
+    /// use tracing::Span;
+    ///
+    /// assert!(SpanSnapshot::of(&Span::none()).spans.is_empty());
+    /// ```
+    pub fn of(span: &Span) -> Self {
+        let spans = span
+            .with_subscriber(|(span_id, dispatch)| {
+                let registry = dispatch.downcast_ref::<Registry>()?;
+                let span = registry.span(span_id)?;
+                Some(
+                    span.scope()
+                        .map(|span| CapturedSpan {
+                            name: span.name(),
+                            fields: span
+                                .extensions()
+                                .get::<CapturedFields>()
+                                .map(|fields| fields.0.clone()),
+                        })
+                        .collect(),
+                )
+            })
+            .flatten()
+            .unwrap_or_default();
+        Self { spans }
+    }
+}
+
+/// Formats the spans on separate lines, starting with the innermost span.
+impl fmt::Display for SpanSnapshot {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, span) in self.spans.iter().enumerate() {
+            if index > 0 {
+                writeln!(formatter)?;
+            }
+            write_span(span.name, span.fields.as_deref(), formatter)?;
+        }
+        Ok(())
+    }
+}
+
+/// Handler for formatting [`SpanSnapshot`] attachments.
+///
+/// Snapshots are displayed like [`Span`] attachments, under a
+/// `Tracing spans:` header. Empty snapshots are hidden.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::report_attachment::ReportAttachment;
+/// use rootcause_tracing::{SpanSnapshot, SpanSnapshotHandler};
+///
+/// let _ = ReportAttachment::new_sendsync_custom::<SpanSnapshotHandler>(SpanSnapshot::capture());
+/// ```
+#[derive(Copy, Clone)]
+pub struct SpanSnapshotHandler;
+
+impl AttachmentHandler<SpanSnapshot> for SpanSnapshotHandler {
+    fn display(value: &SpanSnapshot, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, formatter)
+    }
+
+    fn debug(value: &SpanSnapshot, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, formatter)
+    }
+
+    fn preferred_formatting_style(
+        value: &SpanSnapshot,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: if value.spans.is_empty() {
+                AttachmentFormattingPlacement::Hidden
+            } else {
+                AttachmentFormattingPlacement::InlineWithHeader {
+                    header: "Tracing spans:",
+                }
+            },
+            function: FormattingFunction::Display,
+            priority: 9, // Same priority as span attachments
+        }
     }
 }
 
@@ -228,6 +544,99 @@ fn display_span(
 #[derive(Copy, Clone, Debug, Default)]
 pub struct RootcauseLayer;
 
+impl RootcauseLayer {
+    /// Returns a layer that masks span field values according to
+    /// `field_filter` when capturing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_tracing::{FieldFilter, RootcauseLayer};
+    /// use tracing_subscriber::{Registry, layer::SubscriberExt};
+    ///
+    /// let subscriber = Registry::default()
+    ///     .with(RootcauseLayer.with_field_filter(FieldFilter::deny(["password"])))
+    ///     .with(tracing_subscriber::fmt::layer());
+    ///
+    /// tracing::subscriber::set_global_default(subscriber).expect("failed to set subscriber");
+    /// ```
+    pub fn with_field_filter(self, field_filter: FieldFilter) -> FilteredRootcauseLayer {
+        FilteredRootcauseLayer { field_filter }
+    }
+}
+
+/// A [`RootcauseLayer`] that masks span field values using a [`FieldFilter`].
+///
+/// Created using [`RootcauseLayer::with_field_filter`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_tracing::{FieldFilter, FilteredRootcauseLayer, RootcauseLayer};
+///
+/// let layer: FilteredRootcauseLayer =
+///     RootcauseLayer.with_field_filter(FieldFilter::deny(["password"]));
+/// assert!(layer.field_filter().is_masked("password"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct FilteredRootcauseLayer {
+    field_filter: FieldFilter,
+}
+
+impl FilteredRootcauseLayer {
+    /// Returns the [`FieldFilter`] used by this layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause_tracing::{FieldFilter, RootcauseLayer};
+    ///
+    /// let layer = RootcauseLayer.with_field_filter(FieldFilter::allow(["user_id"]));
+    /// assert!(!layer.field_filter().is_masked("user_id"));
+    /// ```
+    pub fn field_filter(&self) -> &FieldFilter {
+        &self.field_filter
+    }
+}
+
+fn capture_fields<S>(
+    attrs: &tracing::span::Attributes<'_>,
+    id: &tracing::span::Id,
+    ctx: tracing_subscriber::layer::Context<'_, S>,
+    field_filter: &FieldFilter,
+) where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    let span = ctx.span(id).expect("span not found");
+    let mut extensions = span.extensions_mut();
+
+    struct Visitor<'a> {
+        captured: String,
+        field_filter: &'a FieldFilter,
+    }
+
+    impl Visit for Visitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            use std::fmt::Write;
+            if !self.captured.is_empty() {
+                self.captured.push(' ');
+            }
+            if self.field_filter.is_masked(field.name()) {
+                let _ = write!(self.captured, "{}={MASKED_FIELD_VALUE}", field.name());
+            } else {
+                let _ = write!(self.captured, "{}={value:?}", field.name());
+            }
+        }
+    }
+
+    let mut visitor = Visitor {
+        captured: String::new(),
+        field_filter,
+    };
+    attrs.record(&mut visitor);
+    extensions.insert(CapturedFields(visitor.captured));
+}
+
 impl<S> tracing_subscriber::Layer<S> for RootcauseLayer
 where
     S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
@@ -238,25 +647,21 @@ where
         id: &tracing::span::Id,
         ctx: tracing_subscriber::layer::Context<'_, S>,
     ) {
-        let span = ctx.span(id).expect("span not found");
-        let mut extensions = span.extensions_mut();
-
-        struct Visitor(String);
-
-        impl Visit for Visitor {
-            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
-                use std::fmt::Write;
-                if self.0.is_empty() {
-                    let _ = write!(self.0, "{}={value:?}", field.name());
-                } else {
-                    let _ = write!(self.0, " {}={value:?}", field.name());
-                }
-            }
-        }
+        capture_fields(attrs, id, ctx, &FieldFilter::AllowAll);
+    }
+}
 
-        let mut visitor = Visitor(String::new());
-        attrs.record(&mut visitor);
-        extensions.insert(CapturedFields(visitor.0));
+impl<S> tracing_subscriber::Layer<S> for FilteredRootcauseLayer
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        id: &tracing::span::Id,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        capture_fields(attrs, id, ctx, &self.field_filter);
     }
 }
 
@@ -304,9 +709,35 @@ pub struct SpanCollector {
     pub capture_span_for_reports_with_children: bool,
 }
 
+/// Attachment collector for capturing snapshots of tracing spans.
+///
+/// This works like [`SpanCollector`], except that it attaches a
+/// [`SpanSnapshot`] instead of a [`Span`]. The names and field values of the
+/// spans are copied when the report is created, so the report does not keep
+/// the spans open.
+///
+/// Created using [`SpanCollector::with_snapshots`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::Hooks;
+/// use rootcause_tracing::SpanCollector;
+///
+/// Hooks::new()
+///     .report_creation_hook(SpanCollector::new().with_snapshots())
+///     .install()
+///     .expect("failed to install hooks");
+/// ```
+#[derive(Copy, Clone)]
+pub struct SpanSnapshotCollector {
+    collector: SpanCollector,
+}
+
 #[derive(Debug)]
 struct RootcauseTracingEnvOptions {
     span_leafs_only: bool,
+    snapshot_spans: bool,
 }
 
 impl RootcauseTracingEnvOptions {
@@ -315,16 +746,22 @@ impl RootcauseTracingEnvOptions {
 
         ROOTCAUSE_TRACING_FLAGS.get_or_init(|| {
             let mut span_leafs_only = false;
+            let mut snapshot_spans = false;
 
             if let Some(var) = std::env::var_os("ROOTCAUSE_TRACING") {
                 for v in var.to_string_lossy().split(',') {
                     if v.eq_ignore_ascii_case("leafs") {
                         span_leafs_only = true;
+                    } else if v.eq_ignore_ascii_case("snapshot") {
+                        snapshot_spans = true;
                     }
                 }
             }
 
-            RootcauseTracingEnvOptions { span_leafs_only }
+            RootcauseTracingEnvOptions {
+                span_leafs_only,
+                snapshot_spans,
+            }
         })
     }
 }
@@ -339,6 +776,9 @@ impl SpanCollector {
     /// - `ROOTCAUSE_TRACING` - Comma-separated options:
     ///   - `leafs` - Only capture tracing spans for leaf errors (errors without
     ///     children)
+    ///   - `snapshot` - Capture tracing spans as [`SpanSnapshot`]s. This
+    ///     applies to every [`SpanCollector`], including the ones created
+    ///     using a struct literal.
     ///
     /// # Examples
     ///
@@ -360,6 +800,32 @@ impl SpanCollector {
             capture_span_for_reports_with_children,
         }
     }
+
+    /// Returns a collector that captures the spans as a [`SpanSnapshot`]
+    /// instead of a [`Span`].
+    ///
+    /// See [`SpanSnapshotCollector`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::Hooks;
+    /// use rootcause_tracing::SpanCollector;
+    ///
+    /// let collector = SpanCollector {
+    ///     capture_span_for_reports_with_children: false,
+    /// }
+    /// .with_snapshots();
+    ///
+    /// Hooks::new()
+    ///     .report_creation_hook(collector)
+    ///     .install()
+    ///     .expect("failed to install hooks");
+    /// ```
+    #[must_use]
+    pub fn with_snapshots(self) -> SpanSnapshotCollector {
+        SpanSnapshotCollector { collector: self }
+    }
 }
 
 impl Default for SpanCollector {
@@ -368,32 +834,194 @@ impl Default for SpanCollector {
     }
 }
 
-impl ReportCreationHook for SpanCollector {
-    fn on_local_creation(&self, mut report: ReportMut<'_, Dynamic, markers::Local>) {
+impl SpanCollector {
+    fn capture<T>(
+        &self,
+        report: &ReportMut<'_, Dynamic, T>,
+        snapshot_spans: bool,
+    ) -> Option<ReportAttachment<Dynamic, markers::SendSync>> {
         let do_capture =
             self.capture_span_for_reports_with_children || report.children().is_empty();
-        if do_capture {
-            let span = Span::current();
-            if !span.is_none() {
-                let attachment = ReportAttachment::new_custom::<SpanHandler>(span);
-                report.attachments_mut().push(attachment.into_dynamic());
-            }
+        if !do_capture {
+            return None;
+        }
+
+        let span = Span::current();
+        if span.is_none() {
+            return None;
+        }
+        let attachment = if snapshot_spans {
+            ReportAttachment::new_custom::<SpanSnapshotHandler>(SpanSnapshot::of(&span))
+                .into_dynamic()
+        } else {
+            ReportAttachment::new_custom::<SpanHandler>(span).into_dynamic()
+        };
+        Some(attachment)
+    }
+}
+
+impl ReportCreationHook for SpanCollector {
+    fn on_local_creation(&self, mut report: ReportMut<'_, Dynamic, markers::Local>) {
+        // The attachments are `Send + Sync`, so they are recorded as such to let
+        // `Report::try_into_sendsync` succeed
+        if let Some(attachment) =
+            self.capture(&report, RootcauseTracingEnvOptions::get().snapshot_spans)
+        {
+            report.attachments_mut().push(attachment.into_local());
         }
     }
 
     fn on_sendsync_creation(&self, mut report: ReportMut<'_, Dynamic, markers::SendSync>) {
-        let do_capture =
-            self.capture_span_for_reports_with_children || report.children().is_empty();
-        if do_capture {
-            let span = Span::current();
-            if !span.is_none() {
-                let attachment = ReportAttachment::new_custom::<SpanHandler>(span);
-                report.attachments_mut().push(attachment.into_dynamic());
-            }
+        if let Some(attachment) =
+            self.capture(&report, RootcauseTracingEnvOptions::get().snapshot_spans)
+        {
+            report.attachments_mut().push(attachment);
+        }
+    }
+}
+
+impl ReportCreationHook for SpanSnapshotCollector {
+    fn on_local_creation(&self, mut report: ReportMut<'_, Dynamic, markers::Local>) {
+        // The attachments are `Send + Sync`, so they are recorded as such to let
+        // `Report::try_into_sendsync` succeed
+        if let Some(attachment) = self.collector.capture(&report, true) {
+            report.attachments_mut().push(attachment.into_local());
+        }
+    }
+
+    fn on_sendsync_creation(&self, mut report: ReportMut<'_, Dynamic, markers::SendSync>) {
+        if let Some(attachment) = self.collector.capture(&report, true) {
+            report.attachments_mut().push(attachment);
         }
     }
 }
 
+/// Which report creations are emitted as events by a [`TracingEmitter`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause_tracing::{EmitFor, TracingEmitter};
+///
+/// let emitter = TracingEmitter::new(EmitFor::Roots);
+/// assert_eq!(emitter.emit_for, EmitFor::Roots);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EmitFor {
+    /// Emit an event for reports created without children, which is where an
+    /// error originates.
+    ///
+    /// Each error is logged once when it is created. Wrapping the report in
+    /// new contexts on its way up the call stack does not emit anything else.
+    Leaves,
+    /// Emit an event for reports created with children, such as by
+    /// [`Report::context`] or [`Report::join`].
+    ///
+    /// The event contains the summary of the whole chain below the new
+    /// report. Note that every call to [`Report::context`] creates a new root,
+    /// so a report wrapped several times emits several events.
+    Roots,
+}
+
+/// Report creation hook emitting a [`tracing`] event for new reports.
+///
+/// The event is emitted at the `ERROR` level, with the
+/// [compact summary](rootcause::ReportRef::format_compact) of the report as
+/// its message and the following fields:
+///
+/// - `context_type`: The type name of the context of the new report.
+/// - `location`: Where the report was created, if the location hook is
+///   installed.
+/// - `attachments`: The displayed attachments of the new report. Spans, span
+///   snapshots, locations and hidden attachments are left out.
+///
+/// Only one kind of report creation emits an event, to avoid logging the same
+/// error for every intermediate report. See [`EmitFor`] for the options.
+///
+/// Hooks are run in the order they were installed, so install the emitter
+/// after any attachment collectors whose attachments should be included in
+/// the event.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::Hooks;
+/// use rootcause_tracing::{EmitFor, SpanCollector, TracingEmitter};
+///
+/// Hooks::new()
+///     .report_creation_hook(SpanCollector::new())
+///     .report_creation_hook(TracingEmitter::new(EmitFor::Leaves))
+///     .install()
+///     .expect("failed to install hooks");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct TracingEmitter {
+    /// Which report creations emit an event.
+    pub emit_for: EmitFor,
+}
+
+impl TracingEmitter {
+    /// Creates a new [`TracingEmitter`] emitting events for the given kind of
+    /// report creation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::Hooks;
+    /// use rootcause_tracing::{EmitFor, TracingEmitter};
+    ///
+    /// Hooks::new()
+    ///     .report_creation_hook(TracingEmitter::new(EmitFor::Leaves))
+    ///     .install()
+    ///     .expect("failed to install hooks");
+    /// ```
+    pub const fn new(emit_for: EmitFor) -> Self {
+        Self { emit_for }
+    }
+
+    fn emit<T>(&self, report: ReportRef<'_, Dynamic, markers::Uncloneable, T>) {
+        let is_leaf = report.children().is_empty();
+        let emit = match self.emit_for {
+            EmitFor::Leaves => is_leaf,
+            EmitFor::Roots => !is_leaf,
+        };
+        if !emit {
+            return;
+        }
+
+        let attachments: Vec<String> = report
+            .attachments()
+            .iter()
+            .filter(|attachment| {
+                let style = attachment.preferred_formatting_style(FormattingFunction::Display);
+                !matches!(style.placement, AttachmentFormattingPlacement::Hidden)
+                    && attachment.downcast_inner::<Span>().is_none()
+                    && attachment.downcast_inner::<SpanSnapshot>().is_none()
+                    && attachment.downcast_inner::<Location>().is_none()
+            })
+            .map(|attachment| attachment.format_inner().to_string())
+            .collect();
+
+        tracing::error!(
+            context_type = report.current_context_type_name(),
+            location = report.location().map(tracing::field::display),
+            attachments = ?attachments,
+            "{}",
+            report.format_compact(),
+        );
+    }
+}
+
+impl ReportCreationHook for TracingEmitter {
+    fn on_local_creation(&self, report: ReportMut<'_, Dynamic, markers::Local>) {
+        self.emit(report.into_ref());
+    }
+
+    fn on_sendsync_creation(&self, report: ReportMut<'_, Dynamic, markers::SendSync>) {
+        self.emit(report.into_ref());
+    }
+}
+
 /// Extension trait for attaching tracing spans to reports.
 ///
 /// This trait provides methods to easily attach the current tracing span
@@ -441,11 +1069,31 @@ pub trait SpanExt: Sized {
     /// }
     /// ```
     fn attach_span(self) -> Self;
+
+    /// Attaches a [`SpanSnapshot`] of the current tracing span and its
+    /// ancestors to the report.
+    ///
+    /// Unlike [`SpanExt::attach_span`], the names and field values of the
+    /// spans are copied now, so the report does not keep the spans open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report;
+    /// use rootcause_tracing::SpanExt;
+    ///
+    /// #[tracing::instrument]
+    /// fn example() {
+    ///     let report = report!("error").attach_span_tree();
+    /// }
+    /// ```
+    fn attach_span_tree(self) -> Self;
 }
 
 impl<C: ?Sized, T> SpanExt for Report<C, markers::Mutable, T>
 where
     Span: ObjectMarkerFor<T>,
+    SpanSnapshot: ObjectMarkerFor<T>,
 {
     fn attach_span(mut self) -> Self {
         let span = Span::current();
@@ -454,11 +1102,20 @@ where
         }
         self
     }
+
+    fn attach_span_tree(mut self) -> Self {
+        let span = Span::current();
+        if !span.is_disabled() {
+            self = self.attach_custom::<SpanSnapshotHandler, _>(SpanSnapshot::of(&span));
+        }
+        self
+    }
 }
 
 impl<C: ?Sized, V, T> SpanExt for Result<V, Report<C, markers::Mutable, T>>
 where
     Span: ObjectMarkerFor<T>,
+    SpanSnapshot: ObjectMarkerFor<T>,
 {
     fn attach_span(self) -> Self {
         match self {
@@ -466,4 +1123,175 @@ where
             Err(report) => Err(report.attach_span()),
         }
     }
+
+    fn attach_span_tree(self) -> Self {
+        match self {
+            Ok(v) => Ok(v),
+            Err(report) => Err(report.attach_span_tree()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::{Registry, layer::SubscriberExt};
+
+    use super::*;
+
+    #[test]
+    fn denied_fields_are_masked() {
+        let subscriber = Registry::default()
+            .with(RootcauseLayer.with_field_filter(FieldFilter::deny(["password"])));
+
+        let output = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("login", user = "alice", password = "hunter2");
+            let _guard = span.enter();
+            rootcause::report!("login failed").attach_span().to_string()
+        });
+
+        assert!(output.contains("user=\"alice\""), "{output}");
+        assert!(output.contains("password=<masked>"), "{output}");
+        assert!(!output.contains("hunter2"), "{output}");
+    }
+
+    #[test]
+    fn unlisted_fields_are_masked_by_allow_list() {
+        let subscriber = Registry::default()
+            .with(RootcauseLayer.with_field_filter(FieldFilter::allow(["user"])));
+
+        let output = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("login", user = "alice", token = "secret-token");
+            let _guard = span.enter();
+            rootcause::report!("login failed").attach_span().to_string()
+        });
+
+        assert!(output.contains("user=\"alice\""), "{output}");
+        assert!(output.contains("token=<masked>"), "{output}");
+        assert!(!output.contains("secret-token"), "{output}");
+    }
+
+    #[derive(Clone, Default)]
+    struct ClosedSpans(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for ClosedSpans {
+        fn on_close(
+            &self,
+            _id: tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn span_tree_does_not_keep_spans_open() {
+        let closed = ClosedSpans::default();
+        let subscriber = Registry::default()
+            .with(RootcauseLayer)
+            .with(closed.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", id = 7);
+            let guard = span.enter();
+            let lazy = rootcause::report!("failed").attach_span();
+            let snapshot = rootcause::report!("failed").attach_span_tree();
+            drop(guard);
+            drop(span);
+
+            // The span attachment keeps the span open
+            assert_eq!(closed.0.load(std::sync::atomic::Ordering::SeqCst), 0);
+            drop(lazy);
+            assert_eq!(closed.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+            let output = snapshot.to_string();
+            assert!(output.contains("request{id=7}"), "{output}");
+        });
+    }
+
+    #[test]
+    fn snapshot_collector_attaches_snapshots() {
+        let subscriber = Registry::default().with(RootcauseLayer);
+
+        let collector = SpanCollector {
+            capture_span_for_reports_with_children: true,
+        }
+        .with_snapshots();
+        let report = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", id = 7);
+            let _guard = span.enter();
+            let mut report: Report = rootcause::report!("failed").into_dynamic();
+            collector.on_sendsync_creation(report.as_mut());
+            report
+        });
+
+        assert!(
+            report
+                .attachments()
+                .iter()
+                .any(|attachment| attachment.downcast_inner::<SpanSnapshot>().is_some())
+        );
+        assert!(
+            report
+                .attachments()
+                .iter()
+                .all(|attachment| attachment.downcast_inner::<Span>().is_none())
+        );
+        let output = report.to_string();
+        assert!(output.contains("request{id=7}"), "{output}");
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordedMessages(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for RecordedMessages {
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            struct MessageVisitor(Option<String>);
+            impl Visit for MessageVisitor {
+                fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                    if field.name() == "message" {
+                        self.0 = Some(format!("{value:?}"));
+                    }
+                }
+            }
+
+            let mut visitor = MessageVisitor(None);
+            event.record(&mut visitor);
+            self.0.lock().unwrap().extend(visitor.0);
+        }
+    }
+
+    fn emitted_messages(emit_for: EmitFor) -> Vec<String> {
+        let recorded = RecordedMessages::default();
+        let subscriber = Registry::default().with(recorded.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let emitter = TracingEmitter::new(emit_for);
+            let mut leaf = rootcause::report!("connection refused").attach("port 5432");
+            emitter.on_sendsync_creation(leaf.as_mut());
+            let mut root = leaf.context("failed to load user").into_dynamic();
+            emitter.on_sendsync_creation(root.as_mut());
+        });
+
+        recorded.0.lock().unwrap().clone()
+    }
+
+    #[test]
+    fn emitter_emits_for_leaves_only() {
+        assert_eq!(
+            emitted_messages(EmitFor::Leaves),
+            ["connection refused (+1 attachment)"]
+        );
+    }
+
+    #[test]
+    fn emitter_emits_for_roots_only() {
+        assert_eq!(
+            emitted_messages(EmitFor::Roots),
+            ["failed to load user: connection refused (+1 attachment)"]
+        );
+    }
 }
@@ -0,0 +1,242 @@
+//! Error responses for [`actix-web`] handlers.
+//!
+//! To enable this integration, add the `compat-actix` feature flag to your
+//! `Cargo.toml`. It implements [`ResponseError`] for [`Report`], so reports can
+//! be returned directly from `actix-web` handlers and used with the `?`
+//! operator in handlers returning [`actix_web::Error`].
+//!
+//! [`actix-web`]: https://docs.rs/actix-web
+//!
+//! # Overview
+//!
+//! When a [`Report`] is turned into an HTTP response:
+//!
+//! - The status code is determined by the contexts in the report tree. The
+//!   reports are visited in depth-first order starting with the root, and the
+//!   first context with a status code registered using
+//!   [`register_status_code`] or [`register_status_code_with`] decides the
+//!   status code. If no such context is found, the status code is
+//!   `500 Internal Server Error`.
+//! - The full report tree is logged using [`tracing`](https://docs.rs/tracing),
+//!   at [`Level::ERROR`](tracing::Level::ERROR) for server errors and
+//!   [`Level::WARN`](tracing::Level::WARN) for all other status codes. Enable
+//!   the `log` feature of `tracing` to forward these events to the `log`
+//!   crate.
+//! - The body of the response only contains the canonical reason of the status
+//!   code, such as `Not Found`. The report itself is never sent to the client,
+//!   since it may contain internal details such as file paths or user data.
+//!
+//! # The `'static` Requirement
+//!
+//! `actix-web` stores errors as boxed [`ResponseError`] trait objects, so the
+//! conversion from an error to [`actix_web::Error`] requires the error to be
+//! `'static`. Contexts and attachments of a [`Report`] are always `'static`,
+//! so every report satisfies this requirement, but it also means that data
+//! borrowed from the request cannot be attached to a report. Copy the data
+//! into an owned value, such as a [`String`], before
+//! attaching it.
+//!
+//! `actix-web` does not require errors to be [`Send`] or [`Sync`], so both
+//! [`SendSync`](crate::markers::SendSync) and [`Local`](crate::markers::Local)
+//! reports can be returned from handlers.
+//!
+//! # Examples
+//!
+//! ```
+//! use actix_web::{ResponseError, http::StatusCode};
+//! use rootcause::{compat::actix::register_status_code, prelude::*};
+//!
+//! #[derive(Debug)]
+//! struct UserNotFound {
+//!     user_id: u32,
+//! }
+//!
+//! impl std::fmt::Display for UserNotFound {
+//!     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//!         write!(f, "user {} not found", self.user_id)
+//!     }
+//! }
+//!
+//! register_status_code::<UserNotFound>(StatusCode::NOT_FOUND);
+//!
+//! fn get_user(user_id: u32) -> Result<String, Report> {
+//!     let user = find_user(user_id).context("failed to look up user")?;
+//!     Ok(user)
+//! }
+//!
+//! fn find_user(user_id: u32) -> Result<String, Report<UserNotFound>> {
+//!     Err(report!(UserNotFound { user_id }))
+//! }
+//!
+//! let report = get_user(7).unwrap_err();
+//! assert_eq!(report.status_code(), StatusCode::NOT_FOUND);
+//!
+//! let response = report.error_response();
+//! assert_eq!(response.status(), StatusCode::NOT_FOUND);
+//! ```
+
+use actix_web::{
+    HttpResponse, ResponseError,
+    http::{StatusCode, header::ContentType},
+};
+
+use crate::{Report, compat::type_registry::TypeRegistry, markers::ReportOwnershipMarker};
+
+/// The registered status codes, keyed by the type of the context.
+static STATUS_CODES: TypeRegistry<StatusCode> = TypeRegistry::new();
+
+/// Registers the status code used for reports containing a context of type
+/// `C`.
+///
+/// Registering a status code for a type that already has one replaces the
+/// previous registration.
+///
+/// See the [module-level documentation](self) for how the status code of a
+/// report is determined.
+///
+/// # Examples
+///
+/// ```
+/// use actix_web::{ResponseError, http::StatusCode};
+/// use rootcause::{compat::actix::register_status_code, prelude::*};
+///
+/// struct PermissionDenied;
+///
+/// register_status_code::<PermissionDenied>(StatusCode::FORBIDDEN);
+///
+/// let report: Report = report!(PermissionDenied).context("cannot delete order").into_dynamic();
+/// assert_eq!(report.status_code(), StatusCode::FORBIDDEN);
+/// ```
+pub fn register_status_code<C>(status_code: StatusCode)
+where
+    C: Sized + 'static,
+{
+    STATUS_CODES.register(move |_: &C| status_code);
+}
+
+/// Registers a function computing the status code used for reports containing
+/// a context of type `C`.
+///
+/// This is useful for error enums where each variant has its own status code.
+/// Registering a status code for a type that already has one replaces the
+/// previous registration.
+///
+/// See the [module-level documentation](self) for how the status code of a
+/// report is determined.
+///
+/// # Examples
+///
+/// ```
+/// use actix_web::{ResponseError, http::StatusCode};
+/// use rootcause::{compat::actix::register_status_code_with, prelude::*};
+///
+/// #[derive(Debug)]
+/// enum OrderError {
+///     NotFound,
+///     AlreadyShipped,
+/// }
+/// # impl std::fmt::Display for OrderError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         std::fmt::Debug::fmt(self, f)
+/// #     }
+/// # }
+///
+/// register_status_code_with(|error: &OrderError| match error {
+///     OrderError::NotFound => StatusCode::NOT_FOUND,
+///     OrderError::AlreadyShipped => StatusCode::CONFLICT,
+/// });
+///
+/// assert_eq!(report!(OrderError::NotFound).status_code(), StatusCode::NOT_FOUND);
+/// assert_eq!(report!(OrderError::AlreadyShipped).status_code(), StatusCode::CONFLICT);
+/// ```
+pub fn register_status_code_with<C, F>(f: F)
+where
+    C: Sized + 'static,
+    F: Fn(&C) -> StatusCode + Send + Sync + 'static,
+{
+    STATUS_CODES.register(f);
+}
+
+/// Turns a [`Report`] into an HTTP response with a sanitized body.
+///
+/// See the [module-level documentation](self) for more information.
+impl<C, O, T> ResponseError for Report<C, O, T>
+where
+    C: ?Sized,
+    O: ReportOwnershipMarker,
+{
+    fn status_code(&self) -> StatusCode {
+        self.iter_reports()
+            .find_map(|report| {
+                let status_code = STATUS_CODES.get(report.current_context_type_id())?;
+                Some(status_code(report.current_context_as_any()))
+            })
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let status_code = self.status_code();
+        if status_code.is_server_error() {
+            tracing::error!(status = status_code.as_u16(), "{self}");
+        } else {
+            tracing::warn!(status = status_code.as_u16(), "{self}");
+        }
+
+        HttpResponse::build(status_code)
+            .content_type(ContentType::plaintext())
+            .body(status_code.canonical_reason().unwrap_or("Error"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::body::MessageBody;
+
+    use super::*;
+    use crate::markers::Dynamic;
+
+    #[test]
+    fn test_status_code_unregistered() {
+        struct Unregistered;
+
+        let report = report!(Unregistered);
+        assert_eq!(report.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_status_code_nearest_to_root_wins() {
+        #[derive(Debug, derive_more::Display)]
+        struct Outer;
+        struct Inner;
+        register_status_code::<Outer>(StatusCode::BAD_REQUEST);
+        register_status_code::<Inner>(StatusCode::NOT_FOUND);
+
+        let report = report!(Inner);
+        assert_eq!(report.status_code(), StatusCode::NOT_FOUND);
+
+        let report: Report<Dynamic> = report.context(Outer).into_dynamic();
+        assert_eq!(report.status_code(), StatusCode::BAD_REQUEST);
+
+        let report = report.context("unrelated context");
+        assert_eq!(report.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_register_replaces() {
+        struct Replaced;
+        register_status_code::<Replaced>(StatusCode::BAD_REQUEST);
+        register_status_code::<Replaced>(StatusCode::GONE);
+
+        assert_eq!(report!(Replaced).status_code(), StatusCode::GONE);
+    }
+
+    #[test]
+    fn test_error_response_body_is_sanitized() {
+        let report = report!("secret token abc123").attach("password: hunter2");
+        let response = report.error_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = response.into_body().try_into_bytes().unwrap();
+        assert_eq!(body, "Internal Server Error");
+    }
+}
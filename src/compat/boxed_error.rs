@@ -93,6 +93,31 @@
 //! }
 //! ```
 //!
+//! The [`source`](core::error::Error::source) chain of the boxed error is
+//! preserved as a chain of child reports, with one report per source and the
+//! deepest source as the leaf. Since the sources are only borrowed from the
+//! boxed error, each child report has a [`BoxedErrorSource`] context with the
+//! formatted output of the source:
+//!
+//! ```
+//! use std::error::Error;
+//!
+//! use rootcause::{compat::boxed_error::BoxedErrorSource, prelude::*};
+//!
+//! #[derive(Debug, thiserror::Error)]
+//! #[error("failed to load the config")]
+//! struct ConfigError(#[source] std::io::Error);
+//!
+//! let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "config.toml not found");
+//! let boxed: Box<dyn Error + Send + Sync> = Box::new(ConfigError(io_error));
+//!
+//! let report: Report = boxed.into_rootcause();
+//! assert_eq!(report.children().len(), 1);
+//! let source = report.children().get(0).unwrap();
+//! let source = source.downcast_current_context::<BoxedErrorSource>().unwrap();
+//! assert_eq!(source.to_string(), "config.toml not found");
+//! ```
+//!
 //! # Using `From` Trait
 //!
 //! The `From` trait is also implemented for direct conversions:
@@ -113,15 +138,22 @@
 //! }
 //! ```
 
-use alloc::boxed::Box;
-use core::error::Error;
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{error::Error, fmt};
 
 use rootcause_internals::handlers::{ContextFormattingStyle, ContextHandler, FormattingFunction};
 
 use super::{IntoRootcause, ReportAsError};
 use crate::{
     Report,
-    markers::{self, Dynamic, Local, SendSync},
+    markers::{self, Dynamic, Local, Mutable, SendSync},
+    report_attachments::ReportAttachments,
+    report_collection::ReportCollection,
 };
 
 /// A custom handler for boxed error trait objects that delegates to the
@@ -215,6 +247,176 @@ impl ContextHandler<Box<dyn Error>> for BoxedErrorHandler {
     }
 }
 
+/// A snapshot of an error in the [`source`](Error::source) chain of a boxed
+/// error.
+///
+/// When a boxed error is converted into a [`Report`] using [`IntoRootcause`],
+/// every error in its source chain becomes a child report with a
+/// [`BoxedErrorSource`] context. The source errors are only borrowed from the
+/// boxed error, so the snapshot stores their formatted
+/// [`Display`](fmt::Display) and [`Debug`](fmt::Debug) output.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::compat::boxed_error::BoxedErrorSource;
+///
+/// let error = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
+/// let source = BoxedErrorSource::new(&error);
+/// assert_eq!(source.to_string(), "file not found");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BoxedErrorSource {
+    display: String,
+    debug: String,
+}
+
+impl BoxedErrorSource {
+    /// Creates a new [`BoxedErrorSource`] from the formatted output of an
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::compat::boxed_error::BoxedErrorSource;
+    ///
+    /// let error = std::io::Error::other("connection reset");
+    /// let source = BoxedErrorSource::new(&error);
+    /// ```
+    pub fn new(error: &dyn Error) -> Self {
+        Self {
+            display: error.to_string(),
+            debug: format!("{error:?}"),
+        }
+    }
+}
+
+impl fmt::Display for BoxedErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display)
+    }
+}
+
+impl ContextHandler<BoxedErrorSource> for BoxedErrorHandler {
+    fn source(_value: &BoxedErrorSource) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+
+    fn display(value: &BoxedErrorSource, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&value.display)
+    }
+
+    fn debug(value: &BoxedErrorSource, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&value.debug)
+    }
+}
+
+/// The backtrace offered by a boxed error through
+/// [`Error::provide`](core::error::Error::provide).
+///
+/// This is only available on nightly, when building with
+/// `--cfg rootcause_unstable_error_provide` and the `std` feature flag. When a
+/// boxed error is converted using [`IntoRootcause`] and provides a captured
+/// [`std::backtrace::Backtrace`], its formatted output is attached to the
+/// report as a [`ProvidedBacktrace`], since the backtrace is only borrowed
+/// from the error.
+#[cfg(all(rootcause_unstable_error_provide, feature = "std"))]
+#[derive(Clone, Debug)]
+pub struct ProvidedBacktrace(pub String);
+
+#[cfg(all(rootcause_unstable_error_provide, feature = "std"))]
+impl fmt::Display for ProvidedBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Handler for [`ProvidedBacktrace`] attachments, which are shown inline with
+/// a `Backtrace` header.
+#[cfg(all(rootcause_unstable_error_provide, feature = "std"))]
+#[derive(Copy, Clone, Debug)]
+pub struct ProvidedBacktraceHandler;
+
+#[cfg(all(rootcause_unstable_error_provide, feature = "std"))]
+impl rootcause_internals::handlers::AttachmentHandler<ProvidedBacktrace>
+    for ProvidedBacktraceHandler
+{
+    fn display(value: &ProvidedBacktrace, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &ProvidedBacktrace, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn preferred_formatting_style(
+        _value: &ProvidedBacktrace,
+        _report_formatting_function: FormattingFunction,
+    ) -> rootcause_internals::handlers::AttachmentFormattingStyle {
+        rootcause_internals::handlers::AttachmentFormattingStyle {
+            placement:
+                rootcause_internals::handlers::AttachmentFormattingPlacement::InlineWithHeader {
+                    header: "Backtrace",
+                },
+            function: FormattingFunction::Display,
+            priority: 0,
+        }
+    }
+}
+
+/// Collects the values offered by a boxed error through
+/// [`Error::provide`](core::error::Error::provide) as attachments.
+///
+/// Without `--cfg rootcause_unstable_error_provide` and the `std` feature
+/// flag, no attachments are collected.
+fn provided_attachments(error: &dyn Error) -> ReportAttachments<SendSync> {
+    #[allow(unused_mut)]
+    let mut attachments = ReportAttachments::new();
+
+    #[cfg(all(rootcause_unstable_error_provide, feature = "std"))]
+    if let Some(backtrace) = core::error::request_ref::<std::backtrace::Backtrace>(error)
+        && backtrace.status() == std::backtrace::BacktraceStatus::Captured
+    {
+        attachments.push(
+            crate::report_attachment::ReportAttachment::new_custom::<ProvidedBacktraceHandler>(
+                ProvidedBacktrace(backtrace.to_string()),
+            )
+            .into_dynamic(),
+        );
+    }
+
+    #[cfg(not(all(rootcause_unstable_error_provide, feature = "std")))]
+    let _ = error;
+
+    attachments
+}
+
+/// Builds the chain of child reports for the source chain of `error`, with the
+/// deepest source as the leaf.
+///
+/// The child reports are created without running the creation hooks, as they
+/// describe the boxed error rather than a new location in the program.
+fn source_chain_children<T>(error: &dyn Error) -> ReportCollection<Dynamic, T>
+where
+    BoxedErrorSource: markers::ObjectMarkerFor<T>,
+{
+    let sources: Vec<BoxedErrorSource> =
+        core::iter::successors(error.source(), |&error| error.source())
+            .map(BoxedErrorSource::new)
+            .collect();
+
+    let mut children = ReportCollection::new();
+    for source in sources.into_iter().rev() {
+        let report = Report::<BoxedErrorSource, Mutable, T>::from_parts_unhooked::<BoxedErrorHandler>(
+            source,
+            children,
+            ReportAttachments::new(),
+        );
+        children = ReportCollection::from([report.into_dynamic().into_cloneable()]);
+    }
+    children
+}
+
 /// A trait for converting rootcause [`Report`]s into boxed error trait objects.
 ///
 /// This trait provides the `.into_boxed_error()` method for converting
@@ -360,7 +562,10 @@ impl IntoRootcause for Box<dyn Error + Send + Sync> {
 
     #[inline(always)]
     fn into_rootcause(self) -> Self::Output {
-        Report::new_sendsync_custom::<BoxedErrorHandler>(self).into_dynamic()
+        let children = source_chain_children(&*self);
+        let attachments = provided_attachments(&*self);
+        Report::<_, Mutable, SendSync>::from_parts::<BoxedErrorHandler>(self, children, attachments)
+            .into_dynamic()
     }
 }
 
@@ -369,7 +574,10 @@ impl IntoRootcause for Box<dyn Error> {
 
     #[inline(always)]
     fn into_rootcause(self) -> Self::Output {
-        Report::new_local_custom::<BoxedErrorHandler>(self).into_dynamic()
+        let children = source_chain_children(&*self);
+        let attachments = provided_attachments(&*self).into_local();
+        Report::<_, Mutable, Local>::from_parts::<BoxedErrorHandler>(self, children, attachments)
+            .into_dynamic()
     }
 }
 
@@ -390,3 +598,111 @@ impl<T> IntoRootcause for Result<T, Box<dyn Error>> {
         self.map_err(|e| e.into_rootcause())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct ChainError {
+        message: &'static str,
+        source: Option<Box<ChainError>>,
+    }
+
+    impl fmt::Display for ChainError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.message)
+        }
+    }
+
+    impl Error for ChainError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.source
+                .as_deref()
+                .map(|source| source as &(dyn Error + 'static))
+        }
+    }
+
+    #[test]
+    fn test_into_rootcause_preserves_source_chain() {
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(ChainError {
+            message: "outer",
+            source: Some(Box::new(ChainError {
+                message: "middle",
+                source: Some(Box::new(ChainError {
+                    message: "inner",
+                    source: None,
+                })),
+            })),
+        });
+
+        let report = boxed.into_rootcause();
+        let contexts: Vec<String> = report
+            .iter_reports()
+            .map(|node| node.format_current_context_unhooked().to_string())
+            .collect();
+        assert_eq!(contexts, ["outer", "middle", "inner"]);
+
+        let leaf = report.iter_reports().last().unwrap();
+        assert!(leaf.children().is_empty());
+        assert_eq!(
+            leaf.downcast_current_context::<BoxedErrorSource>()
+                .unwrap()
+                .to_string(),
+            "inner"
+        );
+    }
+
+    #[cfg(rootcause_unstable_error_provide)]
+    #[test]
+    fn test_report_as_error_provides_attachments() {
+        use crate::{error_code::ErrorCode, severity::Severity};
+
+        let report = crate::report!("disk full")
+            .attach_error_code("E42")
+            .context("failed to save")
+            .attach_severity(Severity::Critical)
+            .into_dynamic();
+        let error = ReportAsError::from(report);
+        let error: &dyn Error = &error;
+
+        assert_eq!(
+            core::error::request_ref::<ErrorCode>(error).map(ErrorCode::as_str),
+            Some("E42")
+        );
+        assert_eq!(
+            core::error::request_value::<Severity>(error),
+            Some(Severity::Critical)
+        );
+        assert!(core::error::request_ref::<Report<Dynamic, markers::Cloneable>>(error).is_some());
+    }
+
+    #[cfg(all(rootcause_unstable_error_provide, feature = "std"))]
+    #[test]
+    fn test_into_rootcause_collects_provided_backtrace() {
+        #[derive(Debug)]
+        struct BacktraceError(std::backtrace::Backtrace);
+
+        impl fmt::Display for BacktraceError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("backtrace error")
+            }
+        }
+
+        impl Error for BacktraceError {
+            fn provide<'a>(&'a self, request: &mut core::error::Request<'a>) {
+                request.provide_ref(&self.0);
+            }
+        }
+
+        let boxed: Box<dyn Error + Send + Sync> =
+            Box::new(BacktraceError(std::backtrace::Backtrace::force_capture()));
+        let report = boxed.into_rootcause();
+        assert!(
+            report
+                .attachments()
+                .iter()
+                .any(|attachment| attachment.downcast_inner::<ProvidedBacktrace>().is_some())
+        );
+    }
+}
@@ -0,0 +1,503 @@
+//! Bidirectional integration with the legacy [`failure`] error handling
+//! library.
+//!
+//! This module supports `failure` version 0.1.x. To enable this integration,
+//! add the `compat-failure` feature flag to your `Cargo.toml`. The feature
+//! requires the `std` feature, since [`failure::Error`] is only available with
+//! the standard library.
+//!
+//! [`failure`]: https://docs.rs/failure
+//!
+//! # Overview
+//!
+//! `failure` is no longer maintained, but it is still found in older
+//! codebases. This module makes it possible to move such a codebase to
+//! rootcause one function at a time, by converting between
+//! [`failure::Error`] and [`Report`] at the boundaries.
+//!
+//! Enabling this feature pulls in `failure`, which is affected by the
+//! unpatched advisories [RUSTSEC-2019-0036] and [RUSTSEC-2020-0036]. Only
+//! enable it while migrating away from `failure`. The feature is excluded from
+//! the `cargo-deny` checks of this repository, so that the advisories stay
+//! active for everything else.
+//!
+//! [RUSTSEC-2019-0036]: https://rustsec.org/advisories/RUSTSEC-2019-0036
+//! [RUSTSEC-2020-0036]: https://rustsec.org/advisories/RUSTSEC-2020-0036
+//!
+//! # Converting from Failure to Rootcause
+//!
+//! Use the [`IntoRootcause`] trait to convert [`failure::Error`] values,
+//! [`failure::Context`] values and [`failure::Fallible`] results:
+//!
+//! ```
+//! use rootcause::prelude::*;
+//!
+//! fn failure_function() -> failure::Fallible<String> {
+//!     Err(failure::err_msg("something went wrong"))
+//! }
+//!
+//! fn rootcause_function() -> Result<String, Report> {
+//!     // Convert failure::Fallible<T> to Result<T, Report>
+//!     let value = failure_function().into_rootcause()?;
+//!     Ok(value)
+//! }
+//! ```
+//!
+//! Other [`Fail`](failure::Fail) types can be converted by going through
+//! [`failure::Error`] first:
+//!
+//! ```
+//! use rootcause::prelude::*;
+//!
+//! let io_error = std::io::Error::from(std::io::ErrorKind::NotFound);
+//! let report: Report = failure::Error::from(io_error).into_rootcause();
+//! ```
+//!
+//! The causes returned by [`failure::Error::iter_causes`] become a chain of
+//! child reports with [`FailureCause`] contexts, so they are shown in the
+//! report tree. If `failure` captured a backtrace, it is attached to the
+//! report as a [`FailureBacktrace`].
+//!
+//! ```
+//! use failure::ResultExt;
+//! use rootcause::prelude::*;
+//!
+//! let result: Result<(), failure::Error> = Err(failure::err_msg("connection refused"))
+//!     .context("failed to connect")
+//!     .map_err(failure::Error::from);
+//!
+//! let report: Report = result.into_rootcause().unwrap_err();
+//! assert_eq!(report.children().len(), 1);
+//! assert_eq!(
+//!     report.children().get(0).unwrap().format_current_context().to_string(),
+//!     "connection refused"
+//! );
+//! ```
+//!
+//! # Converting from Rootcause to Failure
+//!
+//! Use the [`ToFailure`] trait to convert reports into [`failure::Error`]:
+//!
+//! ```
+//! use rootcause::{compat::failure::ToFailure, prelude::*};
+//!
+//! fn rootcause_function() -> Result<String, Report> {
+//!     Err(report!("database connection failed"))
+//! }
+//!
+//! fn failure_function() -> failure::Fallible<String> {
+//!     // Convert Result<T, Report> to failure::Fallible<T>
+//!     let value = rootcause_function().to_failure()?;
+//!     Ok(value)
+//! }
+//! ```
+//!
+//! Or using the `From` trait directly, which also makes the `?` operator work:
+//!
+//! ```
+//! use rootcause::prelude::*;
+//!
+//! fn rootcause_function() -> Result<String, Report> {
+//!     Err(report!("something failed"))
+//! }
+//!
+//! fn failure_function() -> failure::Fallible<String> {
+//!     // The ? operator automatically converts Report to failure::Error
+//!     rootcause_function()?;
+//!     Ok("success".to_string())
+//! }
+//! ```
+//!
+//! The report is wrapped in a [`ReportAsError`], so the
+//! [`Display`](core::fmt::Display) output of the [`failure::Error`] is the
+//! same as that of the report, and the report can be recovered with
+//! [`failure::Error::downcast_ref`].
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use rootcause_internals::handlers::{
+    AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler,
+    ContextFormattingStyle, ContextHandler, FormattingFunction,
+};
+
+use super::{IntoRootcause, ReportAsError};
+use crate::{
+    Report,
+    markers::{Dynamic, Mutable, SendSync},
+    report_attachment::ReportAttachment,
+    report_attachments::ReportAttachments,
+    report_collection::ReportCollection,
+};
+
+/// A custom handler for [`failure::Error`] and [`FailureCause`] that
+/// delegates to `failure`'s own formatting.
+///
+/// You typically don't need to use this handler directly - it's used
+/// automatically by the [`IntoRootcause`] trait.
+///
+/// # Implementation Details
+///
+/// - **Display**: Uses [`failure::Error`]'s [`Display`](core::fmt::Display)
+///   implementation
+/// - **Debug**: Uses [`failure::Error`]'s [`Debug`](core::fmt::Debug)
+///   implementation
+/// - **Source**: Always `None`, since the causes of a [`failure::Error`] are
+///   stored as child reports instead
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{Report, compat::failure::FailureHandler};
+///
+/// let failure_error = failure::err_msg("connection timeout");
+/// let report = Report::new_sendsync_custom::<FailureHandler>(failure_error);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct FailureHandler;
+
+impl ContextHandler<failure::Error> for FailureHandler {
+    fn source(_failure_error: &failure::Error) -> Option<&(dyn core::error::Error + 'static)> {
+        None
+    }
+
+    fn display(failure_error: &failure::Error, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(failure_error, formatter)
+    }
+
+    fn debug(failure_error: &failure::Error, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(failure_error.as_fail(), formatter)
+    }
+
+    fn preferred_formatting_style(
+        _value: &failure::Error,
+        formatting_function: FormattingFunction,
+    ) -> ContextFormattingStyle {
+        ContextFormattingStyle {
+            function: formatting_function,
+            follow_source: false,
+            follow_source_depth: None,
+        }
+    }
+}
+
+/// The formatted output of a cause of a [`failure::Error`].
+///
+/// The causes returned by [`failure::Error::iter_causes`] are borrowed from
+/// the error, so they are formatted when the error is converted into a
+/// [`Report`] and stored as the contexts of its child reports.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{compat::failure::FailureCause, prelude::*};
+///
+/// let cause = failure::err_msg("connection refused");
+/// let error = failure::Error::from(cause.context("failed to connect"));
+///
+/// let report: Report = error.into_rootcause();
+/// let child = report.children().get(0).unwrap();
+/// assert_eq!(
+///     child.downcast_current_context::<FailureCause>().unwrap().to_string(),
+///     "connection refused"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FailureCause {
+    display: String,
+    debug: String,
+}
+
+impl FailureCause {
+    /// Formats a cause of a [`failure::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::compat::failure::FailureCause;
+    ///
+    /// let error = failure::err_msg("connection refused");
+    /// let cause = FailureCause::new(error.as_fail());
+    /// assert_eq!(cause.to_string(), "connection refused");
+    /// ```
+    pub fn new(fail: &dyn failure::Fail) -> Self {
+        Self {
+            display: fail.to_string(),
+            debug: format!("{fail:?}"),
+        }
+    }
+}
+
+impl fmt::Display for FailureCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display)
+    }
+}
+
+impl ContextHandler<FailureCause> for FailureHandler {
+    fn source(_value: &FailureCause) -> Option<&(dyn core::error::Error + 'static)> {
+        None
+    }
+
+    fn display(value: &FailureCause, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&value.display)
+    }
+
+    fn debug(value: &FailureCause, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&value.debug)
+    }
+}
+
+/// The formatted backtrace captured by a [`failure::Error`].
+///
+/// `failure` only captures backtraces when the `RUST_BACKTRACE` environment
+/// variable is set, so this attachment is only added to converted reports
+/// when a backtrace is available.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{compat::failure::FailureBacktrace, prelude::*};
+///
+/// let report: Report = failure::err_msg("disk full").into_rootcause();
+/// if let Some(backtrace) = report.attachments().iter().find_map(|attachment| {
+///     attachment.downcast_inner::<FailureBacktrace>()
+/// }) {
+///     println!("{backtrace}");
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FailureBacktrace(pub String);
+
+impl fmt::Display for FailureBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Handler for formatting [`FailureBacktrace`] attachments.
+///
+/// The backtrace is displayed inline with a `Backtrace` header.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     compat::failure::{FailureBacktrace, FailureBacktraceHandler},
+///     prelude::*,
+/// };
+///
+/// let backtrace = FailureBacktrace(String::from("   0: main"));
+/// let report = report!("error").attach_custom::<FailureBacktraceHandler, _>(backtrace);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct FailureBacktraceHandler;
+
+impl AttachmentHandler<FailureBacktrace> for FailureBacktraceHandler {
+    fn display(value: &FailureBacktrace, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &FailureBacktrace, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, f)
+    }
+
+    fn preferred_formatting_style(
+        _value: &FailureBacktrace,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::InlineWithHeader {
+                header: "Backtrace",
+            },
+            function: FormattingFunction::Display,
+            priority: 10,
+        }
+    }
+}
+
+fn cause_chain_children(error: &failure::Error) -> ReportCollection<Dynamic, SendSync> {
+    let causes: Vec<FailureCause> = error.iter_causes().map(FailureCause::new).collect();
+
+    let mut children = ReportCollection::new();
+    for cause in causes.into_iter().rev() {
+        let report = Report::<FailureCause, Mutable, SendSync>::from_parts_unhooked::<FailureHandler>(
+            cause,
+            children,
+            ReportAttachments::new(),
+        );
+        children = ReportCollection::from([report.into_dynamic().into_cloneable()]);
+    }
+    children
+}
+
+impl IntoRootcause for failure::Error {
+    type Output = Report;
+
+    #[inline(always)]
+    fn into_rootcause(self) -> Self::Output {
+        let children = cause_chain_children(&self);
+
+        let mut attachments = ReportAttachments::new();
+        let backtrace = self.backtrace();
+        if !backtrace.is_empty() {
+            attachments.push(
+                ReportAttachment::new_custom::<FailureBacktraceHandler>(FailureBacktrace(
+                    backtrace.to_string(),
+                ))
+                .into_dynamic(),
+            );
+        }
+
+        Report::<_, Mutable, SendSync>::from_parts::<FailureHandler>(self, children, attachments)
+            .into_dynamic()
+    }
+}
+
+impl<D> IntoRootcause for failure::Context<D>
+where
+    D: fmt::Display + Send + Sync + 'static,
+{
+    type Output = Report;
+
+    #[inline(always)]
+    fn into_rootcause(self) -> Self::Output {
+        failure::Error::from(self).into_rootcause()
+    }
+}
+
+impl<T> IntoRootcause for Result<T, failure::Error> {
+    type Output = Result<T, Report>;
+
+    #[inline(always)]
+    fn into_rootcause(self) -> Self::Output {
+        self.map_err(|e| e.into_rootcause())
+    }
+}
+
+/// A trait for converting rootcause [`Report`]s into [`failure::Error`].
+///
+/// This trait provides the `.to_failure()` method for converting rootcause
+/// reports into `failure` errors. It's implemented for both [`Report`] and
+/// [`Result<T, Report>`], making it easy to call `failure`-based APIs from
+/// rootcause code.
+///
+/// The report is wrapped in a [`ReportAsError`], preserving all contexts,
+/// attachments, and formatting behavior.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     compat::{ReportAsError, failure::ToFailure},
+///     prelude::*,
+/// };
+///
+/// let report = report!("operation failed").attach("debug info");
+/// let failure_error: failure::Error = report.to_failure();
+///
+/// let recovered = failure_error.downcast_ref::<ReportAsError>().unwrap();
+/// assert_eq!(
+///     recovered.0.format_current_context().to_string(),
+///     "operation failed"
+/// );
+/// ```
+pub trait ToFailure {
+    /// The type produced by the conversion.
+    ///
+    /// - For [`Report`]: produces [`failure::Error`]
+    /// - For [`Result<T, Report>`]: produces [`failure::Fallible<T>`]
+    type Output;
+
+    /// Converts this value into a `failure` type.
+    ///
+    /// For [`Report`], this wraps the report in a [`failure::Error`]. For
+    /// [`Result<T, Report>`], this converts the error variant while preserving
+    /// the success value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{compat::failure::ToFailure, prelude::*};
+    ///
+    /// // Convert a result
+    /// let result: Result<i32, Report> = Ok(42);
+    /// let converted: failure::Fallible<i32> = result.to_failure();
+    /// assert_eq!(converted.unwrap(), 42);
+    ///
+    /// // Convert a report
+    /// let report: Report = report!("failed");
+    /// let failure_error: failure::Error = report.to_failure();
+    /// ```
+    fn to_failure(self) -> Self::Output;
+}
+
+impl<C: ?Sized, O> ToFailure for Report<C, O, SendSync> {
+    type Output = failure::Error;
+
+    fn to_failure(self) -> Self::Output {
+        failure::Error::from(self)
+    }
+}
+
+impl<T, C: ?Sized, O> ToFailure for Result<T, Report<C, O, SendSync>> {
+    type Output = Result<T, failure::Error>;
+
+    fn to_failure(self) -> Self::Output {
+        self.map_err(|r| r.to_failure())
+    }
+}
+
+impl<C: ?Sized, O> From<Report<C, O, SendSync>> for failure::Error {
+    fn from(report: Report<C, O, SendSync>) -> Self {
+        failure::Error::from(ReportAsError(report.into_dynamic().into_cloneable()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use failure::ResultExt;
+
+    use super::*;
+
+    #[test]
+    fn test_cause_chain_children() {
+        let error: failure::Error = Err::<(), _>(failure::err_msg("connection refused"))
+            .context("failed to connect")
+            .context("failed to load user")
+            .unwrap_err()
+            .into();
+
+        let report = error.into_rootcause();
+        assert_eq!(
+            report.format_current_context().to_string(),
+            "failed to load user"
+        );
+
+        let chain: Vec<String> = report
+            .iter_primary_chain()
+            .skip(1)
+            .map(|report| report.format_current_context().to_string())
+            .collect();
+        assert_eq!(chain, ["failed to connect", "connection refused"]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let report = report!("database unavailable").into_dynamic();
+        let failure_error = report.to_failure();
+        assert_eq!(
+            failure_error
+                .downcast_ref::<ReportAsError>()
+                .unwrap()
+                .0
+                .format_current_context()
+                .to_string(),
+            "database unavailable"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Flattening of report trees into key/value pairs.
+//!
+//! The structured logging and error tracking integrations all name the
+//! flattened values the same way:
+//!
+//! - [`KeyValue`] attachments use their own key, such as `user_id`.
+//! - [`Location`] attachments use the key `location`.
+//! - Other attachments use the key `attachment.<index>`, where the index is
+//!   the position of the attachment in the report.
+//! - The context of the child at index `i` uses the key `cause.<i>`, and its
+//!   attachments and children are nested below it with dotted keys, such as
+//!   `cause.0.user_id` or `cause.0.cause.1`.
+//!
+//! Attachments whose preferred placement is
+//! [`Hidden`](AttachmentFormattingPlacement::Hidden) are left out. The
+//! integrations decide how the values are converted, and can skip values by
+//! ignoring them.
+
+use alloc::{format, string::String};
+
+use crate::{
+    ReportRef,
+    handlers::{AttachmentFormattingPlacement, FormattingFunction},
+    hooks::builtin_hooks::location::Location,
+    key_value::KeyValue,
+    markers::{Dynamic, Local, Uncloneable},
+    report_attachment::ReportAttachmentRef,
+};
+
+/// A value of a flattened report tree.
+pub(crate) enum FlatValue<'a> {
+    /// The context of a child report
+    Cause(ReportRef<'a, Dynamic, Uncloneable, Local>),
+    /// A [`KeyValue`] attachment
+    KeyValue(&'a KeyValue),
+    /// A [`Location`] attachment
+    Location(&'a Location),
+    /// Any other attachment
+    Attachment(ReportAttachmentRef<'a, Dynamic>),
+}
+
+/// A key/value pair of a flattened report tree.
+pub(crate) struct FlatEntry<'a> {
+    /// The dotted key of the value
+    pub(crate) key: String,
+    /// The value
+    pub(crate) value: FlatValue<'a>,
+    /// Whether the value belongs to a report of the primary chain, which
+    /// follows the first child of each report starting from the root
+    pub(crate) in_primary_chain: bool,
+}
+
+/// Returns whether the preferred placement of an attachment is
+/// [`Hidden`](AttachmentFormattingPlacement::Hidden).
+pub(crate) fn is_hidden(attachment: ReportAttachmentRef<'_, Dynamic>) -> bool {
+    let style = attachment.preferred_formatting_style(FormattingFunction::Display);
+    matches!(style.placement, AttachmentFormattingPlacement::Hidden)
+}
+
+/// Calls `visit` for each attachment of `report` that is not hidden, with keys
+/// starting with `prefix`.
+pub(crate) fn flatten_attachments<'a>(
+    prefix: &str,
+    report: ReportRef<'a, Dynamic, Uncloneable, Local>,
+    in_primary_chain: bool,
+    visit: &mut impl FnMut(FlatEntry<'a>),
+) {
+    for (index, attachment) in report.attachments().iter().enumerate() {
+        if is_hidden(attachment) {
+            continue;
+        }
+
+        let (key, value) = if let Some(key_value) = attachment.downcast_inner::<KeyValue>() {
+            (
+                format!("{prefix}{}", key_value.key),
+                FlatValue::KeyValue(key_value),
+            )
+        } else if let Some(location) = attachment.downcast_inner::<Location>() {
+            (format!("{prefix}location"), FlatValue::Location(location))
+        } else {
+            (
+                format!("{prefix}attachment.{index}"),
+                FlatValue::Attachment(attachment),
+            )
+        };
+        visit(FlatEntry {
+            key,
+            value,
+            in_primary_chain,
+        });
+    }
+}
+
+/// Calls `visit` for each attachment of `report` that is not hidden, and for
+/// the context, attachments and children of each of its children, with keys
+/// starting with `prefix`.
+///
+/// The context of `report` itself is not visited.
+pub(crate) fn flatten_report<'a>(
+    prefix: &str,
+    report: ReportRef<'a, Dynamic, Uncloneable, Local>,
+    in_primary_chain: bool,
+    visit: &mut impl FnMut(FlatEntry<'a>),
+) {
+    flatten_attachments(prefix, report, in_primary_chain, visit);
+
+    for (index, child) in report.children().iter().enumerate() {
+        let child = child.into_uncloneable();
+        let key = format!("{prefix}cause.{index}");
+        let child_prefix = format!("{key}.");
+        let in_primary_chain = in_primary_chain && index == 0;
+        visit(FlatEntry {
+            key,
+            value: FlatValue::Cause(child),
+            in_primary_chain,
+        });
+        flatten_report(&child_prefix, child, in_primary_chain, visit);
+    }
+}
@@ -0,0 +1,176 @@
+//! Structured logging of reports with the [`log`] crate.
+//!
+//! To enable this integration, add the `compat-log` feature flag to your
+//! `Cargo.toml`. It enables the `kv` feature of `log`, so the report tree can
+//! be passed to structured backends as key/value pairs.
+//!
+//! [`log`]: https://docs.rs/log
+//! [`KeyValue`]: crate::key_value::KeyValue
+//! [`Location`]: crate::hooks::builtin_hooks::location::Location
+//!
+//! # Overview
+//!
+//! [`Report::log_error`] emits a report as a single record at
+//! [`Level::Error`]. The message of the record is the
+//! [`Display`](core::fmt::Display) output of the root context, and the rest of
+//! the report tree is flattened into key/value pairs:
+//!
+//! - [`KeyValue`] attachments use their own key, such as `user_id`.
+//! - [`Location`] attachments use the key `location`.
+//! - Other attachments use the key `attachment.<index>`, where the index is
+//!   the position of the attachment in the report.
+//! - The context of the child at index `i` uses the key `cause.<i>`, and its
+//!   attachments and children are nested below it with dotted keys, such as
+//!   `cause.0.user_id` or `cause.0.cause.1`.
+//!
+//! Attachments whose preferred placement is
+//! [`Hidden`](crate::handlers::AttachmentFormattingPlacement::Hidden) are left
+//! out, just as they are when the report is formatted. If the root report has
+//! a [`Location`] attachment, it is also used as the file and line of the
+//! record.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::prelude::*;
+//!
+//! let report = report!("database unavailable")
+//!     .attach_kv("host", "db-1")
+//!     .context("failed to load user")
+//!     .attach_kv("user_id", 42);
+//!
+//! // Emits "failed to load user" with the key/value pairs
+//! // `user_id = 42`, `cause.0 = database unavailable` and
+//! // `cause.0.host = db-1`, along with the `location` and `cause.0.location`
+//! // of both reports.
+//! report.log_error("my_app::users");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use ::log::{
+    Level, Record,
+    kv::{self, Key, Source, Value, VisitSource},
+};
+
+use crate::{
+    Report, ReportRef,
+    compat::flatten::{FlatValue, flatten_report},
+    markers::{Dynamic, Local, Uncloneable},
+};
+
+impl<C: ?Sized, O, T> Report<C, O, T> {
+    /// Emits the report as a [`log`](::log) record at
+    /// [`Level::Error`](::log::Level::Error) with the given target.
+    ///
+    /// The message of the record is the root context, and the attachments
+    /// and child reports are passed as key/value pairs. See the
+    /// [module-level documentation](crate::compat::log) for how the keys are
+    /// named.
+    ///
+    /// Nothing is formatted if the logger is not enabled for the target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let report = report!("connection reset").attach_kv("peer", "10.0.0.7");
+    /// report.log_error("my_app::net");
+    /// ```
+    pub fn log_error(&self, target: &str) {
+        if !::log::log_enabled!(target: target, Level::Error) {
+            return;
+        }
+
+        let report: ReportRef<'_, Dynamic, Uncloneable, Local> = self
+            .as_uncloneable_ref()
+            .into_dynamic()
+            .into_uncloneable()
+            .into_local();
+
+        let pairs = KeyValuePairs::new(report);
+
+        let location = report.location();
+        ::log::logger().log(
+            &Record::builder()
+                .level(Level::Error)
+                .target(target)
+                .args(format_args!("{}", report.format_current_context()))
+                .file(location.map(|location| location.file))
+                .line(location.map(|location| location.line))
+                .key_values(&pairs)
+                .build(),
+        );
+    }
+}
+
+/// The flattened key/value pairs of a report tree.
+struct KeyValuePairs(Vec<(String, String)>);
+
+impl KeyValuePairs {
+    fn new(report: ReportRef<'_, Dynamic, Uncloneable, Local>) -> Self {
+        let mut pairs = Vec::new();
+        flatten_report("", report, true, &mut |entry| {
+            let value = match entry.value {
+                FlatValue::Cause(child) => child.format_current_context().to_string(),
+                FlatValue::KeyValue(key_value) => key_value.value.clone(),
+                FlatValue::Location(location) => location.to_string(),
+                FlatValue::Attachment(attachment) => attachment.format_inner().to_string(),
+            };
+            pairs.push((entry.key, value));
+        });
+        Self(pairs)
+    }
+}
+
+impl Source for KeyValuePairs {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn VisitSource<'kvs>) -> Result<(), kv::Error> {
+        for (key, value) in &self.0 {
+            visitor.visit_pair(Key::from_str(key), Value::from(value.as_str()))?;
+        }
+        Ok(())
+    }
+
+    fn count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(report: ReportRef<'_, Dynamic, Uncloneable, Local>) -> Vec<(String, String)> {
+        KeyValuePairs::new(report)
+            .0
+            .into_iter()
+            .filter(|(key, _)| !key.ends_with("location"))
+            .collect()
+    }
+
+    #[test]
+    fn test_flattened_key_value_pairs() {
+        let first = report!("database unavailable").attach_kv("host", "db-1");
+        let second = report!("cache unavailable").attach("cache miss");
+        let report = first
+            .join(second, "failed to load user")
+            .attach_kv("user_id", 42)
+            .into_dynamic();
+
+        let report = report.as_ref().into_local();
+        assert_eq!(
+            pairs(report),
+            [
+                ("user_id".to_string(), "42".to_string()),
+                ("cause.0".to_string(), "database unavailable".to_string()),
+                ("cause.0.host".to_string(), "db-1".to_string()),
+                ("cause.1".to_string(), "cache unavailable".to_string()),
+                ("cause.1.attachment.1".to_string(), "cache miss".to_string()),
+            ]
+        );
+    }
+}
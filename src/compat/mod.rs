@@ -9,6 +9,8 @@
 //!
 //! # Available Integrations
 //!
+//! - [`actix`] - Error responses for `actix-web` handlers returning [`Report`]
+//!   errors (requires the `compat-actix` feature flag)
 //! - [`anyhow1`] - Integration with the `anyhow` 1.x error handling library
 //!   (requires the `compat-anyhow1` feature flag)
 //! - [`boxed_error`] - Convert reports to and from boxed error trait objects
@@ -21,6 +23,18 @@
 //!   handling library (requires the `compat-error-stack07` feature flag)
 //! - [`eyre06`] - Integration with the `eyre` 0.6.x error handling library
 //!   (requires the `compat-eyre06` feature flag)
+//! - [`failure`] - Integration with the legacy `failure` 0.1.x error handling
+//!   library (requires the `compat-failure` feature flag)
+//! - [`log`] - Structured logging of [`Report`]s with the `log` crate
+//!   (requires the `compat-log` feature flag)
+//! - [`sentry`] - Capturing [`Report`]s as Sentry events (requires the
+//!   `compat-sentry` feature flag)
+//! - [`slog`] - Structured logging of [`Report`]s with the `slog` crate
+//!   (requires the `compat-slog` feature flag)
+//! - [`tonic`] - Conversions between [`Report`]s and gRPC statuses of `tonic`
+//!   (requires the `compat-tonic` feature flag)
+//! - [`tower`] - Middleware for logging and converting the [`Report`] errors of
+//!   `tower` services (requires the `compat-tower` feature flag)
 //!
 //! # When to Use Compatibility Modules
 //!
@@ -105,10 +119,15 @@ use crate::{
 ///   error_stack::Report<C>>`
 /// - [`eyre06`] module provides implementations for [`eyre::Report`] and
 ///   [`eyre::Result<T>`]
+/// - [`failure`] module provides implementations for [`failure::Error`],
+///   [`failure::Context<D>`] and [`failure::Fallible<T>`]
 ///
 /// [`anyhow::Error`]: ::anyhow::Error
 /// [`anyhow::Result<T>`]: ::anyhow::Result
 /// [`error_stack::Report<C>`]: ::error_stack07::Report
+/// [`failure::Error`]: ::failure::Error
+/// [`failure::Context<D>`]: ::failure::Context
+/// [`failure::Fallible<T>`]: ::failure::Fallible
 ///
 /// # Examples
 ///
@@ -165,6 +184,10 @@ pub trait IntoRootcause {
 
 pub mod boxed_error;
 
+#[cfg(feature = "compat-actix")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compat-actix")))]
+pub mod actix;
+
 #[cfg(feature = "compat-anyhow1")]
 #[cfg_attr(docsrs, doc(cfg(feature = "compat-anyhow1")))]
 pub mod anyhow1;
@@ -185,6 +208,49 @@ pub mod error_stack07;
 #[cfg_attr(docsrs, doc(cfg(feature = "compat-eyre06")))]
 pub mod eyre06;
 
+#[cfg(feature = "compat-failure")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compat-failure")))]
+pub mod failure;
+
+#[cfg(feature = "compat-log")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compat-log")))]
+pub mod log;
+
+#[cfg(feature = "compat-sentry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compat-sentry")))]
+pub mod sentry;
+
+#[cfg(feature = "compat-slog")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compat-slog")))]
+pub mod slog;
+
+#[cfg(feature = "compat-tonic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compat-tonic")))]
+pub mod tonic;
+
+#[cfg(feature = "compat-tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compat-tower")))]
+pub mod tower;
+
+#[cfg(any(
+    feature = "compat-log",
+    feature = "compat-sentry",
+    feature = "compat-slog"
+))]
+// Each integration only uses the parts of the helpers it needs
+#[cfg_attr(
+    not(all(
+        feature = "compat-log",
+        feature = "compat-sentry",
+        feature = "compat-slog"
+    )),
+    allow(dead_code)
+)]
+mod flatten;
+
+#[cfg(any(feature = "compat-actix", feature = "compat-tonic"))]
+mod type_registry;
+
 /// A wrapper that adapts a rootcause [`Report`] to implement
 /// [`core::error::Error`].
 ///
@@ -234,7 +300,55 @@ impl<C: ?Sized, T> core::fmt::Display for ReportAsError<C, T> {
     }
 }
 
-impl<C: ?Sized, T> core::error::Error for ReportAsError<C, T> {}
+/// On nightly, building with `--cfg rootcause_unstable_error_provide` also
+/// implements [`Error::provide`](core::error::Error::provide), which offers
+/// typed data from the report to [`core::error::request_ref`] and
+/// [`core::error::request_value`]:
+///
+/// - the wrapped `Report<C, Cloneable, T>`,
+/// - the [`Location`], [`ErrorCode`], [`KeyValue`] and [`Severity`]
+///   attachments, and with the `std` feature flag any attached
+///   [`std::backtrace::Backtrace`].
+///
+/// The attachments are searched in the order of
+/// [`Report::iter_reports`], so the attachments of the root report take
+/// precedence over those of its children.
+///
+/// [`Location`]: crate::hooks::builtin_hooks::location::Location
+/// [`ErrorCode`]: crate::error_code::ErrorCode
+/// [`KeyValue`]: crate::key_value::KeyValue
+/// [`Severity`]: crate::severity::Severity
+impl<C: ?Sized, T> core::error::Error for ReportAsError<C, T> {
+    #[cfg(rootcause_unstable_error_provide)]
+    fn provide<'a>(&'a self, request: &mut core::error::Request<'a>) {
+        use crate::{
+            error_code::ErrorCode, hooks::builtin_hooks::location::Location, key_value::KeyValue,
+            severity::Severity,
+        };
+
+        request.provide_ref::<Report<C, markers::Cloneable, T>>(&self.0);
+        for report in self.0.iter_reports() {
+            for attachment in report.attachments().iter() {
+                if let Some(location) = attachment.downcast_inner::<Location>() {
+                    request.provide_ref(location);
+                } else if let Some(error_code) = attachment.downcast_inner::<ErrorCode>() {
+                    request.provide_ref(error_code);
+                } else if let Some(key_value) = attachment.downcast_inner::<KeyValue>() {
+                    request.provide_ref(key_value);
+                } else if let Some(severity) = attachment.downcast_inner::<Severity>() {
+                    request.provide_value(*severity);
+                } else {
+                    #[cfg(feature = "std")]
+                    if let Some(backtrace) =
+                        attachment.downcast_inner::<std::backtrace::Backtrace>()
+                    {
+                        request.provide_ref(backtrace);
+                    }
+                }
+            }
+        }
+    }
+}
 
 impl<C: ?Sized, O, T> From<Report<C, O, T>> for ReportAsError<C, T> {
     fn from(value: Report<C, O, T>) -> Self {
@@ -248,6 +362,84 @@ impl<C: ?Sized, T> From<ReportRef<'_, C, markers::Cloneable, T>> for ReportAsErr
     }
 }
 
+/// Converts a [`Report`] into a [`std::io::Error`] with
+/// [`ErrorKind::Other`](std::io::ErrorKind::Other).
+///
+/// This makes it possible to use `?` on reports in functions that must return
+/// an [`io::Result`](std::io::Result), such as implementations of
+/// [`Read`](std::io::Read) and [`Write`](std::io::Write). The report is
+/// wrapped in an error whose [`Display`](core::fmt::Display) output is the
+/// same as that of the report, and whose
+/// [`source`](core::error::Error::source) is the report as a
+/// [`ReportAsError`]. The report can be recovered by downcasting the
+/// [`source`](core::error::Error::source) of the
+/// [`io::Error`](std::io::Error) to [`ReportAsError`].
+///
+/// This requires the `std` feature flag.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use std::{error::Error, io};
+///
+/// use rootcause::{compat::ReportAsError, prelude::*};
+///
+/// fn parse_header(data: &[u8]) -> Result<u8, Report> {
+///     data.first().copied().ok_or_else(|| report!("missing header"))
+/// }
+///
+/// fn read_header(data: &[u8]) -> io::Result<u8> {
+///     Ok(parse_header(data)?)
+/// }
+///
+/// let error = read_header(&[]).unwrap_err();
+/// assert_eq!(error.kind(), io::ErrorKind::Other);
+///
+/// let report = error
+///     .source()
+///     .and_then(|source| source.downcast_ref::<ReportAsError>())
+///     .unwrap();
+/// assert_eq!(report.0.format_current_context().to_string(), "missing header");
+/// # }
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<C: ?Sized, O> From<Report<C, O, markers::SendSync>> for std::io::Error {
+    fn from(report: Report<C, O, markers::SendSync>) -> Self {
+        std::io::Error::other(IoReportError(ReportAsError::from(report.into_dynamic())))
+    }
+}
+
+/// The error stored in the [`std::io::Error`] created from a [`Report`].
+///
+/// It is displayed the same way as the report, and returns the report as its
+/// [`source`](core::error::Error::source), so that the report can be reached
+/// through [`std::io::Error::source`], which skips the stored error itself.
+#[cfg(feature = "std")]
+struct IoReportError(ReportAsError);
+
+#[cfg(feature = "std")]
+impl core::fmt::Debug for IoReportError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for IoReportError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for IoReportError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
 /// Utility wrapper type for pretty-printing reports when returning
 /// `Result<(), Report>` from main.
 ///
@@ -305,3 +497,28 @@ impl core::fmt::Debug for MainReport {
         core::fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloc::string::ToString;
+    use core::error::Error;
+
+    use super::*;
+
+    #[test]
+    fn test_io_error_source_is_report() {
+        let error = std::io::Error::from(report!("disk full").context("failed to save"));
+        assert_eq!(error.kind(), std::io::ErrorKind::Other);
+
+        let report = error
+            .source()
+            .unwrap()
+            .downcast_ref::<ReportAsError>()
+            .unwrap();
+        assert_eq!(
+            report.0.format_current_context().to_string(),
+            "failed to save"
+        );
+        assert_eq!(error.to_string(), report.to_string());
+    }
+}
@@ -0,0 +1,420 @@
+//! Capturing reports as [Sentry](https://sentry.io) events.
+//!
+//! To enable this integration, add the `compat-sentry` feature flag to your
+//! `Cargo.toml`. It depends on `sentry-core`, so events are sent to the client
+//! that was bound by the `sentry` crate, for instance using `sentry::init`.
+//!
+//! [`Location`]: crate::hooks::builtin_hooks::location::Location
+//!
+//! # Overview
+//!
+//! [`Report::to_sentry_event`] builds an [`Event`] from a report, and
+//! [`Report::capture_sentry`] builds the event and sends it to the current
+//! hub. The report is mapped to the event as follows:
+//!
+//! - Each report of the primary chain, which follows the first child of each
+//!   report, becomes an [`Exception`]. Its type is the type name of the
+//!   context, and its value is the [`Display`](core::fmt::Display) output of
+//!   the context. As expected by Sentry, the exceptions are ordered from the
+//!   innermost cause to the root report.
+//! - If a report of the primary chain has a backtrace attachment, the frames
+//!   of the first one become the stacktrace of its exception. Otherwise, if it
+//!   has a [`Location`] attachment, the stacktrace contains a single frame
+//!   pointing to it.
+//! - [`KeyValue`] attachments become tags. If several reports use the same
+//!   key, the first one in depth-first order is used.
+//! - An [`ErrorCode`] becomes the `error_code` tag, and the highest
+//!   [`Severity`] in the report tree becomes the level of the event.
+//! - The contexts of child reports and the remaining attachments become extra
+//!   data, using the same keys as the [`log`](crate::compat::log)
+//!   integration, such as `attachment.1` or `cause.0.location`.
+//!
+//! Attachments whose preferred placement is
+//! [`Hidden`](crate::handlers::AttachmentFormattingPlacement::Hidden) are left
+//! out, just as they are when the report is formatted.
+//!
+//! Backtrace attachments are the attachments whose handler returns frames from
+//! [`AttachmentHandler::stack_frames`], as the handlers of the backtraces
+//! collected by the `rootcause-backtrace` crate do. Backtraces of reports
+//! outside of the primary chain have no exception to belong to, so they are
+//! included in the extra data as text.
+//!
+//! [`AttachmentHandler::stack_frames`]: crate::handlers::AttachmentHandler::stack_frames
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::prelude::*;
+//!
+//! let report = report!("database unavailable")
+//!     .attach_kv("host", "db-1")
+//!     .context("failed to load user");
+//!
+//! let event = report.to_sentry_event();
+//! assert_eq!(event.exception.len(), 2);
+//! assert_eq!(event.exception[1].value.as_deref(), Some("failed to load user"));
+//! assert_eq!(event.tags["host"], "db-1");
+//!
+//! // Does nothing unless a Sentry client is bound
+//! let event_id = report.capture_sentry();
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use sentry_core::{
+    protocol::{Event, Exception, Frame, Level, Map, Stacktrace, Value},
+    types::Uuid,
+};
+
+use crate::{
+    Report, ReportRef,
+    compat::flatten::{FlatValue, flatten_report, is_hidden},
+    error_code::ErrorCode,
+    key_value::KeyValue,
+    markers::{Dynamic, Local, Uncloneable},
+    report_attachment::ReportAttachmentRef,
+    severity::Severity,
+};
+
+impl<C: ?Sized, O, T> Report<C, O, T> {
+    /// Builds a Sentry [`Event`] from the report.
+    ///
+    /// The primary chain of reports becomes the exceptions of the event, and
+    /// the attachments become tags and extra data. See the
+    /// [module-level documentation](crate::compat::sentry) for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let report = report!("connection reset").context("request failed");
+    /// let event = report.to_sentry_event();
+    ///
+    /// let values: Vec<_> = event
+    ///     .exception
+    ///     .iter()
+    ///     .map(|exception| exception.value.as_deref().unwrap())
+    ///     .collect();
+    /// assert_eq!(values, ["connection reset", "request failed"]);
+    /// ```
+    #[must_use]
+    pub fn to_sentry_event(&self) -> Event<'static> {
+        let report: ReportRef<'_, Dynamic, Uncloneable, Local> = self
+            .as_uncloneable_ref()
+            .into_dynamic()
+            .into_uncloneable()
+            .into_local();
+
+        let mut backtraces = Vec::new();
+        let mut exceptions: Vec<Exception> = report
+            .iter_primary_chain()
+            .map(|report| {
+                let backtrace = backtrace(report);
+                let stacktrace = backtrace.map(|(attachment, stacktrace)| {
+                    backtraces.push(attachment);
+                    stacktrace
+                });
+                exception(report, stacktrace)
+            })
+            .collect();
+        exceptions.reverse();
+
+        let mut event = Event {
+            level: report.max_severity().map_or(Level::Error, level),
+            exception: exceptions.into(),
+            ..Event::default()
+        };
+        for report in report.iter_reports() {
+            push_tags(&mut event.tags, report.into_uncloneable());
+        }
+        push_extra(&mut event.extra, report, &backtraces);
+        event
+    }
+
+    /// Sends the report to Sentry as an event, and returns the identifier of
+    /// the event.
+    ///
+    /// The event is built using [`Report::to_sentry_event`] and captured by
+    /// the current hub. If no Sentry client is bound, nothing is sent and the
+    /// nil identifier is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let report = report!("connection reset").attach_kv("peer", "10.0.0.7");
+    /// let event_id = report.capture_sentry();
+    /// ```
+    pub fn capture_sentry(&self) -> Uuid {
+        sentry_core::capture_event(self.to_sentry_event())
+    }
+}
+
+/// Creates the exception for a single report of the primary chain, using
+/// the stacktrace of its backtrace if it has one.
+fn exception(
+    report: ReportRef<'_, Dynamic, Uncloneable, Local>,
+    stacktrace: Option<Stacktrace>,
+) -> Exception {
+    let stacktrace = stacktrace.or_else(|| {
+        report.location().map(|location| Stacktrace {
+            frames: alloc::vec![Frame {
+                filename: Some(location.file.to_string()),
+                lineno: Some(u64::from(location.line)),
+                ..Frame::default()
+            }],
+            ..Stacktrace::default()
+        })
+    });
+
+    Exception {
+        ty: report.current_context_type_name().to_string(),
+        value: Some(report.format_current_context().to_string()),
+        stacktrace,
+        ..Exception::default()
+    }
+}
+
+/// Finds the first attachment of a report that is a backtrace and is not
+/// hidden, and converts its frames to a stacktrace.
+///
+/// The frames of a backtrace are ordered from the innermost frame, while
+/// Sentry expects the innermost frame last.
+fn backtrace<'a>(
+    report: ReportRef<'a, Dynamic, Uncloneable, Local>,
+) -> Option<(ReportAttachmentRef<'a, Dynamic>, Stacktrace)> {
+    report
+        .attachments()
+        .iter()
+        .filter(|&attachment| !is_hidden(attachment))
+        .find_map(|attachment| {
+            let frames = attachment.stack_frames()?;
+            let frames = frames
+                .iter()
+                .rev()
+                .map(|frame| Frame {
+                    function: Some(frame.function.to_string()),
+                    filename: frame.file.map(String::from),
+                    lineno: frame.line.map(u64::from),
+                    ..Frame::default()
+                })
+                .collect();
+            let stacktrace = Stacktrace {
+                frames,
+                ..Stacktrace::default()
+            };
+            Some((attachment, stacktrace))
+        })
+}
+
+/// Maps the severity of a report to the level of the event.
+fn level(severity: Severity) -> Level {
+    match severity {
+        Severity::Warning => Level::Warning,
+        Severity::Error => Level::Error,
+        Severity::Critical => Level::Fatal,
+    }
+}
+
+/// Adds the tags of a report, without replacing existing tags.
+fn push_tags(tags: &mut Map<String, String>, report: ReportRef<'_, Dynamic, Uncloneable, Local>) {
+    for attachment in report.attachments().iter() {
+        if let Some(key_value) = attachment.downcast_inner::<KeyValue>() {
+            tags.entry(key_value.key.to_string())
+                .or_insert_with(|| key_value.value.clone());
+        } else if let Some(error_code) = attachment.downcast_inner::<ErrorCode>() {
+            tags.entry(String::from("error_code"))
+                .or_insert_with(|| error_code.to_string());
+        }
+    }
+}
+
+/// Adds the attachments and child reports of a report as extra data.
+///
+/// [`KeyValue`], [`ErrorCode`] and [`Severity`] attachments are left out, as
+/// they are part of the tags and the level of the event, and so are the
+/// `backtraces` that became the stacktraces of the exceptions.
+fn push_extra(
+    extra: &mut Map<String, Value>,
+    report: ReportRef<'_, Dynamic, Uncloneable, Local>,
+    backtraces: &[ReportAttachmentRef<'_, Dynamic>],
+) {
+    flatten_report("", report, true, &mut |entry| {
+        let value = match entry.value {
+            FlatValue::Cause(child) => child.format_current_context().to_string(),
+            FlatValue::KeyValue(_) => return,
+            FlatValue::Location(location) => location.to_string(),
+            FlatValue::Attachment(attachment) => {
+                let is_exception_backtrace = entry.in_primary_chain
+                    && backtraces.iter().any(|backtrace| {
+                        core::ptr::addr_eq(backtrace.inner_as_any(), attachment.inner_as_any())
+                    });
+                if attachment.downcast_inner::<ErrorCode>().is_some()
+                    || attachment.downcast_inner::<Severity>().is_some()
+                    || is_exception_backtrace
+                {
+                    return;
+                }
+                attachment.format_inner().to_string()
+            }
+        };
+        extra.insert(entry.key, Value::String(value));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::{JsonSink, StackFrame};
+
+    #[test]
+    fn test_to_sentry_event() {
+        let first = report!("database unavailable")
+            .attach_kv("host", "db-1")
+            .attach_severity(Severity::Critical);
+        let second = report!("cache unavailable").attach("cache miss");
+        let report = first
+            .join(second, "failed to load user")
+            .attach_kv("host", "app-1")
+            .attach_error_code("E42");
+
+        let event = report.to_sentry_event();
+        assert_eq!(event.level, Level::Fatal);
+
+        let exceptions: Vec<_> = event
+            .exception
+            .iter()
+            .map(|exception| (exception.ty.as_str(), exception.value.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            exceptions,
+            [
+                ("&str", "database unavailable"),
+                ("&str", "failed to load user"),
+            ]
+        );
+        let frame = &event.exception[1].stacktrace.as_ref().unwrap().frames[0];
+        assert_eq!(frame.filename.as_deref(), Some(file!()));
+
+        assert_eq!(event.tags["host"], "app-1");
+        assert_eq!(event.tags["error_code"], "E42");
+
+        assert_eq!(event.extra["cause.1"], "cache unavailable");
+        assert_eq!(event.extra["cause.1.attachment.1"], "cache miss");
+        assert!(event.extra.contains_key("cause.0.location"));
+        assert!(!event.extra.keys().any(|key| key.ends_with("attachment.2")));
+    }
+
+    #[test]
+    fn test_backtrace_becomes_stacktrace() {
+        struct Trace;
+        struct TraceHandler;
+        impl crate::handlers::AttachmentHandler<Trace> for TraceHandler {
+            fn display(_value: &Trace, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("inner at src/inner.rs:3\nouter at src/outer.rs:7")
+            }
+
+            fn debug(value: &Trace, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Self::display(value, f)
+            }
+
+            fn stack_frames(_value: &Trace) -> Option<Vec<StackFrame<'_>>> {
+                Some(alloc::vec![
+                    StackFrame::new("inner", Some("src/inner.rs"), Some(3)),
+                    StackFrame::new("outer", Some("src/outer.rs"), Some(7)),
+                ])
+            }
+        }
+
+        let sibling = report!("cache unavailable").attach_custom::<TraceHandler, _>(Trace);
+        let report = report!("database unavailable")
+            .attach_custom::<TraceHandler, _>(Trace)
+            .join(sibling, "failed to load user");
+
+        let event = report.to_sentry_event();
+        let frames: Vec<_> = event.exception[0]
+            .stacktrace
+            .as_ref()
+            .unwrap()
+            .frames
+            .iter()
+            .map(|frame| {
+                (
+                    frame.function.as_deref().unwrap(),
+                    frame.filename.as_deref().unwrap(),
+                    frame.lineno.unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            frames,
+            [("outer", "src/outer.rs", 7), ("inner", "src/inner.rs", 3)]
+        );
+
+        // The root has no backtrace, so its location is used instead
+        let frame = &event.exception[1].stacktrace.as_ref().unwrap().frames[0];
+        assert_eq!(frame.filename.as_deref(), Some(file!()));
+
+        assert!(!event.extra.contains_key("cause.0.attachment.1"));
+        assert!(
+            event.extra["cause.1.attachment.1"]
+                .as_str()
+                .unwrap()
+                .contains("outer at")
+        );
+    }
+
+    #[test]
+    fn test_backtrace_like_json_is_not_a_stacktrace() {
+        struct Frames;
+        struct FramesHandler;
+        impl crate::handlers::AttachmentHandler<Frames> for FramesHandler {
+            fn display(_value: &Frames, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("main at src/main.rs:1")
+            }
+
+            fn debug(value: &Frames, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Self::display(value, f)
+            }
+
+            fn json(_value: &Frames, sink: &mut dyn JsonSink) -> core::fmt::Result {
+                sink.begin_array()?;
+                sink.begin_object()?;
+                sink.key("function")?;
+                sink.str("main")?;
+                sink.key("file")?;
+                sink.str("src/main.rs")?;
+                sink.key("line")?;
+                sink.u64(1)?;
+                sink.end_object()?;
+                sink.end_array()
+            }
+        }
+
+        let report = report!("database unavailable").attach_custom::<FramesHandler, _>(Frames);
+        let event = report.to_sentry_event();
+
+        // The location is used as the stacktrace, and the attachment is kept
+        let frame = &event.exception[0].stacktrace.as_ref().unwrap().frames[0];
+        assert_eq!(frame.filename.as_deref(), Some(file!()));
+        assert_eq!(event.extra["attachment.1"], "main at src/main.rs:1");
+    }
+
+    #[test]
+    fn test_capture_sentry() {
+        let events = sentry_core::test::with_captured_events(|| {
+            let event_id = report!("connection reset").capture_sentry();
+            assert!(!event_id.is_nil());
+        });
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].exception[0].value.as_deref(),
+            Some("connection reset")
+        );
+    }
+}
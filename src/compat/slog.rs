@@ -0,0 +1,437 @@
+//! Structured logging of reports with the [`slog`] crate.
+//!
+//! To enable this integration, add the `compat-slog` feature flag to your
+//! `Cargo.toml`. It enables the `dynamic-keys` feature of `slog`, since the
+//! keys of a flattened report are built at runtime.
+//!
+//! [`slog`]: https://docs.rs/slog
+//! [`KeyValue`]: crate::key_value::KeyValue
+//! [`Location`]: crate::hooks::builtin_hooks::location::Location
+//!
+//! # Overview
+//!
+//! [`Report::to_slog_kv`] flattens the primary chain of a report, which
+//! follows the first child of each report, into a [`ReportKv`] that implements
+//! [`slog::KV`]. [`Report::log_slog`] emits the report as a single
+//! record at [`Level::Error`](::slog::Level::Error), with the root context as
+//! the message and the [`ReportKv`] as its key/value pairs.
+//!
+//! The keys are named as follows:
+//!
+//! - The root context uses the key `error`.
+//! - [`KeyValue`] attachments use their own key, such as `user_id`.
+//! - [`Location`] attachments use the key `location`.
+//! - Other attachments use the key `attachment.<index>`, where the index is
+//!   the position of the attachment in the report.
+//! - The context of the `n`th cause in the chain, starting from `0` for the
+//!   first child of the root, uses the key `cause.<n>`, and its attachments
+//!   are nested below it, such as `cause.0.host`.
+//!
+//! Contexts and attachments are emitted using the structured output of their
+//! handlers, so a handler that writes a number or a boolean produces a real
+//! `slog` number or boolean instead of a string. Objects and arrays are
+//! emitted as compact JSON strings. Attachments whose preferred placement is
+//! [`Hidden`](crate::handlers::AttachmentFormattingPlacement::Hidden) are left
+//! out, just as they are when the report is formatted.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::prelude::*;
+//!
+//! let logger = slog::Logger::root(slog::Discard, slog::o!());
+//!
+//! let report = report!("database unavailable")
+//!     .attach_kv("host", "db-1")
+//!     .context("failed to load user")
+//!     .attach_kv("user_id", 42);
+//!
+//! // Emits "failed to load user" with the key/value pairs
+//! // `error = failed to load user`, `user_id = 42`,
+//! // `cause.0 = database unavailable` and `cause.0.host = db-1`, along with
+//! // the `location` and `cause.0.location` of both reports.
+//! report.log_slog(&logger);
+//!
+//! // The pairs can also be added to a record of your own
+//! slog::warn!(logger, "retrying"; report.to_slog_kv());
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use ::slog::{KV, Key, Record, Serializer};
+
+use crate::{
+    Report, ReportRef,
+    compat::flatten::{FlatValue, flatten_attachments},
+    handlers::{JsonSink, JsonWriter},
+    markers::{Dynamic, Local, Uncloneable},
+};
+
+impl<C: ?Sized, O, T> Report<C, O, T> {
+    /// Flattens the primary chain of the report into key/value pairs for
+    /// [`slog`](::slog).
+    ///
+    /// See the [module-level documentation](crate::compat::slog) for how the
+    /// keys are named.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let logger = slog::Logger::root(slog::Discard, slog::o!());
+    /// let report = report!("connection reset").attach_kv("peer", "10.0.0.7");
+    ///
+    /// slog::error!(logger, "request failed"; report.to_slog_kv());
+    /// ```
+    #[must_use]
+    pub fn to_slog_kv(&self) -> ReportKv {
+        let report: ReportRef<'_, Dynamic, Uncloneable, Local> = self
+            .as_uncloneable_ref()
+            .into_dynamic()
+            .into_uncloneable()
+            .into_local();
+
+        let mut pairs = Vec::new();
+        for (depth, report) in report.iter_primary_chain().enumerate() {
+            let prefix = if depth == 0 {
+                pairs.push((String::from("error"), context_value(report)));
+                String::new()
+            } else {
+                let key = format!("cause.{}", depth - 1);
+                pairs.push((key.clone(), context_value(report)));
+                format!("{key}.")
+            };
+            push_attachments(&mut pairs, &prefix, report);
+        }
+        ReportKv { pairs }
+    }
+
+    /// Emits the report as a [`slog`](::slog) record at
+    /// [`Level::Error`](::slog::Level::Error).
+    ///
+    /// The message of the record is the root context, and the key/value pairs
+    /// are the ones returned by [`Report::to_slog_kv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let logger = slog::Logger::root(slog::Discard, slog::o!());
+    /// let report = report!("connection reset").attach_kv("peer", "10.0.0.7");
+    /// report.log_slog(&logger);
+    /// ```
+    pub fn log_slog(&self, logger: &::slog::Logger) {
+        ::slog::error!(logger, "{}", self.format_current_context(); self.to_slog_kv());
+    }
+}
+
+/// The flattened key/value pairs of the primary chain of a report.
+///
+/// This is created by [`Report::to_slog_kv`], and can be passed to the `slog`
+/// logging macros after the `;`.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{compat::slog::ReportKv, prelude::*};
+///
+/// let report = report!("connection reset").context("request failed");
+/// let pairs: ReportKv = report.to_slog_kv();
+/// assert_eq!(pairs.len(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReportKv {
+    pairs: Vec<(String, SlogValue)>,
+}
+
+impl ReportKv {
+    /// Returns the number of key/value pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let report = report!("connection reset");
+    /// assert_eq!(report.to_slog_kv().len(), 2); // `error` and `location`
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Returns `true` if there are no key/value pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let report = report!("connection reset");
+    /// assert!(!report.to_slog_kv().is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+impl KV for ReportKv {
+    fn serialize(&self, _record: &Record<'_>, serializer: &mut dyn Serializer) -> ::slog::Result {
+        for (key, value) in &self.pairs {
+            let key = Key::from(key.clone());
+            match value {
+                SlogValue::None => serializer.emit_none(key)?,
+                SlogValue::Bool(value) => serializer.emit_bool(key, *value)?,
+                SlogValue::I64(value) => serializer.emit_i64(key, *value)?,
+                SlogValue::U64(value) => serializer.emit_u64(key, *value)?,
+                SlogValue::F64(value) => serializer.emit_f64(key, *value)?,
+                SlogValue::Str(value) => serializer.emit_str(key, value)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A value captured from the structured output of a handler.
+#[derive(Debug, Clone, PartialEq)]
+enum SlogValue {
+    None,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Str(String),
+}
+
+fn context_value(report: ReportRef<'_, Dynamic, Uncloneable, Local>) -> SlogValue {
+    let mut sink = ValueSink::default();
+    match report.write_current_context_json(&mut sink) {
+        Ok(()) => sink.finish(),
+        Err(_) => SlogValue::Str(report.format_current_context().to_string()),
+    }
+}
+
+fn push_attachments(
+    pairs: &mut Vec<(String, SlogValue)>,
+    prefix: &str,
+    report: ReportRef<'_, Dynamic, Uncloneable, Local>,
+) {
+    flatten_attachments(prefix, report, true, &mut |entry| {
+        let value = match entry.value {
+            FlatValue::Cause(child) => context_value(child),
+            FlatValue::KeyValue(key_value) => SlogValue::Str(key_value.value.clone()),
+            FlatValue::Location(location) => SlogValue::Str(location.to_string()),
+            FlatValue::Attachment(attachment) => {
+                let mut sink = ValueSink::default();
+                match attachment.write_inner_json(&mut sink) {
+                    Ok(()) => sink.finish(),
+                    Err(_) => SlogValue::Str(attachment.format_inner().to_string()),
+                }
+            }
+        };
+        pairs.push((entry.key, value));
+    });
+}
+
+/// A [`JsonSink`] that keeps a single scalar value as it is, and collects
+/// objects and arrays as JSON text.
+struct ValueSink {
+    json: JsonWriter<String>,
+    depth: usize,
+    scalar: Option<SlogValue>,
+    nested: bool,
+}
+
+impl Default for ValueSink {
+    fn default() -> Self {
+        Self {
+            json: JsonWriter::new(String::new()),
+            depth: 0,
+            scalar: None,
+            nested: false,
+        }
+    }
+}
+
+impl ValueSink {
+    fn scalar(&mut self, value: SlogValue) {
+        if self.depth == 0 {
+            self.scalar = Some(value);
+        }
+    }
+
+    fn finish(self) -> SlogValue {
+        match self.scalar {
+            Some(value) if !self.nested => value,
+            _ => SlogValue::Str(self.json.into_inner()),
+        }
+    }
+}
+
+impl JsonSink for ValueSink {
+    fn null(&mut self) -> core::fmt::Result {
+        self.scalar(SlogValue::None);
+        self.json.null()
+    }
+
+    fn bool(&mut self, value: bool) -> core::fmt::Result {
+        self.scalar(SlogValue::Bool(value));
+        self.json.bool(value)
+    }
+
+    fn i64(&mut self, value: i64) -> core::fmt::Result {
+        self.scalar(SlogValue::I64(value));
+        self.json.i64(value)
+    }
+
+    fn u64(&mut self, value: u64) -> core::fmt::Result {
+        self.scalar(SlogValue::U64(value));
+        self.json.u64(value)
+    }
+
+    fn f64(&mut self, value: f64) -> core::fmt::Result {
+        self.scalar(SlogValue::F64(value));
+        self.json.f64(value)
+    }
+
+    fn str(&mut self, value: &str) -> core::fmt::Result {
+        self.scalar(SlogValue::Str(value.to_string()));
+        self.json.str(value)
+    }
+
+    fn begin_object(&mut self) -> core::fmt::Result {
+        self.nested = true;
+        self.depth += 1;
+        self.json.begin_object()
+    }
+
+    fn key(&mut self, key: &str) -> core::fmt::Result {
+        self.json.key(key)
+    }
+
+    fn end_object(&mut self) -> core::fmt::Result {
+        self.depth = self.depth.saturating_sub(1);
+        self.json.end_object()
+    }
+
+    fn begin_array(&mut self) -> core::fmt::Result {
+        self.nested = true;
+        self.depth += 1;
+        self.json.begin_array()
+    }
+
+    fn end_array(&mut self) -> core::fmt::Result {
+        self.depth = self.depth.saturating_sub(1);
+        self.json.end_array()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers;
+
+    fn pairs(report: &Report) -> Vec<(String, SlogValue)> {
+        report
+            .to_slog_kv()
+            .pairs
+            .into_iter()
+            .filter(|(key, _)| !key.ends_with("location"))
+            .collect()
+    }
+
+    #[test]
+    fn test_flattened_primary_chain() {
+        let first = report!("database unavailable").attach_kv("host", "db-1");
+        let second = report!("cache unavailable").attach("cache miss");
+        let report = first
+            .join(second, "failed to load user")
+            .attach_kv("user_id", 42)
+            .into_dynamic();
+
+        assert_eq!(
+            pairs(&report),
+            [
+                (
+                    "error".to_string(),
+                    SlogValue::Str("failed to load user".to_string())
+                ),
+                ("user_id".to_string(), SlogValue::Str("42".to_string())),
+                (
+                    "cause.0".to_string(),
+                    SlogValue::Str("database unavailable".to_string())
+                ),
+                (
+                    "cause.0.host".to_string(),
+                    SlogValue::Str("db-1".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_structured_attachment_values() {
+        struct Retries(u64);
+        struct Tags;
+
+        struct RetriesHandler;
+        impl handlers::AttachmentHandler<Retries> for RetriesHandler {
+            fn display(value: &Retries, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}", value.0)
+            }
+
+            fn debug(value: &Retries, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}", value.0)
+            }
+
+            fn json(value: &Retries, sink: &mut dyn JsonSink) -> core::fmt::Result {
+                sink.u64(value.0)
+            }
+        }
+
+        struct TagsHandler;
+        impl handlers::AttachmentHandler<Tags> for TagsHandler {
+            fn display(_value: &Tags, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("a, b")
+            }
+
+            fn debug(_value: &Tags, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("a, b")
+            }
+
+            fn json(_value: &Tags, sink: &mut dyn JsonSink) -> core::fmt::Result {
+                sink.begin_array()?;
+                sink.str("a")?;
+                sink.str("b")?;
+                sink.end_array()
+            }
+        }
+
+        let report: Report = report!("request failed")
+            .attach_custom::<RetriesHandler, _>(Retries(3))
+            .attach_custom::<TagsHandler, _>(Tags)
+            .into_dynamic();
+
+        assert_eq!(
+            pairs(&report),
+            [
+                (
+                    "error".to_string(),
+                    SlogValue::Str("request failed".to_string())
+                ),
+                ("attachment.1".to_string(), SlogValue::U64(3)),
+                (
+                    "attachment.2".to_string(),
+                    SlogValue::Str(r#"["a","b"]"#.to_string())
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,579 @@
+//! Conversions between [`Report`]s and gRPC [`Status`] values of [`tonic`].
+//!
+//! To enable this integration, add the `compat-tonic` feature flag to your
+//! `Cargo.toml`.
+//!
+//! [`tonic`]: https://docs.rs/tonic
+//!
+//! # Overview
+//!
+//! - [`IntoStatus::into_status`] and the [`From`] implementation turn a
+//!   [`Report`] into a [`Status`], so reports can be returned from `tonic`
+//!   service methods with the `?` operator.
+//! - [`IntoRootcause`] is implemented for [`Status`] and `Result<T, Status>`,
+//!   so errors returned by `tonic` clients can be turned into reports.
+//!
+//! When a [`Report`] is turned into a [`Status`]:
+//!
+//! - The status code is determined by the contexts in the report tree. The
+//!   reports are visited in depth-first order starting with the root, and the
+//!   first context with a code registered using [`register_code`] or
+//!   [`register_code_with`] decides the code. A [`Status`] context decides the
+//!   code as well, so statuses received from other services keep their code.
+//!   If no such context is found, the code is [`Code::Internal`].
+//! - The details of the status are the bytes of the first [`StatusDetails`]
+//!   attachment in the report tree, if there is one.
+//! - The message of the status depends on whether verbose messages are
+//!   enabled, see below.
+//!
+//! # Message Verbosity
+//!
+//! The message of a [`Status`] is sent to the client, so it should not contain
+//! internal details such as file paths, queries or user data. Reports often
+//! contain exactly that, so the message can be rendered in two ways:
+//!
+//! - **Sanitized**: The message is the display output of the context that
+//!   decided the code, or the description of the code if no context decided
+//!   it. Neither the rest of the report tree nor any attachments are sent.
+//! - **Verbose**: The message is the full formatted report, which is useful
+//!   while developing and debugging a service.
+//!
+//! Messages are sanitized by default, in every build profile, so that a
+//! debug build deployed by accident does not leak internals to clients.
+//! Verbose messages have to be enabled explicitly using
+//! [`set_verbose_messages`], for instance based on a configuration value.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{
+//!     compat::tonic::{IntoStatus, register_code},
+//!     prelude::*,
+//! };
+//! use tonic::Code;
+//!
+//! #[derive(Debug)]
+//! struct UserNotFound {
+//!     user_id: u32,
+//! }
+//!
+//! impl std::fmt::Display for UserNotFound {
+//!     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//!         write!(f, "user {} not found", self.user_id)
+//!     }
+//! }
+//!
+//! register_code::<UserNotFound>(Code::NotFound);
+//!
+//! fn get_user(user_id: u32) -> Result<String, tonic::Status> {
+//!     let user = find_user(user_id).context("failed to look up user")?;
+//!     Ok(user)
+//! }
+//!
+//! fn find_user(user_id: u32) -> Result<String, Report<UserNotFound>> {
+//!     Err(report!(UserNotFound { user_id }).attach("SELECT * FROM users"))
+//! }
+//!
+//! let status = get_user(7).unwrap_err();
+//! assert_eq!(status.code(), Code::NotFound);
+//! assert_eq!(status.message(), "user 7 not found");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use ::tonic::{Code, Status};
+
+use crate::{
+    Report,
+    compat::{IntoRootcause, type_registry::TypeRegistry},
+    handlers::{
+        AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler,
+        ContextHandler, FormattingFunction,
+    },
+    markers::{Dynamic, ReportOwnershipMarker},
+};
+
+/// The registered codes, keyed by the type of the context.
+static CODES: TypeRegistry<Code> = TypeRegistry::new();
+
+/// Whether the message of a [`Status`] contains the full report.
+static VERBOSE_MESSAGES: AtomicBool = AtomicBool::new(false);
+
+/// Registers the gRPC status code used for reports containing a context of
+/// type `C`.
+///
+/// Registering a code for a type that already has one replaces the previous
+/// registration.
+///
+/// See the [module-level documentation](self) for how the code of a report is
+/// determined.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     compat::tonic::{IntoStatus, register_code},
+///     prelude::*,
+/// };
+/// use tonic::Code;
+///
+/// struct PermissionDenied;
+///
+/// register_code::<PermissionDenied>(Code::PermissionDenied);
+///
+/// let report = report!(PermissionDenied).context("cannot delete order");
+/// assert_eq!(report.into_status().code(), Code::PermissionDenied);
+/// ```
+pub fn register_code<C>(code: Code)
+where
+    C: Sized + 'static,
+{
+    CODES.register(move |_: &C| code);
+}
+
+/// Registers a function computing the gRPC status code used for reports
+/// containing a context of type `C`.
+///
+/// This is useful for error enums where each variant has its own code.
+/// Registering a code for a type that already has one replaces the previous
+/// registration.
+///
+/// See the [module-level documentation](self) for how the code of a report is
+/// determined.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     compat::tonic::{IntoStatus, register_code_with},
+///     prelude::*,
+/// };
+/// use tonic::Code;
+///
+/// #[derive(Debug)]
+/// enum OrderError {
+///     NotFound,
+///     AlreadyShipped,
+/// }
+/// # impl std::fmt::Display for OrderError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         std::fmt::Debug::fmt(self, f)
+/// #     }
+/// # }
+///
+/// register_code_with(|error: &OrderError| match error {
+///     OrderError::NotFound => Code::NotFound,
+///     OrderError::AlreadyShipped => Code::FailedPrecondition,
+/// });
+///
+/// assert_eq!(report!(OrderError::NotFound).into_status().code(), Code::NotFound);
+/// assert_eq!(
+///     report!(OrderError::AlreadyShipped).into_status().code(),
+///     Code::FailedPrecondition
+/// );
+/// ```
+pub fn register_code_with<C, F>(f: F)
+where
+    C: Sized + 'static,
+    F: Fn(&C) -> Code + Send + Sync + 'static,
+{
+    CODES.register(f);
+}
+
+/// Sets whether the message of a [`Status`] created from a [`Report`] contains
+/// the full formatted report.
+///
+/// Verbose messages are disabled by default, regardless of the build profile.
+/// See the [module-level documentation](self#message-verbosity) for more
+/// information.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     compat::tonic::{IntoStatus, set_verbose_messages},
+///     prelude::*,
+/// };
+///
+/// let report = report!("secret token abc123").into_cloneable();
+/// assert_eq!(report.clone().into_status().message(), "Internal error");
+///
+/// set_verbose_messages(true);
+/// assert_eq!(report.clone().into_status().message(), report.to_string());
+/// ```
+pub fn set_verbose_messages(verbose: bool) {
+    VERBOSE_MESSAGES.store(verbose, Ordering::Relaxed);
+}
+
+/// Returns whether the message of a [`Status`] created from a [`Report`]
+/// contains the full formatted report.
+///
+/// See [`set_verbose_messages`] for more information.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::compat::tonic::{set_verbose_messages, verbose_messages};
+///
+/// assert!(!verbose_messages());
+/// set_verbose_messages(true);
+/// assert!(verbose_messages());
+/// ```
+#[must_use]
+pub fn verbose_messages() -> bool {
+    VERBOSE_MESSAGES.load(Ordering::Relaxed)
+}
+
+/// Binary details sent in a [`Status`] created from a [`Report`].
+///
+/// The bytes are usually an encoded protobuf message, such as a
+/// `google.rpc.Status` with error details. The first [`StatusDetails`]
+/// attachment found in the report tree, in depth-first order, becomes the
+/// details of the status. The details are not shown when the report is
+/// formatted.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     compat::tonic::{IntoStatus, StatusDetails, StatusDetailsHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("quota exceeded")
+///     .attach_custom::<StatusDetailsHandler, _>(StatusDetails::new(vec![8, 1]));
+/// assert_eq!(report.into_status().details(), [8, 1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatusDetails(Vec<u8>);
+
+impl StatusDetails {
+    /// Creates status details from the given bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::compat::tonic::StatusDetails;
+    ///
+    /// let details = StatusDetails::new(vec![8, 1]);
+    /// assert_eq!(details.as_bytes(), [8, 1]);
+    /// ```
+    #[must_use]
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(bytes.into())
+    }
+
+    /// Returns the bytes of the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::compat::tonic::StatusDetails;
+    ///
+    /// assert!(StatusDetails::new(Vec::new()).as_bytes().is_empty());
+    /// ```
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Handler for [`StatusDetails`] attachments, which hides them from the
+/// formatted report.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     compat::tonic::{StatusDetails, StatusDetailsHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("quota exceeded")
+///     .attach_custom::<StatusDetailsHandler, _>(StatusDetails::new(vec![8, 1]));
+/// assert!(!report.to_string().contains("StatusDetails"));
+/// ```
+#[derive(Copy, Clone)]
+pub struct StatusDetailsHandler;
+
+impl AttachmentHandler<StatusDetails> for StatusDetailsHandler {
+    fn display(value: &StatusDetails, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} byte(s) of status details", value.0.len())
+    }
+
+    fn debug(value: &StatusDetails, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, formatter)
+    }
+
+    fn preferred_formatting_style(
+        _value: &StatusDetails,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::Hidden,
+            function: FormattingFunction::Display,
+            priority: 0,
+        }
+    }
+}
+
+/// Handler for [`Status`] contexts, which displays them as their code followed
+/// by their message.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{compat::tonic::StatusHandler, prelude::*};
+///
+/// let report = Report::new_sendsync_custom::<StatusHandler>(tonic::Status::not_found(
+///     "user 7 not found",
+/// ));
+/// assert_eq!(
+///     report.format_current_context().to_string(),
+///     "NotFound: user 7 not found"
+/// );
+/// ```
+#[derive(Copy, Clone)]
+pub struct StatusHandler;
+
+impl ContextHandler<Status> for StatusHandler {
+    fn source(status: &Status) -> Option<&(dyn core::error::Error + 'static)> {
+        core::error::Error::source(status)
+    }
+
+    fn display(status: &Status, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if status.message().is_empty() {
+            write!(formatter, "{:?}", status.code())
+        } else {
+            write!(formatter, "{:?}: {}", status.code(), status.message())
+        }
+    }
+
+    fn debug(status: &Status, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(status, formatter)
+    }
+}
+
+/// A trait for converting rootcause [`Report`]s into gRPC [`Status`] values.
+///
+/// See the [module-level documentation](self) for how the code, message and
+/// details of the status are determined.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{compat::tonic::IntoStatus, prelude::*};
+/// use tonic::Code;
+///
+/// let status = report!("database unavailable").into_status();
+/// assert_eq!(status.code(), Code::Internal);
+/// ```
+pub trait IntoStatus {
+    /// The type produced by the conversion.
+    ///
+    /// This is [`Status`] for reports, and `Result<T, Status>` for results.
+    type Output;
+
+    /// Converts this value into a [`Status`], or a result with a [`Status`]
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{compat::tonic::IntoStatus, prelude::*};
+    ///
+    /// let result: Result<u32, Report> = Ok(42);
+    /// let result: Result<u32, tonic::Status> = result.into_status();
+    /// assert_eq!(result.unwrap(), 42);
+    /// ```
+    fn into_status(self) -> Self::Output;
+}
+
+impl<C: ?Sized, O, T> IntoStatus for Report<C, O, T>
+where
+    O: ReportOwnershipMarker,
+{
+    type Output = Status;
+
+    fn into_status(self) -> Self::Output {
+        report_to_status(self.into_dynamic(), verbose_messages())
+    }
+}
+
+impl<V, C: ?Sized, O, T> IntoStatus for Result<V, Report<C, O, T>>
+where
+    O: ReportOwnershipMarker,
+{
+    type Output = Result<V, Status>;
+
+    fn into_status(self) -> Self::Output {
+        self.map_err(IntoStatus::into_status)
+    }
+}
+
+/// Converts a [`Report`] into a [`Status`], so reports can be returned from
+/// `tonic` service methods with the `?` operator.
+///
+/// This is the same as [`IntoStatus::into_status`].
+impl<C: ?Sized, O, T> From<Report<C, O, T>> for Status
+where
+    O: ReportOwnershipMarker,
+{
+    fn from(report: Report<C, O, T>) -> Self {
+        report.into_status()
+    }
+}
+
+/// Converts a [`Report`] into a [`Status`], with the full report as the
+/// message if `verbose` is set.
+fn report_to_status<O, T>(report: Report<Dynamic, O, T>, verbose: bool) -> Status
+where
+    O: ReportOwnershipMarker,
+{
+    let decided = report.iter_reports().find_map(|node| {
+        if let Some(status) = node.downcast_current_context::<Status>() {
+            return Some((status.code(), String::from(status.message())));
+        }
+        let code = CODES.get(node.current_context_type_id())?;
+        Some((
+            code(node.current_context_as_any()),
+            node.format_current_context().to_string(),
+        ))
+    });
+
+    let details = report.iter_reports().find_map(|node| {
+        node.attachments()
+            .iter()
+            .find_map(|attachment| attachment.downcast_inner::<StatusDetails>())
+            .map(|details| details.0.clone())
+    });
+
+    let (code, message) = match decided {
+        Some((code, message)) => (code, message),
+        None => (Code::Internal, String::from(Code::Internal.description())),
+    };
+    let message = if verbose { report.to_string() } else { message };
+
+    match details {
+        Some(details) => Status::with_details(code, message, details.into()),
+        None => Status::new(code, message),
+    }
+}
+
+impl IntoRootcause for Status {
+    type Output = Report;
+
+    #[track_caller]
+    fn into_rootcause(self) -> Self::Output {
+        Report::new_sendsync_custom::<StatusHandler>(self).into_dynamic()
+    }
+}
+
+impl<V> IntoRootcause for Result<V, Status> {
+    type Output = Result<V, Report>;
+
+    #[track_caller]
+    fn into_rootcause(self) -> Self::Output {
+        self.map_err(IntoRootcause::into_rootcause)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_unregistered() {
+        struct Unregistered;
+
+        let status = report!(Unregistered).into_status();
+        assert_eq!(status.code(), Code::Internal);
+    }
+
+    #[test]
+    fn test_code_nearest_to_root_wins() {
+        #[derive(Debug, derive_more::Display)]
+        struct Outer;
+        #[derive(Debug, derive_more::Display)]
+        struct Inner;
+        register_code::<Outer>(Code::InvalidArgument);
+        register_code::<Inner>(Code::NotFound);
+
+        let report = report!(Inner).into_cloneable();
+        assert_eq!(report.clone().into_status().code(), Code::NotFound);
+
+        let report = report.context(Outer).context("unrelated context");
+        assert_eq!(report.into_status().code(), Code::InvalidArgument);
+    }
+
+    #[test]
+    fn test_code_fn_can_register() {
+        #[derive(Debug, derive_more::Display)]
+        struct Registering;
+        #[derive(Debug, derive_more::Display)]
+        struct RegisteredLater;
+        register_code_with(|_: &Registering| {
+            register_code::<RegisteredLater>(Code::Aborted);
+            Code::Unavailable
+        });
+
+        assert_eq!(report!(Registering).into_status().code(), Code::Unavailable);
+        assert_eq!(report!(RegisteredLater).into_status().code(), Code::Aborted);
+    }
+
+    #[test]
+    fn test_verbose_messages_disabled_by_default() {
+        assert!(!VERBOSE_MESSAGES.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_sanitized_message() {
+        #[derive(Debug, derive_more::Display)]
+        #[display("order {_0} not found")]
+        struct OrderNotFound(u32);
+        register_code::<OrderNotFound>(Code::NotFound);
+
+        let report = report!(OrderNotFound(7))
+            .attach("SELECT * FROM orders")
+            .context("failed to ship order");
+        let status = report_to_status(report.into_dynamic(), false);
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "order 7 not found");
+
+        let report = report!("secret token abc123").attach("user: alice");
+        let status = report_to_status(report.into_dynamic(), false);
+        assert_eq!(status.code(), Code::Internal);
+        assert_eq!(status.message(), Code::Internal.description());
+    }
+
+    #[test]
+    fn test_verbose_message() {
+        let report = report!("secret token abc123")
+            .attach("user: alice")
+            .into_dynamic()
+            .into_cloneable();
+        let status = report_to_status(report.clone(), true);
+        assert_eq!(status.code(), Code::Internal);
+        assert_eq!(status.message(), report.to_string());
+        assert!(status.message().contains("user: alice"));
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        let report: Report = Status::unavailable("backend down").into_rootcause();
+        assert_eq!(
+            report.format_current_context().to_string(),
+            "Unavailable: backend down"
+        );
+
+        let status = report.context("failed to call backend").into_status();
+        assert_eq!(status.code(), Code::Unavailable);
+    }
+}
@@ -0,0 +1,333 @@
+//! Error handling middleware for [`tower`] services.
+//!
+//! To enable this integration, add the `compat-tower` feature flag to your
+//! `Cargo.toml`. It depends on `tower-service` and `tower-layer` 0.3.x, which
+//! are the versions used by `tower`, `tonic`, `axum` and `hyper`.
+//!
+//! [`tower`]: https://docs.rs/tower
+//!
+//! # Overview
+//!
+//! Services in a `tower` stack usually need to return an error type expected by
+//! the framework, such as `tonic::Status` or an HTTP response, while the
+//! handlers themselves are easiest to write with rootcause [`Report`]s. Instead
+//! of converting and logging the report in every handler, the [`ReportLayer`]
+//! wraps a service whose error type is a [`Report`] and does this once at the
+//! service boundary:
+//!
+//! - The full report tree is logged using [`tracing`](https://docs.rs/tracing)
+//!   at a configurable level, [`Level::ERROR`] by default.
+//! - The report is then converted to the target error type using a
+//!   user-provided closure.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{compat::tower::ReportLayer, prelude::*};
+//! use tower::{Layer, ServiceExt, service_fn};
+//!
+//! #[derive(Debug)]
+//! struct Status {
+//!     message: String,
+//! }
+//!
+//! async fn handle(user_id: u32) -> Result<String, Report> {
+//!     if user_id == 0 {
+//!         return Err(report!("user not found").attach(format!("user_id: {user_id}")));
+//!     }
+//!     Ok(format!("user {user_id}"))
+//! }
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let layer = ReportLayer::new(|report: Report| Status {
+//!     message: report.format_current_context().to_string(),
+//! });
+//! let service = layer.layer(service_fn(handle));
+//!
+//! let status = service.oneshot(0).await.unwrap_err();
+//! assert_eq!(status.message, "user not found");
+//! # });
+//! ```
+
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use tower_layer::Layer;
+use tower_service::Service;
+use tracing::Level;
+
+use crate::Report;
+
+/// A [`Layer`] that wraps services in a [`ReportService`].
+///
+/// See the [module-level documentation](self) for more information.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{compat::tower::ReportLayer, prelude::*};
+/// use tracing::Level;
+///
+/// let layer = ReportLayer::new(|report: Report| report.to_string()).with_level(Level::WARN);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ReportLayer<F> {
+    level: Option<Level>,
+    map_err: F,
+}
+
+impl<F> ReportLayer<F> {
+    /// Creates a new [`ReportLayer`] that logs reports at [`Level::ERROR`] and
+    /// converts them to the target error type using `map_err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{compat::tower::ReportLayer, prelude::*};
+    ///
+    /// let layer = ReportLayer::new(|report: Report| std::io::Error::other(report.to_string()));
+    /// ```
+    pub fn new(map_err: F) -> Self {
+        Self {
+            level: Some(Level::ERROR),
+            map_err,
+        }
+    }
+
+    /// Sets the level at which reports are logged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{compat::tower::ReportLayer, prelude::*};
+    /// use tracing::Level;
+    ///
+    /// let layer = ReportLayer::new(|report: Report| report.to_string()).with_level(Level::WARN);
+    /// ```
+    #[must_use]
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Disables logging, so reports are only converted to the target error
+    /// type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{compat::tower::ReportLayer, prelude::*};
+    ///
+    /// let layer = ReportLayer::new(|report: Report| report.to_string()).without_logging();
+    /// ```
+    #[must_use]
+    pub fn without_logging(mut self) -> Self {
+        self.level = None;
+        self
+    }
+}
+
+impl<F> fmt::Debug for ReportLayer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReportLayer")
+            .field("level", &self.level)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F: Clone> Layer<S> for ReportLayer<F> {
+    type Service = ReportService<S, F>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ReportService {
+            inner,
+            level: self.level,
+            map_err: self.map_err.clone(),
+        }
+    }
+}
+
+/// A [`Service`] that logs the [`Report`] errors of an inner service and
+/// converts them to another error type.
+///
+/// This is usually created using a [`ReportLayer`]. See the
+/// [module-level documentation](self) for more information.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{compat::tower::ReportLayer, prelude::*};
+/// use tower::{Layer, service_fn};
+///
+/// let service = ReportLayer::new(|report: Report| report.to_string())
+///     .layer(service_fn(|request: u32| async move { Ok::<_, Report>(request) }));
+/// ```
+#[derive(Clone, Copy)]
+pub struct ReportService<S, F> {
+    inner: S,
+    level: Option<Level>,
+    map_err: F,
+}
+
+impl<S, F> ReportService<S, F> {
+    /// Returns a reference to the inner service.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{compat::tower::ReportLayer, prelude::*};
+    /// use tower::{Layer, service_fn};
+    ///
+    /// let service = ReportLayer::new(|report: Report| report.to_string())
+    ///     .layer(service_fn(|request: u32| async move { Ok::<_, Report>(request) }));
+    /// let inner = service.get_ref();
+    /// ```
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner service.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{compat::tower::ReportLayer, prelude::*};
+    /// use tower::{Layer, service_fn};
+    ///
+    /// let mut service = ReportLayer::new(|report: Report| report.to_string())
+    ///     .layer(service_fn(|request: u32| async move { Ok::<_, Report>(request) }));
+    /// let inner = service.get_mut();
+    /// ```
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consumes the service and returns the inner service.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{compat::tower::ReportLayer, prelude::*};
+    /// use tower::{Layer, service_fn};
+    ///
+    /// let service = ReportLayer::new(|report: Report| report.to_string())
+    ///     .layer(service_fn(|request: u32| async move { Ok::<_, Report>(request) }));
+    /// let inner = service.into_inner();
+    /// ```
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for ReportService<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReportService")
+            .field("inner", &self.inner)
+            .field("level", &self.level)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F, Request, C, O, T, E> Service<Request> for ReportService<S, F>
+where
+    S: Service<Request, Error = Report<C, O, T>>,
+    F: FnMut(Report<C, O, T>) -> E + Clone,
+    C: ?Sized + 'static,
+    O: 'static,
+    T: 'static,
+{
+    type Response = S::Response;
+    type Error = E;
+    type Future = ReportFuture<S::Future, F>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner
+            .poll_ready(cx)
+            .map_err(|report| handle_report(self.level, &mut self.map_err, report))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        ReportFuture {
+            inner: self.inner.call(request),
+            level: self.level,
+            map_err: Some(self.map_err.clone()),
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The [`Future`] returned by [`ReportService`].
+    ///
+    /// It resolves to the output of the inner future, with any [`Report`]
+    /// logged and converted to the target error type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{compat::tower::{ReportFuture, ReportLayer}, prelude::*};
+    /// use tower::{Layer, Service, service_fn};
+    ///
+    /// let mut service = ReportLayer::new(|report: Report| report.to_string())
+    ///     .layer(service_fn(|request: u32| async move { Ok::<_, Report>(request) }));
+    /// let future: ReportFuture<_, _> = service.call(1);
+    /// ```
+    pub struct ReportFuture<Fut, F> {
+        #[pin]
+        inner: Fut,
+        level: Option<Level>,
+        map_err: Option<F>,
+    }
+}
+
+impl<Fut: fmt::Debug, F> fmt::Debug for ReportFuture<Fut, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReportFuture")
+            .field("inner", &self.inner)
+            .field("level", &self.level)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Fut, F, Response, C, O, T, E> Future for ReportFuture<Fut, F>
+where
+    Fut: Future<Output = Result<Response, Report<C, O, T>>>,
+    F: FnMut(Report<C, O, T>) -> E,
+    C: ?Sized + 'static,
+    O: 'static,
+    T: 'static,
+{
+    type Output = Result<Response, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = core::task::ready!(this.inner.poll(cx));
+        Poll::Ready(result.map_err(|report| {
+            let mut map_err = this
+                .map_err
+                .take()
+                .expect("ReportFuture polled after completion");
+            handle_report(*this.level, &mut map_err, report)
+        }))
+    }
+}
+
+fn handle_report<C: ?Sized + 'static, O: 'static, T: 'static, E>(
+    level: Option<Level>,
+    map_err: &mut impl FnMut(Report<C, O, T>) -> E,
+    report: Report<C, O, T>,
+) -> E {
+    // The level of a `tracing` event must be known at compile time
+    match level {
+        Some(Level::ERROR) => tracing::event!(Level::ERROR, "{report}"),
+        Some(Level::WARN) => tracing::event!(Level::WARN, "{report}"),
+        Some(Level::INFO) => tracing::event!(Level::INFO, "{report}"),
+        Some(Level::DEBUG) => tracing::event!(Level::DEBUG, "{report}"),
+        Some(Level::TRACE) => tracing::event!(Level::TRACE, "{report}"),
+        None => {}
+    }
+    map_err(report)
+}
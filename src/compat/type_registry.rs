@@ -0,0 +1,100 @@
+//! A global registry of functions keyed by the type of a context.
+//!
+//! The web framework integrations use this to map contexts to status codes.
+//! Lookups clone the matching function out of the registry and release the
+//! lock before returning, so the registered functions and anything that runs
+//! after a lookup, such as formatting hooks, are free to register new entries
+//! without deadlocking.
+
+use alloc::{sync::Arc, vec::Vec};
+use core::any::{Any, TypeId};
+use std::sync::{PoisonError, RwLock};
+
+/// A registered function, which is passed the context as a `&dyn Any` whose
+/// type matches the [`TypeId`] the function was registered for.
+pub(crate) type RegisteredFn<V> = Arc<dyn Fn(&(dyn Any + 'static)) -> V + Send + Sync>;
+
+/// A registry mapping context types to functions computing a `V` from a
+/// context of that type.
+pub(crate) struct TypeRegistry<V> {
+    entries: RwLock<Vec<(TypeId, RegisteredFn<V>)>>,
+}
+
+impl<V> TypeRegistry<V> {
+    /// Creates an empty registry.
+    pub(crate) const fn new() -> Self {
+        Self {
+            entries: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Registers `f` for contexts of type `C`, replacing any previous
+    /// registration for that type.
+    pub(crate) fn register<C, F>(&self, f: F)
+    where
+        C: Sized + 'static,
+        F: Fn(&C) -> V + Send + Sync + 'static,
+    {
+        let type_id = TypeId::of::<C>();
+        let f: RegisteredFn<V> =
+            Arc::new(
+                move |context: &(dyn Any + 'static)| match context.downcast_ref::<C>() {
+                    Some(context) => f(context),
+                    None => unreachable!("registry entries are looked up by the TypeId of `C`"),
+                },
+            );
+
+        let mut entries = self.entries.write().unwrap_or_else(PoisonError::into_inner);
+        match entries.iter_mut().find(|(id, _)| *id == type_id) {
+            Some((_, existing)) => *existing = f,
+            None => entries.push((type_id, f)),
+        }
+    }
+
+    /// Returns the function registered for the type with the given
+    /// [`TypeId`], if any.
+    ///
+    /// The lock is released before this returns, so the function can be
+    /// called while other threads, or the function itself, register entries.
+    pub(crate) fn get(&self, type_id: TypeId) -> Option<RegisteredFn<V>> {
+        self.entries
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|(id, _)| *id == type_id)
+            .map(|(_, f)| Arc::clone(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_replaces() {
+        struct Key;
+        let registry = TypeRegistry::<u32>::new();
+        assert!(registry.get(TypeId::of::<Key>()).is_none());
+
+        registry.register(|_: &Key| 1);
+        registry.register(|_: &Key| 2);
+        let f = registry.get(TypeId::of::<Key>()).unwrap();
+        assert_eq!(f(&Key), 2);
+        assert!(registry.get(TypeId::of::<u8>()).is_none());
+    }
+
+    #[test]
+    fn test_registered_fn_can_register() {
+        struct Outer;
+        struct Inner;
+        static REGISTRY: TypeRegistry<u32> = TypeRegistry::new();
+
+        REGISTRY.register(|_: &Outer| {
+            REGISTRY.register(|_: &Inner| 2);
+            1
+        });
+        let f = REGISTRY.get(TypeId::of::<Outer>()).unwrap();
+        assert_eq!(f(&Outer), 1);
+        assert_eq!(REGISTRY.get(TypeId::of::<Inner>()).unwrap()(&Inner), 2);
+    }
+}
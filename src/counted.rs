@@ -0,0 +1,154 @@
+//! Attachments that count repeated values.
+//!
+//! Attaching the same value several times, for instance once per iteration of
+//! a retry loop, fills the report with identical lines. A [`Counted`]
+//! attachment, usually added using [`Report::attach_counted`], stores the
+//! value once together with the number of times it was attached, and is
+//! displayed as `value (xN)`.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{counted::Counted, prelude::*};
+//!
+//! let mut report = report!("request failed");
+//! for _ in 0..3 {
+//!     report = report.attach_counted("connection reset");
+//! }
+//!
+//! assert!(report.to_string().contains("connection reset (x3)"));
+//!
+//! let counted = report
+//!     .attachments()
+//!     .iter()
+//!     .find_map(|attachment| attachment.downcast_inner::<Counted<&str>>())
+//!     .unwrap();
+//! assert_eq!(counted.count, 3);
+//! ```
+//!
+//! [`Report::attach_counted`]: crate::Report::attach_counted
+
+use core::fmt;
+
+use crate::handlers::AttachmentHandler;
+
+/// An attachment holding a value and the number of times it was attached.
+///
+/// When displayed, the value is followed by ` (xN)` if it was attached more
+/// than once.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::counted::Counted;
+///
+/// let mut counted = Counted::new("connection reset");
+/// assert_eq!(counted.to_string(), "connection reset");
+///
+/// counted.count += 1;
+/// assert_eq!(counted.to_string(), "connection reset (x2)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Counted<A> {
+    /// The attached value.
+    pub value: A,
+    /// The number of times the value was attached.
+    pub count: usize,
+}
+
+impl<A> Counted<A> {
+    /// Creates a new [`Counted`] with a count of one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::counted::Counted;
+    ///
+    /// let counted = Counted::new(42);
+    /// assert_eq!(counted.value, 42);
+    /// assert_eq!(counted.count, 1);
+    /// ```
+    pub fn new(value: A) -> Self {
+        Self { value, count: 1 }
+    }
+}
+
+/// Formats the value, followed by ` (xN)` if the count is more than one.
+impl<A: fmt::Display> fmt::Display for Counted<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)?;
+        if self.count > 1 {
+            write!(f, " (x{})", self.count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Handler for formatting [`Counted`] attachments.
+///
+/// The value is formatted using its [`Display`](fmt::Display) or
+/// [`Debug`](fmt::Debug) implementation, followed by ` (xN)` if it was
+/// attached more than once.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     counted::{Counted, CountedHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("error").attach_custom::<CountedHandler, _>(Counted::new("retrying"));
+/// ```
+#[derive(Copy, Clone)]
+pub struct CountedHandler;
+
+impl<A: fmt::Display + fmt::Debug> AttachmentHandler<Counted<A>> for CountedHandler {
+    fn display(value: &Counted<A>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &Counted<A>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&value.value, f)?;
+        if value.count > 1 {
+            write!(f, " (x{})", value.count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+
+    #[test]
+    fn test_counted_debug() {
+        let report = crate::report!("error")
+            .attach_counted("retrying")
+            .attach_counted("retrying");
+        assert_eq!(report.attachments().len(), 2); // Location and the counted value
+        assert!(format!("{report:?}").contains("\"retrying\" (x2)"));
+    }
+
+    #[test]
+    fn test_counted_only_considers_root_attachments() {
+        let report = crate::report!("inner")
+            .attach_counted(7)
+            .context("outer")
+            .attach_counted(7);
+        let count = |report: crate::ReportRef<'_, _, _>| {
+            report
+                .attachments()
+                .iter()
+                .find_map(|attachment| attachment.downcast_inner::<Counted<i32>>())
+                .map(|counted| counted.count)
+        };
+        assert_eq!(count(report.as_ref().into_dynamic()), Some(1));
+        assert_eq!(
+            count(report.children().get(0).unwrap().into_uncloneable()),
+            Some(1)
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! Machine-readable error code attachments.
+//!
+//! An [`ErrorCode`] identifies the kind of an error with a stable string such
+//! as `"E1234"`, which callers can match on to decide how to recover, or which
+//! can be looked up in documentation. It is usually added using
+//! [`Report::attach_error_code`], and found anywhere in a report tree with
+//! [`Report::error_code`].
+//!
+//! The default formatter renders the code as a badge in front of the context
+//! of the report it is attached to.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::prelude::*;
+//!
+//! let report = report!("user not found")
+//!     .attach_error_code("E1234")
+//!     .context("failed to load profile");
+//!
+//! assert_eq!(report.error_code(), Some("E1234"));
+//! assert!(report.to_string().contains("[E1234] user not found"));
+//! ```
+//!
+//! [`Report::attach_error_code`]: crate::Report::attach_error_code
+//! [`Report::error_code`]: crate::Report::error_code
+
+use alloc::{borrow::Cow, string::String};
+use core::fmt;
+
+use crate::handlers::{
+    AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler,
+    FormattingFunction, JsonSink,
+};
+
+/// A machine-readable code identifying the kind of an error.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::error_code::ErrorCode;
+///
+/// let code = ErrorCode::new("E1234");
+/// assert_eq!(code.as_str(), "E1234");
+/// assert_eq!(code.to_string(), "E1234");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ErrorCode(pub Cow<'static, str>);
+
+impl ErrorCode {
+    /// Creates a new [`ErrorCode`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::error_code::ErrorCode;
+    ///
+    /// let code = ErrorCode::new(format!("HTTP{}", 404));
+    /// assert_eq!(code.as_str(), "HTTP404");
+    /// ```
+    pub fn new(code: impl Into<Cow<'static, str>>) -> Self {
+        Self(code.into())
+    }
+
+    /// Returns the code as a string slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::error_code::ErrorCode;
+    ///
+    /// assert_eq!(ErrorCode::from("E1234").as_str(), "E1234");
+    /// ```
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&'static str> for ErrorCode {
+    fn from(code: &'static str) -> Self {
+        Self(Cow::Borrowed(code))
+    }
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        Self(Cow::Owned(code))
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Handler for formatting [`ErrorCode`] attachments.
+///
+/// The code is displayed inline as `Error code: E1234`. The default formatter
+/// instead renders it as a badge in front of the context of the report, see
+/// [`AttachmentBadge::error_code`](crate::hooks::builtin_hooks::report_formatter::AttachmentBadge::error_code).
+/// When emitted as JSON, it is written as a string such as `"E1234"`.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     error_code::{ErrorCode, ErrorCodeHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("error").attach_custom::<ErrorCodeHandler, _>(ErrorCode::new("E1234"));
+/// ```
+#[derive(Copy, Clone)]
+pub struct ErrorCodeHandler;
+
+impl AttachmentHandler<ErrorCode> for ErrorCodeHandler {
+    fn display(value: &ErrorCode, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Error code: {value}")
+    }
+
+    fn debug(value: &ErrorCode, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, f)
+    }
+
+    fn json(value: &ErrorCode, sink: &mut dyn JsonSink) -> fmt::Result {
+        sink.str(value.as_str())
+    }
+
+    fn preferred_formatting_style(
+        _value: &ErrorCode,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::Inline,
+            function: FormattingFunction::Display,
+            priority: 100,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use super::*;
+    use crate::{
+        handlers::JsonWriter,
+        hooks::builtin_hooks::report_formatter::{
+            AttachmentBadge, DefaultReportFormatter, LineFormatting,
+        },
+    };
+
+    #[test]
+    fn test_error_code_json() {
+        let mut writer = JsonWriter::new(String::new());
+        ErrorCodeHandler::json(&ErrorCode::new("E1234"), &mut writer).unwrap();
+        assert_eq!(writer.into_inner(), r#""E1234""#);
+    }
+
+    #[test]
+    fn test_error_code_badge() {
+        let report = crate::report!("user not found")
+            .attach_error_code("E1234")
+            .attach_severity(crate::severity::Severity::Error);
+        let formatter = DefaultReportFormatter::ASCII;
+        let output = report.format_with(&formatter).to_string();
+        assert!(output.contains("[error] [E1234] user not found"));
+        assert!(!output.contains("Error code:"));
+
+        const BADGES: &[AttachmentBadge] =
+            &[AttachmentBadge::severity(LineFormatting::new("[", "] "))];
+        let formatter = DefaultReportFormatter {
+            attachment_badges: BADGES,
+            ..DefaultReportFormatter::ASCII
+        };
+        let output = report.format_with(&formatter).to_string();
+        assert!(output.contains("[error] user not found"));
+        assert!(output.contains("Error code: E1234"));
+    }
+}
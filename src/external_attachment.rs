@@ -0,0 +1,281 @@
+//! References to attachment data that is stored outside of the report.
+//!
+//! Some attachments, such as core dumps or large request payloads, are too
+//! big to keep in memory inside a report. Instead, the data can be written to
+//! an external store and the report only carries an [`ExternalAttachment`]
+//! pointing to it. This keeps the memory usage of a report bounded while
+//! preserving the pointer to the out-of-band data.
+//!
+//! An [`ExternalAttachment`] is added using [`Report::attach_external`], and
+//! is rendered as `<external: id>` in reports. Log sinks that have access to
+//! the external store can implement [`ExternalAttachmentResolver`] to fetch
+//! the data by id later.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{external_attachment::ExternalAttachment, prelude::*};
+//!
+//! // The core dump was written to an external store under this id
+//! let report = report!("worker crashed").attach_external("dumps/7f3a9c", "core-dump");
+//!
+//! let output = report.to_string();
+//! assert!(output.contains("<external: dumps/7f3a9c>"));
+//!
+//! // The report only stores the reference, not the data
+//! let external = report
+//!     .attachments()
+//!     .iter()
+//!     .find_map(|attachment| attachment.downcast_inner::<ExternalAttachment>())
+//!     .unwrap();
+//! assert_eq!(external.kind, "core-dump");
+//! ```
+//!
+//! [`Report::attach_external`]: crate::Report::attach_external
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{ReportRef, handlers::AttachmentHandler};
+
+/// A reference to attachment data stored outside of the report.
+///
+/// Only the `id` and `kind` are stored in the report, so the size of this
+/// attachment does not depend on the size of the data it refers to.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::external_attachment::ExternalAttachment;
+///
+/// let attachment = ExternalAttachment::new("s3://crash-dumps/7f3a9c", "core-dump");
+/// assert_eq!(attachment.to_string(), "<external: s3://crash-dumps/7f3a9c>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExternalAttachment {
+    /// The identifier of the data in the external store.
+    pub id: String,
+    /// The kind of data that is stored, such as `"core-dump"` or
+    /// `"request-body"`.
+    pub kind: &'static str,
+}
+
+impl ExternalAttachment {
+    /// Creates a new reference to externally stored data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::external_attachment::ExternalAttachment;
+    ///
+    /// let attachment = ExternalAttachment::new("payload-1234", "request-body");
+    /// assert_eq!(attachment.id, "payload-1234");
+    /// assert_eq!(attachment.kind, "request-body");
+    /// ```
+    pub fn new(id: impl Into<String>, kind: &'static str) -> Self {
+        Self {
+            id: id.into(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for ExternalAttachment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<external: {}>", self.id)
+    }
+}
+
+/// Handler for formatting [`ExternalAttachment`] attachments.
+///
+/// Renders the attachment as `<external: id>` when displayed, and includes
+/// the kind of data when debug-formatted.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     external_attachment::{ExternalAttachment, ExternalAttachmentHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("upload failed").attach_custom::<ExternalAttachmentHandler, _>(
+///     ExternalAttachment::new("payload-1234", "request-body"),
+/// );
+/// ```
+#[derive(Copy, Clone)]
+pub struct ExternalAttachmentHandler;
+
+impl AttachmentHandler<ExternalAttachment> for ExternalAttachmentHandler {
+    fn display(value: &ExternalAttachment, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &ExternalAttachment, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<external {}: {}>", value.kind, value.id)
+    }
+}
+
+/// Fetches the data referred to by an [`ExternalAttachment`].
+///
+/// This is implemented by whatever has access to the external store, usually
+/// a log sink that wants to include the data when it processes a report.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rootcause::{
+///     external_attachment::{ExternalAttachment, ExternalAttachmentResolver},
+///     prelude::*,
+/// };
+///
+/// struct InMemoryStore(HashMap<String, Vec<u8>>);
+///
+/// impl ExternalAttachmentResolver for InMemoryStore {
+///     type Error = String;
+///
+///     fn resolve(&self, attachment: &ExternalAttachment) -> Result<Vec<u8>, Self::Error> {
+///         self.0
+///             .get(&attachment.id)
+///             .cloned()
+///             .ok_or_else(|| format!("no blob with id {}", attachment.id))
+///     }
+/// }
+///
+/// let store = InMemoryStore(HashMap::from([("blob-1".to_string(), b"data".to_vec())]));
+/// let report = report!("failed").attach_external("blob-1", "payload");
+///
+/// let resolved = store.resolve_all(report.as_ref());
+/// assert_eq!(resolved.len(), 1);
+/// assert_eq!(resolved[0].data.as_deref(), Ok(&b"data"[..]));
+/// ```
+pub trait ExternalAttachmentResolver {
+    /// The error returned when the data could not be fetched.
+    type Error;
+
+    /// Fetches the data referred to by `attachment`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::external_attachment::{ExternalAttachment, ExternalAttachmentResolver};
+    ///
+    /// struct AlwaysEmpty;
+    ///
+    /// impl ExternalAttachmentResolver for AlwaysEmpty {
+    ///     type Error = core::convert::Infallible;
+    ///
+    ///     fn resolve(&self, _attachment: &ExternalAttachment) -> Result<Vec<u8>, Self::Error> {
+    ///         Ok(Vec::new())
+    ///     }
+    /// }
+    ///
+    /// let data = AlwaysEmpty.resolve(&ExternalAttachment::new("id", "kind"));
+    /// assert_eq!(data, Ok(Vec::new()));
+    /// ```
+    fn resolve(&self, attachment: &ExternalAttachment) -> Result<Vec<u8>, Self::Error>;
+
+    /// Fetches the data for every [`ExternalAttachment`] in the report tree.
+    ///
+    /// The attachments are visited in the same order as
+    /// [`ReportRef::iter_reports`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{
+    ///     external_attachment::{ExternalAttachment, ExternalAttachmentResolver},
+    ///     prelude::*,
+    /// };
+    ///
+    /// struct IdAsData;
+    ///
+    /// impl ExternalAttachmentResolver for IdAsData {
+    ///     type Error = core::convert::Infallible;
+    ///
+    ///     fn resolve(&self, attachment: &ExternalAttachment) -> Result<Vec<u8>, Self::Error> {
+    ///         Ok(attachment.id.as_bytes().to_vec())
+    ///     }
+    /// }
+    ///
+    /// let report = report!("inner")
+    ///     .attach_external("a", "payload")
+    ///     .context("outer")
+    ///     .attach_external("b", "payload");
+    ///
+    /// let ids: Vec<_> = IdAsData
+    ///     .resolve_all(report.as_ref())
+    ///     .into_iter()
+    ///     .map(|resolved| resolved.attachment.id.clone())
+    ///     .collect();
+    /// assert_eq!(ids, ["b", "a"]);
+    /// ```
+    fn resolve_all<'a, C: ?Sized, O, T>(
+        &self,
+        report: ReportRef<'a, C, O, T>,
+    ) -> Vec<ResolvedAttachment<'a, Self::Error>> {
+        report
+            .iter_reports()
+            .flat_map(|report| report.attachments().iter())
+            .filter_map(|attachment| attachment.downcast_inner::<ExternalAttachment>())
+            .map(|attachment| ResolvedAttachment {
+                attachment,
+                data: self.resolve(attachment),
+            })
+            .collect()
+    }
+}
+
+/// The result of resolving an [`ExternalAttachment`] using
+/// [`ExternalAttachmentResolver::resolve_all`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     external_attachment::{ExternalAttachment, ExternalAttachmentResolver, ResolvedAttachment},
+///     prelude::*,
+/// };
+///
+/// struct Unavailable;
+///
+/// impl ExternalAttachmentResolver for Unavailable {
+///     type Error = &'static str;
+///
+///     fn resolve(&self, _attachment: &ExternalAttachment) -> Result<Vec<u8>, Self::Error> {
+///         Err("store unavailable")
+///     }
+/// }
+///
+/// let report = report!("failed").attach_external("blob-1", "payload");
+/// let resolved: Vec<ResolvedAttachment<'_, &str>> = Unavailable.resolve_all(report.as_ref());
+/// assert_eq!(resolved[0].attachment.id, "blob-1");
+/// assert_eq!(resolved[0].data, Err("store unavailable"));
+/// ```
+#[derive(Debug)]
+pub struct ResolvedAttachment<'a, E> {
+    /// The reference to the external data, as stored in the report.
+    pub attachment: &'a ExternalAttachment,
+    /// The fetched data, or the error returned by the resolver.
+    pub data: Result<Vec<u8>, E>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_attachment_send_sync() {
+        static_assertions::assert_impl_all!(ExternalAttachment: Send, Sync);
+    }
+
+    #[test]
+    fn test_external_attachment_size() {
+        // Only the id and kind are stored, regardless of the size of the data
+        static_assertions::const_assert!(
+            core::mem::size_of::<ExternalAttachment>() <= 5 * core::mem::size_of::<usize>()
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Exporting error messages across a C API.
+//!
+//! Libraries exposing a C API cannot hand a [`Report`] to their callers.
+//! Instead, this module turns reports into NUL-terminated C strings, which C
+//! callers can print and then release with [`rootcause_free_error_string`]:
+//!
+//! - [`Report::into_c_string`] converts a report into a string that is owned
+//!   by the caller.
+//! - [`set_last_error`] stores the message of a report for the current thread,
+//!   following the common "last error" pattern of C libraries. C callers can
+//!   retrieve it with [`rootcause_last_error_message`] after a function has
+//!   signaled a failure, for instance by returning `-1`.
+//!
+//! The strings contain the [`Display`] output of the
+//! reports, with any interior NUL bytes removed.
+//!
+//! # Examples
+//!
+//! ```
+//! use core::ffi::c_int;
+//!
+//! use rootcause::{ffi, prelude::*};
+//!
+//! fn parse_port(input: &str) -> Result<u16, Report> {
+//!     Ok(input.parse::<u16>().context("invalid port")?)
+//! }
+//!
+//! #[unsafe(no_mangle)]
+//! pub extern "C" fn mylib_parse_port(value: c_int) -> c_int {
+//!     match parse_port(&value.to_string()) {
+//!         Ok(port) => c_int::from(port),
+//!         Err(report) => {
+//!             ffi::set_last_error(report);
+//!             -1
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(mylib_parse_port(-5), -1);
+//!
+//! // On the C side:
+//! //   char *message = rootcause_last_error_message();
+//! //   fprintf(stderr, "%s\n", message);
+//! //   rootcause_free_error_string(message);
+//! let message = ffi::rootcause_last_error_message();
+//! assert!(!message.is_null());
+//! // SAFETY: The pointer was returned by `rootcause_last_error_message`, and
+//! // is freed only once.
+//! unsafe { ffi::rootcause_free_error_string(message) };
+//! ```
+//!
+//! # Symbol Names
+//!
+//! [`rootcause_free_error_string`] and [`rootcause_last_error_message`] are
+//! exported with unmangled names, so they can be declared in a C header and
+//! called directly. As a consequence, only a single version of rootcause with
+//! the `ffi` feature enabled can be linked into a program.
+//!
+//! [`Report`]: crate::Report
+//! [`Report::into_c_string`]: crate::Report::into_c_string
+
+use alloc::{ffi::CString, string::String};
+use core::{cell::RefCell, ffi::c_char, fmt::Display};
+
+use crate::Report;
+
+std::thread_local! {
+    /// The message of the last error reported on this thread using
+    /// [`set_last_error`].
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Converts the [`Display`] output of a value into a [`CString`], removing
+/// any interior NUL bytes.
+pub(crate) fn to_c_string(value: impl Display) -> CString {
+    let mut bytes = alloc::format!("{value}").into_bytes();
+    bytes.retain(|&byte| byte != 0);
+    CString::new(bytes).expect("interior NUL bytes were removed")
+}
+
+/// Stores the message of a report as the last error of the current thread.
+///
+/// The message replaces any previously stored error, and can be retrieved by
+/// C callers using [`rootcause_last_error_message`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{ffi, prelude::*};
+///
+/// ffi::set_last_error(report!("database connection failed"));
+/// assert!(ffi::last_error_message().unwrap().contains("database connection failed"));
+/// ```
+pub fn set_last_error<C: ?Sized, O, T>(report: Report<C, O, T>) {
+    let message = to_c_string(report);
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+/// Returns a copy of the last error message of the current thread, without
+/// clearing it.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{ffi, prelude::*};
+///
+/// ffi::clear_last_error();
+/// assert_eq!(ffi::last_error_message(), None);
+///
+/// ffi::set_last_error(report!("out of memory"));
+/// assert!(ffi::last_error_message().unwrap().contains("out of memory"));
+/// ```
+#[must_use]
+pub fn last_error_message() -> Option<String> {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map(|message| String::from(message.to_string_lossy()))
+    })
+}
+
+/// Clears the last error of the current thread.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{ffi, prelude::*};
+///
+/// ffi::set_last_error(report!("out of memory"));
+/// ffi::clear_last_error();
+/// assert_eq!(ffi::last_error_message(), None);
+/// ```
+pub fn clear_last_error() {
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = None);
+}
+
+/// Takes the last error message of the current thread, as a C string owned by
+/// the caller.
+///
+/// Returns a null pointer if no error was stored using [`set_last_error`]
+/// since the last call. The returned string must be released using
+/// [`rootcause_free_error_string`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{ffi, prelude::*};
+///
+/// ffi::set_last_error(report!("out of memory"));
+/// let message = ffi::rootcause_last_error_message();
+/// assert!(!message.is_null());
+/// assert!(ffi::rootcause_last_error_message().is_null());
+///
+/// // SAFETY: The pointer was returned by `rootcause_last_error_message`, and
+/// // is freed only once.
+/// unsafe { ffi::rootcause_free_error_string(message) };
+/// ```
+#[unsafe(no_mangle)]
+#[must_use]
+pub extern "C" fn rootcause_last_error_message() -> *mut c_char {
+    LAST_ERROR
+        .with(|last_error| last_error.borrow_mut().take())
+        .map_or(core::ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by [`Report::into_c_string`] or
+/// [`rootcause_last_error_message`].
+///
+/// Passing a null pointer is allowed, and does nothing.
+///
+/// # Safety
+///
+/// 1. The pointer must either be null, or have been returned by
+///    [`Report::into_c_string`] or [`rootcause_last_error_message`].
+/// 2. The string must not have been released already, and must not be used
+///    after this call.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{ffi, prelude::*};
+///
+/// let message = report!("out of memory").into_c_string();
+///
+/// // SAFETY: The pointer was returned by `into_c_string`, and is freed only
+/// // once.
+/// unsafe { ffi::rootcause_free_error_string(message) };
+/// ```
+///
+/// [`Report::into_c_string`]: crate::Report::into_c_string
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rootcause_free_error_string(ptr: *mut c_char) {
+    if ptr.is_null() {
+        return;
+    }
+
+    // SAFETY:
+    // 1. The pointer is not null, so by the safety requirements of this function
+    //    it was created by `CString::into_raw` in `Report::into_c_string` or
+    //    `rootcause_last_error_message`.
+    // 2. The string has not been released yet, and is not used afterwards.
+    let string = unsafe {
+        // @add-unsafe-context: rootcause_last_error_message
+        CString::from_raw(ptr)
+    };
+    drop(string);
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ffi::CStr;
+
+    use super::*;
+
+    #[test]
+    fn test_to_c_string_removes_nul_bytes() {
+        assert_eq!(to_c_string("a\0b").as_bytes(), b"ab");
+    }
+
+    #[test]
+    fn test_into_c_string_roundtrip() {
+        let ptr = crate::report!("disk full").into_c_string();
+
+        // SAFETY: The pointer was just returned by `into_c_string`.
+        let message = unsafe { CStr::from_ptr(ptr) };
+        assert!(message.to_str().unwrap().contains("disk full"));
+
+        // SAFETY: The pointer was returned by `into_c_string`, and is freed only
+        // once.
+        unsafe { rootcause_free_error_string(ptr) };
+    }
+}
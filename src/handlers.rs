@@ -42,9 +42,21 @@
 //! - **InlineWithHeader**: Rendered inline but with a header (for multi-line
 //!   content)
 //! - **Appendix**: Rendered in a separate appendix section
+//! - **Footer**: Rendered in a footer section after the report, with a label
+//! - **Collapsed**: Rendered as a one-line summary, and in full with the
+//!   alternate flag
 //! - **Opaque**: Not shown, but counted in a summary
 //! - **Hidden**: Not shown at all
 //!
+//! ## 3. Structured Output
+//!
+//! The [`ContextHandler::json`] and [`AttachmentHandler::json`] methods emit a
+//! structured representation of a value to a [`JsonSink`], for use by
+//! structured log sinks and JSON formatters. The default implementation emits
+//! the output of the `display` method as a JSON string, and handlers can
+//! override it to provide real structure. [`JsonWriter`] is a [`JsonSink`]
+//! that writes JSON text.
+//!
 //! # Built-in Handlers
 //!
 //! ## [`Error`]
@@ -76,8 +88,10 @@
 //!
 //! Handlers are typically selected automatically by the
 //! [`report!`](crate::report!) macro based on the traits implemented by your
-//! context type. You can also specify a handler explicitly using
-//! [`Report::new_custom`](crate::Report::new_custom).
+//! context type. A type can choose the handler used by
+//! [`report!`](crate::report!) by implementing [`RootcauseContext`], which is
+//! derivable with the `derive` feature flag. You can also specify a handler
+//! explicitly using [`Report::new_custom`](crate::Report::new_custom).
 //!
 //! # Examples
 //!
@@ -95,9 +109,11 @@
 //! let report2: Report<String> = report!(msg);
 //! ```
 
+use alloc::vec::Vec;
+
 pub use rootcause_internals::handlers::{
     AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler,
-    ContextFormattingStyle, ContextHandler, FormattingFunction,
+    ContextFormattingStyle, ContextHandler, FormattingFunction, JsonSink, StackFrame,
 };
 
 /// Handler for types implementing [`std::error::Error`](core::error::Error).
@@ -391,3 +407,317 @@ impl<C> ContextHandler<C> for Any {
         write!(f, "An object of type {}", core::any::type_name::<C>())
     }
 }
+
+/// Selects the handler used by the [`report!`](crate::report!) macro for a
+/// context type.
+///
+/// Without this trait, [`report!`](crate::report!) picks [`Error`],
+/// [`Display`], [`struct@Debug`] or [`Any`] depending on the traits
+/// implemented by the context. Implementing it overrides that choice, so a
+/// type with a custom [`ContextHandler`] does not need
+/// [`Report::new_custom`](crate::Report::new_custom) at every call site.
+///
+/// With the `derive` feature flag, this trait can be derived. By default, the
+/// derived implementation uses the [`Error`] handler, which fits types using
+/// `thiserror`. The handler can be changed with attributes:
+///
+/// - `#[rootcause(handler = path::to::Handler)]` uses the given handler.
+/// - `#[rootcause(display = "...")]` uses a generated handler that formats
+///   the context with the given format string, in which the fields of a
+///   struct with named fields can be referred to by name. The context is
+///   debug-formatted using its [`Debug`](core::fmt::Debug) implementation and
+///   has no source.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     handlers::{self, RootcauseContext},
+///     prelude::*,
+/// };
+///
+/// #[derive(Debug)]
+/// struct Timeout {
+///     seconds: u64,
+/// }
+///
+/// struct TimeoutHandler;
+///
+/// impl handlers::ContextHandler<Timeout> for TimeoutHandler {
+///     fn source(_context: &Timeout) -> Option<&(dyn core::error::Error + 'static)> {
+///         None
+///     }
+///
+///     fn display(context: &Timeout, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         write!(f, "timed out after {}s", context.seconds)
+///     }
+///
+///     fn debug(context: &Timeout, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         core::fmt::Debug::fmt(context, f)
+///     }
+/// }
+///
+/// impl RootcauseContext for Timeout {
+///     type Handler = TimeoutHandler;
+/// }
+///
+/// let report = report!(Timeout { seconds: 30 });
+/// assert_eq!(report.format_current_context().to_string(), "timed out after 30s");
+/// ```
+///
+/// Using the derive macro:
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use rootcause::{handlers::RootcauseContext, prelude::*};
+///
+/// #[derive(Debug, RootcauseContext)]
+/// #[rootcause(display = "timed out after {seconds}s")]
+/// struct Timeout {
+///     seconds: u64,
+/// }
+///
+/// let report = report!(Timeout { seconds: 30 });
+/// assert_eq!(report.format_current_context().to_string(), "timed out after 30s");
+/// # }
+/// ```
+pub trait RootcauseContext: Sized {
+    /// The handler used for this context type by the
+    /// [`report!`](crate::report!) macro.
+    type Handler: ContextHandler<Self>;
+}
+
+/// Derives [`RootcauseContext`](trait@RootcauseContext). See the trait for
+/// the supported attributes.
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use rootcause_derive::RootcauseContext;
+
+/// A [`JsonSink`] that writes compact JSON text to a [`core::fmt::Write`].
+///
+/// Commas between values and escaping of strings are handled automatically,
+/// so the output is valid JSON as long as the calls to the [`JsonSink`]
+/// methods describe a valid JSON value. Non-finite floating point values are
+/// written as `null`.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     handlers::{JsonSink, JsonWriter},
+///     prelude::*,
+/// };
+///
+/// let mut writer = JsonWriter::new(String::new());
+/// writer.begin_object().unwrap();
+/// writer.key("message").unwrap();
+/// writer.str("line 1\nline 2").unwrap();
+/// writer.key("codes").unwrap();
+/// writer.begin_array().unwrap();
+/// writer.u64(1).unwrap();
+/// writer.i64(-2).unwrap();
+/// writer.end_array().unwrap();
+/// writer.end_object().unwrap();
+/// assert_eq!(writer.into_inner(), r#"{"message":"line 1\nline 2","codes":[1,-2]}"#);
+///
+/// // Contexts and attachments can be written using their handlers
+/// let report = report!("file not found");
+/// let mut writer = JsonWriter::new(String::new());
+/// report.as_ref().write_current_context_json(&mut writer).unwrap();
+/// assert_eq!(writer.into_inner(), r#""file not found""#);
+/// ```
+pub struct JsonWriter<W> {
+    writer: W,
+    /// One entry per open object or array, which is `true` if a value has
+    /// already been written to it
+    has_values: Vec<bool>,
+    /// Whether a key was just written, so the next value must not be
+    /// preceded by a comma
+    after_key: bool,
+}
+
+impl<W: core::fmt::Write> JsonWriter<W> {
+    /// Creates a new [`JsonWriter`] that writes to `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::handlers::{JsonSink, JsonWriter};
+    ///
+    /// let mut writer = JsonWriter::new(String::new());
+    /// writer.bool(true).unwrap();
+    /// assert_eq!(writer.into_inner(), "true");
+    /// ```
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            has_values: Vec::new(),
+            after_key: false,
+        }
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::handlers::{JsonSink, JsonWriter};
+    ///
+    /// let mut writer = JsonWriter::new(String::new());
+    /// writer.null().unwrap();
+    /// let output: String = writer.into_inner();
+    /// assert_eq!(output, "null");
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn begin_value(&mut self) -> core::fmt::Result {
+        if core::mem::take(&mut self.after_key) {
+            return Ok(());
+        }
+        if let Some(has_values) = self.has_values.last_mut() {
+            if *has_values {
+                self.writer.write_char(',')?;
+            }
+            *has_values = true;
+        }
+        Ok(())
+    }
+
+    fn write_string(&mut self, value: core::fmt::Arguments<'_>) -> core::fmt::Result {
+        self.writer.write_char('"')?;
+        core::fmt::write(&mut JsonEscaper(&mut self.writer), value)?;
+        self.writer.write_char('"')
+    }
+}
+
+impl<W: core::fmt::Write> JsonSink for JsonWriter<W> {
+    fn null(&mut self) -> core::fmt::Result {
+        self.begin_value()?;
+        self.writer.write_str("null")
+    }
+
+    fn bool(&mut self, value: bool) -> core::fmt::Result {
+        self.begin_value()?;
+        self.writer.write_str(if value { "true" } else { "false" })
+    }
+
+    fn i64(&mut self, value: i64) -> core::fmt::Result {
+        self.begin_value()?;
+        write!(self.writer, "{value}")
+    }
+
+    fn u64(&mut self, value: u64) -> core::fmt::Result {
+        self.begin_value()?;
+        write!(self.writer, "{value}")
+    }
+
+    fn f64(&mut self, value: f64) -> core::fmt::Result {
+        self.begin_value()?;
+        if value.is_finite() {
+            write!(self.writer, "{value}")
+        } else {
+            self.writer.write_str("null")
+        }
+    }
+
+    fn str(&mut self, value: &str) -> core::fmt::Result {
+        self.begin_value()?;
+        self.write_string(format_args!("{value}"))
+    }
+
+    fn display(&mut self, value: &dyn core::fmt::Display) -> core::fmt::Result {
+        self.begin_value()?;
+        self.write_string(format_args!("{value}"))
+    }
+
+    fn begin_object(&mut self) -> core::fmt::Result {
+        self.begin_value()?;
+        self.has_values.push(false);
+        self.writer.write_char('{')
+    }
+
+    fn key(&mut self, key: &str) -> core::fmt::Result {
+        self.begin_value()?;
+        self.write_string(format_args!("{key}"))?;
+        self.after_key = true;
+        self.writer.write_char(':')
+    }
+
+    fn end_object(&mut self) -> core::fmt::Result {
+        self.has_values.pop();
+        self.writer.write_char('}')
+    }
+
+    fn begin_array(&mut self) -> core::fmt::Result {
+        self.begin_value()?;
+        self.has_values.push(false);
+        self.writer.write_char('[')
+    }
+
+    fn end_array(&mut self) -> core::fmt::Result {
+        self.has_values.pop();
+        self.writer.write_char(']')
+    }
+}
+
+/// Escapes everything written to it for use inside a JSON string.
+struct JsonEscaper<'a, W>(&'a mut W);
+
+impl<W: core::fmt::Write> core::fmt::Write for JsonEscaper<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let mut unescaped_start = 0;
+        for (index, c) in s.char_indices() {
+            if c != '"' && c != '\\' && c >= ' ' {
+                continue;
+            }
+            self.0.write_str(&s[unescaped_start..index])?;
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                _ => write!(self.0, "\\u{:04x}", c as u32)?,
+            }
+            unescaped_start = index + c.len_utf8();
+        }
+        self.0.write_str(&s[unescaped_start..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::*;
+
+    #[test]
+    fn test_json_writer_escaping() {
+        let mut writer = JsonWriter::new(String::new());
+        writer
+            .str("quote \" backslash \\ tab \t bell \u{7} é")
+            .unwrap();
+        assert_eq!(
+            writer.into_inner(),
+            r#""quote \" backslash \\ tab \t bell \u0007 é""#
+        );
+    }
+
+    #[test]
+    fn test_json_writer_nesting() {
+        let mut writer = JsonWriter::new(String::new());
+        writer.begin_array().unwrap();
+        writer.begin_object().unwrap();
+        writer.key("a").unwrap();
+        writer.begin_array().unwrap();
+        writer.end_array().unwrap();
+        writer.key("b").unwrap();
+        writer.f64(f64::NAN).unwrap();
+        writer.end_object().unwrap();
+        writer.display(&format_args!("{}", 1.5)).unwrap();
+        writer.end_array().unwrap();
+        assert_eq!(writer.into_inner(), r#"[{"a":[],"b":null},"1.5"]"#);
+    }
+}
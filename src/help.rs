@@ -0,0 +1,301 @@
+//! Help text attachments, such as suggestions and notes.
+//!
+//! This module provides the [`Suggestion`] and [`Note`] attachment types,
+//! which are similar to the help sections of `color-eyre`. Instead of being
+//! rendered next to the report node they are attached to, they are placed in
+//! a footer section after the report by the default formatter, which makes
+//! them easy to spot in CLI output.
+//!
+//! They are usually added using [`ResultExt::suggestion`] and
+//! [`ResultExt::note`].
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::prelude::*;
+//!
+//! fn read_config() -> Result<String, Report> {
+//!     std::fs::read_to_string("/nonexistent/config.toml")
+//!         .context("Failed to read the config file")
+//!         .note("The config file is read from /nonexistent/config.toml")
+//!         .suggestion("Run `app init` to create a default config file")
+//!         .map_err(|report| report.into_dynamic())
+//! }
+//!
+//! let output = read_config().unwrap_err().to_string();
+//! assert!(output.contains("Note: The config file is read from /nonexistent/config.toml"));
+//! assert!(output.contains("Suggestion: Run `app init` to create a default config file"));
+//! ```
+//!
+//! [`ResultExt::suggestion`]: crate::prelude::ResultExt::suggestion
+//! [`ResultExt::note`]: crate::prelude::ResultExt::note
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::handlers::{
+    AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler, FormattingFunction,
+};
+
+/// A suggestion for how to fix an error.
+///
+/// When added using [`SuggestionHandler`], the default formatter renders it
+/// as `Suggestion: ...` in the footer section of the report.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     help::{Suggestion, SuggestionHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("permission denied")
+///     .attach_custom::<SuggestionHandler, _>(Suggestion::new("try running with sudo"));
+/// assert!(report.to_string().contains("Suggestion: try running with sudo"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Suggestion(pub String);
+
+impl Suggestion {
+    /// Creates a new [`Suggestion`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::help::Suggestion;
+    ///
+    /// let suggestion = Suggestion::new("try running with sudo");
+    /// assert_eq!(suggestion.to_string(), "try running with sudo");
+    /// ```
+    pub fn new(suggestion: impl Into<String>) -> Self {
+        Self(suggestion.into())
+    }
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A note with additional information about an error.
+///
+/// When added using [`NoteHandler`], the default formatter renders it as
+/// `Note: ...` in the footer section of the report.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     help::{Note, NoteHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("connection refused")
+///     .attach_custom::<NoteHandler, _>(Note::new("the server may still be starting"));
+/// assert!(report.to_string().contains("Note: the server may still be starting"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Note(pub String);
+
+impl Note {
+    /// Creates a new [`Note`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::help::Note;
+    ///
+    /// let note = Note::new("the server may still be starting");
+    /// assert_eq!(note.to_string(), "the server may still be starting");
+    /// ```
+    pub fn new(note: impl Into<String>) -> Self {
+        Self(note.into())
+    }
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Handler for formatting [`Suggestion`] attachments.
+///
+/// Places the suggestion in the footer section of the report, with the label
+/// `Suggestion`.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     help::{Suggestion, SuggestionHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("error").attach_custom::<SuggestionHandler, _>(Suggestion::new("retry"));
+/// ```
+#[derive(Copy, Clone)]
+pub struct SuggestionHandler;
+
+impl AttachmentHandler<Suggestion> for SuggestionHandler {
+    fn display(value: &Suggestion, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &Suggestion, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, f)
+    }
+
+    fn preferred_formatting_style(
+        _value: &Suggestion,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::Footer {
+                label: "Suggestion",
+            },
+            function: FormattingFunction::Display,
+            priority: 0,
+        }
+    }
+}
+
+/// Handler for formatting [`Note`] attachments.
+///
+/// Places the note in the footer section of the report, with the label
+/// `Note`.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     help::{Note, NoteHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("error").attach_custom::<NoteHandler, _>(Note::new("this is a note"));
+/// ```
+#[derive(Copy, Clone)]
+pub struct NoteHandler;
+
+impl AttachmentHandler<Note> for NoteHandler {
+    fn display(value: &Note, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &Note, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, f)
+    }
+
+    fn preferred_formatting_style(
+        _value: &Note,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::Footer { label: "Note" },
+            function: FormattingFunction::Display,
+            priority: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::{
+        Report,
+        hooks::builtin_hooks::report_formatter::DefaultReportFormatter,
+        markers::{Mutable, SendSync},
+        report_attachments::ReportAttachments,
+        report_collection::ReportCollection,
+    };
+
+    /// Creates a report without running the hooks, so that the output does
+    /// not contain locations.
+    fn unhooked(context: &'static str) -> Report<&'static str, Mutable, SendSync> {
+        Report::from_parts_unhooked::<crate::handlers::Display>(
+            context,
+            ReportCollection::new(),
+            ReportAttachments::new(),
+        )
+    }
+
+    /// A parent report with a suggestion and a note, and a child with a note
+    /// and a regular attachment.
+    fn report_with_help() -> Report<&'static str, Mutable, SendSync> {
+        let child = unhooked("child")
+            .attach_custom::<NoteHandler, _>(Note::new("child note"))
+            .attach("detail");
+        let mut parent = unhooked("parent")
+            .attach_custom::<SuggestionHandler, _>(Suggestion::new("first"))
+            .attach_custom::<NoteHandler, _>(Note::new("parent note"))
+            .attach_custom::<SuggestionHandler, _>(Suggestion::new("multi\nline"));
+        parent
+            .children_mut()
+            .push(child.into_dynamic().into_cloneable());
+        parent
+    }
+
+    #[test]
+    fn test_footer_separator() {
+        let formatter = DefaultReportFormatter {
+            report_footer_separator: "-- help --\n",
+            ..DefaultReportFormatter::ASCII
+        };
+
+        let output = report_with_help().format_with(&formatter).to_string();
+        let (tree, footer) = output.split_once("-- help --\n").unwrap();
+        assert!(tree.ends_with("|- detail\n"));
+        assert!(footer.starts_with("Suggestion: first\n"));
+
+        // The separator is only written when there are footer attachments
+        let output = unhooked("error").format_with(&formatter).to_string();
+        assert!(!output.contains("-- help --"));
+    }
+
+    #[test]
+    fn test_footer_collects_labels_of_all_nodes() {
+        let output = report_with_help()
+            .format_with(&DefaultReportFormatter::ASCII)
+            .to_string();
+        assert_eq!(
+            output,
+            "\n\
+             o  parent\n\
+             o  child\n\
+             |- detail\n\
+             \n\
+             Suggestion: first\n\
+             Note: parent note\n\
+             Suggestion: multi\n\
+             \x20 line\n\
+             Note: child note\n"
+        );
+    }
+
+    #[test]
+    fn test_footer_order_with_line_prefix() {
+        let formatter = DefaultReportFormatter {
+            line_prefix: "| ",
+            ..DefaultReportFormatter::ASCII
+        };
+        let output = report_with_help().format_with(&formatter).to_string();
+        assert_eq!(
+            output,
+            "| \n\
+             | o  parent\n\
+             | o  child\n\
+             | |- detail\n\
+             | \n\
+             | Suggestion: first\n\
+             | Note: parent note\n\
+             | Suggestion: multi\n\
+             |   line\n\
+             | Note: child note\n"
+        );
+    }
+}
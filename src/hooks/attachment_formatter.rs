@@ -527,6 +527,30 @@ pub trait AttachmentFormatterHook<A>: 'static + Send + Sync {
     }
 }
 
+/// An [`AttachmentFormatterHook`] that overrides the
+/// [`Display`](fmt::Display) formatting with a closure, used by
+/// [`Hooks::attachment_formatter_fn`].
+///
+/// [`Hooks::attachment_formatter_fn`]: crate::hooks::Hooks::attachment_formatter_fn
+pub(crate) struct AttachmentDisplayFn<F>(pub(crate) F);
+
+impl<A, F> AttachmentFormatterHook<A> for AttachmentDisplayFn<F>
+where
+    F: Fn(ReportAttachmentRef<'_, A>, &mut fmt::Formatter<'_>) -> fmt::Result
+        + 'static
+        + Send
+        + Sync,
+{
+    fn display(
+        &self,
+        attachment: ReportAttachmentRef<'_, A>,
+        _attachment_parent: Option<AttachmentParent<'_>>,
+        formatter: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        (self.0)(attachment, formatter)
+    }
+}
+
 impl<A, H> StoredHook for Hook<A, H>
 where
     H: AttachmentFormatterHook<A>,
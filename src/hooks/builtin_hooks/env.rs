@@ -0,0 +1,257 @@
+//! Environment variable snapshots for reproducing errors.
+//!
+//! An [`EnvSnapshot`] records the values of a list of environment variables,
+//! such as `LANG`, `TZ` or application-specific configuration, at the time
+//! an error occurred. Variables that are not set are omitted from the
+//! snapshot. It can be added to a single report with [`Report::attach_env`],
+//! or to every report by installing an [`EnvCollector`] as an attachment
+//! collector.
+//!
+//! The snapshot is displayed in an `Environment` appendix, with the names
+//! aligned so that the values line up.
+//!
+//! [`Report::attach_env`]: crate::Report::attach_env
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::prelude::*;
+//!
+//! // SAFETY: No other threads are reading or writing the environment
+//! unsafe { std::env::set_var("APP_MODE", "staging") };
+//!
+//! let report = report!("configuration invalid").attach_env(&["APP_MODE", "APP_UNSET"]);
+//! let output = report.to_string();
+//! assert!(output.contains("APP_MODE = staging"));
+//! assert!(!output.contains("APP_UNSET"));
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use rootcause_internals::handlers::{
+    AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler, FormattingFunction,
+};
+
+use crate::{handlers::JsonSink, hooks::report_creation::AttachmentCollector};
+
+/// The values of a list of environment variables at a point in time.
+///
+/// Only variables that were set when the snapshot was captured are stored.
+/// Values that are not valid Unicode are converted lossily.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::builtin_hooks::env::EnvSnapshot;
+///
+/// // SAFETY: No other threads are reading or writing the environment
+/// unsafe { std::env::set_var("APP_REGION", "eu-west-1") };
+///
+/// let snapshot = EnvSnapshot::capture(&["APP_REGION", "APP_UNSET"]);
+/// let vars: Vec<(&str, &str)> = snapshot.vars().collect();
+/// assert_eq!(vars, [("APP_REGION", "eu-west-1")]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct EnvSnapshot {
+    vars: Vec<(String, String)>,
+}
+
+impl EnvSnapshot {
+    /// Reads the given environment variables, keeping the ones that are set.
+    ///
+    /// The variables are stored in the order they are given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::env::EnvSnapshot;
+    ///
+    /// let snapshot = EnvSnapshot::capture(&["APP_UNSET"]);
+    /// assert!(snapshot.is_empty());
+    /// ```
+    #[must_use]
+    pub fn capture<S: AsRef<str>>(names: &[S]) -> Self {
+        let vars = names
+            .iter()
+            .filter_map(|name| {
+                let name = name.as_ref();
+                let value = std::env::var_os(name)?;
+                Some((String::from(name), value.to_string_lossy().into_owned()))
+            })
+            .collect();
+        Self { vars }
+    }
+
+    /// Returns an iterator over the captured `(name, value)` pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::env::EnvSnapshot;
+    ///
+    /// let snapshot = EnvSnapshot::capture(&["PATH"]);
+    /// for (name, value) in snapshot.vars() {
+    ///     println!("{name} = {value}");
+    /// }
+    /// ```
+    pub fn vars(&self) -> impl ExactSizeIterator<Item = (&str, &str)> {
+        self.vars
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Returns `true` if none of the variables were set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::env::EnvSnapshot;
+    ///
+    /// assert!(EnvSnapshot::default().is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+}
+
+/// Formats one `name = value` line per variable, with the names padded so
+/// that the values line up.
+impl fmt::Display for EnvSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.vars().map(|(name, _)| name.len()).max().unwrap_or(0);
+        for (index, (name, value)) in self.vars().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{name:<width$} = {value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Handler for formatting [`EnvSnapshot`] attachments.
+///
+/// The snapshot is displayed in an `Environment` appendix, with one aligned
+/// `name = value` line per variable. When emitted as JSON, it is written as
+/// an object mapping each name to its value.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     hooks::builtin_hooks::env::{EnvSnapshot, EnvSnapshotHandler},
+///     prelude::*,
+/// };
+///
+/// let report =
+///     report!("error").attach_custom::<EnvSnapshotHandler, _>(EnvSnapshot::capture(&["LANG"]));
+/// ```
+#[derive(Copy, Clone)]
+pub struct EnvSnapshotHandler;
+
+impl AttachmentHandler<EnvSnapshot> for EnvSnapshotHandler {
+    fn display(value: &EnvSnapshot, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &EnvSnapshot, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(value.vars()).finish()
+    }
+
+    fn json(value: &EnvSnapshot, sink: &mut dyn JsonSink) -> fmt::Result {
+        sink.begin_object()?;
+        for (name, value) in value.vars() {
+            sink.key(name)?;
+            sink.str(value)?;
+        }
+        sink.end_object()
+    }
+
+    fn preferred_formatting_style(
+        _value: &EnvSnapshot,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::Appendix {
+                appendix_name: "Environment",
+            },
+            function: FormattingFunction::Display,
+            priority: 0,
+        }
+    }
+}
+
+/// Attachment collector adding an [`EnvSnapshot`] of a fixed list of
+/// environment variables to every report.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::{Hooks, builtin_hooks::env::EnvCollector};
+///
+/// Hooks::new()
+///     .attachment_collector(EnvCollector::new(["LANG", "TZ", "APP_CONFIG"]))
+///     .install()
+///     .ok();
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnvCollector {
+    names: Vec<String>,
+}
+
+impl EnvCollector {
+    /// Creates a collector capturing the given environment variables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::env::EnvCollector;
+    ///
+    /// let collector = EnvCollector::new(["LANG", "TZ"]);
+    /// ```
+    #[must_use]
+    pub fn new<I>(names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl AttachmentCollector<EnvSnapshot> for EnvCollector {
+    type Handler = EnvSnapshotHandler;
+
+    fn collect(&self) -> EnvSnapshot {
+        EnvSnapshot::capture(&self.names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_aligns_names() {
+        let snapshot = EnvSnapshot {
+            vars: alloc::vec![
+                ("TZ".into(), "UTC".into()),
+                ("LANG".into(), "en_US.UTF-8".into()),
+            ],
+        };
+        assert_eq!(snapshot.to_string(), "TZ   = UTC\nLANG = en_US.UTF-8");
+    }
+
+    #[test]
+    fn test_capture_omits_missing_vars() {
+        let snapshot = EnvSnapshot::capture(&["ROOTCAUSE_TEST_SURELY_UNSET_VARIABLE"]);
+        assert!(snapshot.is_empty());
+        assert_eq!(snapshot.to_string(), "");
+    }
+}
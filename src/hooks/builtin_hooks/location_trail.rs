@@ -0,0 +1,176 @@
+//! Call-site trail built from the captured source code locations.
+//!
+//! This module provides a lightweight alternative to a full stack backtrace.
+//! Every time a report is created or wrapped with a new context, the
+//! [`LocationHook`] records the [`Location`] of the `#[track_caller]` call
+//! site. A [`LocationTrail`] collects those locations along the chain of
+//! reports into an ordered list of call sites, which can be rendered as a
+//! pseudo-backtrace.
+//!
+//! Unlike `rootcause-backtrace`, this does not depend on the `backtrace`
+//! crate, `std` or any OS support for unwinding the stack, so it works on any
+//! target that has `alloc`.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{hooks::builtin_hooks::location_trail::LocationTrail, prelude::*};
+//!
+//! fn read_config() -> Result<(), Report> {
+//!     Err(report!("file not found"))
+//! }
+//!
+//! fn start() -> Result<(), Report> {
+//!     read_config().context("Failed to read the config")?;
+//!     Ok(())
+//! }
+//!
+//! let report = start().unwrap_err().attach_location_trail();
+//! let trail = LocationTrail::from_report(report.as_ref());
+//! assert_eq!(trail.locations.len(), 2);
+//! assert!(report.to_string().contains("Location trail"));
+//! ```
+//!
+//! [`LocationHook`]: crate::hooks::builtin_hooks::location::LocationHook
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use rootcause_internals::handlers::{
+    AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler, FormattingFunction,
+};
+
+use crate::{
+    ReportRef, hooks::builtin_hooks::location::Location, report_attachments::ReportAttachments,
+};
+
+/// An ordered trail of the call sites where a report and its contexts were
+/// created.
+///
+/// The first location is where the error originated, and each following
+/// location is where it was wrapped in a new context on its way up the call
+/// stack, similar to the frames of a backtrace.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{hooks::builtin_hooks::location_trail::LocationTrail, prelude::*};
+///
+/// let report = report!("inner error").context("outer context");
+/// let trail = LocationTrail::from_report(report.as_ref());
+///
+/// assert_eq!(trail.locations.len(), 2);
+/// assert!(trail.to_string().starts_with("0: "));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct LocationTrail {
+    /// The call sites, starting with the one where the error originated.
+    pub locations: Vec<Location>,
+}
+
+impl LocationTrail {
+    /// Collects the [`Location`] attachments along the chain of reports.
+    ///
+    /// Starting at `report`, the first child of each report is followed down
+    /// to the report where the error originated. The locations are returned
+    /// in the reverse order, so the trail starts at the origin of the error.
+    ///
+    /// Reports without a [`Location`] attachment, such as those created with
+    /// [`Hooks::new_without_locations`], do not add anything to the trail.
+    ///
+    /// [`Hooks::new_without_locations`]: crate::hooks::Hooks::new_without_locations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{hooks::builtin_hooks::location_trail::LocationTrail, prelude::*};
+    ///
+    /// let inner_line = line!() + 1;
+    /// let report = report!("inner error");
+    /// let outer_line = line!() + 1;
+    /// let report = report.context("outer context");
+    ///
+    /// let trail = LocationTrail::from_report(report.as_ref());
+    /// let lines: Vec<u32> = trail.locations.iter().map(|location| location.line).collect();
+    /// assert_eq!(lines, [inner_line, outer_line]);
+    /// ```
+    pub fn from_report<C: ?Sized, O, T>(report: ReportRef<'_, C, O, T>) -> Self {
+        let mut locations = Vec::new();
+        for report in report.iter_primary_chain() {
+            push_locations(&mut locations, report.attachments());
+        }
+
+        locations.reverse();
+        Self { locations }
+    }
+}
+
+fn push_locations<T>(locations: &mut Vec<Location>, attachments: &ReportAttachments<T>) {
+    locations.extend(
+        attachments
+            .iter()
+            .filter_map(|attachment| attachment.downcast_inner::<Location>())
+            .copied(),
+    );
+}
+
+/// Formats the trail with one numbered call site per line, starting with the
+/// origin of the error.
+impl fmt::Display for LocationTrail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, location) in self.locations.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{index}: {location}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Handler for formatting [`LocationTrail`] attachments.
+///
+/// The trail is displayed inline with a `Location trail` header, and hidden
+/// if it is empty.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     hooks::builtin_hooks::location_trail::{LocationTrail, LocationTrailHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("error");
+/// let trail = LocationTrail::from_report(report.as_ref());
+/// let report = report.attach_custom::<LocationTrailHandler, _>(trail);
+/// ```
+#[derive(Copy, Clone)]
+pub struct LocationTrailHandler;
+
+impl AttachmentHandler<LocationTrail> for LocationTrailHandler {
+    fn display(value: &LocationTrail, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &LocationTrail, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, f)
+    }
+
+    fn preferred_formatting_style(
+        value: &LocationTrail,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: if value.locations.is_empty() {
+                AttachmentFormattingPlacement::Hidden
+            } else {
+                AttachmentFormattingPlacement::InlineWithHeader {
+                    header: "Location trail",
+                }
+            },
+            function: FormattingFunction::Display,
+            priority: 10,
+        }
+    }
+}
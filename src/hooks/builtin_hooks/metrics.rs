@@ -0,0 +1,165 @@
+//! Counting created reports for observability.
+//!
+//! [`MetricsHook`] is a report creation hook that counts every report created
+//! while it is installed, grouped by the type of the context of the report.
+//! The counters are global and updated atomically, so they can be read at any
+//! time with [`MetricsHook::snapshot`], for instance to export them to a
+//! metrics system. Requires the `std` feature and is not enabled by default.
+//!
+//! Every created report is counted, including the reports created by
+//! [`Report::context`](crate::Report::context) when wrapping another report.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{
+//!     hooks::{Hooks, builtin_hooks::metrics::MetricsHook},
+//!     prelude::*,
+//! };
+//!
+//! Hooks::new()
+//!     .report_creation_hook(MetricsHook)
+//!     .install()
+//!     .expect("failed to install hooks");
+//!
+//! let _ = report!("connection refused").context("failed to load user");
+//! let _ = report!("timeout");
+//!
+//! let snapshot = MetricsHook::snapshot();
+//! assert_eq!(snapshot, [("&str", 3)]);
+//! ```
+
+use alloc::vec::Vec;
+use core::{
+    any::TypeId,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use std::sync::{PoisonError, RwLock};
+
+use crate::{
+    ReportMut,
+    hooks::report_creation::ReportCreationHook,
+    markers::{Dynamic, Local, SendSync},
+};
+
+/// The number of reports created for a context type.
+struct Counter {
+    type_id: TypeId,
+    type_name: &'static str,
+    count: AtomicU64,
+}
+
+/// The counters, in the order the context types were first seen.
+static COUNTERS: RwLock<Vec<Counter>> = RwLock::new(Vec::new());
+
+/// Increments the counter of the given context type.
+///
+/// Only the first report of each context type takes the write lock; the
+/// others increment their counter while holding the read lock.
+fn increment(type_id: TypeId, type_name: &'static str) {
+    {
+        let counters = COUNTERS.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(counter) = counters.iter().find(|counter| counter.type_id == type_id) {
+            counter.count.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+    }
+
+    let mut counters = COUNTERS.write().unwrap_or_else(PoisonError::into_inner);
+    if let Some(counter) = counters.iter().find(|counter| counter.type_id == type_id) {
+        counter.count.fetch_add(1, Ordering::Relaxed);
+    } else {
+        counters.push(Counter {
+            type_id,
+            type_name,
+            count: AtomicU64::new(1),
+        });
+    }
+}
+
+/// Report creation hook counting the created reports by context type.
+///
+/// See the [module-level documentation](self) for more information.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::{Hooks, builtin_hooks::metrics::MetricsHook};
+///
+/// Hooks::new()
+///     .report_creation_hook(MetricsHook)
+///     .install()
+///     .expect("failed to install hooks");
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct MetricsHook;
+
+impl MetricsHook {
+    /// Returns the number of reports created so far for each context type,
+    /// as pairs of the type name and the count.
+    ///
+    /// The counters are shared by all installed [`MetricsHook`]s, and are
+    /// returned in the order the context types were first seen. Context types
+    /// for which no report was created are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::metrics::MetricsHook;
+    ///
+    /// // Nothing is counted until the hook is installed
+    /// assert!(MetricsHook::snapshot().is_empty());
+    /// ```
+    #[must_use]
+    pub fn snapshot() -> Vec<(&'static str, u64)> {
+        COUNTERS
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|counter| (counter.type_name, counter.count.load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+impl ReportCreationHook for MetricsHook {
+    fn on_local_creation(&self, report: ReportMut<'_, Dynamic, Local>) {
+        increment(
+            report.current_context_type_id(),
+            report.current_context_type_name(),
+        );
+    }
+
+    fn on_sendsync_creation(&self, report: ReportMut<'_, Dynamic, SendSync>) {
+        increment(
+            report.current_context_type_id(),
+            report.current_context_type_name(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_by_context_type() {
+        struct First;
+        struct Second;
+
+        let mut first = crate::report!(First).into_dynamic();
+        let mut second = crate::report!(Second).into_dynamic().into_local();
+        MetricsHook.on_sendsync_creation(first.as_mut());
+        MetricsHook.on_sendsync_creation(first.as_mut());
+        MetricsHook.on_local_creation(second.as_mut());
+
+        let snapshot = MetricsHook::snapshot();
+        let count = |name: &str| {
+            snapshot
+                .iter()
+                .find(|(type_name, _)| type_name.ends_with(name))
+                .map(|(_, count)| *count)
+        };
+        assert_eq!(count("::First"), Some(2));
+        assert_eq!(count("::Second"), Some(1));
+    }
+}
@@ -20,6 +20,51 @@
 //!
 //! [`Location`]: crate::hooks::builtin_hooks::location::Location
 //!
+//! ## Location Trail
+//!
+//! - **[`location_trail`]**: Collects the captured locations along the chain
+//!   of reports into a [`LocationTrail`], a lightweight pseudo-backtrace that
+//!   works without `std` or the `backtrace` crate.
+//!
+//! [`LocationTrail`]: crate::hooks::builtin_hooks::location_trail::LocationTrail
+//!
+//! ## Timestamps
+//!
+//! - **[`timestamp`]**: A [`Timestamp`] attachment recording when a report was
+//!   created, and a [`TimestampCollector`] to add one to every report. Not
+//!   enabled by default.
+//!
+//! [`Timestamp`]: crate::hooks::builtin_hooks::timestamp::Timestamp
+//! [`TimestampCollector`]: crate::hooks::builtin_hooks::timestamp::TimestampCollector
+//!
+//! ## Environment Variables
+//!
+//! - **[`env`](mod@env)**: An [`EnvSnapshot`] attachment recording selected environment
+//!   variables, and an [`EnvCollector`] to add one to every report. Requires
+//!   the `std` feature and is not enabled by default.
+//!
+//! [`EnvSnapshot`]: crate::hooks::builtin_hooks::env::EnvSnapshot
+//! [`EnvCollector`]: crate::hooks::builtin_hooks::env::EnvCollector
+//!
+//! ## Process Information
+//!
+//! - **[`process`]**: A [`ProcessInfo`] attachment recording the id,
+//!   executable and arguments of the current process, and a
+//!   [`ProcessInfoCollector`] to add one to every report. Requires the `std`
+//!   feature and is not enabled by default.
+//!
+//! [`ProcessInfo`]: crate::hooks::builtin_hooks::process::ProcessInfo
+//! [`ProcessInfoCollector`]: crate::hooks::builtin_hooks::process::ProcessInfoCollector
+//!
+//! ## Metrics
+//!
+//! - **[`metrics`]**: A [`MetricsHook`] counting the created reports by context
+//!   type, with the counts available from [`MetricsHook::snapshot`]. Requires
+//!   the `std` feature and is not enabled by default.
+//!
+//! [`MetricsHook`]: crate::hooks::builtin_hooks::metrics::MetricsHook
+//! [`MetricsHook::snapshot`]: crate::hooks::builtin_hooks::metrics::MetricsHook::snapshot
+//!
 //! ## Report Formatter
 //!
 //! - **[`report_formatter`]**: Controls the overall report layout and styling.
@@ -33,5 +78,16 @@
 //! [`ReportFormatter`]: crate::hooks::report_formatter::ReportFormatter
 //! [`Hooks::report_formatter`]: crate::hooks::Hooks::report_formatter
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod env;
 pub mod location;
+pub mod location_trail;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod metrics;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod process;
 pub mod report_formatter;
+pub mod timestamp;
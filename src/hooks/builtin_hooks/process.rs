@@ -0,0 +1,383 @@
+//! Process information for crash reports.
+//!
+//! A [`ProcessInfo`] records the id of the current process, the path of its
+//! executable and its command-line arguments. It can be added to a single
+//! report with [`Report::attach_process_info`], or to every report by
+//! installing a [`ProcessInfoCollector`] as an attachment collector.
+//!
+//! The information is displayed in a `Process` appendix, with the names
+//! aligned so that the values line up.
+//!
+//! # Sensitive Arguments
+//!
+//! Command-line arguments can contain secrets, such as passwords or tokens
+//! passed as flags. [`ProcessInfo::capture_with`],
+//! [`Report::attach_process_info_with`] and
+//! [`ProcessInfoCollector::with_redaction`] take a predicate that is called
+//! with the previous argument and the argument itself, and replace the
+//! arguments for which it returns `true` with `<redacted>`. Passing the
+//! previous argument allows redacting the value of a flag given as a separate
+//! argument, as in `--password hunter2`, and [`redact_flags`] creates such a
+//! predicate for a list of flags. Arguments are captured as they are, so make
+//! sure to redact them if reports can leave the process, for instance when
+//! they are sent to an error tracking service.
+//!
+//! [`Report::attach_process_info`]: crate::Report::attach_process_info
+//! [`Report::attach_process_info_with`]: crate::Report::attach_process_info_with
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{hooks::builtin_hooks::process::redact_flags, prelude::*};
+//!
+//! let report =
+//!     report!("worker crashed").attach_process_info_with(redact_flags(&["--token"]));
+//! let output = report.to_string();
+//! assert!(output.contains("Process"));
+//! assert!(output.contains(&format!("pid  = {}", std::process::id())));
+//! ```
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
+
+use rootcause_internals::handlers::{
+    AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler, FormattingFunction,
+};
+
+use crate::{handlers::JsonSink, hooks::report_creation::AttachmentCollector};
+
+/// The text replacing redacted arguments.
+const REDACTED: &str = "<redacted>";
+
+/// Decides whether an argument should be redacted, given the previous argument
+/// and the argument.
+type RedactFn = Box<dyn Fn(Option<&str>, &str) -> bool + Send + Sync>;
+
+/// The id, executable and arguments of a process.
+///
+/// Values that are not valid Unicode are converted lossily.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::builtin_hooks::process::ProcessInfo;
+///
+/// let info = ProcessInfo::capture();
+/// assert_eq!(info.pid, std::process::id());
+/// assert!(!info.args.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProcessInfo {
+    /// The id of the process
+    pub pid: u32,
+    /// The path of the executable, if it could be determined
+    pub exe: Option<String>,
+    /// The command-line arguments, starting with the program name
+    pub args: Vec<String>,
+}
+
+impl ProcessInfo {
+    /// Captures the information of the current process, including all of its
+    /// arguments.
+    ///
+    /// See [`ProcessInfo::capture_with`] for redacting sensitive arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::process::ProcessInfo;
+    ///
+    /// let info = ProcessInfo::capture();
+    /// println!("{info}");
+    /// ```
+    #[must_use]
+    pub fn capture() -> Self {
+        Self::capture_with(|_, _| false)
+    }
+
+    /// Captures the information of the current process, replacing the
+    /// arguments for which `redact` returns `true` with `<redacted>`.
+    ///
+    /// `redact` is called with the previous argument, or `None` for the
+    /// program name, and the argument itself. The previous argument is the
+    /// original one, even if it was redacted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::process::ProcessInfo;
+    ///
+    /// let info = ProcessInfo::capture_with(|_, _| true);
+    /// assert!(info.args.iter().all(|arg| arg == "<redacted>"));
+    ///
+    /// // Redacts the argument following `--password`
+    /// let info = ProcessInfo::capture_with(|previous, _| previous == Some("--password"));
+    /// ```
+    #[must_use]
+    pub fn capture_with(redact: impl Fn(Option<&str>, &str) -> bool) -> Self {
+        let exe = std::env::current_exe()
+            .ok()
+            .map(|path| path.to_string_lossy().into_owned());
+        let args = std::env::args_os()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        Self {
+            pid: std::process::id(),
+            exe,
+            args: redact_args(args, redact),
+        }
+    }
+}
+
+/// Replaces the arguments for which `redact` returns `true` with
+/// `<redacted>`.
+fn redact_args(mut args: Vec<String>, redact: impl Fn(Option<&str>, &str) -> bool) -> Vec<String> {
+    let redacted: Vec<bool> = args
+        .iter()
+        .enumerate()
+        .map(|(index, arg)| {
+            let previous = index.checked_sub(1).map(|index| args[index].as_str());
+            redact(previous, arg)
+        })
+        .collect();
+    for (arg, redacted) in args.iter_mut().zip(redacted) {
+        if redacted {
+            *arg = String::from(REDACTED);
+        }
+    }
+    args
+}
+
+/// Creates a predicate redacting the values of the given flags.
+///
+/// The value is redacted both when it is part of the argument, as in
+/// `--password=hunter2`, in which case the whole argument is redacted, and
+/// when it is the following argument, as in `--password hunter2`. In the
+/// latter case the argument following the flag is always redacted, even if it
+/// is another flag.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::builtin_hooks::process::{ProcessInfo, redact_flags};
+///
+/// let info = ProcessInfo::capture_with(redact_flags(&["--password", "--token"]));
+/// ```
+pub fn redact_flags<'a>(
+    flags: &'a [&'a str],
+) -> impl Fn(Option<&str>, &str) -> bool + Send + Sync + 'a {
+    move |previous, arg| {
+        flags.iter().any(|&flag| {
+            previous == Some(flag)
+                || arg
+                    .strip_prefix(flag)
+                    .is_some_and(|rest| rest.starts_with('='))
+        })
+    }
+}
+
+/// Formats aligned `pid = ...`, `exe = ...` and `args = ...` lines, with the
+/// arguments separated by spaces.
+impl fmt::Display for ProcessInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "pid  = {}", self.pid)?;
+        match &self.exe {
+            Some(exe) => writeln!(f, "exe  = {exe}")?,
+            None => writeln!(f, "exe  = <unknown>")?,
+        }
+        write!(f, "args =")?;
+        for arg in &self.args {
+            write!(f, " {arg}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Handler for formatting [`ProcessInfo`] attachments.
+///
+/// The information is displayed in a `Process` appendix, with one aligned
+/// `name = value` line per field. When emitted as JSON, it is written as an
+/// object with the `pid`, `exe` and `args` fields.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     hooks::builtin_hooks::process::{ProcessInfo, ProcessInfoHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("error").attach_custom::<ProcessInfoHandler, _>(ProcessInfo::capture());
+/// ```
+#[derive(Copy, Clone)]
+pub struct ProcessInfoHandler;
+
+impl AttachmentHandler<ProcessInfo> for ProcessInfoHandler {
+    fn display(value: &ProcessInfo, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &ProcessInfo, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, f)
+    }
+
+    fn json(value: &ProcessInfo, sink: &mut dyn JsonSink) -> fmt::Result {
+        sink.begin_object()?;
+        sink.key("pid")?;
+        sink.u64(u64::from(value.pid))?;
+        sink.key("exe")?;
+        match &value.exe {
+            Some(exe) => sink.str(exe)?,
+            None => sink.null()?,
+        }
+        sink.key("args")?;
+        sink.begin_array()?;
+        for arg in &value.args {
+            sink.str(arg)?;
+        }
+        sink.end_array()?;
+        sink.end_object()
+    }
+
+    fn preferred_formatting_style(
+        _value: &ProcessInfo,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::Appendix {
+                appendix_name: "Process",
+            },
+            function: FormattingFunction::Display,
+            priority: 0,
+        }
+    }
+}
+
+/// Attachment collector adding a [`ProcessInfo`] to every report.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::{Hooks, builtin_hooks::process::ProcessInfoCollector};
+///
+/// Hooks::new()
+///     .attachment_collector(ProcessInfoCollector::with_redaction(|previous, arg| {
+///         previous == Some("--password") || arg.starts_with("--password=")
+///     }))
+///     .install()
+///     .ok();
+/// ```
+#[derive(Default)]
+pub struct ProcessInfoCollector {
+    redact: Option<RedactFn>,
+}
+
+impl ProcessInfoCollector {
+    /// Creates a collector capturing all arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::process::ProcessInfoCollector;
+    ///
+    /// let collector = ProcessInfoCollector::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a collector replacing the arguments for which `redact`
+    /// returns `true` with `<redacted>`.
+    ///
+    /// See [`ProcessInfo::capture_with`] for the arguments of `redact`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::process::{ProcessInfoCollector, redact_flags};
+    ///
+    /// let collector = ProcessInfoCollector::with_redaction(redact_flags(&["--token"]));
+    /// ```
+    #[must_use]
+    pub fn with_redaction<F>(redact: F) -> Self
+    where
+        F: Fn(Option<&str>, &str) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            redact: Some(Box::new(redact)),
+        }
+    }
+}
+
+impl fmt::Debug for ProcessInfoCollector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProcessInfoCollector")
+            .field("redact", &self.redact.is_some())
+            .finish()
+    }
+}
+
+impl AttachmentCollector<ProcessInfo> for ProcessInfoCollector {
+    type Handler = ProcessInfoHandler;
+
+    fn collect(&self) -> ProcessInfo {
+        match &self.redact {
+            Some(redact) => ProcessInfo::capture_with(redact),
+            None => ProcessInfo::capture(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use super::*;
+
+    #[test]
+    fn test_display_aligns_names() {
+        let info = ProcessInfo {
+            pid: 42,
+            exe: None,
+            args: vec!["app".into(), "--verbose".into()],
+        };
+        assert_eq!(
+            info.to_string(),
+            "pid  = 42\nexe  = <unknown>\nargs = app --verbose"
+        );
+    }
+
+    #[test]
+    fn test_collector_redacts_args() {
+        let collector = ProcessInfoCollector::with_redaction(|_, _| true);
+        let info = collector.collect();
+        assert_eq!(info.args.len(), std::env::args_os().len());
+        assert!(info.args.iter().all(|arg| arg == REDACTED));
+    }
+
+    #[test]
+    fn test_redact_flags() {
+        let args = [
+            "app",
+            "--password",
+            "hunter2",
+            "--token=secret",
+            "--tokenizer=simple",
+            "--verbose",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(
+            redact_args(args, redact_flags(&["--password", "--token"])),
+            [
+                "app",
+                "--password",
+                REDACTED,
+                REDACTED,
+                "--tokenizer=simple",
+                "--verbose"
+            ]
+        );
+    }
+}
@@ -44,18 +44,23 @@
 //!
 //! The formatter uses a hierarchy of configuration structures:
 //! - [`LineFormatting`] - Basic line prefix/suffix formatting
+//! - [`AttachmentBadge`] - Attachments rendered as badges in front of the
+//!   context of their report
 //! - [`ItemFormatting`] - Multi-line item formatting with different rules for
 //!   first/middle/last lines
 //! - [`NodeConfig`] - Hierarchical node formatting with header and child
 //!   indentation
+//! - [`TreeCharset`] - The characters used to draw the report tree, which can
+//!   be applied to any configuration
 //! - [`DefaultReportFormatter`] - Main configuration struct combining all
 //!   formatting options
 
 use alloc::{
+    boxed::Box,
     string::{String, ToString},
     vec::Vec,
 };
-use core::fmt::{self, Formatter, Write};
+use core::fmt::{self, Write};
 
 use indexmap::IndexMap;
 use rootcause_internals::handlers::{
@@ -64,9 +69,12 @@ use rootcause_internals::handlers::{
 
 use crate::{
     ReportRef,
+    error_code::ErrorCode,
     hooks::report_formatter::ReportFormatter,
+    key_value::KeyValue,
     markers::{Dynamic, Local, Uncloneable},
     report_attachment::ReportAttachmentRef,
+    severity::Severity,
 };
 
 /// The default report formatter implementation that provides comprehensive
@@ -111,11 +119,86 @@ use crate::{
 ///     .ok();
 /// // Use in environments without Unicode/ANSI support
 /// ```
+///
+/// Prefixing every line, for instance to embed the output in a quoted block:
+/// ```
+/// use rootcause::{hooks::builtin_hooks::report_formatter::DefaultReportFormatter, prelude::*};
+///
+/// let formatter = DefaultReportFormatter {
+///     line_prefix: "| ",
+///     ..DefaultReportFormatter::ASCII
+/// };
+/// let report = report!("error message").attach("first line\nsecond line");
+/// let output = report.format_with(&formatter).to_string();
+/// assert!(output.lines().all(|line| line.starts_with("| ")));
+/// ```
+///
+/// The keys of the inline [`KeyValue`] attachments of a report are padded to
+/// the same width, so their values line up:
+/// ```
+/// use rootcause::{hooks::builtin_hooks::report_formatter::DefaultReportFormatter, prelude::*};
+///
+/// let report = report!("request failed")
+///     .attach_kv("user_id", 42)
+///     .attach_kv("path", "/api/orders");
+/// let output = report.format_with(&DefaultReportFormatter::ASCII).to_string();
+/// assert!(output.contains("user_id: 42"));
+/// assert!(output.contains("path   : /api/orders"));
+/// ```
+///
+/// Limiting the size of the rendered tree:
+/// ```
+/// use rootcause::{hooks::builtin_hooks::report_formatter::DefaultReportFormatter, prelude::*};
+///
+/// let mut report = report!("batch failed");
+/// for i in 0..5 {
+///     report
+///         .children_mut()
+///         .push(report!("item {i} failed").into_cloneable());
+/// }
+/// let formatter = DefaultReportFormatter {
+///     max_children_per_node: Some(2),
+///     ..DefaultReportFormatter::ASCII
+/// };
+/// let output = report.format_with(&formatter).to_string();
+/// assert!(output.contains("item 1 failed"));
+/// assert!(!output.contains("item 2 failed"));
+/// assert!(output.contains("|--> ... 3 more children"));
+/// ```
+///
+/// Rendering the single-line summary with `{}` and the full tree with `{:#}`:
+/// ```
+/// use rootcause::{
+///     hooks::builtin_hooks::report_formatter::{AlternateDisplay, DefaultReportFormatter},
+///     prelude::*,
+/// };
+///
+/// let formatter = DefaultReportFormatter {
+///     alternate_display: AlternateDisplay::Swapped,
+///     ..DefaultReportFormatter::ASCII
+/// };
+/// let report = report!("connection refused").context("failed to load user");
+/// let formatted = report.format_with(&formatter);
+/// assert_eq!(
+///     format!("{formatted}"),
+///     "failed to load user: connection refused"
+/// );
+/// assert!(format!("{formatted:#}").contains("connection refused\n"));
+/// ```
 #[derive(Debug)]
 pub struct DefaultReportFormatter {
     /// Header text displayed at the beginning of report output
     pub report_header: &'static str,
 
+    /// Prefix applied to every emitted line of the output, before any other
+    /// formatting
+    ///
+    /// Unlike [`report_line_prefix_always`](Self::report_line_prefix_always),
+    /// this is also applied to the header, the separators, the appendices and
+    /// every line of multi-line attachments. This is useful when embedding the
+    /// output in a larger document, for instance as a quoted block.
+    pub line_prefix: &'static str,
+
     /// Prefix applied to every line of report content
     pub report_line_prefix_always: &'static str,
 
@@ -133,6 +216,16 @@ pub struct DefaultReportFormatter {
     /// than one sibling
     pub report_node_last_formatting: NodeConfig,
 
+    /// The attachments rendered as badges in front of the context of report
+    /// nodes, in the order of the badges, such as
+    /// [`AttachmentBadge::severity`] and [`AttachmentBadge::error_code`]
+    ///
+    /// Attachments that are rendered as a badge are not displayed as regular
+    /// [`Inline`] attachments.
+    ///
+    /// [`Inline`]: AttachmentFormattingPlacement::Inline
+    pub attachment_badges: &'static [AttachmentBadge],
+
     /// Formatting configuration for attachment items that output in [`Inline`]
     /// mode and have more data below them from the same report node
     ///
@@ -215,6 +308,113 @@ pub struct DefaultReportFormatter {
     /// [`Opaque`]: AttachmentFormattingPlacement::Opaque
     pub notice_opaque_last_formatting: LineFormatting,
 
+    /// The formatting for the `... N more levels` and `... N more children`
+    /// notices, which replace the child reports omitted because of
+    /// [`max_depth`](Self::max_depth) or
+    /// [`max_children_per_node`](Self::max_children_per_node)
+    pub notice_truncated_formatting: LineFormatting,
+
+    /// The maximum depth of child reports to render, or `None` to render the
+    /// whole tree
+    ///
+    /// The reports being formatted are at depth 0, their children at depth 1,
+    /// and so on. The children of a report at the maximum depth are replaced
+    /// by a single `... N more levels` notice, where `N` is the depth of the
+    /// omitted subtree.
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of child reports to render for each report node, or
+    /// `None` to render all of them
+    ///
+    /// The remaining children are replaced by a single `... N more children`
+    /// notice after the last rendered child.
+    pub max_children_per_node: Option<usize>,
+
+    /// How reports are rendered with [`Display`](fmt::Display) depending on
+    /// the alternate flag, i.e. `{}` versus `{:#}`
+    ///
+    /// The presets use [`AlternateDisplay::Full`], so both `{}` and `{:#}`
+    /// render the full tree unless this is changed. [`Debug`](fmt::Debug)
+    /// output, including `{:#?}`, always renders the full tree.
+    ///
+    /// When the full tree is rendered with the alternate flag, attachments
+    /// placed [`Collapsed`](AttachmentFormattingPlacement::Collapsed) are
+    /// shown in full. Otherwise only their header and summary are shown,
+    /// followed by a hint naming the format specifier that expands them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{
+    ///     handlers::{
+    ///         AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler,
+    ///         FormattingFunction,
+    ///     },
+    ///     prelude::*,
+    /// };
+    ///
+    /// struct Dump(&'static str);
+    ///
+    /// struct DumpHandler;
+    /// impl AttachmentHandler<Dump> for DumpHandler {
+    ///     fn display(value: &Dump, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         f.write_str(value.0)
+    ///     }
+    ///
+    ///     fn debug(value: &Dump, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         f.write_str(value.0)
+    ///     }
+    ///
+    ///     fn preferred_formatting_style(
+    ///         _value: &Dump,
+    ///         _report_formatting_function: FormattingFunction,
+    ///     ) -> AttachmentFormattingStyle {
+    ///         AttachmentFormattingStyle {
+    ///             placement: AttachmentFormattingPlacement::Collapsed {
+    ///                 header: "Dump",
+    ///                 summary: "3 lines",
+    ///             },
+    ///             ..Default::default()
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let report = report!("parse failed")
+    ///     .attach_custom::<DumpHandler, _>(Dump("first\nsecond\nthird"));
+    ///
+    /// let collapsed = format!("{report}");
+    /// assert!(collapsed.contains("Dump: 3 lines (use {:#} to expand)"));
+    /// assert!(!collapsed.contains("second"));
+    ///
+    /// let expanded = format!("{report:#}");
+    /// assert!(expanded.contains("second"));
+    /// assert!(!expanded.contains("to expand"));
+    ///
+    /// let collapsed = format!("{report:?}");
+    /// assert!(collapsed.contains("Dump: 3 lines (use {:#?} to expand)"));
+    ///
+    /// let expanded = format!("{report:#?}");
+    /// assert!(expanded.contains("second"));
+    /// ```
+    pub alternate_display: AlternateDisplay,
+
+    /// Optional callback writing custom content before the report tree, and
+    /// before the [`report_header`](Self::report_header)
+    ///
+    /// The callback is invoked once per formatting pass, with the same
+    /// [`fmt::Formatter`] that the tree is written to. Its output is not
+    /// prefixed with the [`line_prefix`](Self::line_prefix). It is not invoked
+    /// when rendering the single-line summary selected by
+    /// [`alternate_display`](Self::alternate_display).
+    pub custom_header: Option<FormatCallback>,
+
+    /// Optional callback writing custom content after the report tree,
+    /// including its appendices
+    ///
+    /// This is invoked in the same way as
+    /// [`custom_header`](Self::custom_header).
+    pub custom_footer: Option<FormatCallback>,
+
     /// Optional separator inserted before child contexts
     pub pre_child_separator: Option<&'static str>,
 
@@ -245,6 +445,25 @@ pub struct DefaultReportFormatter {
     /// Separator text inserted between multiple reports
     pub report_report_separator: &'static str,
 
+    /// Separator text inserted between report content and the footer section
+    ///
+    /// The footer section contains the attachments that output in [`Footer`]
+    /// mode, and is only rendered if there is at least one such attachment.
+    ///
+    /// [`Footer`]: AttachmentFormattingPlacement::Footer
+    pub report_footer_separator: &'static str,
+
+    /// Formatting for the label of attachments that output in [`Footer`] mode
+    ///
+    /// [`Footer`]: AttachmentFormattingPlacement::Footer
+    pub footer_label: LineFormatting,
+
+    /// Formatting for attachments that output in [`Footer`] mode. The first
+    /// line starts with the formatted label.
+    ///
+    /// [`Footer`]: AttachmentFormattingPlacement::Footer
+    pub footer_item: ItemFormatting,
+
     /// Separator text inserted between report content and appendices
     pub report_appendix_separator: &'static str,
 
@@ -286,6 +505,7 @@ impl DefaultReportFormatter {
     /// ```
     pub const ASCII: Self = Self {
         report_header: "\n",
+        line_prefix: "",
         report_line_prefix_always: "",
         appendix_line_prefix_always: "",
         report_node_standalone_formatting: NodeConfig::new(
@@ -309,6 +529,10 @@ impl DefaultReportFormatter {
             ("     |  ", "\n"),
             "     ",
         ),
+        attachment_badges: &[
+            AttachmentBadge::severity(LineFormatting::new("[", "] ")),
+            AttachmentBadge::error_code(LineFormatting::new("[", "] ")),
+        ],
         attachment_inline_formatting_middle: ItemFormatting::new(
             ("|- ", "\n"),
             ("|- ", "\n"),
@@ -347,6 +571,12 @@ impl DefaultReportFormatter {
         notice_see_also_last_formatting: LineFormatting::new(r"|- See ", " below\n"),
         notice_opaque_middle_formatting: LineFormatting::new("|- ", "\n"),
         notice_opaque_last_formatting: LineFormatting::new(r"|- ", "\n"),
+        notice_truncated_formatting: LineFormatting::new("|--> ", "\n"),
+        max_depth: None,
+        max_children_per_node: None,
+        alternate_display: AlternateDisplay::Full,
+        custom_header: None,
+        custom_footer: None,
         pre_child_separator: None,
         child_child_separator: None,
         source_chain_header_middle_formatting: NodeConfig::new(
@@ -378,6 +608,9 @@ impl DefaultReportFormatter {
         source_chain_omitted_formatting: LineFormatting::new("|- note: ", "\n"),
         source_chain_separator: None,
         report_report_separator: "--\n",
+        report_footer_separator: "\n",
+        footer_label: LineFormatting::new("", ": "),
+        footer_item: ItemFormatting::new(("", "\n"), ("", "\n"), ("  ", "\n"), ("  ", "\n")),
         report_appendix_separator: "----------------------------------------\n",
         appendix_appendix_separator: "----------------------------------------\n",
         appendix_header: LineFormatting::new(" ", "\n\n"),
@@ -401,6 +634,7 @@ impl DefaultReportFormatter {
     /// environments).
     pub const UNICODE: Self = Self {
         report_header: "\n",
+        line_prefix: "",
         report_line_prefix_always: " ",
         appendix_line_prefix_always: "",
         report_node_standalone_formatting: NodeConfig::new(
@@ -424,6 +658,10 @@ impl DefaultReportFormatter {
             ("   │ ", "\n"),
             "   ",
         ),
+        attachment_badges: &[
+            AttachmentBadge::severity(LineFormatting::new("[", "] ")),
+            AttachmentBadge::error_code(LineFormatting::new("[", "] ")),
+        ],
         attachment_inline_formatting_middle: ItemFormatting::new(
             ("├ ", "\n"),
             ("├ ", "\n"),
@@ -462,6 +700,12 @@ impl DefaultReportFormatter {
         notice_see_also_last_formatting: LineFormatting::new("╰ See ", " below\n"),
         notice_opaque_middle_formatting: LineFormatting::new("├ ", "\n"),
         notice_opaque_last_formatting: LineFormatting::new("╰ ", "\n"),
+        notice_truncated_formatting: LineFormatting::new("╰─ ", "\n"),
+        max_depth: None,
+        max_children_per_node: None,
+        alternate_display: AlternateDisplay::Full,
+        custom_header: None,
+        custom_footer: None,
         pre_child_separator: Some("│\n"),
         child_child_separator: Some("│\n"),
         source_chain_header_middle_formatting: NodeConfig::new(
@@ -493,6 +737,9 @@ impl DefaultReportFormatter {
         source_chain_omitted_formatting: LineFormatting::new("│ note: ", "\n"),
         source_chain_separator: None,
         report_report_separator: "━━\n",
+        report_footer_separator: "\n",
+        footer_label: LineFormatting::new("", ": "),
+        footer_item: ItemFormatting::new(("", "\n"), ("", "\n"), ("  ", "\n"), ("  ", "\n")),
         report_appendix_separator: "\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n",
         appendix_appendix_separator: "\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n",
         appendix_header: LineFormatting::new(" ", "\n\n"),
@@ -509,6 +756,7 @@ impl DefaultReportFormatter {
     /// environments.
     pub const UNICODE_COLORS: Self = Self {
         report_header: "\n",
+        line_prefix: "",
         report_line_prefix_always: " ",
         appendix_line_prefix_always: "",
         report_node_standalone_formatting: NodeConfig::new(
@@ -532,6 +780,10 @@ impl DefaultReportFormatter {
             ("   │ \x1b[1;97m", "\x1b[0m\n"),
             "   ",
         ),
+        attachment_badges: &[
+            AttachmentBadge::severity(LineFormatting::new("\x1b[1;31m[", "]\x1b[0m ")),
+            AttachmentBadge::error_code(LineFormatting::new("\x1b[1;33m[", "]\x1b[0m ")),
+        ],
         attachment_inline_formatting_middle: ItemFormatting::new(
             ("├ ", "\n"),
             ("├ ", "\n"),
@@ -570,6 +822,12 @@ impl DefaultReportFormatter {
         notice_see_also_last_formatting: LineFormatting::new("╰ See \x1b[4m", "\x1b[0m below\n"),
         notice_opaque_middle_formatting: LineFormatting::new("├ ", "\n"),
         notice_opaque_last_formatting: LineFormatting::new("╰ ", "\n"),
+        notice_truncated_formatting: LineFormatting::new("╰─ ", "\n"),
+        max_depth: None,
+        max_children_per_node: None,
+        alternate_display: AlternateDisplay::Full,
+        custom_header: None,
+        custom_footer: None,
         pre_child_separator: Some("│\n"),
         child_child_separator: Some("│\n"),
         source_chain_header_middle_formatting: NodeConfig::new(
@@ -601,6 +859,9 @@ impl DefaultReportFormatter {
         source_chain_omitted_formatting: LineFormatting::new("│ note: ", "\n"),
         source_chain_separator: None,
         report_report_separator: "━━\n",
+        report_footer_separator: "\n",
+        footer_label: LineFormatting::new("\x1b[1;36m", ":\x1b[0m "),
+        footer_item: ItemFormatting::new(("", "\n"), ("", "\n"), ("  ", "\n"), ("  ", "\n")),
         report_appendix_separator: "\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n",
         appendix_appendix_separator: "\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n",
         appendix_header: LineFormatting::new(" \x1b[4m", "\x1b[0m\n\n"),
@@ -608,6 +869,100 @@ impl DefaultReportFormatter {
         appendices_footer: "\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n",
         no_appendices_footer: "",
     };
+
+    /// Returns the characters used by this configuration to draw the report
+    /// tree and the separators between its sections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::report_formatter::DefaultReportFormatter;
+    ///
+    /// let charset = DefaultReportFormatter::ASCII.charset();
+    /// assert_eq!(charset.report_report_separator, "--\n");
+    /// ```
+    pub const fn charset(&self) -> TreeCharset {
+        TreeCharset {
+            report_line_prefix_always: self.report_line_prefix_always,
+            report_node_standalone_formatting: self.report_node_standalone_formatting,
+            report_node_middle_formatting: self.report_node_middle_formatting,
+            report_node_last_formatting: self.report_node_last_formatting,
+            attachment_inline_formatting_middle: self.attachment_inline_formatting_middle,
+            attachment_inline_formatting_last: self.attachment_inline_formatting_last,
+            attachment_headered_formatting_middle: self.attachment_headered_formatting_middle,
+            attachment_headered_formatting_last: self.attachment_headered_formatting_last,
+            attachment_headered_formatting_data: self.attachment_headered_formatting_data,
+            attachment_headered_data_prefix: self.attachment_headered_data_prefix,
+            attachment_headered_data_suffix: self.attachment_headered_data_suffix,
+            notice_see_also_middle_formatting: self.notice_see_also_middle_formatting,
+            notice_see_also_last_formatting: self.notice_see_also_last_formatting,
+            notice_opaque_middle_formatting: self.notice_opaque_middle_formatting,
+            notice_opaque_last_formatting: self.notice_opaque_last_formatting,
+            notice_truncated_formatting: self.notice_truncated_formatting,
+            pre_child_separator: self.pre_child_separator,
+            child_child_separator: self.child_child_separator,
+            source_chain_header_middle_formatting: self.source_chain_header_middle_formatting,
+            source_chain_header_last_formatting: self.source_chain_header_last_formatting,
+            source_chain_item_middle_formatting: self.source_chain_item_middle_formatting,
+            source_chain_item_last_formatting: self.source_chain_item_last_formatting,
+            source_chain_omitted_formatting: self.source_chain_omitted_formatting,
+            report_report_separator: self.report_report_separator,
+            report_appendix_separator: self.report_appendix_separator,
+            appendix_appendix_separator: self.appendix_appendix_separator,
+            appendices_footer: self.appendices_footer,
+        }
+    }
+
+    /// Replaces the characters used to draw the report tree and the
+    /// separators between its sections, keeping all other settings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{
+    ///     hooks::builtin_hooks::report_formatter::{DefaultReportFormatter, TreeCharset},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let formatter = DefaultReportFormatter::UNICODE.with_charset(TreeCharset::ASCII);
+    /// let report = report!("connection refused").context("failed to load user");
+    /// let output = report.format_with(&formatter).to_string();
+    /// assert!(output.contains("o  connection refused"));
+    /// assert!(!output.contains('●'));
+    /// ```
+    #[must_use]
+    pub fn with_charset(self, charset: TreeCharset) -> Self {
+        Self {
+            report_line_prefix_always: charset.report_line_prefix_always,
+            report_node_standalone_formatting: charset.report_node_standalone_formatting,
+            report_node_middle_formatting: charset.report_node_middle_formatting,
+            report_node_last_formatting: charset.report_node_last_formatting,
+            attachment_inline_formatting_middle: charset.attachment_inline_formatting_middle,
+            attachment_inline_formatting_last: charset.attachment_inline_formatting_last,
+            attachment_headered_formatting_middle: charset.attachment_headered_formatting_middle,
+            attachment_headered_formatting_last: charset.attachment_headered_formatting_last,
+            attachment_headered_formatting_data: charset.attachment_headered_formatting_data,
+            attachment_headered_data_prefix: charset.attachment_headered_data_prefix,
+            attachment_headered_data_suffix: charset.attachment_headered_data_suffix,
+            notice_see_also_middle_formatting: charset.notice_see_also_middle_formatting,
+            notice_see_also_last_formatting: charset.notice_see_also_last_formatting,
+            notice_opaque_middle_formatting: charset.notice_opaque_middle_formatting,
+            notice_opaque_last_formatting: charset.notice_opaque_last_formatting,
+            notice_truncated_formatting: charset.notice_truncated_formatting,
+            pre_child_separator: charset.pre_child_separator,
+            child_child_separator: charset.child_child_separator,
+            source_chain_header_middle_formatting: charset.source_chain_header_middle_formatting,
+            source_chain_header_last_formatting: charset.source_chain_header_last_formatting,
+            source_chain_item_middle_formatting: charset.source_chain_item_middle_formatting,
+            source_chain_item_last_formatting: charset.source_chain_item_last_formatting,
+            source_chain_omitted_formatting: charset.source_chain_omitted_formatting,
+            report_report_separator: charset.report_report_separator,
+            report_appendix_separator: charset.report_appendix_separator,
+            appendix_appendix_separator: charset.appendix_appendix_separator,
+            appendices_footer: charset.appendices_footer,
+            ..self
+        }
+    }
 }
 
 impl Default for DefaultReportFormatter {
@@ -616,6 +971,470 @@ impl Default for DefaultReportFormatter {
     }
 }
 
+/// The characters used by [`DefaultReportFormatter`] to draw the report tree
+/// and the separators between its sections.
+///
+/// This groups the fields of [`DefaultReportFormatter`] that only affect the
+/// drawing characters, such as the connectors, bullets and branches of the
+/// tree, so a custom glyph set can be defined once and applied to any
+/// configuration using [`DefaultReportFormatter::with_charset`]. The current
+/// characters of a configuration are returned by
+/// [`DefaultReportFormatter::charset`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     hooks::builtin_hooks::report_formatter::{
+///         DefaultReportFormatter, LineFormatting, NodeConfig, TreeCharset,
+///     },
+///     prelude::*,
+/// };
+///
+/// const HEAVY: TreeCharset = TreeCharset {
+///     report_node_middle_formatting: NodeConfig::new(
+///         ("┣━ ■ ", "\n"),
+///         ("┣━ ■ ", "\n"),
+///         ("┃  ┃ ", "\n"),
+///         ("┃  ┃ ", "\n"),
+///         "┃  ",
+///     ),
+///     report_node_last_formatting: NodeConfig::new(
+///         ("┗━ ■ ", "\n"),
+///         ("┗━ ■ ", "\n"),
+///         ("   ┃ ", "\n"),
+///         ("   ┃ ", "\n"),
+///         "   ",
+///     ),
+///     notice_truncated_formatting: LineFormatting::new("┗━ ", "\n"),
+///     ..TreeCharset::UNICODE
+/// };
+///
+/// let formatter = DefaultReportFormatter::UNICODE.with_charset(HEAVY);
+/// let report = report!("connection refused").join(report!("cache unavailable"), "request failed");
+/// let output = report.format_with(&formatter).to_string();
+/// assert!(output.contains("┣━ ■ connection refused"));
+/// assert!(output.contains("┗━ ■ cache unavailable"));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct TreeCharset {
+    /// See [`DefaultReportFormatter::report_line_prefix_always`]
+    pub report_line_prefix_always: &'static str,
+
+    /// See [`DefaultReportFormatter::report_node_standalone_formatting`]
+    pub report_node_standalone_formatting: NodeConfig,
+
+    /// See [`DefaultReportFormatter::report_node_middle_formatting`]
+    pub report_node_middle_formatting: NodeConfig,
+
+    /// See [`DefaultReportFormatter::report_node_last_formatting`]
+    pub report_node_last_formatting: NodeConfig,
+
+    /// See [`DefaultReportFormatter::attachment_inline_formatting_middle`]
+    pub attachment_inline_formatting_middle: ItemFormatting,
+
+    /// See [`DefaultReportFormatter::attachment_inline_formatting_last`]
+    pub attachment_inline_formatting_last: ItemFormatting,
+
+    /// See [`DefaultReportFormatter::attachment_headered_formatting_middle`]
+    pub attachment_headered_formatting_middle: NodeConfig,
+
+    /// See [`DefaultReportFormatter::attachment_headered_formatting_last`]
+    pub attachment_headered_formatting_last: NodeConfig,
+
+    /// See [`DefaultReportFormatter::attachment_headered_formatting_data`]
+    pub attachment_headered_formatting_data: ItemFormatting,
+
+    /// See [`DefaultReportFormatter::attachment_headered_data_prefix`]
+    pub attachment_headered_data_prefix: Option<&'static str>,
+
+    /// See [`DefaultReportFormatter::attachment_headered_data_suffix`]
+    pub attachment_headered_data_suffix: Option<&'static str>,
+
+    /// See [`DefaultReportFormatter::notice_see_also_middle_formatting`]
+    pub notice_see_also_middle_formatting: LineFormatting,
+
+    /// See [`DefaultReportFormatter::notice_see_also_last_formatting`]
+    pub notice_see_also_last_formatting: LineFormatting,
+
+    /// See [`DefaultReportFormatter::notice_opaque_middle_formatting`]
+    pub notice_opaque_middle_formatting: LineFormatting,
+
+    /// See [`DefaultReportFormatter::notice_opaque_last_formatting`]
+    pub notice_opaque_last_formatting: LineFormatting,
+
+    /// See [`DefaultReportFormatter::notice_truncated_formatting`]
+    pub notice_truncated_formatting: LineFormatting,
+
+    /// See [`DefaultReportFormatter::pre_child_separator`]
+    pub pre_child_separator: Option<&'static str>,
+
+    /// See [`DefaultReportFormatter::child_child_separator`]
+    pub child_child_separator: Option<&'static str>,
+
+    /// See [`DefaultReportFormatter::source_chain_header_middle_formatting`]
+    pub source_chain_header_middle_formatting: NodeConfig,
+
+    /// See [`DefaultReportFormatter::source_chain_header_last_formatting`]
+    pub source_chain_header_last_formatting: NodeConfig,
+
+    /// See [`DefaultReportFormatter::source_chain_item_middle_formatting`]
+    pub source_chain_item_middle_formatting: ItemFormatting,
+
+    /// See [`DefaultReportFormatter::source_chain_item_last_formatting`]
+    pub source_chain_item_last_formatting: ItemFormatting,
+
+    /// See [`DefaultReportFormatter::source_chain_omitted_formatting`]
+    pub source_chain_omitted_formatting: LineFormatting,
+
+    /// See [`DefaultReportFormatter::report_report_separator`]
+    pub report_report_separator: &'static str,
+
+    /// See [`DefaultReportFormatter::report_appendix_separator`]
+    pub report_appendix_separator: &'static str,
+
+    /// See [`DefaultReportFormatter::appendix_appendix_separator`]
+    pub appendix_appendix_separator: &'static str,
+
+    /// See [`DefaultReportFormatter::appendices_footer`]
+    pub appendices_footer: &'static str,
+}
+
+impl TreeCharset {
+    /// The ASCII characters used by [`DefaultReportFormatter::ASCII`].
+    pub const ASCII: Self = DefaultReportFormatter::ASCII.charset();
+    /// The Unicode box-drawing characters used by
+    /// [`DefaultReportFormatter::UNICODE`].
+    pub const UNICODE: Self = DefaultReportFormatter::UNICODE.charset();
+}
+
+/// Controls which rendering [`DefaultReportFormatter`] uses for
+/// [`Display`](fmt::Display) output with and without the alternate flag.
+///
+/// The two renderings are the full tree, and the single-line summary of the
+/// primary context chain produced by
+/// [`format_compact`](crate::Report::format_compact).
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     hooks::builtin_hooks::report_formatter::{AlternateDisplay, DefaultReportFormatter},
+///     prelude::*,
+/// };
+///
+/// let report = report!("connection refused").context("failed to load user");
+///
+/// // With the default configuration, `{:#}` renders the full tree as well
+/// assert_eq!(format!("{report:#}"), format!("{report}"));
+///
+/// let formatter = DefaultReportFormatter {
+///     alternate_display: AlternateDisplay::Compact,
+///     ..DefaultReportFormatter::ASCII
+/// };
+/// let formatted = report.format_with(&formatter);
+/// assert_eq!(
+///     format!("{formatted:#}"),
+///     "failed to load user: connection refused"
+/// );
+/// assert!(format!("{formatted}").lines().count() > 1);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum AlternateDisplay {
+    /// Both `{}` and `{:#}` render the full tree.
+    #[default]
+    Full,
+    /// `{}` renders the full tree and `{:#}` renders the single-line summary.
+    Compact,
+    /// `{}` renders the single-line summary and `{:#}` renders the full tree.
+    Swapped,
+}
+
+impl AlternateDisplay {
+    /// Returns whether the single-line summary should be rendered.
+    fn is_compact(self, alternate: bool) -> bool {
+        match self {
+            AlternateDisplay::Full => false,
+            AlternateDisplay::Compact => alternate,
+            AlternateDisplay::Swapped => !alternate,
+        }
+    }
+}
+
+/// Callback writing custom content around the output of
+/// [`DefaultReportFormatter`].
+///
+/// This is used for the [`custom_header`](DefaultReportFormatter::custom_header)
+/// and [`custom_footer`](DefaultReportFormatter::custom_footer) of the
+/// formatter, for instance to surround every report with the name and version
+/// of the service that produced it.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     hooks::builtin_hooks::report_formatter::{DefaultReportFormatter, FormatCallback},
+///     prelude::*,
+/// };
+///
+/// let formatter = DefaultReportFormatter {
+///     custom_header: Some(FormatCallback::new(|f| writeln!(f, "== my-service v1.2.3 =="))),
+///     custom_footer: Some(FormatCallback::new(|f| write!(f, "== end of report =="))),
+///     ..DefaultReportFormatter::ASCII
+/// };
+/// let report = report!("connection refused");
+/// let output = report.format_with(&formatter).to_string();
+/// assert!(output.starts_with("== my-service v1.2.3 ==\n"));
+/// assert!(output.ends_with("== end of report =="));
+/// ```
+pub struct FormatCallback(Box<dyn Fn(&mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync>);
+
+impl FormatCallback {
+    /// Creates a new callback from the given function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::report_formatter::FormatCallback;
+    ///
+    /// let hostname = String::from("web-01");
+    /// let callback = FormatCallback::new(move |f| writeln!(f, "host: {hostname}"));
+    /// ```
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync + 'static,
+    {
+        Self(Box::new(callback))
+    }
+}
+
+impl fmt::Debug for FormatCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormatCallback").finish_non_exhaustive()
+    }
+}
+
+/// An attachment type rendered as a badge in front of the context of the
+/// reports it is attached to, such as `[E1234] user not found`.
+///
+/// The badge is rendered for attachments whose preferred placement is
+/// [`Inline`](AttachmentFormattingPlacement::Inline) and for which
+/// [`extract`](Self::extract) returns a value. When several attachments of the
+/// same report return a value, the one with the highest
+/// [`rank`](BadgeValue::rank) is used, or the first one if their ranks are
+/// equal. Every attachment that returns a value is left out of the regular
+/// attachments of the report.
+///
+/// # Examples
+///
+/// ```
+/// use core::fmt;
+///
+/// use rootcause::{
+///     hooks::builtin_hooks::report_formatter::{
+///         AttachmentBadge, BadgeValue, DefaultReportFormatter, LineFormatting,
+///     },
+///     prelude::*,
+///     report_attachment::ReportAttachmentRef,
+/// };
+///
+/// #[derive(Debug)]
+/// struct Retryable;
+///
+/// impl fmt::Display for Retryable {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("retryable")
+///     }
+/// }
+///
+/// fn retryable(attachment: ReportAttachmentRef<'_>) -> Option<BadgeValue<'_>> {
+///     attachment
+///         .downcast_inner::<Retryable>()
+///         .map(|value| BadgeValue::new(value, 0))
+/// }
+///
+/// const BADGES: &[AttachmentBadge] = &[AttachmentBadge::new(LineFormatting::new("(", ") "), retryable)];
+///
+/// let formatter = DefaultReportFormatter {
+///     attachment_badges: BADGES,
+///     ..DefaultReportFormatter::ASCII
+/// };
+/// let report = report!("connection reset").attach(Retryable);
+/// let output = report.format_with(&formatter).to_string();
+/// assert!(output.contains("(retryable) connection reset"));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct AttachmentBadge {
+    /// The prefix and suffix written around the value of the badge
+    pub formatting: LineFormatting,
+    /// Returns the value of the badge for an attachment, or `None` if the
+    /// attachment is not rendered as this badge
+    pub extract: for<'a> fn(ReportAttachmentRef<'a, Dynamic>) -> Option<BadgeValue<'a>>,
+}
+
+impl AttachmentBadge {
+    /// Creates a new badge from its formatting and its extractor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::report_formatter::{
+    ///     AttachmentBadge, BadgeValue, LineFormatting,
+    /// };
+    ///
+    /// let badge = AttachmentBadge::new(LineFormatting::new("<", "> "), |attachment| {
+    ///     attachment
+    ///         .downcast_inner::<u16>()
+    ///         .map(|status| BadgeValue::new(status, 0))
+    /// });
+    /// ```
+    pub const fn new(
+        formatting: LineFormatting,
+        extract: for<'a> fn(ReportAttachmentRef<'a, Dynamic>) -> Option<BadgeValue<'a>>,
+    ) -> Self {
+        Self {
+            formatting,
+            extract,
+        }
+    }
+
+    /// The badge of [`Severity`] attachments, which renders the highest
+    /// severity attached to a report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{
+    ///     hooks::builtin_hooks::report_formatter::{
+    ///         AttachmentBadge, DefaultReportFormatter, LineFormatting,
+    ///     },
+    ///     prelude::*,
+    ///     severity::Severity,
+    /// };
+    ///
+    /// const BADGES: &[AttachmentBadge] = &[AttachmentBadge::severity(LineFormatting::new("!", " "))];
+    ///
+    /// let formatter = DefaultReportFormatter {
+    ///     attachment_badges: BADGES,
+    ///     ..DefaultReportFormatter::ASCII
+    /// };
+    /// let report = report!("disk almost full")
+    ///     .attach_severity(Severity::Warning)
+    ///     .attach_severity(Severity::Error);
+    /// let output = report.format_with(&formatter).to_string();
+    /// assert!(output.contains("!error disk almost full"));
+    /// ```
+    pub const fn severity(formatting: LineFormatting) -> Self {
+        Self::new(formatting, |attachment| {
+            attachment
+                .downcast_inner::<Severity>()
+                .map(|severity| BadgeValue::new(severity, *severity as i32))
+        })
+    }
+
+    /// The badge of [`ErrorCode`] attachments, which renders the first code
+    /// attached to a report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{
+    ///     hooks::builtin_hooks::report_formatter::{
+    ///         AttachmentBadge, DefaultReportFormatter, LineFormatting,
+    ///     },
+    ///     prelude::*,
+    /// };
+    ///
+    /// const BADGES: &[AttachmentBadge] = &[AttachmentBadge::error_code(LineFormatting::new("#", " "))];
+    ///
+    /// let formatter = DefaultReportFormatter {
+    ///     attachment_badges: BADGES,
+    ///     ..DefaultReportFormatter::ASCII
+    /// };
+    /// let report = report!("user not found").attach_error_code("E1234");
+    /// let output = report.format_with(&formatter).to_string();
+    /// assert!(output.contains("#E1234 user not found"));
+    /// ```
+    pub const fn error_code(formatting: LineFormatting) -> Self {
+        Self::new(formatting, |attachment| {
+            attachment
+                .downcast_inner::<ErrorCode>()
+                .map(|error_code| BadgeValue::new(error_code, 0))
+        })
+    }
+
+    /// Returns the value of this badge for a report, using the attachments
+    /// displayed inline.
+    fn value<'a>(
+        &self,
+        attachments: impl Iterator<Item = (AttachmentFormattingStyle, ReportAttachmentRef<'a, Dynamic>)>,
+    ) -> Option<BadgeValue<'a>> {
+        attachments
+            .filter_map(|(style, attachment)| self.extract_inline(style, attachment))
+            .fold(None, |best: Option<BadgeValue<'a>>, value| match best {
+                Some(best) if best.rank >= value.rank => Some(best),
+                _ => Some(value),
+            })
+    }
+
+    /// Returns the value of this badge for an attachment, if it is displayed
+    /// inline.
+    fn extract_inline<'a>(
+        &self,
+        style: AttachmentFormattingStyle,
+        attachment: ReportAttachmentRef<'a, Dynamic>,
+    ) -> Option<BadgeValue<'a>> {
+        match style.placement {
+            AttachmentFormattingPlacement::Inline => (self.extract)(attachment),
+            _ => None,
+        }
+    }
+}
+
+/// The value of an [`AttachmentBadge`] for an attachment.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::builtin_hooks::report_formatter::BadgeValue;
+///
+/// let value = BadgeValue::new(&"E1234", 0);
+/// assert_eq!(value.value.to_string(), "E1234");
+/// ```
+#[derive(Copy, Clone)]
+pub struct BadgeValue<'a> {
+    /// The text of the badge
+    pub value: &'a dyn fmt::Display,
+    /// The rank of the value. When several attachments of a report have a
+    /// value for the same badge, the highest rank is rendered.
+    pub rank: i32,
+}
+
+impl<'a> BadgeValue<'a> {
+    /// Creates a new badge value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::report_formatter::BadgeValue;
+    ///
+    /// let value = BadgeValue::new(&42, 1);
+    /// assert_eq!(value.rank, 1);
+    /// ```
+    pub const fn new(value: &'a dyn fmt::Display, rank: i32) -> Self {
+        Self { value, rank }
+    }
+}
+
+impl fmt::Debug for BadgeValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BadgeValue")
+            .field("value", &format_args!("{}", self.value))
+            .field("rank", &self.rank)
+            .finish()
+    }
+}
+
 /// Configuration for formatting individual lines with prefix and suffix text.
 ///
 /// This is the fundamental building block for all report formatting, allowing
@@ -818,13 +1637,24 @@ type Appendices<'a> = IndexMap<
     Vec<(ReportAttachmentRef<'a, Dynamic>, FormattingFunction)>,
     rustc_hash::FxBuildHasher,
 >;
+type Footers<'a> = Vec<(
+    &'static str,
+    ReportAttachmentRef<'a, Dynamic>,
+    FormattingFunction,
+)>;
 
-struct DefaultFormatterState<'a, 'b> {
+struct DefaultFormatterState<'a> {
     config: &'a DefaultReportFormatter,
     appendices: Appendices<'a>,
+    footers: Footers<'a>,
     line_prefix: String,
-    formatter: &'a mut Formatter<'b>,
+    writer: &'a mut dyn Write,
     report_formatting_function: FormattingFunction,
+    depth: usize,
+    /// Whether [`Collapsed`](AttachmentFormattingPlacement::Collapsed)
+    /// attachments are shown in full, which is the case when the alternate
+    /// flag is set
+    expand_collapsed: bool,
 }
 
 impl ReportFormatter for DefaultReportFormatter {
@@ -834,33 +1664,196 @@ impl ReportFormatter for DefaultReportFormatter {
         formatter: &mut fmt::Formatter<'_>,
         report_formatting_function: FormattingFunction,
     ) -> fmt::Result {
-        formatter.write_str(self.report_header)?;
-        DefaultFormatterState::new(self, formatter, report_formatting_function)
+        let compact = report_formatting_function == FormattingFunction::Display
+            && self.alternate_display.is_compact(formatter.alternate());
+        if !compact && let Some(custom_header) = &self.custom_header {
+            (custom_header.0)(formatter)?;
+        }
+        if self.line_prefix.is_empty() {
+            let alternate = formatter.alternate();
+            self.write_reports(
+                reports,
+                formatter,
+                report_formatting_function,
+                compact,
+                alternate,
+            )?;
+        } else {
+            let alternate = formatter.alternate();
+            let mut writer = LinePrefixWriter {
+                inner: formatter,
+                prefix: self.line_prefix,
+                at_line_start: true,
+            };
+            self.write_reports(
+                reports,
+                &mut writer,
+                report_formatting_function,
+                compact,
+                alternate,
+            )?;
+        }
+        if !compact && let Some(custom_footer) = &self.custom_footer {
+            (custom_footer.0)(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+impl DefaultReportFormatter {
+    /// Writes the formatted reports to `writer`.
+    ///
+    /// The output is written line by line as it is produced, so formatting a
+    /// report never requires the entire output to be buffered. If `compact`
+    /// is set, each report is written as a single-line summary instead. If
+    /// `alternate` is set, collapsed attachments are shown in full.
+    fn write_reports(
+        &self,
+        reports: &[ReportRef<'_, Dynamic, Uncloneable, Local>],
+        writer: &mut dyn Write,
+        report_formatting_function: FormattingFunction,
+        compact: bool,
+        alternate: bool,
+    ) -> fmt::Result {
+        if compact {
+            for (index, report) in reports.iter().enumerate() {
+                if index > 0 {
+                    writer.write_char('\n')?;
+                }
+                write!(writer, "{}", report.format_compact())?;
+            }
+            return Ok(());
+        }
+        writer.write_str(self.report_header)?;
+        DefaultFormatterState::new(self, writer, report_formatting_function, alternate)
             .format_reports(reports)
     }
 }
 
+/// Writer that inserts the [`DefaultReportFormatter::line_prefix`] at the
+/// start of every line written to it.
+struct LinePrefixWriter<'a> {
+    inner: &'a mut dyn Write,
+    prefix: &'static str,
+    at_line_start: bool,
+}
+
+impl Write for LinePrefixWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for line in s.split_inclusive('\n') {
+            if self.at_line_start {
+                self.inner.write_str(self.prefix)?;
+            }
+            self.inner.write_str(line)?;
+            self.at_line_start = line.ends_with('\n');
+        }
+        Ok(())
+    }
+}
+
+/// Returns the [`KeyValue`] of an attachment if it is displayed inline, and
+/// should therefore be aligned with the other key/value attachments of its
+/// report.
+fn aligned_key_value<'a>(
+    style: AttachmentFormattingStyle,
+    attachment: ReportAttachmentRef<'a, Dynamic>,
+) -> Option<&'a KeyValue> {
+    match (style.placement, style.function) {
+        (AttachmentFormattingPlacement::Inline, FormattingFunction::Display) => {
+            attachment.downcast_inner::<KeyValue>()
+        }
+        _ => None,
+    }
+}
+
+/// Returns the number of levels of child reports below a report.
+fn subtree_depth(report: ReportRef<'_, Dynamic, Uncloneable, Local>) -> usize {
+    report
+        .children()
+        .iter()
+        .map(|child| 1 + subtree_depth(child.into_uncloneable()))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Formats a value with the given width, which [`KeyValue`] uses to pad its
+/// key.
+struct WithWidth<V> {
+    value: V,
+    width: usize,
+}
+
+impl<V: fmt::Display> fmt::Display for WithWidth<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:width$}", self.value, width = self.width)
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for WithWidth<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+/// Formats a context with the values of its [`AttachmentBadge`]s in front of
+/// it.
+struct WithBadges<'a, V> {
+    badges: Vec<(LineFormatting, BadgeValue<'a>)>,
+    value: V,
+}
+
+impl<V> WithBadges<'_, V> {
+    fn write_badges(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (formatting, badge) in &self.badges {
+            write!(
+                f,
+                "{}{}{}",
+                formatting.prefix, badge.value, formatting.suffix
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<V: fmt::Display> fmt::Display for WithBadges<'_, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_badges(f)?;
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for WithBadges<'_, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_badges(f)?;
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
 type TmpValueBuffer = String;
 type TmpAttachmentsBuffer<'a> = Vec<(AttachmentFormattingStyle, ReportAttachmentRef<'a, Dynamic>)>;
 
-impl<'a, 'b> DefaultFormatterState<'a, 'b> {
+impl<'a> DefaultFormatterState<'a> {
     fn new(
         config: &'a DefaultReportFormatter,
-        formatter: &'a mut Formatter<'b>,
+        writer: &'a mut dyn Write,
         report_formatting_function: FormattingFunction,
+        expand_collapsed: bool,
     ) -> Self {
         Self {
             config,
             appendices: IndexMap::default(),
+            footers: Vec::new(),
             line_prefix: String::new(),
-            formatter,
+            writer,
             report_formatting_function,
+            depth: 0,
+            expand_collapsed,
         }
     }
 
     fn format_with_line_prefix(&mut self, line: &str) -> fmt::Result {
-        self.formatter.write_str(&self.line_prefix)?;
-        self.formatter.write_str(line)?;
+        self.writer.write_str(&self.line_prefix)?;
+        self.writer.write_str(line)?;
         Ok(())
     }
 
@@ -891,7 +1884,11 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
         let mut is_first = true;
         tmp_value_buffer.clear();
         match function {
-            FormattingFunction::Display => write!(tmp_value_buffer, "{value}")?,
+            // This is a text formatter, so structured output is rendered using
+            // the display output instead
+            FormattingFunction::Display | FormattingFunction::Json => {
+                write!(tmp_value_buffer, "{value}")?
+            }
             FormattingFunction::Debug => write!(tmp_value_buffer, "{value:?}")?,
         }
 
@@ -915,10 +1912,10 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
         line_info: &LineFormatting,
         line: impl core::fmt::Display,
     ) -> fmt::Result {
-        self.formatter.write_str(&self.line_prefix)?;
-        self.formatter.write_str(line_info.prefix)?;
-        line.fmt(self.formatter)?;
-        self.formatter.write_str(line_info.suffix)?;
+        self.writer.write_str(&self.line_prefix)?;
+        self.writer.write_str(line_info.prefix)?;
+        write!(self.writer, "{line}")?;
+        self.writer.write_str(line_info.suffix)?;
         Ok(())
     }
 
@@ -934,8 +1931,7 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
             if is_first {
                 is_first = false;
             } else {
-                self.formatter
-                    .write_str(self.config.report_report_separator)?;
+                self.writer.write_str(self.config.report_report_separator)?;
             }
             self.format_report_node(
                 &mut tmp_value_buffer,
@@ -945,6 +1941,7 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
                 true,
             )?;
         }
+        self.format_footers(&mut tmp_value_buffer)?;
         self.line_prefix = self.config.appendix_line_prefix_always.to_string();
         self.format_appendices(&mut tmp_value_buffer)?;
         Ok(())
@@ -967,10 +1964,29 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
         };
         let context_style =
             report.preferred_context_formatting_style(self.report_formatting_function);
+        let badges = self
+            .config
+            .attachment_badges
+            .iter()
+            .filter_map(|badge| {
+                let attachments = report.attachments().iter().map(|attachment| {
+                    (
+                        attachment.preferred_formatting_style(self.report_formatting_function),
+                        attachment,
+                    )
+                });
+                badge
+                    .value(attachments)
+                    .map(|value| (badge.formatting, value))
+            })
+            .collect();
         self.format_node(
             tmp_value_buffer,
             formatting,
-            report.format_current_context(),
+            WithBadges {
+                badges,
+                value: report.format_current_context(),
+            },
             context_style.function,
             |this, tmp_value_buffer| {
                 this.format_node_data(
@@ -1019,18 +2035,42 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
                     )
                 })
                 .filter(
-                    |(formatting_style, _attachment)| match formatting_style.placement {
+                    |(formatting_style, attachment)| match formatting_style.placement {
+                        AttachmentFormattingPlacement::Footer { label } => {
+                            self.footers
+                                .push((label, *attachment, formatting_style.function));
+                            false
+                        }
                         AttachmentFormattingPlacement::Opaque => {
                             opaque_attachment_count += 1;
                             false
                         }
                         AttachmentFormattingPlacement::Hidden => false,
+                        // Rendered as a badge in front of the context instead
+                        AttachmentFormattingPlacement::Inline
+                            if self.config.attachment_badges.iter().any(|badge| {
+                                badge
+                                    .extract_inline(*formatting_style, *attachment)
+                                    .is_some()
+                            }) =>
+                        {
+                            false
+                        }
                         _ => true,
                     },
                 ),
         );
         tmp_attachments_buffer
             .sort_by_key(|(style1, _attachment)| core::cmp::Reverse(style1.priority));
+
+        // Pad the keys of the inline key/value attachments to the same width,
+        // so their values line up
+        let key_value_width = tmp_attachments_buffer
+            .iter()
+            .filter_map(|&(style, attachment)| aligned_key_value(style, attachment))
+            .map(|key_value| key_value.key.chars().count())
+            .max();
+
         for (attachment_index, &(attachment_formatting_style, attachment)) in
             tmp_attachments_buffer.iter().enumerate()
         {
@@ -1039,6 +2079,7 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
                 tmp_value_buffer,
                 attachment_formatting_style,
                 attachment,
+                key_value_width,
                 is_last_attachment && !has_children,
             )?;
         }
@@ -1073,14 +2114,34 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
             self.format_with_line_prefix(pre_child_separator)?;
         }
 
-        for (report_index, child) in report.children().iter().enumerate() {
+        if has_children && self.config.max_depth.is_some_and(|max| self.depth >= max) {
+            let levels = subtree_depth(report);
+            self.format_line(
+                &self.config.notice_truncated_formatting,
+                format_args!(
+                    "... {levels} more {word}",
+                    word = if levels == 1 { "level" } else { "levels" },
+                ),
+            )?;
+            return Ok(());
+        }
+
+        let children = report.children();
+        let shown_children = self
+            .config
+            .max_children_per_node
+            .map_or(children.len(), |max| max.min(children.len()));
+        let omitted_children = children.len() - shown_children;
+
+        self.depth += 1;
+        for (report_index, child) in children.iter().take(shown_children).enumerate() {
             if report_index != 0
                 && let Some(child_child_separator) = self.config.child_child_separator
             {
                 self.format_with_line_prefix(child_child_separator)?;
             }
             let is_first_child = report_index == 0;
-            let is_last_child = report_index + 1 == report.children().len();
+            let is_last_child = report_index + 1 == shown_children && omitted_children == 0;
             self.format_report_node(
                 tmp_value_buffer,
                 tmp_attachments_buffer,
@@ -1089,6 +2150,26 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
                 is_last_child,
             )?;
         }
+        self.depth -= 1;
+
+        if omitted_children != 0 {
+            if shown_children != 0
+                && let Some(child_child_separator) = self.config.child_child_separator
+            {
+                self.format_with_line_prefix(child_child_separator)?;
+            }
+            self.format_line(
+                &self.config.notice_truncated_formatting,
+                format_args!(
+                    "... {omitted_children} more {word}",
+                    word = if omitted_children == 1 {
+                        "child"
+                    } else {
+                        "children"
+                    },
+                ),
+            )?;
+        }
 
         Ok(())
     }
@@ -1098,22 +2179,16 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
         tmp_value_buffer: &mut TmpValueBuffer,
         attachment_formatting_style: AttachmentFormattingStyle,
         attachment: ReportAttachmentRef<'a, Dynamic>,
+        key_value_width: Option<usize>,
         is_last: bool,
     ) -> fmt::Result {
-        match attachment_formatting_style.placement {
-            AttachmentFormattingPlacement::Inline => {
-                let formatting = if is_last {
-                    &self.config.attachment_inline_formatting_last
-                } else {
-                    &self.config.attachment_inline_formatting_middle
-                };
-                self.format_item(
-                    tmp_value_buffer,
-                    formatting,
-                    attachment.format_inner(),
-                    attachment_formatting_style.function,
-                )?;
+        let placement = match attachment_formatting_style.placement {
+            AttachmentFormattingPlacement::Collapsed { header, .. } if self.expand_collapsed => {
+                AttachmentFormattingPlacement::InlineWithHeader { header }
             }
+            placement => placement,
+        };
+        match placement {
             AttachmentFormattingPlacement::InlineWithHeader { header } => {
                 let formatting = if is_last {
                     &self.config.attachment_headered_formatting_last
@@ -1149,6 +2224,27 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
                     },
                 )?;
             }
+            AttachmentFormattingPlacement::Collapsed { header, summary } => {
+                let formatting = if is_last {
+                    &self.config.attachment_inline_formatting_last
+                } else {
+                    &self.config.attachment_inline_formatting_middle
+                };
+                // `{:#}` only expands the attachment if it renders the full tree
+                let expand_with = if self.report_formatting_function == FormattingFunction::Display
+                    && !self.config.alternate_display.is_compact(true)
+                {
+                    "{:#}"
+                } else {
+                    "{:#?}"
+                };
+                self.format_item(
+                    tmp_value_buffer,
+                    formatting,
+                    format_args!("{header}: {summary} (use {expand_with} to expand)"),
+                    FormattingFunction::Display,
+                )?;
+            }
             AttachmentFormattingPlacement::Appendix { appendix_name } => {
                 let appendices = self.appendices.entry(appendix_name).or_default();
                 appendices.push((attachment, attachment_formatting_style.function));
@@ -1160,7 +2256,36 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
                 let line = format_args!("{appendix_name} #{}", appendices.len());
                 self.format_line(formatting, line)?;
             }
-            AttachmentFormattingPlacement::Opaque | AttachmentFormattingPlacement::Hidden => {}
+            AttachmentFormattingPlacement::Footer { .. }
+            | AttachmentFormattingPlacement::Opaque
+            | AttachmentFormattingPlacement::Hidden => {}
+            // `Inline`, and placements added in future versions
+            _ => {
+                let formatting = if is_last {
+                    &self.config.attachment_inline_formatting_last
+                } else {
+                    &self.config.attachment_inline_formatting_middle
+                };
+                match key_value_width.filter(|_| {
+                    aligned_key_value(attachment_formatting_style, attachment).is_some()
+                }) {
+                    Some(width) => self.format_item(
+                        tmp_value_buffer,
+                        formatting,
+                        WithWidth {
+                            value: attachment.format_inner(),
+                            width,
+                        },
+                        attachment_formatting_style.function,
+                    )?,
+                    None => self.format_item(
+                        tmp_value_buffer,
+                        formatting,
+                        attachment.format_inner(),
+                        attachment_formatting_style.function,
+                    )?,
+                }
+            }
         }
         Ok(())
     }
@@ -1257,15 +2382,51 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
         Ok(true)
     }
 
+    fn format_footers(&mut self, tmp_value_buffer: &mut TmpValueBuffer) -> fmt::Result {
+        let footers = core::mem::take(&mut self.footers);
+
+        if footers.is_empty() {
+            return Ok(());
+        }
+
+        self.writer.write_str(self.config.report_footer_separator)?;
+
+        let mut footer_buffer = String::new();
+        for (label, attachment, formatting_function) in footers {
+            footer_buffer.clear();
+            let label_formatting = &self.config.footer_label;
+            write!(
+                footer_buffer,
+                "{}{label}{}",
+                label_formatting.prefix, label_formatting.suffix
+            )?;
+            match formatting_function {
+                FormattingFunction::Display | FormattingFunction::Json => {
+                    write!(footer_buffer, "{}", attachment.format_inner())?
+                }
+                FormattingFunction::Debug => {
+                    write!(footer_buffer, "{:?}", attachment.format_inner())?
+                }
+            }
+            self.format_item(
+                tmp_value_buffer,
+                &self.config.footer_item,
+                footer_buffer.as_str(),
+                FormattingFunction::Display,
+            )?;
+        }
+        Ok(())
+    }
+
     fn format_appendices(&mut self, tmp_value_buffer: &mut TmpValueBuffer) -> fmt::Result {
         let appendices = core::mem::take(&mut self.appendices);
 
         if appendices.is_empty() {
-            self.formatter.write_str(self.config.no_appendices_footer)?;
+            self.writer.write_str(self.config.no_appendices_footer)?;
             return Ok(());
         }
 
-        self.formatter
+        self.writer
             .write_str(self.config.report_appendix_separator)?;
 
         let mut is_first = true;
@@ -1276,7 +2437,7 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
                 if is_first {
                     is_first = false;
                 } else {
-                    self.formatter
+                    self.writer
                         .write_str(self.config.appendix_appendix_separator)?;
                 }
 
@@ -1290,7 +2451,7 @@ impl<'a, 'b> DefaultFormatterState<'a, 'b> {
                 )?;
             }
         }
-        self.formatter.write_str(self.config.appendices_footer)?;
+        self.writer.write_str(self.config.appendices_footer)?;
         Ok(())
     }
 }
@@ -0,0 +1,317 @@
+//! Timestamp attachment recording when a report was created.
+//!
+//! A [`Timestamp`] is a point in time measured since the Unix epoch, rendered
+//! in ISO-8601 format in UTC, such as `2026-10-16T08:30:00.250Z`. It can be
+//! added to a single report with [`Report::attach_timestamp`], or to every
+//! report by installing a [`TimestampCollector`] as an attachment collector.
+//!
+//! Reading the system clock requires the `std` feature. Without it, a clock
+//! function can be provided instead, using [`Report::attach_timestamp_with`]
+//! or [`TimestampCollector::with_clock`].
+//!
+//! [`Report::attach_timestamp`]: crate::Report::attach_timestamp
+//! [`Report::attach_timestamp_with`]: crate::Report::attach_timestamp_with
+//!
+//! # Examples
+//!
+//! ```
+//! use core::time::Duration;
+//!
+//! use rootcause::{hooks::builtin_hooks::timestamp::Timestamp, prelude::*};
+//!
+//! fn clock() -> Timestamp {
+//!     Timestamp::from_unix_duration(Duration::from_secs(1_700_000_000))
+//! }
+//!
+//! let report = report!("disk full").attach_timestamp_with(clock);
+//! assert!(report.to_string().contains("2023-11-14T22:13:20.000Z"));
+//! ```
+
+use core::{fmt, time::Duration};
+
+use rootcause_internals::handlers::{
+    AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler, FormattingFunction,
+};
+
+use crate::hooks::report_creation::AttachmentCollector;
+
+/// A point in time, measured since the Unix epoch.
+///
+/// Timestamps are ordered chronologically and are displayed in ISO-8601
+/// format in UTC with millisecond precision.
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use rootcause::hooks::builtin_hooks::timestamp::Timestamp;
+///
+/// let timestamp = Timestamp::from_unix_duration(Duration::from_millis(1_700_000_000_250));
+/// assert_eq!(timestamp.to_string(), "2023-11-14T22:13:20.250Z");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    since_unix_epoch: Duration,
+}
+
+impl Timestamp {
+    /// The Unix epoch, `1970-01-01T00:00:00Z`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::timestamp::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::UNIX_EPOCH.to_string(), "1970-01-01T00:00:00.000Z");
+    /// ```
+    pub const UNIX_EPOCH: Self = Self::from_unix_duration(Duration::ZERO);
+
+    /// Creates a timestamp from the time elapsed since the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::time::Duration;
+    ///
+    /// use rootcause::hooks::builtin_hooks::timestamp::Timestamp;
+    ///
+    /// let timestamp = Timestamp::from_unix_duration(Duration::from_secs(86_400));
+    /// assert_eq!(timestamp.to_string(), "1970-01-02T00:00:00.000Z");
+    /// ```
+    #[must_use]
+    pub const fn from_unix_duration(since_unix_epoch: Duration) -> Self {
+        Self { since_unix_epoch }
+    }
+
+    /// Returns the time elapsed since the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::time::Duration;
+    ///
+    /// use rootcause::hooks::builtin_hooks::timestamp::Timestamp;
+    ///
+    /// let duration = Duration::from_secs(60);
+    /// assert_eq!(Timestamp::from_unix_duration(duration).unix_duration(), duration);
+    /// ```
+    #[must_use]
+    pub const fn unix_duration(&self) -> Duration {
+        self.since_unix_epoch
+    }
+
+    /// Reads the current time from the system clock.
+    ///
+    /// If the system clock is set before the Unix epoch, the timestamp is
+    /// [`Timestamp::UNIX_EPOCH`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::builtin_hooks::timestamp::Timestamp;
+    ///
+    /// let timestamp = Timestamp::now();
+    /// assert!(timestamp > Timestamp::UNIX_EPOCH);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    pub fn now() -> Self {
+        Self::from(std::time::SystemTime::now())
+    }
+}
+
+/// Converts a [`SystemTime`](std::time::SystemTime) into a [`Timestamp`].
+///
+/// Times before the Unix epoch are converted to [`Timestamp::UNIX_EPOCH`].
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl From<std::time::SystemTime> for Timestamp {
+    fn from(time: std::time::SystemTime) -> Self {
+        let since_unix_epoch = time
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        Self::from_unix_duration(since_unix_epoch)
+    }
+}
+
+/// Formats the timestamp in ISO-8601 format in UTC, such as
+/// `2026-10-16T08:30:00.250Z`.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.since_unix_epoch.as_secs();
+        let millis = self.since_unix_epoch.subsec_millis();
+        let (year, month, day) = civil_from_days(seconds / 86_400);
+        let seconds_of_day = seconds % 86_400;
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{millis:03}Z",
+            seconds_of_day / 3_600,
+            seconds_of_day / 60 % 60,
+            seconds_of_day % 60,
+        )
+    }
+}
+
+/// Converts a number of days since the Unix epoch into a proleptic Gregorian
+/// `(year, month, day)` date.
+///
+/// This is the `civil_from_days` algorithm by Howard Hinnant, restricted to
+/// dates after the epoch.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Handler for formatting [`Timestamp`] attachments.
+///
+/// The timestamp is displayed inline in ISO-8601 format, after the
+/// [`Location`](crate::hooks::builtin_hooks::location::Location) of the report
+/// and before any other attachments with the default priority.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     hooks::builtin_hooks::timestamp::{Timestamp, TimestampHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("error").attach_custom::<TimestampHandler, _>(Timestamp::UNIX_EPOCH);
+/// ```
+#[derive(Copy, Clone)]
+pub struct TimestampHandler;
+
+impl AttachmentHandler<Timestamp> for TimestampHandler {
+    fn display(value: &Timestamp, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &Timestamp, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn preferred_formatting_style(
+        _value: &Timestamp,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::Inline,
+            function: FormattingFunction::Display,
+            priority: 15,
+        }
+    }
+}
+
+/// Attachment collector adding a [`Timestamp`] to every report.
+///
+/// With the `std` feature, [`TimestampCollector::SYSTEM`] reads the system
+/// clock. Otherwise, a clock function can be provided using
+/// [`TimestampCollector::with_clock`].
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::hooks::{
+///     Hooks,
+///     builtin_hooks::timestamp::{Timestamp, TimestampCollector},
+/// };
+///
+/// # fn read_clock() -> Timestamp { Timestamp::UNIX_EPOCH }
+/// Hooks::new()
+///     .attachment_collector(TimestampCollector::with_clock(read_clock))
+///     .install()
+///     .ok();
+/// ```
+#[derive(Copy, Clone)]
+pub struct TimestampCollector {
+    clock: fn() -> Timestamp,
+}
+
+impl TimestampCollector {
+    /// A collector reading the current time from the system clock using
+    /// [`Timestamp::now`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::{Hooks, builtin_hooks::timestamp::TimestampCollector};
+    ///
+    /// Hooks::new()
+    ///     .attachment_collector(TimestampCollector::SYSTEM)
+    ///     .install()
+    ///     .ok();
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub const SYSTEM: Self = Self::with_clock(Timestamp::now);
+
+    /// Creates a collector reading the current time from the given clock
+    /// function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::{
+    ///     Hooks,
+    ///     builtin_hooks::timestamp::{Timestamp, TimestampCollector},
+    /// };
+    ///
+    /// fn clock() -> Timestamp {
+    ///     // Read the time from a hardware clock, for instance
+    ///     Timestamp::UNIX_EPOCH
+    /// }
+    ///
+    /// Hooks::new()
+    ///     .attachment_collector(TimestampCollector::with_clock(clock))
+    ///     .install()
+    ///     .ok();
+    /// ```
+    #[must_use]
+    pub const fn with_clock(clock: fn() -> Timestamp) -> Self {
+        Self { clock }
+    }
+}
+
+impl AttachmentCollector<Timestamp> for TimestampCollector {
+    type Handler = TimestampHandler;
+
+    fn collect(&self) -> Timestamp {
+        (self.clock)()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_display_iso_8601() {
+        let cases = [
+            (0, "1970-01-01T00:00:00.000Z"),
+            (951_782_400_000, "2000-02-29T00:00:00.000Z"),
+            (1_709_251_199_999, "2024-02-29T23:59:59.999Z"),
+            (4_102_444_800_000, "2100-01-01T00:00:00.000Z"),
+        ];
+        for (millis, expected) in cases {
+            let timestamp = Timestamp::from_unix_duration(Duration::from_millis(millis));
+            assert_eq!(timestamp.to_string(), expected);
+        }
+    }
+}
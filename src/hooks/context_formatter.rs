@@ -426,6 +426,28 @@ pub trait ContextFormatterHook<C>: 'static + Send + Sync {
     }
 }
 
+/// A [`ContextFormatterHook`] that overrides the [`Display`](fmt::Display)
+/// formatting with a closure, used by [`Hooks::context_formatter_fn`].
+///
+/// [`Hooks::context_formatter_fn`]: crate::hooks::Hooks::context_formatter_fn
+pub(crate) struct ContextDisplayFn<F>(pub(crate) F);
+
+impl<C, F> ContextFormatterHook<C> for ContextDisplayFn<F>
+where
+    F: Fn(ReportRef<'_, C, Uncloneable, Local>, &mut fmt::Formatter<'_>) -> fmt::Result
+        + 'static
+        + Send
+        + Sync,
+{
+    fn display(
+        &self,
+        report: ReportRef<'_, C, Uncloneable, Local>,
+        formatter: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        (self.0)(report, formatter)
+    }
+}
+
 impl<C, H> StoredHook for Hook<C, H>
 where
     C: 'static,
@@ -78,14 +78,19 @@ use core::{
 };
 
 use self::{
-    attachment_formatter::AttachmentFormatterHook,
+    attachment_formatter::{AttachmentDisplayFn, AttachmentFormatterHook},
     builtin_hooks::location::{Location, LocationHandler, LocationHook},
-    context_formatter::ContextFormatterHook,
+    context_formatter::{ContextDisplayFn, ContextFormatterHook},
     report_creation::{
         AttachmentCollector, ReportCreationHook, StoredReportCreationHook,
         attachment_hook_to_stored_hook, creation_hook_to_stored_hook,
     },
-    report_formatter::ReportFormatter,
+    report_formatter::{ConditionalReportFormatter, ReportFormatter},
+};
+use crate::{
+    ReportRef,
+    markers::{Local, Uncloneable},
+    report_attachment::ReportAttachmentRef,
 };
 
 /// Builder for configuring and installing hooks globally.
@@ -107,8 +112,13 @@ use self::{
 ///   attached data
 /// - [`context_formatter()`](Self::context_formatter) - Format main error
 ///   messages
+/// - [`attachment_formatter_fn()`](Self::attachment_formatter_fn) and
+///   [`context_formatter_fn()`](Self::context_formatter_fn) - Override the
+///   display output of a single type with a closure
 /// - [`report_formatter()`](Self::report_formatter) - Customize entire report
 ///   layout
+/// - [`report_formatter_when()`](Self::report_formatter_when) - Use a different
+///   report layout while a condition holds
 ///
 /// # Examples
 ///
@@ -192,12 +202,25 @@ impl Default for Hooks {
 //   happening when reclaiming the hooks.
 // - Deallocation: If the `HooksHandle` object is dropped without calling
 //   `replace()` or `reclaim()`, the `HookData` is leaked.
+//
+// ### `SCOPED_HOOKS`
+//
+// - `Hooks->SCOPED_HOOKS`: When the `scope()` method is called on a `Hooks`
+//   instance, a pointer to its `HookData` is stored in the thread-local
+//   `SCOPED_HOOKS` for the duration of the closure. The `Hooks` instance is
+//   kept alive by `scope()` until the previous pointer has been restored.
+// - `SCOPED_HOOKS->use_hooks`: Only the current thread can access the pointer,
+//   and every `use_hooks` call that reads it completes before the closure
+//   passed to `scope()` returns.
+// - Deallocation: The `HookData` is deallocated when `scope()` returns or
+//   unwinds, after the previous pointer has been restored.
 #[derive(Debug)]
 pub(crate) struct HookData {
     pub(crate) report_creation: Vec<Box<dyn StoredReportCreationHook>>,
     pub(crate) attachment_formatters: attachment_formatter::HookMap,
     pub(crate) context_formatters: context_formatter::HookMap,
     pub(crate) report_formatter: Option<Box<dyn ReportFormatter>>,
+    pub(crate) conditional_report_formatters: Vec<ConditionalReportFormatter>,
     #[allow(dead_code, reason = "only used for debugging purposes")]
     pub(crate) created_at: Location,
 }
@@ -261,6 +284,7 @@ impl Hooks {
             attachment_formatters: Default::default(),
             context_formatters: Default::default(),
             report_formatter: None,
+            conditional_report_formatters: Vec::new(),
             created_at: Location::caller(),
         }))
     }
@@ -286,6 +310,7 @@ impl Hooks {
             attachment_formatters: Default::default(),
             context_formatters: Default::default(),
             report_formatter: None,
+            conditional_report_formatters: Vec::new(),
             created_at: Location::caller(),
         }))
     }
@@ -411,6 +436,37 @@ impl Hooks {
         self
     }
 
+    /// Registers a closure that formats attachments of a specific type.
+    ///
+    /// This is a shorthand for [`Hooks::attachment_formatter`] for the common
+    /// case where only the [`Display`](core::fmt::Display) output should be
+    /// changed, without defining a type that implements
+    /// [`AttachmentFormatterHook`]. The [`Debug`](core::fmt::Debug) output and
+    /// the formatting style of the attachment are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::Hooks;
+    ///
+    /// struct ErrorCode(u32);
+    ///
+    /// let hooks = Hooks::new().attachment_formatter_fn::<ErrorCode, _>(|attachment, f| {
+    ///     write!(f, "Error code: 0x{:04X}", attachment.inner().0)
+    /// });
+    /// hooks.install().expect("failed to install hooks");
+    /// ```
+    pub fn attachment_formatter_fn<A, F>(self, f: F) -> Self
+    where
+        A: Sized + 'static,
+        F: Fn(ReportAttachmentRef<'_, A>, &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+            + 'static
+            + Send
+            + Sync,
+    {
+        self.attachment_formatter::<A, _>(AttachmentDisplayFn(f))
+    }
+
     /// Registers a formatter for a specific context (error) type.
     ///
     /// This controls how contexts of type `C` are displayed when they appear
@@ -452,10 +508,49 @@ impl Hooks {
         self
     }
 
+    /// Registers a closure that formats contexts of a specific type.
+    ///
+    /// This is a shorthand for [`Hooks::context_formatter`] for the common
+    /// case where only the [`Display`](core::fmt::Display) output should be
+    /// changed, without defining a type that implements
+    /// [`ContextFormatterHook`]. The [`Debug`](core::fmt::Debug) output and
+    /// the formatting style of the context are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::hooks::Hooks;
+    ///
+    /// struct HttpError {
+    ///     status: u16,
+    /// }
+    ///
+    /// let hooks = Hooks::new().context_formatter_fn::<HttpError, _>(|report, f| {
+    ///     write!(f, "HTTP {}", report.current_context().status)
+    /// });
+    /// hooks.install().expect("failed to install hooks");
+    /// ```
+    pub fn context_formatter_fn<C, F>(self, f: F) -> Self
+    where
+        C: Sized + 'static,
+        F: Fn(
+                ReportRef<'_, C, Uncloneable, Local>,
+                &mut core::fmt::Formatter<'_>,
+            ) -> core::fmt::Result
+            + 'static
+            + Send
+            + Sync,
+    {
+        self.context_formatter::<C, _>(ContextDisplayFn(f))
+    }
+
     /// Registers a hook for formatting entire reports.
     ///
     /// This controls the overall layout, structure, and appearance of error
-    /// reports. Only one report formatting hook can be active at a time.
+    /// reports. Only one report formatting hook can be registered this way;
+    /// registering another one replaces it. It is used whenever none of the
+    /// formatters registered with
+    /// [`report_formatter_when`](Self::report_formatter_when) applies.
     ///
     /// # Examples
     ///
@@ -475,6 +570,55 @@ impl Hooks {
         self
     }
 
+    /// Registers a hook for formatting entire reports, which is only used
+    /// while the given predicate returns `true`.
+    ///
+    /// The predicate is called every time a report is formatted. The
+    /// formatters registered with this method are tried in registration order,
+    /// and the first one with a matching predicate is used. If none matches,
+    /// the formatter registered with
+    /// [`report_formatter`](Self::report_formatter) is used, or the default
+    /// formatter if there is none.
+    ///
+    /// This makes it possible to switch between output formats, for instance
+    /// based on a thread-local flag, without replacing the installed hooks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    ///
+    /// use rootcause::hooks::{Hooks, builtin_hooks::report_formatter::DefaultReportFormatter};
+    ///
+    /// thread_local! {
+    ///     static PLAIN_OUTPUT: Cell<bool> = const { Cell::new(false) };
+    /// }
+    ///
+    /// // Use ASCII-only formatting while `PLAIN_OUTPUT` is set, and colored
+    /// // output otherwise
+    /// let hooks = Hooks::new()
+    ///     .report_formatter_when(
+    ///         || PLAIN_OUTPUT.with(Cell::get),
+    ///         DefaultReportFormatter::ASCII,
+    ///     )
+    ///     .report_formatter(DefaultReportFormatter::UNICODE_COLORS);
+    ///
+    /// hooks.install().expect("failed to install hooks");
+    /// ```
+    pub fn report_formatter_when<P, H>(mut self, predicate: P, hook: H) -> Self
+    where
+        P: Fn() -> bool + Send + Sync + 'static,
+        H: ReportFormatter + 'static,
+    {
+        self.0
+            .conditional_report_formatters
+            .push(ConditionalReportFormatter {
+                predicate: Box::new(predicate),
+                formatter: Box::new(hook),
+            });
+        self
+    }
+
     /// Installs the hooks globally.
     ///
     /// If hooks are already installed, returns an error
@@ -568,6 +712,57 @@ impl Hooks {
         self.leak().replace()
     }
 
+    /// Runs a closure with the hooks installed for the current thread only.
+    ///
+    /// While the closure runs, reports created and formatted on the current
+    /// thread use these hooks instead of the globally installed ones. The
+    /// scoped hooks replace the global hooks entirely, rather than adding to
+    /// them. Afterwards, the previous state is restored, even if the closure
+    /// panics, and the hooks are dropped.
+    ///
+    /// Unlike [`install`](Self::install) and [`replace`](Self::replace), this
+    /// neither touches the global hooks nor leaks memory, so tests that depend
+    /// on hooks can run in parallel in the same test binary without
+    /// interfering with each other. Scopes can be nested.
+    ///
+    /// Reports created on other threads, including threads spawned by the
+    /// closure, use the global hooks.
+    ///
+    /// This requires the `std` feature flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")] {
+    /// use rootcause::{hooks::Hooks, prelude::*};
+    ///
+    /// let output = Hooks::new()
+    ///     .attachment_collector(|| "request id: 42")
+    ///     .scope(|| report!("not found").to_string());
+    /// assert!(output.contains("request id: 42"));
+    ///
+    /// // Outside of the scope, the global hooks are used again
+    /// let output = report!("not found").to_string();
+    /// assert!(!output.contains("request id: 42"));
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        /// Restores the previously scoped hooks when dropped.
+        struct RestoreGuard(Option<NonNull<HookData>>);
+
+        impl Drop for RestoreGuard {
+            fn drop(&mut self) {
+                SCOPED_HOOKS.with(|scoped| scoped.set(self.0));
+            }
+        }
+
+        let hook_data = NonNull::from(&*self.0);
+        let _guard = RestoreGuard(SCOPED_HOOKS.with(|scoped| scoped.replace(Some(hook_data))));
+        f()
+    }
+
     /// Leaks the hooks, returning a [`HooksHandle`] handle.
     ///
     /// This is useful for installing the hooks later using
@@ -836,6 +1031,21 @@ impl GlobalHooks {
 
 static HOOKS: GlobalHooks = GlobalHooks::new();
 
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// The hooks installed for the current thread using [`Hooks::scope`].
+    ///
+    /// # Safety
+    ///
+    /// 1. If the pointer is non-null, then it points to the `HookData` of a
+    ///    `Hooks` instance that is kept alive by an active call to
+    ///    `Hooks::scope` on this thread.
+    /// 2. The pointer is only used to create shared references, and only for
+    ///    the duration of a `use_hooks` call.
+    static SCOPED_HOOKS: core::cell::Cell<Option<NonNull<HookData>>> =
+        const { core::cell::Cell::new(None) };
+}
+
 /// A trait for calling hook functions with optional `HookData`.
 ///
 /// This is functionally similar to `FnOnce(Option<&HookData>) -> R`, but
@@ -860,6 +1070,24 @@ pub(crate) fn use_hooks<F, R>(f: F) -> R
 where
     F: HookCallback<R>,
 {
+    #[cfg(feature = "std")]
+    if let Some(hook_data) = SCOPED_HOOKS.try_with(core::cell::Cell::get).ok().flatten() {
+        // SAFETY:
+        // 1. The pointer points to the `HookData` of a `Hooks` instance that is kept
+        //    alive by an active call to `Hooks::scope` on this thread.
+        // 2. This `use_hooks` call runs inside that `Hooks::scope` call, since the
+        //    pointer is only visible on this thread while it is active. The shared
+        //    reference is limited to this function, so it cannot outlive the
+        //    `Hooks` instance.
+        let hook_data = unsafe {
+            // @add-unsafe-context: Hooks
+            // @add-unsafe-context: HookData
+            // @add-unsafe-context: SCOPED_HOOKS
+            hook_data.as_ref()
+        };
+        return f.call(Some(hook_data));
+    }
+
     let ptr = HOOKS.ptr.load(Ordering::Acquire);
 
     // SAFETY:
@@ -888,3 +1116,94 @@ where
 
     f.call(ptr)
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn scoped_output() -> alloc::string::String {
+        crate::report!("error").to_string()
+    }
+
+    #[test]
+    fn test_scope_nested() {
+        Hooks::new().attachment_collector(|| "outer").scope(|| {
+            assert!(scoped_output().contains("outer"));
+
+            Hooks::new().attachment_collector(|| "inner").scope(|| {
+                let output = scoped_output();
+                assert!(output.contains("inner"));
+                assert!(!output.contains("outer"));
+            });
+
+            assert!(scoped_output().contains("outer"));
+        });
+
+        assert!(!scoped_output().contains("outer"));
+    }
+
+    #[test]
+    fn test_collected_attachments_of_local_reports_are_sendsync() {
+        let report = Hooks::new()
+            .attachment_collector(|| "collected")
+            .scope(|| crate::report!(alloc::rc::Rc::new("local")).into_dynamic());
+        let child = report.into_cloneable();
+
+        let report = Hooks::new()
+            .attachment_collector(|| "collected")
+            .scope(|| crate::report!("parent", source: child.clone()));
+        assert!(report.to_string().contains("collected"));
+        let mut report = report.try_into_sendsync().unwrap_err();
+
+        // Without the child holding the `Rc`, every object is thread-safe
+        report.children_mut().pop();
+        assert!(report.try_into_sendsync().is_ok());
+    }
+
+    #[test]
+    fn test_scope_restored_after_panic() {
+        let result = std::panic::catch_unwind(|| {
+            Hooks::new()
+                .attachment_collector(|| "scoped")
+                .scope(|| panic!("closure panicked"))
+        });
+        assert!(result.is_err());
+        assert!(!scoped_output().contains("scoped"));
+    }
+
+    #[test]
+    fn test_report_formatter_when() {
+        use core::cell::Cell;
+
+        use crate::hooks::builtin_hooks::report_formatter::DefaultReportFormatter;
+
+        std::thread_local! {
+            static ASCII_OUTPUT: Cell<bool> = const { Cell::new(false) };
+        }
+
+        let report = crate::report!("error").context("outer");
+        let unicode = report
+            .format_with(&DefaultReportFormatter::UNICODE)
+            .to_string();
+        let ascii = report
+            .format_with(&DefaultReportFormatter::ASCII)
+            .to_string();
+        assert_ne!(unicode, ascii);
+
+        Hooks::new()
+            .report_formatter_when(
+                || ASCII_OUTPUT.with(Cell::get),
+                DefaultReportFormatter::ASCII,
+            )
+            .report_formatter(DefaultReportFormatter::UNICODE)
+            .scope(|| {
+                assert_eq!(report.to_string(), unicode);
+                ASCII_OUTPUT.with(|flag| flag.set(true));
+                assert_eq!(report.to_string(), ascii);
+                ASCII_OUTPUT.with(|flag| flag.set(false));
+                assert_eq!(report.to_string(), unicode);
+            });
+    }
+}
@@ -330,9 +330,13 @@ where
         #[track_caller]
         fn on_local_creation(&self, mut report: ReportMut<'_, Dynamic, Local>) {
             let attachment = self.collector.collect();
-            report
-                .attachments_mut()
-                .push(ReportAttachment::new_local_custom::<Handler>(attachment).into_dynamic());
+            // The attachment is `Send + Sync`, so it is recorded as such to let
+            // `Report::try_into_sendsync` succeed
+            report.attachments_mut().push(
+                ReportAttachment::new_sendsync_custom::<Handler>(attachment)
+                    .into_local()
+                    .into_dynamic(),
+            );
         }
 
         #[track_caller]
@@ -486,7 +490,8 @@ pub(crate) fn run_creation_hooks_local(report: ReportMut<'_, Dynamic, Local>) {
                 }
             } else {
                 report.attachments_mut().push(
-                    ReportAttachment::new_local_custom::<LocationHandler>(Location::caller())
+                    ReportAttachment::new_sendsync_custom::<LocationHandler>(Location::caller())
+                        .into_local()
                         .into_dynamic(),
                 );
             }
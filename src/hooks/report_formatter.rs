@@ -73,6 +73,7 @@
 //! [`Report::format_with`]: crate::Report::format_with
 //! [`DefaultReportFormatter::UNICODE`]: crate::hooks::builtin_hooks::report_formatter::DefaultReportFormatter::UNICODE
 
+use alloc::boxed::Box;
 use core::fmt;
 
 use rootcause_internals::handlers::FormattingFunction;
@@ -87,8 +88,10 @@ use crate::{
 ///
 /// This trait allows you to completely control the presentation of reports,
 /// including their structure, layout, colors, and how multiple reports in a
-/// collection are displayed together. Only one report formatter hook can be
-/// active at a time.
+/// collection are displayed together. The formatter to use is selected each
+/// time a report is formatted, see [`Hooks::report_formatter_when`].
+///
+/// [`Hooks::report_formatter_when`]: crate::hooks::Hooks::report_formatter_when
 ///
 /// # Examples
 ///
@@ -150,6 +153,12 @@ pub trait ReportFormatter: 'static + Send + Sync + fmt::Debug {
     /// includes how multiple reports at the "same level" are presented
     /// together.
     ///
+    /// The `formatter` is the one passed to the [`Display`](fmt::Display) or
+    /// [`Debug`](fmt::Debug) implementation of the report, so its flags are
+    /// available to the formatter. For instance, [`fmt::Formatter::alternate`]
+    /// can be used to render `{report:#}` differently from `{report}`, as
+    /// [`DefaultReportFormatter`] does.
+    ///
     /// # Examples
     ///
     /// ```
@@ -249,15 +258,41 @@ pub trait ReportFormatter: 'static + Send + Sync + fmt::Debug {
     }
 }
 
+/// A report formatter registered with [`Hooks::report_formatter_when`], which
+/// is only used while its predicate returns `true`.
+///
+/// [`Hooks::report_formatter_when`]: crate::hooks::Hooks::report_formatter_when
+pub(crate) struct ConditionalReportFormatter {
+    pub(crate) predicate: Box<dyn Fn() -> bool + Send + Sync>,
+    pub(crate) formatter: Box<dyn ReportFormatter>,
+}
+
+impl fmt::Debug for ConditionalReportFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConditionalReportFormatter")
+            .field("formatter", &self.formatter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Selects the report formatter to use from the installed hooks, if any.
+fn selected_report_formatter(hook_data: Option<&HookData>) -> Option<&dyn ReportFormatter> {
+    let hook_data = hook_data?;
+    hook_data
+        .conditional_report_formatters
+        .iter()
+        .find(|conditional| (conditional.predicate)())
+        .map(|conditional| &*conditional.formatter)
+        .or(hook_data.report_formatter.as_deref())
+}
+
 pub(crate) fn format_report(
     report: ReportRef<'_, Dynamic, Uncloneable, Local>,
     formatter: &mut fmt::Formatter<'_>,
     report_formatting_function: FormattingFunction,
 ) -> fmt::Result {
     use_hooks(|hook_data: Option<&HookData>| {
-        if let Some(hook_data) = hook_data
-            && let Some(hook) = &hook_data.report_formatter
-        {
+        if let Some(hook) = selected_report_formatter(hook_data) {
             hook.format_report(report, formatter, report_formatting_function)
         } else {
             DefaultReportFormatter::DEFAULT.format_report(
@@ -275,9 +310,7 @@ pub(crate) fn format_reports(
     report_formatting_function: FormattingFunction,
 ) -> fmt::Result {
     use_hooks(|hook_data: Option<&HookData>| {
-        if let Some(hook_data) = hook_data
-            && let Some(hook) = &hook_data.report_formatter
-        {
+        if let Some(hook) = selected_report_formatter(hook_data) {
             hook.format_reports(reports, formatter, report_formatting_function)
         } else {
             DefaultReportFormatter::DEFAULT.format_reports(
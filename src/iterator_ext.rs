@@ -1,7 +1,11 @@
 use alloc::vec::Vec;
 use core::{iter::FusedIterator, mem};
 
-use crate::{IntoReport, report_collection::ReportCollection};
+use crate::{
+    IntoReport, Report,
+    markers::{self, Mutable},
+    report_collection::ReportCollection,
+};
 
 /// Extension methods for iterators over `Result` types to collect errors.
 ///
@@ -260,3 +264,83 @@ where
         Ok(out)
     }
 }
+
+/// Extension methods for iterators over [`Report`]s.
+///
+/// Where [`IteratorExt`] separates the errors from the successful values of an
+/// iterator over `Result`s, this trait covers the case where the iterator
+/// contains only errors, for instance the failures of a batch of jobs.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::prelude::*;
+///
+/// let failures = ["job 1 timed out", "job 4 was cancelled"]
+///     .into_iter()
+///     .map(|failure| report!(failure));
+///
+/// let report: Report<&str> = failures.into_report_with_context("2 jobs failed");
+/// assert_eq!(report.children().len(), 2);
+/// ```
+pub trait ReportIteratorExt<C: ?Sized + 'static, O: 'static, T: 'static>:
+    Sized + Iterator<Item = Report<C, O, T>>
+{
+    /// Collects the reports into a [`ReportCollection`].
+    ///
+    /// Each report is converted into a cloneable report, as required by
+    /// [`ReportCollection`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{prelude::*, report_collection::ReportCollection};
+    ///
+    /// let reports = vec![report!("error A"), report!("error B")];
+    /// let collection: ReportCollection = reports.into_iter().into_report_collection();
+    /// assert_eq!(collection.len(), 2);
+    /// ```
+    fn into_report_collection(self) -> ReportCollection<C, T>;
+
+    /// Collects the reports into a [`ReportCollection`], and creates a new
+    /// [`Report`] with the given context and the collected reports as its
+    /// children.
+    ///
+    /// This is a shorthand for
+    /// `iter.into_report_collection().context(context)`, see
+    /// [`ReportCollection::context`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let reports = vec![report!("error A"), report!("error B")];
+    /// let report: Report<&str> = reports.into_iter().into_report_with_context("both failed");
+    /// assert_eq!(report.children().len(), 2);
+    /// ```
+    #[track_caller]
+    fn into_report_with_context<D>(self, context: D) -> Report<D, Mutable, T>
+    where
+        D: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug;
+}
+
+impl<C: ?Sized + 'static, O: 'static, T: 'static, I> ReportIteratorExt<C, O, T> for I
+where
+    I: Iterator<Item = Report<C, O, T>>,
+    O: markers::ReportOwnershipMarker,
+{
+    #[inline]
+    fn into_report_collection(self) -> ReportCollection<C, T> {
+        self.collect()
+    }
+
+    #[inline]
+    #[track_caller]
+    fn into_report_with_context<D>(self, context: D) -> Report<D, Mutable, T>
+    where
+        D: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        self.into_report_collection().context(context)
+    }
+}
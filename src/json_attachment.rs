@@ -0,0 +1,170 @@
+//! Attachments holding structured JSON data.
+//!
+//! When some relevant state is already available as a [`serde_json::Value`],
+//! attaching it with `attach(format!("{value:?}"))` turns it into a string
+//! and loses its structure. A [`JsonAttachment`], usually added using
+//! [`Report::attach_json`], keeps the value as it is: it is pretty-printed
+//! when the report is displayed, and emitted verbatim when the report is
+//! written as JSON, instead of being re-encoded as a JSON string.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{json_attachment::JsonAttachment, prelude::*};
+//! use serde_json::json;
+//!
+//! let report = report!("order rejected").attach_json(json!({
+//!     "order_id": 1234,
+//!     "items": ["apple", "pear"],
+//! }));
+//!
+//! let output = report.to_string();
+//! assert!(output.contains(r#""order_id": 1234"#));
+//!
+//! let value = report
+//!     .attachments()
+//!     .iter()
+//!     .find_map(|attachment| attachment.downcast_inner::<JsonAttachment>())
+//!     .unwrap();
+//! assert_eq!(value.0["items"][1], "pear");
+//! ```
+//!
+//! [`Report::attach_json`]: crate::Report::attach_json
+
+use core::fmt;
+
+use serde_json::Value;
+
+use crate::handlers::{AttachmentHandler, JsonSink};
+
+/// An attachment holding a [`serde_json::Value`].
+///
+/// When displayed, the value is pretty-printed as JSON. Use
+/// [`JsonAttachmentHandler`] to format it as part of a report.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::json_attachment::JsonAttachment;
+/// use serde_json::json;
+///
+/// let attachment = JsonAttachment::from(json!({ "retries": 3 }));
+/// assert_eq!(attachment.to_string(), "{\n  \"retries\": 3\n}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonAttachment(pub Value);
+
+impl From<Value> for JsonAttachment {
+    fn from(value: Value) -> Self {
+        Self(value)
+    }
+}
+
+/// Pretty-prints the value as JSON.
+impl fmt::Display for JsonAttachment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.0)
+    }
+}
+
+/// Handler for formatting [`JsonAttachment`] attachments.
+///
+/// The value is pretty-printed when displayed, and printed on a single line
+/// when debug formatted. When emitted as JSON, the value is written as it is,
+/// so its structure is kept in the output of structured formatters.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     json_attachment::{JsonAttachment, JsonAttachmentHandler},
+///     prelude::*,
+/// };
+/// use serde_json::json;
+///
+/// let report = report!("error")
+///     .attach_custom::<JsonAttachmentHandler, _>(JsonAttachment(json!([1, 2, 3])));
+/// ```
+#[derive(Copy, Clone)]
+pub struct JsonAttachmentHandler;
+
+impl AttachmentHandler<JsonAttachment> for JsonAttachmentHandler {
+    fn display(value: &JsonAttachment, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &JsonAttachment, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&value.0, f)
+    }
+
+    fn json(value: &JsonAttachment, sink: &mut dyn JsonSink) -> fmt::Result {
+        emit_value(&value.0, sink)
+    }
+}
+
+/// Emits a [`Value`] to a [`JsonSink`], keeping its structure.
+fn emit_value(value: &Value, sink: &mut dyn JsonSink) -> fmt::Result {
+    match value {
+        Value::Null => sink.null(),
+        Value::Bool(value) => sink.bool(*value),
+        Value::Number(number) => {
+            if let Some(number) = number.as_u64() {
+                sink.u64(number)
+            } else if let Some(number) = number.as_i64() {
+                sink.i64(number)
+            } else if let Some(number) = number.as_f64() {
+                sink.f64(number)
+            } else {
+                sink.display(number)
+            }
+        }
+        Value::String(value) => sink.str(value),
+        Value::Array(values) => {
+            sink.begin_array()?;
+            for value in values {
+                emit_value(value, sink)?;
+            }
+            sink.end_array()
+        }
+        Value::Object(entries) => {
+            sink.begin_object()?;
+            for (key, value) in entries {
+                sink.key(key)?;
+                emit_value(value, sink)?;
+            }
+            sink.end_object()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::handlers::JsonWriter;
+
+    #[test]
+    fn test_json_attachment_send_sync() {
+        static_assertions::assert_impl_all!(JsonAttachment: Send, Sync);
+    }
+
+    #[test]
+    fn test_json_attachment_json_is_verbatim() {
+        let value = json!({
+            "id": 7,
+            "offset": -3,
+            "ratio": 0.5,
+            "tags": ["a", "b\"c"],
+            "owner": null,
+            "active": true,
+        });
+        let mut writer = JsonWriter::new(String::new());
+        JsonAttachmentHandler::json(&JsonAttachment(value.clone()), &mut writer).unwrap();
+
+        let output = writer.into_inner();
+        assert_eq!(serde_json::from_str::<Value>(&output).unwrap(), value);
+    }
+}
@@ -0,0 +1,153 @@
+//! Key/value attachments.
+//!
+//! Many attachments are simple `key: value` pairs, such as a user id or the
+//! path of a file. Instead of formatting them into a single string with
+//! `attach(format!("key: {value}"))`, they can be added as a [`KeyValue`]
+//! using [`Report::attach_kv`]. This keeps the key and the value separate, so
+//! structured formatters can emit them as `{"key": "value"}`, and text
+//! formatters can align the keys of several attachments.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{key_value::KeyValue, prelude::*};
+//!
+//! let report = report!("request failed")
+//!     .attach_kv("user_id", 42)
+//!     .attach_kv("path", "/api/orders");
+//!
+//! let output = report.to_string();
+//! assert!(output.contains("user_id: 42"));
+//! assert!(output.contains("path   : /api/orders"));
+//!
+//! let keys: Vec<&str> = report
+//!     .attachments()
+//!     .iter()
+//!     .filter_map(|attachment| attachment.downcast_inner::<KeyValue>())
+//!     .map(|key_value| key_value.key.as_ref())
+//!     .collect();
+//! assert_eq!(keys, ["user_id", "path"]);
+//! ```
+//!
+//! [`Report::attach_kv`]: crate::Report::attach_kv
+
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+};
+use core::fmt;
+
+use crate::handlers::{AttachmentHandler, JsonSink};
+
+/// An attachment consisting of a key and a formatted value.
+///
+/// When displayed, a [`KeyValue`] is formatted as `key: value`. If a width is
+/// given, such as with `{:8}`, the key is padded to that width so that the
+/// values of several attachments line up.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::key_value::KeyValue;
+///
+/// let key_value = KeyValue::new("user_id", 42);
+/// assert_eq!(key_value.to_string(), "user_id: 42");
+///
+/// // Align the values of several key/value pairs
+/// let path = KeyValue::new("path", "/api/orders");
+/// assert_eq!(format!("{key_value:7}"), "user_id: 42");
+/// assert_eq!(format!("{path:7}"), "path   : /api/orders");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyValue {
+    /// The key of the attachment.
+    pub key: Cow<'static, str>,
+    /// The formatted value of the attachment.
+    pub value: String,
+}
+
+impl KeyValue {
+    /// Creates a new [`KeyValue`], formatting the value using its
+    /// [`Display`](fmt::Display) implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::key_value::KeyValue;
+    ///
+    /// let key_value = KeyValue::new("attempt", 3);
+    /// assert_eq!(key_value.key, "attempt");
+    /// assert_eq!(key_value.value, "3");
+    /// ```
+    pub fn new(key: impl Into<Cow<'static, str>>, value: impl fmt::Display) -> Self {
+        Self {
+            key: key.into(),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Formats the pair as `key: value`, padding the key to the width of the
+/// formatter if one is given.
+impl fmt::Display for KeyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.width() {
+            Some(width) => write!(f, "{:<width$}: {}", self.key, self.value),
+            None => write!(f, "{}: {}", self.key, self.value),
+        }
+    }
+}
+
+/// Handler for formatting [`KeyValue`] attachments.
+///
+/// The attachment is displayed as `key: value`, with the key padded to the
+/// width of the formatter if one is given. When emitted as JSON, it is
+/// written as an object with a single entry, `{"key": "value"}`.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     key_value::{KeyValue, KeyValueHandler},
+///     prelude::*,
+/// };
+///
+/// let report = report!("error").attach_custom::<KeyValueHandler, _>(KeyValue::new("user_id", 42));
+/// ```
+#[derive(Copy, Clone)]
+pub struct KeyValueHandler;
+
+impl AttachmentHandler<KeyValue> for KeyValueHandler {
+    fn display(value: &KeyValue, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &KeyValue, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {:?}", value.key, value.value)
+    }
+
+    fn json(value: &KeyValue, sink: &mut dyn JsonSink) -> fmt::Result {
+        sink.begin_object()?;
+        sink.key(&value.key)?;
+        sink.str(&value.value)?;
+        sink.end_object()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::JsonWriter;
+
+    #[test]
+    fn test_key_value_send_sync() {
+        static_assertions::assert_impl_all!(KeyValue: Send, Sync);
+    }
+
+    #[test]
+    fn test_key_value_json() {
+        let mut writer = JsonWriter::new(String::new());
+        KeyValueHandler::json(&KeyValue::new("path", "/tmp/\"x\""), &mut writer).unwrap();
+        assert_eq!(writer.into_inner(), r#"{"path":"/tmp/\"x\""}"#);
+    }
+}
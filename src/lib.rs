@@ -18,6 +18,9 @@
 // Extra checks on nightly
 #![cfg_attr(nightly_extra_checks, feature(rustdoc_missing_doc_code_examples))]
 #![cfg_attr(nightly_extra_checks, forbid(rustdoc::missing_doc_code_examples))]
+// Unstable `Error::provide` support, enabled with
+// `--cfg rootcause_unstable_error_provide` on nightly
+#![cfg_attr(rootcause_unstable_error_provide, feature(error_generic_member_access))]
 // Make docs.rs generate better docs
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
@@ -351,6 +354,8 @@
 //! [`error-stack::Report`]: https://docs.rs/error-stack/latest/error_stack/struct.Report.html
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 #[macro_use]
 mod macros;
@@ -360,12 +365,26 @@ pub mod hooks;
 pub mod markers;
 
 pub mod compat;
+pub mod counted;
+pub mod error_code;
+pub mod external_attachment;
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+pub mod help;
+#[cfg(feature = "serde_json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+pub mod json_attachment;
+pub mod key_value;
 pub mod option_ext;
 pub mod prelude;
 mod report;
 pub mod report_attachment;
 pub mod report_attachments;
 pub mod report_collection;
+pub mod report_testing;
+pub mod severity;
+pub mod source_snippet;
 
 mod into_report;
 mod iterator_ext;
@@ -376,7 +395,7 @@ mod util;
 pub use self::{
     into_report::{IntoReport, IntoReportCollection},
     report::{
-        iter::{DowncastIterator, ReportIter},
+        iter::{DowncastIterator, FilterSubtrees, ReportIter},
         mut_::ReportMut,
         owned::Report,
         ref_::ReportRef,
@@ -431,10 +450,10 @@ pub mod __private {
     #[doc(hidden)]
     pub use alloc::format;
     #[doc(hidden)]
-    pub use core::{format_args, result::Result::Err};
+    pub use core::{format_args, result::Result::Err, stringify};
 
     use crate::{
-        Report, handlers,
+        IntoReport, Report, handlers,
         markers::{self, Dynamic},
         report_attachment::ReportAttachment,
     };
@@ -470,11 +489,81 @@ pub mod __private {
         }
     }
 
+    #[doc(hidden)]
+    #[inline]
+    #[cold]
+    #[must_use]
+    #[track_caller]
+    pub fn format_report_with_source<T, S>(
+        _thread_safety: T,
+        args: fmt::Arguments<'_>,
+        source: S,
+    ) -> Report<Dynamic, markers::Mutable, T>
+    where
+        S: IntoReport<T>,
+        &'static str: markers::ObjectMarkerFor<T>,
+        alloc::string::String: markers::ObjectMarkerFor<T>,
+        markers::SendSync: kind::ContextThreadSafety<T>,
+    {
+        use kind::ContextThreadSafety;
+
+        let child = source.into_report().into_dynamic().into_cloneable();
+        if let Some(message) = args.as_str() {
+            markers::SendSync
+                .new_report_with_child::<handlers::Display, _>(message, child)
+                .into_dynamic()
+        } else {
+            markers::SendSync
+                .new_report_with_child::<handlers::Display, _>(fmt::format(args), child)
+                .into_dynamic()
+        }
+    }
+
+    /// Support code for `#[derive(RootcauseContext)]`.
+    #[doc(hidden)]
+    pub mod derive {
+        use crate::handlers::ContextHandler;
+
+        /// Implemented by the derive macro for types using
+        /// `#[rootcause(display = "...")]`.
+        #[doc(hidden)]
+        pub trait ContextDisplay {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result;
+        }
+
+        /// The handler for types using `#[rootcause(display = "...")]`.
+        #[doc(hidden)]
+        #[derive(Copy, Clone)]
+        pub struct DisplayHandler;
+
+        impl<C> ContextHandler<C> for DisplayHandler
+        where
+            C: ContextDisplay + core::fmt::Debug,
+        {
+            fn source(_context: &C) -> Option<&(dyn core::error::Error + 'static)> {
+                None
+            }
+
+            fn display(context: &C, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                ContextDisplay::fmt(context, f)
+            }
+
+            fn debug(context: &C, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(context, f)
+            }
+        }
+    }
+
     #[doc(hidden)]
     pub mod kind {
+        use core::marker::PhantomData;
+
         use crate::{
-            Report, handlers, markers, report_attachment::ReportAttachment,
-            report_attachments::ReportAttachments, report_collection::ReportCollection,
+            IntoReport, Report, handlers,
+            markers::{self, Dynamic},
+            report_attachment::ReportAttachment,
+            report_attachments::ReportAttachments,
+            report_collection::ReportCollection,
         };
 
         #[doc(hidden)]
@@ -500,11 +589,21 @@ pub mod __private {
 
         impl<C> LocalKind for &C where C: markers::ObjectMarkerFor<markers::Local> {}
 
+        #[doc(hidden)]
+        pub trait HandlerContextKind<C: handlers::RootcauseContext> {
+            #[inline(always)]
+            fn handler(&self) -> PhantomData<C::Handler> {
+                PhantomData
+            }
+        }
+
+        impl<C> HandlerContextKind<C> for &&&&Wrap<'_, C> where C: handlers::RootcauseContext {}
+
         #[doc(hidden)]
         pub trait HandlerErrorKind {
             #[inline(always)]
-            fn handler(&self) -> handlers::Error {
-                handlers::Error
+            fn handler(&self) -> PhantomData<handlers::Error> {
+                PhantomData
             }
         }
 
@@ -513,8 +612,8 @@ pub mod __private {
         #[doc(hidden)]
         pub trait HandlerDisplayKind {
             #[inline(always)]
-            fn handler(&self) -> handlers::Display {
-                handlers::Display
+            fn handler(&self) -> PhantomData<handlers::Display> {
+                PhantomData
             }
         }
 
@@ -523,8 +622,8 @@ pub mod __private {
         #[doc(hidden)]
         pub trait HandlerDebugKind {
             #[inline(always)]
-            fn handler(&self) -> handlers::Debug {
-                handlers::Debug
+            fn handler(&self) -> PhantomData<handlers::Debug> {
+                PhantomData
             }
         }
 
@@ -533,8 +632,8 @@ pub mod __private {
         #[doc(hidden)]
         pub trait HandlerAnyKind {
             #[inline(always)]
-            fn handler(&self) -> handlers::Any {
-                handlers::Any
+            fn handler(&self) -> PhantomData<handlers::Any> {
+                PhantomData
             }
         }
 
@@ -544,7 +643,7 @@ pub mod __private {
         #[must_use]
         #[track_caller]
         pub fn macro_helper_new_report<H, T, C>(
-            _handler: H,
+            _handler: PhantomData<H>,
             _thread_safety: T,
             context: C,
         ) -> Report<C, markers::Mutable, T>
@@ -555,11 +654,100 @@ pub mod __private {
             Report::from_parts::<H>(context, ReportCollection::new(), ReportAttachments::new())
         }
 
+        /// Creates a report with the given child, for a context with the
+        /// thread safety `Self` in a report with the thread safety `T`.
+        ///
+        /// Contexts that are `Send + Sync` are recorded as such even in
+        /// [`Local`](markers::Local) reports, so that
+        /// [`Report::try_into_sendsync`] can succeed for the report.
+        #[doc(hidden)]
+        pub trait ContextThreadSafety<T>: Sized {
+            #[track_caller]
+            fn new_report_with_child<H, C>(
+                self,
+                context: C,
+                child: Report<Dynamic, markers::Cloneable, T>,
+            ) -> Report<C, markers::Mutable, T>
+            where
+                H: handlers::ContextHandler<C>,
+                C: markers::ObjectMarkerFor<T> + markers::ObjectMarkerFor<Self>;
+        }
+
+        impl ContextThreadSafety<markers::SendSync> for markers::SendSync {
+            #[track_caller]
+            fn new_report_with_child<H, C>(
+                self,
+                context: C,
+                child: Report<Dynamic, markers::Cloneable, markers::SendSync>,
+            ) -> Report<C, markers::Mutable, markers::SendSync>
+            where
+                H: handlers::ContextHandler<C>,
+                C: markers::ObjectMarkerFor<markers::SendSync>,
+            {
+                child.context_custom::<H, _>(context)
+            }
+        }
+
+        impl ContextThreadSafety<markers::Local> for markers::SendSync {
+            #[track_caller]
+            fn new_report_with_child<H, C>(
+                self,
+                context: C,
+                child: Report<Dynamic, markers::Cloneable, markers::Local>,
+            ) -> Report<C, markers::Mutable, markers::Local>
+            where
+                H: handlers::ContextHandler<C>,
+                C: markers::ObjectMarkerFor<markers::Local>
+                    + markers::ObjectMarkerFor<markers::SendSync>,
+            {
+                Report::from_parts_sendsync_context::<H>(
+                    context,
+                    ReportCollection::from([child]),
+                    ReportAttachments::new(),
+                )
+            }
+        }
+
+        impl ContextThreadSafety<markers::Local> for markers::Local {
+            #[track_caller]
+            fn new_report_with_child<H, C>(
+                self,
+                context: C,
+                child: Report<Dynamic, markers::Cloneable, markers::Local>,
+            ) -> Report<C, markers::Mutable, markers::Local>
+            where
+                H: handlers::ContextHandler<C>,
+                C: markers::ObjectMarkerFor<markers::Local>,
+            {
+                child.context_custom::<H, _>(context)
+            }
+        }
+
+        #[doc(hidden)]
+        #[must_use]
+        #[track_caller]
+        pub fn macro_helper_new_report_with_source<H, T, U, C, S>(
+            _handler: PhantomData<H>,
+            _thread_safety: T,
+            context_thread_safety: U,
+            context: C,
+            source: S,
+        ) -> Report<C, markers::Mutable, T>
+        where
+            H: handlers::ContextHandler<C>,
+            C: markers::ObjectMarkerFor<T> + markers::ObjectMarkerFor<U>,
+            S: IntoReport<T>,
+            U: ContextThreadSafety<T>,
+        {
+            let child = source.into_report().into_dynamic().into_cloneable();
+            context_thread_safety.new_report_with_child::<H, C>(context, child)
+        }
+
         #[doc(hidden)]
         #[must_use]
         #[track_caller]
         pub fn macro_helper_new_report_attachment<H, T, A>(
-            _handler: H,
+            _handler: PhantomData<H>,
             _thread_safety: T,
             attachment: A,
         ) -> ReportAttachment<A, T>
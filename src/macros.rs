@@ -40,10 +40,33 @@
 /// This mode automatically:
 /// - Infers the thread-safety marker based on whether the context type is `Send
 ///   + Sync`
-/// - Selects the appropriate handler based on the context type
+/// - Selects the appropriate handler based on the context type, or the handler
+///   chosen by the type's [`RootcauseContext`](crate::handlers::RootcauseContext)
+///   implementation
 ///
 /// This is similar to calling [`Report::new`], but with better type inference.
 ///
+/// ## Wrapping a Source Error
+///
+/// In both modes, a `source:` argument (or its alias `caused_by:`) can be added
+/// after the context. The source is converted into a report using
+/// [`IntoReport`] and becomes the single child of the new report, just like
+/// calling [`Report::context`] on it:
+///
+/// ```
+/// use rootcause::prelude::*;
+/// # use std::io;
+///
+/// let error = io::Error::new(io::ErrorKind::NotFound, "file not found");
+/// let report: Report = report!("Failed to load the config", source: error);
+/// assert_eq!(report.children().len(), 1);
+/// ```
+///
+/// Format arguments are not supported together with `source:`, but the message
+/// can still capture variables by name, as in `report!("Failed to open
+/// {path}", source: error)`. The new report is [`Local`](crate::markers::Local)
+/// if the source or the context is not `Send + Sync`.
+///
 /// # Examples
 ///
 /// ## Basic String Reports
@@ -119,6 +142,41 @@
 /// );
 /// ```
 ///
+/// ## Reports with a Source
+///
+/// ```
+/// use std::{any::TypeId, io, rc::Rc};
+///
+/// use rootcause::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct ConfigError {
+///     path: &'static str,
+/// }
+/// # impl std::fmt::Display for ConfigError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         write!(f, "Invalid config at {}", self.path)
+/// #     }
+/// # }
+///
+/// // A message capturing a variable, with an error as its source
+/// let path = "config.toml";
+/// let error = io::Error::new(io::ErrorKind::NotFound, "file not found");
+/// let report: Report = report!("Failed to open {path}", source: error);
+/// assert_eq!(report.format_current_context().to_string(), "Failed to open config.toml");
+/// let child = report.children().get(0).unwrap();
+/// assert_eq!(child.current_context_type_id(), TypeId::of::<io::Error>());
+///
+/// // A context object with another report as its source
+/// let report: Report<ConfigError> =
+///     report!(ConfigError { path: "config.toml" }, caused_by: report);
+/// assert_eq!(report.children().len(), 1);
+///
+/// // A source that is not `Send + Sync` makes the new report `Local`
+/// let local_report = report!("Failed to start", source: report!(Rc::new("local")));
+/// let local_report: Report<markers::Dynamic, markers::Mutable, markers::Local> = local_report;
+/// ```
+///
 /// ## Local (Non-Send) Reports
 ///
 /// When using non-thread-safe types like [`Rc`](std::rc::Rc), the macro
@@ -143,20 +201,57 @@
 ///
 /// [`format!()`]: std::format
 /// [`Report::new`]: crate::Report::new
+/// [`Report::context`]: crate::Report::context
+/// [`IntoReport`]: crate::IntoReport
 #[macro_export]
 macro_rules! report {
     ($msg:literal $(,)?) => {
         $crate::__private::format_report($crate::__private::format_args!($msg))
     };
+    ($msg:literal, source: $source:expr $(,)?) => {
+        {
+            use $crate::__private::kind::*;
+            let source = $source;
+            let thread_safety = (&source).thread_safety();
+            $crate::__private::format_report_with_source(
+                thread_safety,
+                $crate::__private::format_args!($msg),
+                source,
+            )
+        }
+    };
+    ($msg:literal, caused_by: $source:expr $(,)?) => {
+        $crate::report!($msg, source: $source)
+    };
     ($context:expr $(,)?) => {
         {
             use $crate::__private::kind::*;
             let context = $context;
-            let handler = (&&&&Wrap(&context)).handler();
+            let handler = (&&&&&Wrap(&context)).handler();
             let thread_safety = (&context).thread_safety();
             macro_helper_new_report(handler, thread_safety, context)
         }
     };
+    ($context:expr, source: $source:expr $(,)?) => {
+        {
+            use $crate::__private::kind::*;
+            let parts = ($context, $source);
+            let thread_safety = (&parts).thread_safety();
+            let (context, source) = parts;
+            let context_thread_safety = (&context).thread_safety();
+            let handler = (&&&&&Wrap(&context)).handler();
+            macro_helper_new_report_with_source(
+                handler,
+                thread_safety,
+                context_thread_safety,
+                context,
+                source,
+            )
+        }
+    };
+    ($context:expr, caused_by: $source:expr $(,)?) => {
+        $crate::report!($context, source: $source)
+    };
     ($fmt:expr, $($arg:tt)*) => {
         $crate::Report::<
             _,
@@ -338,3 +433,228 @@ macro_rules! bail {
         return $crate::__private::Err($crate::report!($($args)*).into())
     };
 }
+
+/// Returns early with an error report if a condition is not satisfied.
+///
+/// `ensure!(cond, ...)` is equivalent to `if !cond { bail!(...); }`. The
+/// arguments after the condition are passed to [`bail!`], so they accept the
+/// same forms as the [`report!`] macro: a format string, or a context object
+/// whose handler and thread safety are detected automatically. The location
+/// of the `ensure!` invocation is captured, just like with [`report!`].
+///
+/// The macro is similar to the [`ensure!`] macro from the [`anyhow`] crate.
+///
+/// # Without a Message
+///
+/// If only a condition is given, the report contains the condition as text,
+/// such as ``Condition failed: `count > 0` ``. If the condition is a
+/// comparison with `==`, the values of both sides are included using their
+/// [`Debug`](core::fmt::Debug) implementation:
+///
+/// ```
+/// use rootcause::prelude::*;
+///
+/// fn check_checksum(expected: u32, actual: u32) -> Result<(), Report> {
+///     ensure!(expected == actual);
+///     Ok(())
+/// }
+///
+/// let report = check_checksum(7, 9).unwrap_err();
+/// assert_eq!(
+///     report.format_current_context().to_string(),
+///     "Condition failed: `expected == actual` (left: `7`, right: `9`)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct EmptyInput;
+///
+/// fn parse_port(input: &str) -> Result<u16, Report> {
+///     ensure!(!input.is_empty(), EmptyInput);
+///     ensure!(input.len() <= 5, "Port number too long: {input}");
+///     let port: u16 = input.parse()?;
+///     ensure!(port != 0);
+///     Ok(port)
+/// }
+///
+/// assert!(parse_port("8080").is_ok());
+/// assert!(parse_port("").unwrap_err().downcast_current_context::<EmptyInput>().is_some());
+/// assert_eq!(
+///     parse_port("0").unwrap_err().format_current_context().to_string(),
+///     "Condition failed: `port != 0`"
+/// );
+/// ```
+///
+/// [`ensure!`]: https://docs.rs/anyhow/latest/anyhow/macro.ensure.html
+/// [`anyhow`]: https://docs.rs/anyhow/latest/anyhow/
+#[macro_export]
+macro_rules! ensure {
+    // The condition is split at a top-level `==` so both sides can be shown
+    // when the comparison fails. Conditions containing a top-level `&&` or
+    // `||` are kept whole, since splitting them would change the precedence.
+    () => {
+        ::core::compile_error!("`ensure!` requires a condition, such as `ensure!(count > 0)`")
+    };
+    (@plain [$($cond:tt)*] $(,)?) => {{
+        let condition: bool = $($cond)*;
+        if !condition {
+            $crate::bail!(
+                "Condition failed: `{}`",
+                $crate::__private::stringify!($($cond)*)
+            );
+        }
+    }};
+    (@plain [$($cond:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::ensure!(@plain [$($cond)* $next] $($rest)*)
+    };
+    (@left [$($left:tt)*] && $($rest:tt)*) => {
+        $crate::ensure!(@plain [$($left)* &&] $($rest)*)
+    };
+    (@left [$($left:tt)*] || $($rest:tt)*) => {
+        $crate::ensure!(@plain [$($left)* ||] $($rest)*)
+    };
+    (@left [$($left:tt)+] == $($rest:tt)+) => {
+        $crate::ensure!(@right [$($left)+] [] $($rest)+)
+    };
+    (@left [$($left:tt)*] $(,)?) => {
+        $crate::ensure!(@plain [$($left)*])
+    };
+    (@left [$($left:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::ensure!(@left [$($left)* $next] $($rest)*)
+    };
+    (@right [$($left:tt)+] [$($right:tt)*] && $($rest:tt)*) => {
+        $crate::ensure!(@plain [$($left)+ == $($right)* &&] $($rest)*)
+    };
+    (@right [$($left:tt)+] [$($right:tt)*] || $($rest:tt)*) => {
+        $crate::ensure!(@plain [$($left)+ == $($right)* ||] $($rest)*)
+    };
+    (@right [$($left:tt)+] [$($right:tt)+] $(,)?) => {
+        match (&($($left)+), &($($right)+)) {
+            (left, right) => {
+                if !(*left == *right) {
+                    $crate::bail!(
+                        "Condition failed: `{}` (left: `{:?}`, right: `{:?}`)",
+                        $crate::__private::stringify!($($left)+ == $($right)+),
+                        left,
+                        right,
+                    );
+                }
+            }
+        }
+    };
+    (@right [$($left:tt)+] [$($right:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::ensure!(@right [$($left)+] [$($right)* $next] $($rest)*)
+    };
+    ($cond:expr, $($args:tt)+) => {{
+        let condition: bool = $cond;
+        if !condition {
+            $crate::bail!($($args)+);
+        }
+    }};
+    ($($cond:tt)+) => {
+        $crate::ensure!(@left [] $($cond)+)
+    };
+}
+
+/// Asserts that a report has the expected structure.
+///
+/// The report is converted into a [`ReportShape`] and compared with the
+/// expected shape. On a mismatch, the macro panics with a description of the
+/// first difference, followed by both shapes formatted as trees.
+///
+/// The report can be a [`Report`] or a [`ReportRef`], and is borrowed by the
+/// macro.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{assert_report_matches, prelude::*, report_testing::ReportShape};
+///
+/// let report = report!("disk full")
+///     .attach("path: /var/log")
+///     .context("Failed to save");
+///
+/// assert_report_matches!(
+///     report,
+///     ReportShape::new("Failed to save")
+///         .child(ReportShape::new("disk full").attachment("path: /var/log")),
+/// );
+/// ```
+///
+/// ```should_panic
+/// use rootcause::{assert_report_matches, prelude::*, report_testing::ReportShape};
+///
+/// let report = report!("disk full").context("Failed to save");
+/// assert_report_matches!(report, ReportShape::new("Failed to save"));
+/// ```
+///
+/// [`ReportShape`]: crate::report_testing::ReportShape
+/// [`Report`]: crate::Report
+/// [`ReportRef`]: crate::ReportRef
+#[macro_export]
+macro_rules! assert_report_matches {
+    ($report:expr, $expected:expr $(,)?) => {{
+        let actual = $crate::report_testing::ReportShape::from(&$report);
+        let expected: $crate::report_testing::ReportShape = $expected;
+        if let ::core::option::Option::Some(difference) = expected.difference(&actual) {
+            ::core::panic!(
+                "report does not match the expected shape, {difference}\n\nexpected:\n{expected}\nactual:\n{actual}"
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use crate::Report;
+
+    fn message(result: Result<(), Report>) -> String {
+        result.unwrap_err().format_current_context().to_string()
+    }
+
+    #[test]
+    fn test_ensure_keeps_precedence_of_logical_operators() {
+        fn check(a: u32, b: u32, flag: bool) -> Result<(), Report> {
+            ensure!(a == b || flag);
+            Ok(())
+        }
+        assert!(check(1, 2, true).is_ok());
+        assert_eq!(
+            message(check(1, 2, false)),
+            "Condition failed: `a == b || flag`"
+        );
+    }
+
+    #[test]
+    fn test_ensure_trailing_comma() {
+        fn check(a: u32, b: u32) -> Result<(), Report> {
+            ensure!(a + 1 == b * 2,);
+            ensure!(a < 10,);
+            Ok(())
+        }
+        assert!(check(1, 1).is_ok());
+        assert_eq!(
+            message(check(2, 2)),
+            "Condition failed: `a + 1 == b * 2` (left: `3`, right: `4`)"
+        );
+        assert_eq!(message(check(11, 6)), "Condition failed: `a < 10`");
+    }
+
+    #[test]
+    fn test_ensure_captures_location() {
+        fn check() -> Result<(), Report> {
+            ensure!(false, "always fails");
+            Ok(())
+        }
+        let report = check().unwrap_err();
+        let location = report.location().unwrap();
+        assert_eq!(location.file, file!());
+    }
+}
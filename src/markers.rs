@@ -103,6 +103,13 @@
 //! // local_report cannot be sent to another thread - won't compile
 //! ```
 
+use alloc::vec::Vec;
+
+use rootcause_internals::{
+    RawAttachment, RawReport,
+    handlers::{AttachmentHandler, ContextHandler},
+};
+
 use crate::ReportMut;
 
 /// Marker type for reports with dynamic (type-erased) context.
@@ -523,6 +530,14 @@ mod sealed_report_ownership_marker {
     impl Sealed for Cloneable {}
 }
 
+mod sealed_shared_report_marker {
+    use super::*;
+
+    pub trait Sealed: 'static {}
+
+    impl Sealed for SendSync {}
+}
+
 mod sealed_object_marker {
     pub trait Sealed: 'static {}
 
@@ -661,18 +676,87 @@ impl ReportOwnershipMarker for Cloneable {
 // This trait is sealed and cannot be implemented outside of this crate. It is
 // guaranteed to only be implemented for the combinations of types and
 // thread-safety markers listed above.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a context or attachment in a report with the `{T}` marker",
+    label = "`{Self}` does not meet the requirements of the `{T}` marker",
+    note = "`SendSync` reports require their contexts and attachments to be `Send + Sync + 'static`, while `Local` reports only require `'static`",
+    note = "consider using a `Local` report, for instance by calling `into_local()` on the report, or making `{Self}` thread-safe"
+)]
 pub trait ObjectMarkerFor<T>: sealed_object_marker::Sealed + Sized + 'static {
     /// Runs report creation hooks specific to this thread-safety marker.
     #[doc(hidden)]
     #[track_caller]
     fn run_creation_hooks(report: ReportMut<'_, Dynamic, T>);
+
+    /// Creates a raw report with this object as its context.
+    ///
+    /// For the [`SendSync`] marker, the context is recorded as `Send + Sync`
+    /// in the report, which allows [`Local`] reports to be converted back
+    /// using [`Report::try_into_sendsync`](crate::Report::try_into_sendsync).
+    #[doc(hidden)]
+    fn new_raw_report<H: ContextHandler<Self>>(
+        self,
+        children: Vec<RawReport>,
+        attachments: Vec<RawAttachment>,
+    ) -> RawReport;
+
+    /// Creates a raw attachment holding this object.
+    ///
+    /// For the [`SendSync`] marker, the attachment is recorded as
+    /// `Send + Sync`, like in [`ObjectMarkerFor::new_raw_report`].
+    #[doc(hidden)]
+    fn new_raw_attachment<H: AttachmentHandler<Self>>(self) -> RawAttachment;
 }
 
+/// Marker trait for thread-safety markers of reports that can be stored in
+/// shared collections.
+///
+/// This trait is only implemented for [`SendSync`]. It is used as a bound on
+/// [`Report::into_shared`](crate::Report::into_shared), so that calling it on
+/// a [`Local`] report fails to compile with an explanation, instead of
+/// producing a report that cannot be shared between threads.
+///
+/// # Implementation
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+/// # Examples
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+///
+/// use rootcause::prelude::*;
+///
+/// let report = report!(Rc::new("not thread-safe")).into_shared();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "a report with the `{Self}` marker cannot be shared between threads",
+    label = "this report has the `{Self}` marker",
+    note = "shared reports must have the `SendSync` marker, so that they can be cloned and used from any thread",
+    note = "consider making the contexts and attachments of the report `Send + Sync`, or keeping the report `Local` by using `into_dynamic().into_cloneable()` instead"
+)]
+pub trait SharedReportMarker: sealed_shared_report_marker::Sealed {}
+impl SharedReportMarker for SendSync {}
+
 impl<O: Sized + 'static> ObjectMarkerFor<Local> for O {
     #[inline(always)]
     fn run_creation_hooks(report: ReportMut<'_, Dynamic, Local>) {
         crate::hooks::report_creation::run_creation_hooks_local(report);
     }
+
+    #[inline(always)]
+    fn new_raw_report<H: ContextHandler<Self>>(
+        self,
+        children: Vec<RawReport>,
+        attachments: Vec<RawAttachment>,
+    ) -> RawReport {
+        RawReport::new::<O, H>(self, children, attachments)
+    }
+
+    #[inline(always)]
+    fn new_raw_attachment<H: AttachmentHandler<Self>>(self) -> RawAttachment {
+        RawAttachment::new::<O, H>(self)
+    }
 }
 
 impl<O: Sized + 'static> ObjectMarkerFor<SendSync> for O
@@ -683,4 +767,18 @@ where
     fn run_creation_hooks(report: ReportMut<'_, Dynamic, SendSync>) {
         crate::hooks::report_creation::run_creation_hooks_sendsync(report);
     }
+
+    #[inline(always)]
+    fn new_raw_report<H: ContextHandler<Self>>(
+        self,
+        children: Vec<RawReport>,
+        attachments: Vec<RawAttachment>,
+    ) -> RawReport {
+        RawReport::new_send_sync::<O, H>(self, children, attachments)
+    }
+
+    #[inline(always)]
+    fn new_raw_attachment<H: AttachmentHandler<Self>>(self) -> RawAttachment {
+        RawAttachment::new_send_sync::<O, H>(self)
+    }
 }
@@ -29,8 +29,9 @@
 //! - **[`Report`]**: The main error reporting type
 //! - **[`ResultExt`]**: Extension methods for `Result` types
 //! - **[`IteratorExt`]**: Extension methods for iterators
-//! - **[`report!`]** and **[`bail!`]**: Macros for creating and returning
-//!   errors
+//! - **[`ReportIteratorExt`]**: Extension methods for iterators over reports
+//! - **[`report!`]**, **[`bail!`]** and **[`ensure!`]**: Macros for creating
+//!   and returning errors
 //! - **[`handlers`]**: Built-in error handlers for common scenarios
 //! - **[`markers`]**: Type markers for controlling report behavior
 //! - **[`report_attachment!`]**: Macro for attaching contextual data
@@ -44,6 +45,10 @@
 //! specific items directly from their respective modules.
 
 pub use crate::{
-    Report, bail, compat::IntoRootcause, handlers, iterator_ext::IteratorExt, markers, report,
-    report_attachment, result_ext::ResultExt,
+    Report, bail,
+    compat::IntoRootcause,
+    ensure, handlers,
+    iterator_ext::{IteratorExt, ReportIteratorExt},
+    markers, report, report_attachment,
+    result_ext::ResultExt,
 };
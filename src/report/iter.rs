@@ -28,12 +28,39 @@ impl<'a, O, T> ReportIter<'a, O, T> {
     }
 }
 
-impl<'a, O, T> Iterator for ReportIter<'a, O, T> {
-    type Item = ReportRef<'a, Dynamic, O, T>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let cur: ReportRef<'a, Dynamic, O, T> = self.stack.pop()?;
+impl<'a, O, T> ReportIter<'a, O, T> {
+    /// Skips the entire subtree of every report for which `f` returns `false`.
+    ///
+    /// The predicate is called on the reports in the same depth-first
+    /// pre-order as they are visited by this iterator, so it is called for a
+    /// report before any of its descendants. When it returns `false`, neither
+    /// the report nor any of its descendants are yielded, and the predicate is
+    /// not called for the descendants. This differs from [`Iterator::filter`],
+    /// which only skips the report itself and still visits its descendants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let benign = report!("cache miss").context("Cache lookup failed");
+    /// let report = benign.join(report!("connection refused"), "Failed to load the user");
+    ///
+    /// let contexts: Vec<String> = report
+    ///     .iter_reports()
+    ///     .filter_subtrees(|report| report.format_current_context().to_string() != "Cache lookup failed")
+    ///     .map(|report| report.format_current_context().to_string())
+    ///     .collect();
+    /// assert_eq!(contexts, ["Failed to load the user", "connection refused"]);
+    /// ```
+    pub fn filter_subtrees<F>(self, f: F) -> FilterSubtrees<'a, O, T, F>
+    where
+        F: FnMut(ReportRef<'a, Dynamic, O, T>) -> bool,
+    {
+        FilterSubtrees { iter: self, f }
+    }
 
+    fn push_children(&mut self, cur: ReportRef<'a, Dynamic, O, T>) {
         let new_children = cur
             .children()
             .iter()
@@ -51,6 +78,15 @@ impl<'a, O, T> Iterator for ReportIter<'a, O, T> {
             })
             .rev();
         self.stack.extend(new_children);
+    }
+}
+
+impl<'a, O, T> Iterator for ReportIter<'a, O, T> {
+    type Item = ReportRef<'a, Dynamic, O, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cur: ReportRef<'a, Dynamic, O, T> = self.stack.pop()?;
+        self.push_children(cur);
         Some(cur)
     }
 }
@@ -59,6 +95,41 @@ impl<'a, O, T> FusedIterator for ReportIter<'a, O, T> {}
 
 impl<'a, O, T> Unpin for ReportIter<'a, O, T> {}
 
+/// An iterator over a report and its descendant reports in depth-first order,
+/// which skips the subtrees of the reports rejected by a predicate.
+///
+/// This iterator is created by [`ReportIter::filter_subtrees`]. See its
+/// documentation for more information.
+#[must_use]
+pub struct FilterSubtrees<'a, Ownership: 'static, ThreadSafety: 'static, F> {
+    iter: ReportIter<'a, Ownership, ThreadSafety>,
+    f: F,
+}
+
+impl<'a, O, T, F> Iterator for FilterSubtrees<'a, O, T, F>
+where
+    F: FnMut(ReportRef<'a, Dynamic, O, T>) -> bool,
+{
+    type Item = ReportRef<'a, Dynamic, O, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let cur: ReportRef<'a, Dynamic, O, T> = self.iter.stack.pop()?;
+            if (self.f)(cur) {
+                self.iter.push_children(cur);
+                return Some(cur);
+            }
+        }
+    }
+}
+
+impl<'a, O, T, F> FusedIterator for FilterSubtrees<'a, O, T, F> where
+    F: FnMut(ReportRef<'a, Dynamic, O, T>) -> bool
+{
+}
+
+impl<'a, O, T, F> Unpin for FilterSubtrees<'a, O, T, F> {}
+
 /// An iterator over all contexts that can successfully be downcasted to `D`, belonging
 /// a report and all its decendants in a depth-first order.
 ///
@@ -1,3 +1,8 @@
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::any::{Any, TypeId};
 
 use rootcause_internals::handlers::{ContextFormattingStyle, FormattingFunction};
@@ -5,7 +10,7 @@ use rootcause_internals::handlers::{ContextFormattingStyle, FormattingFunction};
 use crate::{
     ReportIter, ReportRef, handlers,
     markers::{self, Cloneable, Dynamic, Local, SendSync, Uncloneable},
-    report_attachment::ReportAttachment,
+    report_attachment::{ReportAttachment, ReportAttachmentRef},
     report_attachments::ReportAttachments,
     report_collection::ReportCollection,
     util::{ErrorNoSourceWrapper, format_helper},
@@ -310,6 +315,335 @@ impl<'a, C: ?Sized, T> ReportMut<'a, C, T> {
         self.as_mut().into_children_mut()
     }
 
+    /// Reverses the order of the child reports in place.
+    ///
+    /// Only the direct children of this report are reordered; the children
+    /// of those reports are left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut};
+    /// let mut report: Report = report!("error message");
+    /// report.children_mut().push(report!("first").into_cloneable());
+    /// report.children_mut().push(report!("second").into_cloneable());
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// report_mut.reverse_children();
+    ///
+    /// let first_child = report.children().get(0).unwrap();
+    /// assert_eq!(first_child.format_current_context().to_string(), "second");
+    /// ```
+    pub fn reverse_children(&mut self) {
+        self.children_mut().reverse();
+    }
+
+    /// Reorders the child reports so that children with the same key are
+    /// next to each other.
+    ///
+    /// The children are stably sorted by the key computed for each child, so
+    /// children with the same key keep their relative order. This is useful
+    /// for turning a large number of aggregated failures into a clustered
+    /// report, for instance by grouping them by the type of their context.
+    /// Only the direct children of this report are reordered.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut};
+    /// let mut report: Report = report!("3 jobs failed");
+    /// report.children_mut().push(report!("timed out").into_cloneable());
+    /// report
+    ///     .children_mut()
+    ///     .push(report!(std::io::Error::other("disk full")).into_dynamic().into_cloneable());
+    /// report.children_mut().push(report!("timed out").into_cloneable());
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// report_mut.group_children_by(|child| child.current_context_type_name());
+    ///
+    /// let children: Vec<String> = report
+    ///     .children()
+    ///     .iter()
+    ///     .map(|child| child.format_current_context().to_string())
+    ///     .collect();
+    /// assert_eq!(children, ["timed out", "timed out", "disk full"]);
+    /// ```
+    pub fn group_children_by<K, F>(&mut self, key: F)
+    where
+        K: Ord,
+        F: FnMut(ReportRef<'_, Dynamic, Cloneable, T>) -> K,
+    {
+        self.children_mut().sort_by_key(key);
+    }
+
+    /// Moves the child reports out of this report, leaving it without
+    /// children.
+    ///
+    /// This does not clone or reallocate the children, which makes it useful
+    /// for passes that rewrite a report: take the children, process them, and
+    /// put back the ones to keep using [`ReportMut::children_mut`] or
+    /// [`ReportMut::set_children`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut, report_collection::ReportCollection};
+    /// let mut report: Report = report!("error message");
+    /// report.children_mut().push(report!("first").into_cloneable());
+    /// report.children_mut().push(report!("second").into_cloneable());
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// let children: ReportCollection = report_mut.take_children();
+    /// assert_eq!(children.len(), 2);
+    /// assert!(report_mut.children().is_empty());
+    ///
+    /// // Put back only the second child
+    /// let second = children.into_iter().nth(1).unwrap();
+    /// report_mut.children_mut().push(second);
+    /// assert_eq!(report.children().len(), 1);
+    /// ```
+    #[must_use]
+    pub fn take_children(&mut self) -> ReportCollection<Dynamic, T> {
+        core::mem::replace(self.children_mut(), ReportCollection::new())
+    }
+
+    /// Replaces the child reports of this report.
+    ///
+    /// The previous children are dropped. Together with
+    /// [`ReportMut::take_children`], this allows rewriting the children of a
+    /// report as a whole.
+    ///
+    /// The children must have the same thread safety marker as this report,
+    /// so a [`SendSync`] report cannot receive [`Local`] children.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut, report_collection::ReportCollection};
+    /// let mut report: Report = report!("error message");
+    /// report.children_mut().push(report!("first").into_cloneable());
+    /// report.children_mut().push(report!("second").into_cloneable());
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// let children: ReportCollection = report_mut
+    ///     .take_children()
+    ///     .into_iter()
+    ///     .map(|child| child.context("while retrying"))
+    ///     .collect();
+    /// report_mut.set_children(children);
+    ///
+    /// assert_eq!(report.children().len(), 2);
+    /// assert_eq!(
+    ///     report.children().get(0).unwrap().format_current_context().to_string(),
+    ///     "while retrying"
+    /// );
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use rootcause::{prelude::*, markers::Local, report_collection::ReportCollection};
+    /// let mut report: Report = report!("error message");
+    /// let children: ReportCollection<_, Local> = ReportCollection::new_local();
+    /// report.as_mut().set_children(children);
+    /// ```
+    ///
+    /// [`SendSync`]: crate::markers::SendSync
+    /// [`Local`]: crate::markers::Local
+    pub fn set_children(&mut self, children: ReportCollection<Dynamic, T>) {
+        *self.children_mut() = children;
+    }
+
+    /// Moves the attachments matching a predicate from all descendants of
+    /// this report to this report.
+    ///
+    /// This is useful for surfacing attachments such as correlation ids,
+    /// which were added deep in the call stack, at the top of an aggregated
+    /// report. The hoisted attachments are deduplicated: an attachment is
+    /// dropped if this report already has an attachment of the same type
+    /// with the same [`Display`](core::fmt::Display) output.
+    ///
+    /// Descendants that are shared with other reports are left untouched,
+    /// together with their own descendants, since they cannot be modified
+    /// without affecting the other holders.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut};
+    /// let first = report!("timed out").attach_kv("request_id", "f3a9");
+    /// let second = report!("disk full").attach_kv("request_id", "f3a9");
+    /// let mut report = first.join(second, "2 jobs failed").into_dynamic();
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// report_mut.hoist_attachments_by(|attachment| {
+    ///     attachment.format_inner().to_string().starts_with("request_id")
+    /// });
+    ///
+    /// assert!(report.attachments().iter().any(|attachment| {
+    ///     attachment.format_inner().to_string() == "request_id: f3a9"
+    /// }));
+    /// for child in report.children().iter() {
+    ///     assert_eq!(child.attachments().len(), 1); // Only the location remains
+    /// }
+    /// ```
+    pub fn hoist_attachments_by<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(ReportAttachmentRef<'_, Dynamic>) -> bool,
+    {
+        let mut hoisted = Vec::new();
+        self.for_each_unshared_descendant_mut(&mut |mut report| {
+            let attachments =
+                core::mem::replace(report.attachments_mut(), ReportAttachments::new());
+            for attachment in attachments {
+                if predicate(attachment.as_ref()) {
+                    hoisted.push(attachment);
+                } else {
+                    report.attachments_mut().push(attachment);
+                }
+            }
+        });
+
+        let mut seen: BTreeSet<(TypeId, String)> =
+            self.attachments().iter().map(attachment_identity).collect();
+        for attachment in hoisted {
+            if seen.insert(attachment_identity(attachment.as_ref())) {
+                self.attachments_mut().push(attachment);
+            }
+        }
+    }
+
+    /// Removes the attachments of this report that have the same type and
+    /// [`Display`](core::fmt::Display) output as an earlier attachment.
+    ///
+    /// The first attachment of every group of duplicates is kept, and the
+    /// order of the remaining attachments is preserved. Only the attachments
+    /// of this report are considered, see
+    /// [`dedup_attachments_recursive`](Self::dedup_attachments_recursive) to
+    /// also deduplicate the attachments of its descendants, and
+    /// [`dedup_attachments_by`](Self::dedup_attachments_by) to use a custom
+    /// equality.
+    ///
+    /// This is useful for reports assembled from several sources, such as
+    /// multiple hooks and manual attachments, which may add the same data
+    /// more than once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut};
+    /// let mut report = report!("timed out")
+    ///     .attach("request f3a9")
+    ///     .attach("retrying")
+    ///     .attach("request f3a9")
+    ///     .into_dynamic();
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// report_mut.dedup_attachments();
+    ///
+    /// let attachments: Vec<String> = report
+    ///     .attachments()
+    ///     .iter()
+    ///     .filter(|attachment| attachment.is::<&str>())
+    ///     .map(|attachment| attachment.format_inner().to_string())
+    ///     .collect();
+    /// assert_eq!(attachments, ["request f3a9", "retrying"]);
+    /// ```
+    pub fn dedup_attachments(&mut self) {
+        let mut seen = BTreeSet::new();
+        let attachments = core::mem::replace(self.attachments_mut(), ReportAttachments::new());
+        for attachment in attachments {
+            if seen.insert(attachment_identity(attachment.as_ref())) {
+                self.attachments_mut().push(attachment);
+            }
+        }
+    }
+
+    /// Removes the attachments of this report that are duplicates of an
+    /// earlier attachment according to a custom equality.
+    ///
+    /// The function is called with an attachment that is kept and a later
+    /// attachment, and returns whether the later attachment is a duplicate of
+    /// the kept one. Duplicates are removed, and the order of the remaining
+    /// attachments is preserved.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut};
+    /// let mut report = report!("timed out")
+    ///     .attach("Request F3A9")
+    ///     .attach("request f3a9")
+    ///     .into_dynamic();
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// report_mut.dedup_attachments_by(|kept, attachment| {
+    ///     kept.inner_type_id() == attachment.inner_type_id()
+    ///         && kept
+    ///             .format_inner()
+    ///             .to_string()
+    ///             .eq_ignore_ascii_case(&attachment.format_inner().to_string())
+    /// });
+    ///
+    /// assert_eq!(report.attachments().iter().filter(|a| a.is::<&str>()).count(), 1);
+    /// ```
+    pub fn dedup_attachments_by<F>(&mut self, mut same: F)
+    where
+        F: FnMut(ReportAttachmentRef<'_, Dynamic>, ReportAttachmentRef<'_, Dynamic>) -> bool,
+    {
+        let attachments = core::mem::replace(self.attachments_mut(), ReportAttachments::new());
+        for attachment in attachments {
+            let is_duplicate = self
+                .attachments()
+                .iter()
+                .any(|kept| same(kept, attachment.as_ref()));
+            if !is_duplicate {
+                self.attachments_mut().push(attachment);
+            }
+        }
+    }
+
+    /// Removes duplicate attachments from this report and from each of its
+    /// descendants, like [`dedup_attachments`](Self::dedup_attachments).
+    ///
+    /// Every report is deduplicated on its own, so an attachment is never
+    /// removed because an equal attachment exists on another report.
+    /// Descendants that are shared with other reports are left untouched,
+    /// together with their own descendants, since they cannot be modified
+    /// without affecting the other holders.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut};
+    /// let mut report = report!("timed out")
+    ///     .attach("request f3a9")
+    ///     .attach("request f3a9")
+    ///     .context("job failed")
+    ///     .into_dynamic();
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// report_mut.dedup_attachments_recursive();
+    ///
+    /// let child = report.children().get(0).unwrap();
+    /// assert_eq!(child.attachments().iter().filter(|a| a.is::<&str>()).count(), 1);
+    /// ```
+    pub fn dedup_attachments_recursive(&mut self) {
+        self.dedup_attachments();
+        self.for_each_unshared_descendant_mut(&mut |mut report| report.dedup_attachments());
+    }
+
+    /// Calls `f` for each descendant of this report, in the same order as
+    /// [`iter_reports`](Self::iter_reports) visits them.
+    ///
+    /// Children that are shared with other reports, meaning that their
+    /// [`strong_count`](ReportRef::strong_count) is larger than 1, cannot be
+    /// modified, so they are skipped together with their descendants.
+    pub(crate) fn for_each_unshared_descendant_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(ReportMut<'_, Dynamic, T>),
+    {
+        let children = self.take_children();
+        *self.children_mut() = children.map(|child| match child.try_into_mutable() {
+            Ok(mut child) => {
+                f(child.as_mut());
+                child.as_mut().for_each_unshared_descendant_mut(f);
+                child.into_cloneable()
+            }
+            Err(shared) => shared,
+        });
+    }
+
     /// Consumes the [`ReportMut`] and returns a mutable reference to the child
     /// reports with the same lifetime.
     ///
@@ -377,6 +711,105 @@ impl<'a, C: ?Sized, T> ReportMut<'a, C, T> {
         self.as_mut().into_attachments_mut()
     }
 
+    /// Moves the attachments out of this report, leaving it without
+    /// attachments.
+    ///
+    /// This does not clone or reallocate the attachments, which makes it
+    /// useful for passes that rewrite a report: take the attachments, process
+    /// them, and put back the ones to keep using
+    /// [`ReportMut::attachments_mut`] or [`ReportMut::set_attachments`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut, report_attachments::ReportAttachments};
+    /// let mut report: Report = report!("error message")
+    ///     .attach("user_id: 42")
+    ///     .attach("password: hunter2");
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// let attachments: ReportAttachments = report_mut.take_attachments();
+    /// assert!(report_mut.attachments().is_empty());
+    ///
+    /// // Put back everything except the password
+    /// for attachment in attachments {
+    ///     if !attachment.format_inner().to_string().starts_with("password") {
+    ///         report_mut.attachments_mut().push(attachment);
+    ///     }
+    /// }
+    /// assert!(!report.to_string().contains("hunter2"));
+    /// ```
+    #[must_use]
+    pub fn take_attachments(&mut self) -> ReportAttachments<T> {
+        core::mem::replace(self.attachments_mut(), ReportAttachments::new())
+    }
+
+    /// Replaces the attachments of this report.
+    ///
+    /// The previous attachments are dropped. Together with
+    /// [`ReportMut::take_attachments`], this allows rewriting the attachments
+    /// of a report as a whole.
+    ///
+    /// The attachments must have the same thread safety marker as this
+    /// report, so a [`SendSync`] report cannot receive [`Local`] attachments.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut, report_attachments::ReportAttachments};
+    /// let mut report: Report = report!("error message").attach("password: hunter2");
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// let attachments: ReportAttachments = report_mut
+    ///     .take_attachments()
+    ///     .into_iter()
+    ///     .filter(|attachment| !attachment.format_inner().to_string().starts_with("password"))
+    ///     .collect();
+    /// report_mut.set_attachments(attachments);
+    ///
+    /// assert!(!report.to_string().contains("hunter2"));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use rootcause::{prelude::*, markers::Local, report_attachments::ReportAttachments};
+    /// let mut report: Report = report!("error message");
+    /// let attachments: ReportAttachments<Local> = ReportAttachments::new_local();
+    /// report.as_mut().set_attachments(attachments);
+    /// ```
+    ///
+    /// [`SendSync`]: crate::markers::SendSync
+    /// [`Local`]: crate::markers::Local
+    pub fn set_attachments(&mut self, attachments: ReportAttachments<T>) {
+        *self.attachments_mut() = attachments;
+    }
+
+    /// Sorts the attachments of this report by their priority, from highest
+    /// to lowest, keeping the insertion order of attachments with the same
+    /// priority.
+    ///
+    /// Only the attachments of this report are reordered; the attachments of
+    /// its children are left unchanged. See
+    /// [`ReportAttachments::sort_by_priority`] for how the priority is
+    /// determined.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportMut};
+    /// use rootcause::hooks::builtin_hooks::location::{Location, LocationHandler};
+    ///
+    /// let mut report: Report = report!("error message")
+    ///     .attach("user_id: 42")
+    ///     .attach_custom::<LocationHandler, _>(Location { file: "main.rs", line: 7 });
+    ///
+    /// let mut report_mut: ReportMut<'_> = report.as_mut();
+    /// report_mut.sort_attachments();
+    ///
+    /// // Locations have a higher priority than plain attachments
+    /// let last = report.attachments().iter().last().unwrap();
+    /// assert_eq!(last.downcast_inner::<&str>(), Some(&"user_id: 42"));
+    /// ```
+    pub fn sort_attachments(&mut self) {
+        self.attachments_mut().sort_by_priority();
+    }
+
     /// Adds a new attachment.
     ///
     /// This is a convenience method used for chaining method calls; it consumes
@@ -724,6 +1157,7 @@ impl<'a, C: ?Sized, T> ReportMut<'a, C, T> {
     /// let type_id = report_mut.current_context_type_id();
     /// assert_eq!(type_id, TypeId::of::<MyError>());
     /// ```
+    #[doc(alias = "context_type_id")]
     #[must_use]
     pub fn current_context_type_id(&self) -> TypeId {
         self.as_raw_ref().context_type_id()
@@ -745,11 +1179,52 @@ impl<'a, C: ?Sized, T> ReportMut<'a, C, T> {
     /// let type_name = report_mut.current_context_type_name();
     /// assert_eq!(type_name, core::any::type_name::<MyError>());
     /// ```
+    #[doc(alias = "context_type_name")]
     #[must_use]
     pub fn current_context_type_name(&self) -> &'static str {
         self.as_raw_ref().context_type_name()
     }
 
+    /// Returns a mutable reference to the current context if it is of type
+    /// `D`, or [`None`] otherwise.
+    ///
+    /// Unlike [`downcast_current_context_mut`], this works whether or not the
+    /// context type of the [`ReportMut`] is known, so the context can be
+    /// modified in place from a report creation hook or through
+    /// [`Report::as_mut`] without decomposing the report.
+    ///
+    /// [`downcast_current_context_mut`]: ReportMut::downcast_current_context_mut
+    /// [`Report::as_mut`]: crate::Report::as_mut
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// #[derive(Debug)]
+    /// struct FetchError {
+    ///     retries: u32,
+    /// }
+    ///
+    /// impl core::fmt::Display for FetchError {
+    ///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         write!(f, "fetch failed after {} retries", self.retries)
+    ///     }
+    /// }
+    ///
+    /// let mut report: Report = report!(FetchError { retries: 0 }).into_dynamic();
+    /// report.as_mut().context_mut::<FetchError>().unwrap().retries += 1;
+    /// assert_eq!(report.format_current_context().to_string(), "fetch failed after 1 retries");
+    ///
+    /// assert!(report.as_mut().context_mut::<String>().is_none());
+    /// ```
+    #[must_use]
+    pub fn context_mut<D>(&mut self) -> Option<&mut D>
+    where
+        D: Sized + 'static,
+    {
+        let report = self.as_mut().into_dynamic().downcast_report::<D>().ok()?;
+        Some(report.into_current_context_mut())
+    }
+
     /// Returns the [`TypeId`] of the handler used for the current context.
     ///
     /// This can be useful for debugging or introspection to understand which
@@ -929,7 +1404,7 @@ impl<'a, C: ?Sized, T> ReportMut<'a, C, T> {
     #[must_use]
     pub fn format_with<H>(&self, hook: &H) -> impl core::fmt::Display + core::fmt::Debug
     where
-        H: crate::hooks::report_formatter::ReportFormatter,
+        H: crate::hooks::report_formatter::ReportFormatter + ?Sized,
     {
         self.as_ref().format_with(hook)
     }
@@ -1308,6 +1783,15 @@ impl<'a, C: Sized> From<ReportMut<'a, C, Local>> for ReportMut<'a, Dynamic, Loca
     }
 }
 
+/// Identifies an attachment by its type and its
+/// [`Display`](core::fmt::Display) output, to deduplicate attachments.
+fn attachment_identity(attachment: ReportAttachmentRef<'_, Dynamic>) -> (TypeId, String) {
+    (
+        attachment.inner_type_id(),
+        attachment.format_inner().to_string(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::string::String;
@@ -1355,4 +1839,86 @@ mod tests {
         static_assertions::assert_not_impl_any!(ReportMut<'static, Dynamic, SendSync>: Copy, Clone);
         static_assertions::assert_not_impl_any!(ReportMut<'static, Dynamic, Local>: Copy, Clone);
     }
+
+    #[test]
+    fn test_hoist_attachments_by_skips_shared_reports() {
+        let shared = report!("shared").attach("request f3a9").into_cloneable();
+        let deep = report!("deep")
+            .attach("request f3a9")
+            .context("middle")
+            .attach("request 77c0");
+        let mut report = deep
+            .join(shared.clone(), "2 jobs failed")
+            .attach("request 77c0")
+            .into_dynamic();
+
+        report
+            .as_mut()
+            .hoist_attachments_by(|attachment| attachment.is::<&str>());
+
+        let hoisted: Vec<String> = report
+            .attachments()
+            .iter()
+            .filter_map(|attachment| attachment.downcast_inner::<&str>())
+            .map(|attachment| String::from(*attachment))
+            .collect();
+        assert_eq!(hoisted, ["request 77c0", "request f3a9"]);
+        assert!(
+            report
+                .iter_sub_reports()
+                .filter(|child| child.format_current_context().to_string() != "shared")
+                .all(|child| !child.attachments().iter().any(|a| a.is::<&str>()))
+        );
+        assert_eq!(shared.attachments().len(), 2);
+    }
+
+    #[test]
+    fn test_dedup_attachments_recursive_skips_shared_reports() {
+        let shared = report!("shared")
+            .attach("request f3a9")
+            .attach("request f3a9")
+            .into_cloneable();
+        let mut report = report!("deep")
+            .attach(7)
+            .attach("7")
+            .attach(7)
+            .join(shared.clone(), "2 jobs failed")
+            .attach("request f3a9")
+            .into_dynamic();
+
+        report.as_mut().dedup_attachments_recursive();
+
+        // Equal Display output with different types is not a duplicate, and
+        // the root keeps its attachment that also exists on a child
+        let deep = report.children().get(0).unwrap();
+        assert_eq!(
+            deep.attachments().iter().filter(|a| a.is::<i32>()).count(),
+            1
+        );
+        assert_eq!(
+            deep.attachments().iter().filter(|a| a.is::<&str>()).count(),
+            1
+        );
+        assert_eq!(
+            report
+                .attachments()
+                .iter()
+                .filter(|a| a.is::<&str>())
+                .count(),
+            1
+        );
+        assert_eq!(shared.attachments().len(), 3);
+    }
+
+    #[test]
+    fn test_context_mut_on_typed_report() {
+        let mut report: crate::Report<String> = crate::report!(String::from("disk full"));
+        let mut report_mut = report.as_mut();
+        report_mut
+            .context_mut::<String>()
+            .unwrap()
+            .push_str(" on /var");
+        assert!(report_mut.context_mut::<&str>().is_none());
+        assert_eq!(report.current_context(), "disk full on /var");
+    }
 }
@@ -1,23 +1,41 @@
-use core::{
-    any::{Any, TypeId},
-    marker::PhantomData,
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
 };
+use core::any::{Any, TypeId};
 
 use rootcause_internals::{
-    RawReport,
-    handlers::{ContextFormattingStyle, FormattingFunction},
+    RawReportRef,
+    handlers::{AttachmentFormattingPlacement, ContextFormattingStyle, FormattingFunction},
 };
 
+#[cfg(feature = "std")]
+use crate::hooks::builtin_hooks::{
+    env::{EnvSnapshot, EnvSnapshotHandler},
+    process::{ProcessInfo, ProcessInfoHandler},
+};
 use crate::{
     ReportConversion, ReportIter, ReportMut, ReportRef,
+    counted::{Counted, CountedHandler},
+    error_code::{ErrorCode, ErrorCodeHandler},
+    external_attachment::{ExternalAttachment, ExternalAttachmentHandler},
     handlers::{self, ContextHandler},
+    hooks::builtin_hooks::{
+        location::{Location, LocationHandler},
+        location_trail::{LocationTrail, LocationTrailHandler},
+        timestamp::{Timestamp, TimestampHandler},
+    },
+    key_value::{KeyValue, KeyValueHandler},
     markers::{
         self, Cloneable, Dynamic, Local, Mutable, ReportOwnershipMarker, SendSync, Uncloneable,
     },
     report::iter::DowncastIterator,
-    report_attachment::ReportAttachment,
-    report_attachments::ReportAttachments,
+    report_attachment::{ReportAttachment, ReportAttachmentRef},
+    report_attachments::{RedactAction, ReportAttachments},
     report_collection::ReportCollection,
+    severity::{Severity, SeverityHandler},
+    source_snippet::{SourceSnippet, SourceSnippetHandler},
     util::ErrorNoSourceWrapper,
 };
 
@@ -261,6 +279,60 @@ impl<C: Sized, T> Report<C, Mutable, T> {
         Self::new_custom::<handlers::Error>(context)
     }
 
+    /// Creates a new [`Report`] with a context that is formatted using its
+    /// [`Display`](core::fmt::Display) implementation.
+    ///
+    /// This is a shorthand for `Report::new_custom::<handlers::Display>`, and
+    /// does not require the context to implement [`Error`].
+    ///
+    /// [`Error`]: core::error::Error
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report: Report<u16> = Report::new_display_context(404);
+    /// assert_eq!(report.format_current_context().to_string(), "404");
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn new_display_context(context: C) -> Self
+    where
+        C: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        Self::new_custom::<handlers::Display>(context)
+    }
+
+    /// Creates a new [`Report`] with a context that is formatted using the
+    /// [`handlers::Debug`] handler.
+    ///
+    /// This is a shorthand for `Report::new_custom::<handlers::Debug>`, and
+    /// does not require the context to implement [`Error`] or
+    /// [`Display`](core::fmt::Display). The [`Debug`](core::fmt::Debug)
+    /// output of the context is only shown when the report is
+    /// debug-formatted; see [`handlers::Debug`] for details.
+    ///
+    /// [`Error`]: core::error::Error
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// #[derive(Debug)]
+    /// struct Timeout {
+    ///     seconds: u64,
+    /// }
+    ///
+    /// let report: Report<Timeout> = Report::new_debug_context(Timeout { seconds: 30 });
+    /// assert!(format!("{report:?}").contains("Timeout { seconds: 30 }"));
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn new_debug_context(context: C) -> Self
+    where
+        C: markers::ObjectMarkerFor<T> + core::fmt::Debug,
+    {
+        Self::new_custom::<handlers::Debug>(context)
+    }
+
     /// Creates a new [`Report`] with the given context and handler.
     ///
     /// This method is generic over the thread safety marker `T`.
@@ -289,6 +361,39 @@ impl<C: Sized, T> Report<C, Mutable, T> {
         Self::from_parts::<H>(context, ReportCollection::new(), ReportAttachments::new())
     }
 
+    /// Creates a new [`Report`] with the given context and sets all of the
+    /// given reports as its children.
+    ///
+    /// This is the variadic version of [`Report::join`], for when several
+    /// independent failures should be reported under a shared context. The
+    /// context will use the [`handlers::Display`] handler for formatting.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let failures: Vec<Report> = vec![
+    ///     report!("shard 1 unavailable"),
+    ///     report!("shard 2 unavailable"),
+    ///     report!("shard 3 unavailable"),
+    /// ];
+    ///
+    /// let report = Report::join_all(failures, "Failed to query all shards");
+    /// assert_eq!(report.children().len(), 3);
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn join_all<I>(reports: I, context: C) -> Self
+    where
+        I: IntoIterator,
+        ReportCollection<Dynamic, T>: FromIterator<I::Item>,
+        C: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        reports
+            .into_iter()
+            .collect::<ReportCollection<Dynamic, T>>()
+            .context(context)
+    }
+
     /// Creates a new [`Report`] with the given context, children, and
     /// attachments.
     ///
@@ -346,7 +451,7 @@ impl<C: Sized, T> Report<C, Mutable, T> {
         C: markers::ObjectMarkerFor<T>,
         H: ContextHandler<C>,
     {
-        let raw = RawReport::new::<C, H>(context, children.into_raw(), attachments.into_raw());
+        let raw = context.new_raw_report::<H>(children.into_raw(), attachments.into_raw());
 
         // SAFETY:
         // 1. `C` is bounded by `Sized`, so this is upheld.
@@ -507,6 +612,41 @@ impl<C: Sized, T> Report<C, Mutable, T> {
         self.as_mut().into_current_context_mut()
     }
 
+    /// Replaces the current context with a new value of the same type,
+    /// returning the old value.
+    ///
+    /// The value is overwritten in place, so this is cheaper than
+    /// [`context_transform`](Report::context_transform) when the type does not
+    /// change. The children, attachments, hook data and the handler used to
+    /// format the context are all left untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct LoadError {
+    ///     path: Option<String>,
+    /// }
+    /// # impl std::fmt::Display for LoadError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "failed to load {:?}", self.path)
+    /// #     }
+    /// # }
+    ///
+    /// let mut report: Report<LoadError> = report!(LoadError { path: None }).attach("retrying");
+    /// let old = report.replace_context(LoadError {
+    ///     path: Some("config.toml".to_string()),
+    /// });
+    ///
+    /// assert_eq!(old.path, None);
+    /// assert_eq!(report.current_context().path.as_deref(), Some("config.toml"));
+    /// assert_eq!(report.attachments().len(), 2);
+    /// ```
+    pub fn replace_context(&mut self, context: C) -> C {
+        core::mem::replace(self.current_context_mut(), context)
+    }
+
     /// Transforms the context type by applying a function, preserving report
     /// structure.
     ///
@@ -555,6 +695,143 @@ impl<C: Sized, T> Report<C, Mutable, T> {
 
         Report::from_parts_unhooked::<handlers::Display>(new_context, children, attachments)
     }
+
+    /// Changes the handler used to format the context of this report.
+    ///
+    /// This is useful when a report was created with one handler, for instance
+    /// [`handlers::Display`] through the [`report!`](crate::report!) macro,
+    /// but the context should be presented in another way. The context,
+    /// children, attachments, and hook data are all preserved.
+    ///
+    /// # Examples
+    /// ```
+    /// use core::fmt;
+    ///
+    /// use rootcause::{handlers::ContextHandler, prelude::*};
+    ///
+    /// struct Shouting;
+    /// impl ContextHandler<String> for Shouting {
+    ///     fn source(_value: &String) -> Option<&(dyn core::error::Error + 'static)> {
+    ///         None
+    ///     }
+    ///
+    ///     fn display(value: &String, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "{}!", value.to_uppercase())
+    ///     }
+    ///
+    ///     fn debug(value: &String, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         fmt::Debug::fmt(value, f)
+    ///     }
+    /// }
+    ///
+    /// let report: Report<String> = report!("disk full".to_string());
+    /// assert_eq!(report.format_current_context().to_string(), "disk full");
+    ///
+    /// let report = report.set_context_handler::<Shouting>();
+    /// assert_eq!(report.format_current_context().to_string(), "DISK FULL!");
+    /// ```
+    #[must_use]
+    pub fn set_context_handler<H>(mut self) -> Self
+    where
+        H: ContextHandler<C>,
+    {
+        // SAFETY: The only thing we do with this is change the handler of the
+        // context, which does not add any new objects to the report.
+        let raw = unsafe { self.as_raw_mut() };
+
+        // SAFETY:
+        // 1. `C` is a `Sized` type, so the invariants of `Report` guarantee that it
+        //    is the actual type of the context.
+        unsafe { raw.set_context_handler::<C, H>() }
+
+        self
+    }
+
+    /// Creates a new report with a clone of the context of this report, which
+    /// shares the children of this report and has the attachments returned by
+    /// `clone_attachment`.
+    ///
+    /// This is useful for forking a report into variants that only differ at
+    /// the top, for instance by attaching different data to each copy.
+    ///
+    /// - The context is cloned, and keeps the handler of this report.
+    /// - The child reports are not copied. The new report gets its own
+    ///   [`ReportCollection`] holding references to the same child reports, like
+    ///   cloning a [`Cloneable`] report does. Adding or removing children of
+    ///   one report does not affect the other, but the child reports
+    ///   themselves are shared.
+    /// - Attachments are not required to implement [`Clone`], so they cannot
+    ///   be copied in general. Instead `clone_attachment` is called for each
+    ///   attachment of this report, in order, and the attachments it returns
+    ///   are added to the new report. It can downcast the attachments whose
+    ///   types it knows and clone them, and return `None` for the others,
+    ///   which are then missing from the new report.
+    /// - No creation hooks are run, so the new report does not get a new
+    ///   [`Location`] or backtrace, and only has the ones that
+    ///   `clone_attachment` copies.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_attachment::ReportAttachment};
+    /// let report: Report<String> = report!("disk full".to_string())
+    ///     .context("failed to write log".to_string())
+    ///     .attach("path: /var/log")
+    ///     .attach(42u32);
+    ///
+    /// let copy = report
+    ///     .clone_root_with(|attachment| {
+    ///         let path = attachment.downcast_inner::<&str>()?;
+    ///         Some(ReportAttachment::new(*path).into_dynamic())
+    ///     })
+    ///     .attach("retrying");
+    /// assert_eq!(copy.current_context(), "failed to write log");
+    /// assert_eq!(copy.children().len(), 1);
+    /// assert_eq!(copy.attachments().len(), 2);
+    /// assert!(copy.attachments().get(0).unwrap().is::<&str>());
+    /// assert!(!report.to_string().contains("retrying"));
+    /// ```
+    #[must_use]
+    pub fn clone_root_with<F>(&self, mut clone_attachment: F) -> Self
+    where
+        C: Clone,
+        F: FnMut(ReportAttachmentRef<'_, Dynamic>) -> Option<ReportAttachment<Dynamic, T>>,
+    {
+        let children = self.children().clone().into_raw();
+        let attachments: ReportAttachments<T> = self
+            .attachments()
+            .iter()
+            .filter_map(&mut clone_attachment)
+            .collect();
+        let attachments = attachments.into_raw();
+
+        // SAFETY:
+        // 1. `C` is a `Sized` type, so the invariants of `Report` guarantee that it
+        //    is the actual type of the context.
+        let raw = unsafe {
+            self.as_raw_ref()
+                .clone_with_parts::<C>(children, attachments)
+        };
+
+        // SAFETY:
+        // 1. `C` is bounded by `Sized`, so this is upheld.
+        // 2. `O=Mutable`, so this is trivially upheld.
+        // 3. `T` is the thread safety marker of this report, so this is upheld.
+        // 4. The context is a clone of the context of this report, which is of type
+        //    `C`.
+        // 5. We just created the report and we are the unique owner.
+        // 6. `O=Mutable`, so this is trivially upheld.
+        // 7. The children were cloned from a `ReportCollection`, whose invariants
+        //    guarantee that they are compatible with shared ownership.
+        // 8. If `T=SendSync`, then the context of this report is `Send+Sync`, and so
+        //    is its clone. The children come from this report, and the invariants
+        //    of the `ReportAttachments<T>` guarantee that the attachments are
+        //    `Send+Sync` as well.
+        unsafe {
+            // @add-unsafe-context: ReportCollection
+            // @add-unsafe-context: ReportAttachments
+            Report::<C, Mutable, T>::from_raw(raw)
+        }
+    }
 }
 
 impl<C: ?Sized, T> Report<C, Mutable, T> {
@@ -639,202 +916,1150 @@ impl<C: ?Sized, T> Report<C, Mutable, T> {
         self
     }
 
-    /// Returns a mutable reference to the child reports.
+    /// Adds all attachments from an iterator to the [`Report`].
+    ///
+    /// This is equivalent to calling [`attach`](Self::attach) for each item,
+    /// but reserves space for all of the attachments up front. Like
+    /// [`attach`](Self::attach), the attachments are formatted using the
+    /// default handler for their type.
     ///
     /// # Examples
     /// ```
-    /// # use rootcause::{prelude::*, report_collection::ReportCollection};
-    /// let mut report: Report = report!("error message");
-    /// let children_mut: &mut ReportCollection = report.children_mut();
+    /// # use rootcause::prelude::*;
+    /// let headers = [("host", "example.com"), ("accept", "*/*")];
+    /// let report: Report = report!("request failed").with_attachments(
+    ///     headers
+    ///         .iter()
+    ///         .map(|(name, value)| format!("{name}: {value}")),
+    /// );
+    /// assert!(report.to_string().contains("host: example.com"));
+    /// assert!(report.to_string().contains("accept: */*"));
     /// ```
     #[must_use]
-    pub fn children_mut(&mut self) -> &mut ReportCollection<Dynamic, T> {
-        self.as_mut().into_children_mut()
+    pub fn with_attachments<I>(mut self, attachments: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        self.attachments_mut().extend(
+            attachments
+                .into_iter()
+                .map(|attachment| ReportAttachment::new(attachment)),
+        );
+        self
     }
 
-    /// Returns a mutable reference to the attachments.
+    /// Adds all attachments from an iterator of values that can be converted
+    /// into a [`ReportAttachment`] to the [`Report`].
+    ///
+    /// This makes it possible to add attachments that use a custom handler, or
+    /// that have been created ahead of time, in a single call. Space for all
+    /// of the attachments is reserved up front.
     ///
     /// # Examples
     /// ```
-    /// # use rootcause::{prelude::*, report_attachments::ReportAttachments};
-    /// let mut report: Report = report!("error message");
-    /// let attachments_mut: &mut ReportAttachments = report.attachments_mut();
+    /// # use rootcause::{prelude::*, report_attachment::ReportAttachment};
+    /// let attachments = [1, 2, 3]
+    ///     .into_iter()
+    ///     .map(|attempt| ReportAttachment::new_custom::<handlers::Debug>(attempt).into_dynamic());
+    /// let report: Report = report!("request failed").with_attachments_from(attachments);
+    /// let attempts = report
+    ///     .attachments()
+    ///     .iter()
+    ///     .filter(|attachment| attachment.downcast_inner::<i32>().is_some())
+    ///     .count();
+    /// assert_eq!(attempts, 3);
     /// ```
     #[must_use]
-    pub fn attachments_mut(&mut self) -> &mut ReportAttachments<T> {
-        self.as_mut().into_attachments_mut()
+    pub fn with_attachments_from<I>(mut self, attachments: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ReportAttachment<Dynamic, T>>,
+    {
+        self.attachments_mut()
+            .extend(attachments.into_iter().map(Into::into));
+        self
     }
 
-    /// Returns a [`&mut dyn Any`](Any) view of the current context.
+    /// Adds a reference to externally stored data to the [`Report`].
     ///
-    /// This works whether the context type `C` is known at compile time or
-    /// erased to [`Dynamic`]. The returned reference can be downcast using
-    /// `<dyn Any>::downcast_mut`.
+    /// Only the `id` and `kind` are stored in the report, which keeps the
+    /// memory usage of the report bounded for very large attachments such as
+    /// core dumps. The reference is rendered as `<external: id>`. See the
+    /// [`external_attachment`] module for how to fetch the data later.
     ///
-    /// [`Dynamic`]: crate::markers::Dynamic
+    /// [`external_attachment`]: crate::external_attachment
     ///
     /// # Examples
     /// ```
     /// # use rootcause::prelude::*;
-    /// # use core::any::Any;
-    /// let mut report: Report<String> = report!(String::from("An error occurred"));
-    /// let any: &mut dyn Any = report.current_context_as_any_mut();
-    /// if let Some(s) = any.downcast_mut::<String>() {
-    ///     s.push_str(" and that's bad");
-    /// }
+    /// let report: Report = report!("worker crashed").attach_external("dumps/7f3a9c", "core-dump");
+    /// assert!(report.to_string().contains("<external: dumps/7f3a9c>"));
     /// ```
     #[must_use]
-    pub fn current_context_as_any_mut(&mut self) -> &mut (dyn Any + 'static) {
-        self.as_mut().into_current_context_as_any_mut()
+    pub fn attach_external(self, id: impl Into<String>, kind: &'static str) -> Self
+    where
+        ExternalAttachment: markers::ObjectMarkerFor<T>,
+    {
+        self.attach_custom::<ExternalAttachmentHandler, _>(ExternalAttachment::new(id, kind))
     }
-}
 
-impl<C: ?Sized, O, T> Report<C, O, T> {
-    /// Creates a new [`Report`] with the given context and sets the current
-    /// report as a child of the new report.
-    ///
-    /// The new context will use the [`handlers::Display`] handler to format the
-    /// context.
-    ///
-    /// This is a convenience method used for chaining method calls; it consumes
-    /// the [`Report`] and returns it.
+    /// Adds an attachment to the [`Report`], or increments the count of an
+    /// equal attachment that was already added this way.
     ///
-    /// If you want a different context handler, you can use
-    /// [`Report::context_custom`].
+    /// The attachment is stored as a [`Counted`] value. If this report already
+    /// has a [`Counted`] attachment with an equal value, its count is
+    /// incremented instead of adding a new attachment, and it is displayed as
+    /// `value (xN)`. Only the attachments of this report are considered, not
+    /// those of its children.
     ///
-    /// If you want to more directly control the allocation of the new report,
-    /// you can use [`Report::from_parts`], which is the underlying method
-    /// used to implement this method.
+    /// [`Counted`]: crate::counted::Counted
     ///
     /// # Examples
     /// ```
     /// # use rootcause::prelude::*;
-    /// let report: Report = report!("initial error");
-    /// let contextual_report: Report<&str> = report.context("additional context");
+    /// let report: Report = report!("request failed")
+    ///     .attach_counted("retrying")
+    ///     .attach_counted("retrying")
+    ///     .attach_counted("giving up");
+    /// let output = report.to_string();
+    /// assert!(output.contains("retrying (x2)"));
+    /// assert!(output.contains("giving up"));
     /// ```
-    #[track_caller]
     #[must_use]
-    pub fn context<D>(self, context: D) -> Report<D, Mutable, T>
+    pub fn attach_counted<A>(mut self, attachment: A) -> Self
     where
-        D: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+        A: PartialEq + core::fmt::Display + core::fmt::Debug,
+        Counted<A>: markers::ObjectMarkerFor<T>,
     {
-        self.context_custom::<handlers::Display, _>(context)
+        let existing = self
+            .attachments_mut()
+            .iter_mut()
+            .filter_map(|existing| existing.downcast_attachment::<Counted<A>>().ok())
+            .map(|existing| existing.into_inner_mut())
+            .find(|existing| existing.value == attachment);
+        if let Some(existing) = existing {
+            existing.count += 1;
+            self
+        } else {
+            self.attach_custom::<CountedHandler, _>(Counted::new(attachment))
+        }
     }
 
-    /// Creates a new [`Report`] with the given context and sets the current
-    /// report as a child of the new report.
+    /// Adds a key/value pair as an attachment to the [`Report`].
     ///
-    /// This is a convenience method used for chaining method calls; it consumes
-    /// the [`Report`] and returns it.
+    /// The value is formatted using its [`Display`](core::fmt::Display)
+    /// implementation and stored as a [`KeyValue`] together with the key. It is
+    /// displayed as `key: value`, but unlike `attach(format!("key: {value}"))`
+    /// the key and value stay separate, so structured formatters can emit them
+    /// as `{"key": "value"}`.
     ///
-    /// If you want to more directly control the allocation of the new report,
-    /// you can use [`Report::from_parts`], which is the underlying method
-    /// used to implement this method.
+    /// [`KeyValue`]: crate::key_value::KeyValue
     ///
     /// # Examples
     /// ```
     /// # use rootcause::prelude::*;
-    /// let report: Report = report!("initial error");
-    /// let contextual_report: Report<&str> = report.context_custom::<handlers::Debug, _>("context");
+    /// let report: Report = report!("request failed")
+    ///     .attach_kv("user_id", 42)
+    ///     .attach_kv("path", "/api/orders");
+    /// let output = report.to_string();
+    /// assert!(output.contains("user_id: 42"));
+    /// assert!(output.contains("path   : /api/orders"));
     /// ```
-    #[track_caller]
     #[must_use]
-    pub fn context_custom<H, D>(self, context: D) -> Report<D, Mutable, T>
+    pub fn attach_kv(
+        self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl core::fmt::Display,
+    ) -> Self
     where
-        D: markers::ObjectMarkerFor<T>,
-        H: ContextHandler<D>,
+        KeyValue: markers::ObjectMarkerFor<T>,
     {
-        Report::from_parts::<H>(
-            context,
-            ReportCollection::from([self.into_dynamic().into_cloneable()]),
-            ReportAttachments::<T>::new(),
-        )
+        self.attach_custom::<KeyValueHandler, _>(KeyValue::new(key, value))
     }
 
-    /// Converts this report to a different context type using
-    /// [`ReportConversion`].
-    ///
-    /// Implement [`ReportConversion`] once to define conversion logic, then use
-    /// [`context_to`](Self::context_to) at call sites. Useful for consistent
-    /// error type coercion across your codebase, especially when integrating
-    /// with external libraries. You typically need to specify the target type
-    /// (`::<Type>`).
+    /// Adds a [`serde_json::Value`] as an attachment to the [`Report`].
     ///
-    /// See also: [`context_transform`](Self::context_transform) for direct
-    /// conversion, `context_transform_nested` (in the
-    /// [`rootcause-preformat`](https://docs.rs/rootcause-preformat) crate) for
-    /// wrapping, [`examples/thiserror_interop.rs`] for integration patterns.
+    /// The value is stored as a [`JsonAttachment`], which is pretty-printed
+    /// when the report is displayed. Unlike attaching the formatted value as a
+    /// string, the value keeps its structure when the report is emitted as
+    /// JSON.
     ///
-    /// [`examples/thiserror_interop.rs`]: https://github.com/rootcause-rs/rootcause/blob/main/examples/thiserror_interop.rs
+    /// [`JsonAttachment`]: crate::json_attachment::JsonAttachment
     ///
     /// # Examples
-    ///
     /// ```
-    /// # use rootcause::{ReportConversion, markers::Mutable, prelude::*};
-    /// # #[derive(Debug)]
-    /// enum AppError { Parse }
-    /// # impl std::fmt::Display for AppError {
-    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "error") }
-    /// # }
-    /// impl<T> ReportConversion<std::num::ParseIntError, Mutable, T> for AppError
-    ///   where AppError: markers::ObjectMarkerFor<T>
-    /// {
-    ///     fn convert_report(report: Report<std::num::ParseIntError, Mutable, T>) -> Report<Self, Mutable, T>
-    ///     {
-    ///         report.context(AppError::Parse)
-    ///     }
-    /// }
-    /// // After implementing ReportConversion, use at call sites:
-    /// let result: Result<i32, Report<AppError>> = "abc".parse::<i32>().context_to();
+    /// # use rootcause::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let report: Report = report!("order rejected").attach_json(json!({ "order_id": 1234 }));
+    /// assert!(report.to_string().contains(r#""order_id": 1234"#));
     /// ```
-    #[track_caller]
+    #[cfg(feature = "serde_json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
     #[must_use]
-    pub fn context_to<D: ReportConversion<C, O, T>>(self) -> Report<D, Mutable, T> {
-        D::convert_report(self)
+    pub fn attach_json(self, value: impl Into<serde_json::Value>) -> Self
+    where
+        crate::json_attachment::JsonAttachment: markers::ObjectMarkerFor<T>,
+    {
+        self.attach_custom::<crate::json_attachment::JsonAttachmentHandler, _>(
+            crate::json_attachment::JsonAttachment(value.into()),
+        )
     }
 
-    /// Returns a reference to the child reports.
+    /// Adds a [`Severity`] as an attachment to the [`Report`].
+    ///
+    /// The highest severity in a report hierarchy can be found using
+    /// [`max_severity`](Self::max_severity). The default formatter renders the
+    /// severity as a badge in front of the context of this report.
     ///
     /// # Examples
     /// ```
-    /// # use rootcause::{prelude::*, report_collection::ReportCollection};
-    /// let report: Report = report!("error message");
-    /// let children: &ReportCollection = report.children();
-    /// assert_eq!(children.len(), 0); // The report has just been created, so it has no children
+    /// # use rootcause::{prelude::*, severity::Severity};
+    /// let report: Report = report!("disk almost full").attach_severity(Severity::Warning);
+    /// assert!(report.to_string().contains("[warning] disk almost full"));
     /// ```
     #[must_use]
-    pub fn children(&self) -> &ReportCollection<Dynamic, T> {
-        self.as_uncloneable_ref().children()
+    pub fn attach_severity(self, severity: Severity) -> Self
+    where
+        Severity: markers::ObjectMarkerFor<T>,
+    {
+        self.attach_custom::<SeverityHandler, _>(severity)
     }
 
-    /// Returns a reference to the attachments.
+    /// Adds an [`ErrorCode`] as an attachment to the [`Report`].
+    ///
+    /// The code can be found anywhere in a report hierarchy using
+    /// [`error_code`](Self::error_code). The default formatter renders the code
+    /// as a badge in front of the context of this report.
+    ///
+    /// [`ErrorCode`]: crate::error_code::ErrorCode
     ///
     /// # Examples
     /// ```
-    /// # use rootcause::{prelude::*, report_attachments::ReportAttachments};
-    /// let report: Report = report!("error message");
-    /// let attachments: &ReportAttachments = report.attachments();
+    /// # use rootcause::prelude::*;
+    /// let report: Report = report!("user not found").attach_error_code("E1234");
+    /// assert!(report.to_string().contains("[E1234] user not found"));
     /// ```
     #[must_use]
-    pub fn attachments(&self) -> &ReportAttachments<T> {
-        self.as_uncloneable_ref().attachments()
+    pub fn attach_error_code(self, code: impl Into<Cow<'static, str>>) -> Self
+    where
+        ErrorCode: markers::ObjectMarkerFor<T>,
+    {
+        self.attach_custom::<ErrorCodeHandler, _>(ErrorCode::new(code))
     }
 
-    /// Changes the context type of the [`Report`] to [`Dynamic`].
+    /// Adds a [`SourceSnippet`] pointing at a byte span of a source file as an
+    /// attachment to the [`Report`].
     ///
-    /// Calling this method is equivalent to calling `report.into()`, however
-    /// this method has been restricted to only change the context mode to
-    /// [`Dynamic`].
+    /// The line and column of the span are computed from the source, and the
+    /// lines containing the span are displayed with the span underlined, in the
+    /// same style as `rustc`. The snippet is displayed before the other
+    /// attachments of the report.
     ///
-    /// This method can be useful to help with type inference or to improve code
-    /// readability, as it more clearly communicates intent.
+    /// # Panics
     ///
-    /// This method does not actually modify the report in any way. It only has
-    /// the effect of "forgetting" that the context actually has the type
-    /// `C`.
+    /// Panics if the span is out of bounds of the source, if its start is
+    /// after its end, or if it does not lie on character boundaries.
     ///
-    /// To get back the report with a concrete `C` you can use the method
-    /// [`Report::downcast_report`].
+    /// [`SourceSnippet`]: crate::source_snippet::SourceSnippet
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let source = "x = 1\ny = x +\n";
+    /// let report: Report = report!("unexpected end of line").attach_snippet("calc.txt", source, 12..13);
+    /// let output = report.to_string();
+    /// assert!(output.contains("--> calc.txt:2:7"));
+    /// assert!(output.contains("2 | y = x +"));
+    /// assert!(output.contains("  |       ^"));
+    /// ```
+    #[must_use]
+    #[track_caller]
+    pub fn attach_snippet(
+        self,
+        filename: impl Into<String>,
+        source: &str,
+        span: core::ops::Range<usize>,
+    ) -> Self
+    where
+        SourceSnippet: markers::ObjectMarkerFor<T>,
+    {
+        self.attach_custom::<SourceSnippetHandler, _>(SourceSnippet::new(filename, source, span))
+    }
+
+    /// Adds a [`LocationTrail`] of the call sites where this report and its
+    /// contexts were created.
+    ///
+    /// The trail is a lightweight alternative to a stack backtrace, built from
+    /// the locations already captured by the location hook. It does not need
+    /// `std` or any OS support. See [`LocationTrail::from_report`] for how the
+    /// trail is collected.
+    ///
+    /// [`LocationTrail`]: crate::hooks::builtin_hooks::location_trail::LocationTrail
+    /// [`LocationTrail::from_report`]: crate::hooks::builtin_hooks::location_trail::LocationTrail::from_report
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("file not found")
+    ///     .context("Failed to read the config")
+    ///     .attach_location_trail();
+    /// let output = report.to_string();
+    /// assert!(output.contains("Location trail"));
+    /// assert!(output.contains("0: "));
+    /// assert!(output.contains("1: "));
+    /// ```
+    #[must_use]
+    pub fn attach_location_trail(self) -> Self
+    where
+        LocationTrail: markers::ObjectMarkerFor<T>,
+    {
+        let trail = LocationTrail::from_report(self.as_ref());
+        self.attach_custom::<LocationTrailHandler, _>(trail)
+    }
+
+    /// Adds a [`Timestamp`] with the current time from the system clock.
+    ///
+    /// The timestamp is displayed in ISO-8601 format in UTC. To add a
+    /// timestamp to every report, install a [`TimestampCollector`] instead.
+    ///
+    /// [`Timestamp`]: crate::hooks::builtin_hooks::timestamp::Timestamp
+    /// [`TimestampCollector`]: crate::hooks::builtin_hooks::timestamp::TimestampCollector
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{hooks::builtin_hooks::timestamp::Timestamp, prelude::*};
+    /// let report = report!("request timed out").attach_timestamp();
+    /// let timestamp = report.attachments().iter().find_map(|a| a.downcast_inner::<Timestamp>());
+    /// assert!(timestamp.is_some());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    pub fn attach_timestamp(self) -> Self
+    where
+        Timestamp: markers::ObjectMarkerFor<T>,
+    {
+        self.attach_timestamp_with(Timestamp::now)
+    }
+
+    /// Adds an [`EnvSnapshot`] with the values of the given environment
+    /// variables.
+    ///
+    /// Variables that are not set are omitted, and if none of them are set,
+    /// no attachment is added. The snapshot is displayed in an `Environment`
+    /// appendix. To add a snapshot to every report, install an
+    /// [`EnvCollector`] instead.
+    ///
+    /// [`EnvSnapshot`]: crate::hooks::builtin_hooks::env::EnvSnapshot
+    /// [`EnvCollector`]: crate::hooks::builtin_hooks::env::EnvCollector
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{hooks::builtin_hooks::env::EnvSnapshot, prelude::*};
+    /// # // SAFETY: No other threads are reading or writing the environment
+    /// # unsafe { std::env::set_var("LANG", "de_DE.UTF-8") };
+    /// let report = report!("locale not supported").attach_env(&["LANG", "LC_ALL", "TZ"]);
+    /// let snapshot = report
+    ///     .attachments()
+    ///     .iter()
+    ///     .find_map(|a| a.downcast_inner::<EnvSnapshot>())
+    ///     .unwrap();
+    /// assert!(snapshot.vars().any(|(name, _)| name == "LANG"));
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    pub fn attach_env(self, names: &[&str]) -> Self
+    where
+        EnvSnapshot: markers::ObjectMarkerFor<T>,
+    {
+        let snapshot = EnvSnapshot::capture(names);
+        if snapshot.is_empty() {
+            self
+        } else {
+            self.attach_custom::<EnvSnapshotHandler, _>(snapshot)
+        }
+    }
+
+    /// Adds a [`ProcessInfo`] with the id, executable and arguments of the
+    /// current process.
+    ///
+    /// The information is displayed in a `Process` appendix. All arguments
+    /// are included, so use [`Report::attach_process_info_with`] instead if
+    /// they can contain secrets. To add the information to every report,
+    /// install a [`ProcessInfoCollector`] instead.
+    ///
+    /// [`ProcessInfo`]: crate::hooks::builtin_hooks::process::ProcessInfo
+    /// [`ProcessInfoCollector`]: crate::hooks::builtin_hooks::process::ProcessInfoCollector
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{hooks::builtin_hooks::process::ProcessInfo, prelude::*};
+    /// let report = report!("worker crashed").attach_process_info();
+    /// let info = report
+    ///     .attachments()
+    ///     .iter()
+    ///     .find_map(|a| a.downcast_inner::<ProcessInfo>())
+    ///     .unwrap();
+    /// assert_eq!(info.pid, std::process::id());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    pub fn attach_process_info(self) -> Self
+    where
+        ProcessInfo: markers::ObjectMarkerFor<T>,
+    {
+        self.attach_custom::<ProcessInfoHandler, _>(ProcessInfo::capture())
+    }
+
+    /// Adds a [`ProcessInfo`] like [`Report::attach_process_info`], replacing
+    /// the arguments for which `redact` returns `true` with `<redacted>`.
+    ///
+    /// `redact` is called with the previous argument and the argument itself,
+    /// see [`ProcessInfo::capture_with`]. Use [`redact_flags`] to redact the
+    /// values of some flags.
+    ///
+    /// [`ProcessInfo`]: crate::hooks::builtin_hooks::process::ProcessInfo
+    /// [`ProcessInfo::capture_with`]: crate::hooks::builtin_hooks::process::ProcessInfo::capture_with
+    /// [`redact_flags`]: crate::hooks::builtin_hooks::process::redact_flags
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{hooks::builtin_hooks::process::redact_flags, prelude::*};
+    /// let report = report!("login failed").attach_process_info_with(redact_flags(&["--password"]));
+    /// assert!(!report.to_string().contains("--password="));
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    pub fn attach_process_info_with(self, redact: impl Fn(Option<&str>, &str) -> bool) -> Self
+    where
+        ProcessInfo: markers::ObjectMarkerFor<T>,
+    {
+        self.attach_custom::<ProcessInfoHandler, _>(ProcessInfo::capture_with(redact))
+    }
+
+    /// Adds a [`Timestamp`] with the current time read from the given clock
+    /// function.
+    ///
+    /// This is useful without the `std` feature, where the system clock is not
+    /// available, and for using a fake clock in tests.
+    ///
+    /// [`Timestamp`]: crate::hooks::builtin_hooks::timestamp::Timestamp
+    ///
+    /// # Examples
+    /// ```
+    /// # use core::time::Duration;
+    /// # use rootcause::{hooks::builtin_hooks::timestamp::Timestamp, prelude::*};
+    /// let report = report!("request timed out").attach_timestamp_with(|| {
+    ///     Timestamp::from_unix_duration(Duration::from_secs(1_700_000_000))
+    /// });
+    /// assert!(report.to_string().contains("2023-11-14T22:13:20.000Z"));
+    /// ```
+    #[must_use]
+    pub fn attach_timestamp_with<F>(self, clock: F) -> Self
+    where
+        F: FnOnce() -> Timestamp,
+        Timestamp: markers::ObjectMarkerFor<T>,
+    {
+        self.attach_custom::<TimestampHandler, _>(clock())
+    }
+
+    /// Sets the [`Location`] where this report was created.
+    ///
+    /// The location hook records the location of the `#[track_caller]` call
+    /// that created the report. When the report is created inside a helper
+    /// function, that location points to the helper instead of its caller.
+    /// Library authors can mark their own wrappers with `#[track_caller]`
+    /// and forward [`core::panic::Location::caller`] to this method to record
+    /// the true caller location.
+    ///
+    /// If the report already has a [`Location`] attachment, such as the one
+    /// added by the location hook, it is replaced. Otherwise a new one is
+    /// added.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// #[track_caller]
+    /// fn invalid_input(message: &'static str) -> Report {
+    ///     report!(message)
+    ///         .into_dynamic()
+    ///         .at_location(core::panic::Location::caller())
+    /// }
+    ///
+    /// let line = line!() + 1;
+    /// let report = invalid_input("empty name");
+    ///
+    /// let location = report.location().unwrap();
+    /// assert_eq!(location.line, line);
+    /// assert_eq!(report.to_string().matches(file!()).count(), 1);
+    /// ```
+    #[must_use]
+    pub fn at_location(mut self, location: &'static core::panic::Location<'static>) -> Self
+    where
+        Location: markers::ObjectMarkerFor<T>,
+    {
+        let location = Location {
+            file: location.file(),
+            line: location.line(),
+        };
+
+        let existing = self.attachments_mut().iter_mut().find_map(|attachment| {
+            attachment
+                .into_inner_as_any_mut()
+                .downcast_mut::<Location>()
+        });
+        match existing {
+            Some(existing) => {
+                *existing = location;
+                self
+            }
+            None => self.attach_custom::<LocationHandler, _>(location),
+        }
+    }
+
+    /// Returns a mutable reference to the child reports.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_collection::ReportCollection};
+    /// let mut report: Report = report!("error message");
+    /// let children_mut: &mut ReportCollection = report.children_mut();
+    /// ```
+    #[must_use]
+    pub fn children_mut(&mut self) -> &mut ReportCollection<Dynamic, T> {
+        self.as_mut().into_children_mut()
+    }
+
+    /// Reverses the order of the child reports in place.
+    ///
+    /// Only the direct children of this report are reordered; the children
+    /// of those reports are left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_collection::ReportCollection};
+    /// let mut report: Report = report!("error message");
+    /// report.children_mut().push(report!("first").into_cloneable());
+    /// report.children_mut().push(report!("second").into_cloneable());
+    ///
+    /// report.reverse_children();
+    /// let first_child = report.children().get(0).unwrap();
+    /// assert_eq!(first_child.format_current_context().to_string(), "second");
+    /// ```
+    pub fn reverse_children(&mut self) {
+        self.children_mut().reverse();
+    }
+
+    /// Reorders the child reports so that children with the same key are
+    /// next to each other.
+    ///
+    /// See [`ReportMut::group_children_by`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let mut report: Report = report!("3 jobs failed");
+    /// report.children_mut().push(report!("timed out").into_cloneable());
+    /// report.children_mut().push(report!("disk full").into_cloneable());
+    /// report.children_mut().push(report!("timed out").into_cloneable());
+    ///
+    /// report.group_children_by(|child| child.format_current_context().to_string());
+    /// let first_child = report.children().get(0).unwrap();
+    /// assert_eq!(first_child.format_current_context().to_string(), "disk full");
+    /// ```
+    pub fn group_children_by<K, F>(&mut self, key: F)
+    where
+        K: Ord,
+        F: FnMut(ReportRef<'_, Dynamic, Cloneable, T>) -> K,
+    {
+        self.children_mut().sort_by_key(key);
+    }
+
+    /// Moves the attachments matching a predicate from all descendants of
+    /// this report to this report, deduplicating them by type and
+    /// [`Display`](core::fmt::Display) output.
+    ///
+    /// See [`ReportMut::hoist_attachments_by`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let mut report = report!("timed out")
+    ///     .attach("request f3a9")
+    ///     .context("job failed");
+    /// report.hoist_attachments_by(|attachment| attachment.downcast_inner::<&str>().is_some());
+    ///
+    /// assert_eq!(report.attachments().iter().filter(|a| a.is::<&str>()).count(), 1);
+    /// ```
+    pub fn hoist_attachments_by<F>(&mut self, predicate: F)
+    where
+        F: FnMut(ReportAttachmentRef<'_, Dynamic>) -> bool,
+    {
+        self.as_mut().into_dynamic().hoist_attachments_by(predicate);
+    }
+
+    /// Removes the attachments of this report that have the same type and
+    /// [`Display`](core::fmt::Display) output as an earlier attachment.
+    ///
+    /// See [`ReportMut::dedup_attachments`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let mut report = report!("timed out")
+    ///     .attach("request f3a9")
+    ///     .attach("request f3a9");
+    /// report.dedup_attachments();
+    ///
+    /// assert_eq!(report.attachments().iter().filter(|a| a.is::<&str>()).count(), 1);
+    /// ```
+    pub fn dedup_attachments(&mut self) {
+        self.as_mut().dedup_attachments();
+    }
+
+    /// Removes the attachments of this report that are duplicates of an
+    /// earlier attachment according to a custom equality.
+    ///
+    /// See [`ReportMut::dedup_attachments_by`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let mut report = report!("timed out").attach("first").attach("second");
+    /// report.dedup_attachments_by(|kept, attachment| kept.inner_type_id() == attachment.inner_type_id());
+    ///
+    /// assert_eq!(report.attachments().iter().filter(|a| a.is::<&str>()).count(), 1);
+    /// ```
+    pub fn dedup_attachments_by<F>(&mut self, same: F)
+    where
+        F: FnMut(ReportAttachmentRef<'_, Dynamic>, ReportAttachmentRef<'_, Dynamic>) -> bool,
+    {
+        self.as_mut().dedup_attachments_by(same);
+    }
+
+    /// Removes duplicate attachments from this report and from each of its
+    /// unshared descendants.
+    ///
+    /// See [`ReportMut::dedup_attachments_recursive`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let mut report = report!("timed out")
+    ///     .attach("request f3a9")
+    ///     .attach("request f3a9")
+    ///     .context("job failed");
+    /// report.dedup_attachments_recursive();
+    ///
+    /// let child = report.children().get(0).unwrap();
+    /// assert_eq!(child.attachments().iter().filter(|a| a.is::<&str>()).count(), 1);
+    /// ```
+    pub fn dedup_attachments_recursive(&mut self) {
+        self.as_mut().dedup_attachments_recursive();
+    }
+
+    /// Returns a mutable reference to the attachments.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_attachments::ReportAttachments};
+    /// let mut report: Report = report!("error message");
+    /// let attachments_mut: &mut ReportAttachments = report.attachments_mut();
+    /// ```
+    #[must_use]
+    pub fn attachments_mut(&mut self) -> &mut ReportAttachments<T> {
+        self.as_mut().into_attachments_mut()
+    }
+
+    /// Returns a [`&mut dyn Any`](Any) view of the current context.
+    ///
+    /// This works whether the context type `C` is known at compile time or
+    /// erased to [`Dynamic`]. The returned reference can be downcast using
+    /// `<dyn Any>::downcast_mut`.
+    ///
+    /// [`Dynamic`]: crate::markers::Dynamic
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// # use core::any::Any;
+    /// let mut report: Report<String> = report!(String::from("An error occurred"));
+    /// let any: &mut dyn Any = report.current_context_as_any_mut();
+    /// if let Some(s) = any.downcast_mut::<String>() {
+    ///     s.push_str(" and that's bad");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn current_context_as_any_mut(&mut self) -> &mut (dyn Any + 'static) {
+        self.as_mut().into_current_context_as_any_mut()
+    }
+
+    /// Removes or replaces attachments in the entire report tree.
+    ///
+    /// The closure is called for every attachment in the report and its
+    /// descendants, and decides whether the attachment is kept, removed, or
+    /// replaced with a string. Unlike a formatting hook that hides an
+    /// attachment when the report is printed, this removes the data from the
+    /// report itself, which makes it suitable as a defensive pass before a
+    /// report leaves the process.
+    ///
+    /// Child reports are stored as [`Cloneable`] reports and may be shared with
+    /// other reports. Children that are shared, meaning that
+    /// [`strong_count`](ReportRef::strong_count) is larger than 1, cannot be
+    /// modified in place. Only the shared reports on the path to an attachment
+    /// that is removed or replaced are copied, so the other reports sharing
+    /// them are not affected. In those copies, the context and the kept
+    /// attachments are stored as `String`s of their display output, since
+    /// they cannot be cloned, and kept attachments whose preferred placement
+    /// is [`Hidden`](crate::handlers::AttachmentFormattingPlacement::Hidden)
+    /// are left out. Shared subtrees in which nothing changes stay shared and
+    /// keep their typed contexts and attachments.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_attachments::RedactAction};
+    /// struct ApiToken(String);
+    ///
+    /// let report = report!("request failed")
+    ///     .attach_custom::<handlers::Any, _>(ApiToken("secret".to_string()))
+    ///     .context("Failed to sync")
+    ///     .attach("retry 3 of 3")
+    ///     .redact(|attachment| {
+    ///         if attachment.downcast_inner::<ApiToken>().is_some() {
+    ///             RedactAction::Remove
+    ///         } else {
+    ///             RedactAction::Keep
+    ///         }
+    ///     });
+    ///
+    /// let tokens = report
+    ///     .iter_reports()
+    ///     .flat_map(|report| report.attachments().iter())
+    ///     .filter(|attachment| attachment.downcast_inner::<ApiToken>().is_some())
+    ///     .count();
+    /// assert_eq!(tokens, 0);
+    /// assert!(report.to_string().contains("retry 3 of 3"));
+    /// ```
+    #[must_use]
+    pub fn redact<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(ReportAttachmentRef<'_, Dynamic>) -> RedactAction,
+        String: markers::ObjectMarkerFor<T>,
+    {
+        self.redact_in_place(&mut f);
+        self
+    }
+
+    /// Applies [`Report::redact`] to this report and its descendants,
+    /// replacing the shared children that change by copies.
+    fn redact_in_place<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(ReportAttachmentRef<'_, Dynamic>) -> RedactAction,
+        String: markers::ObjectMarkerFor<T>,
+    {
+        let attachments = core::mem::replace(self.attachments_mut(), ReportAttachments::new());
+        for attachment in attachments {
+            match f(attachment.as_ref()) {
+                RedactAction::Keep => self.attachments_mut().push(attachment),
+                RedactAction::Remove => {}
+                RedactAction::Replace(replacement) => self.attachments_mut().push(
+                    ReportAttachment::new_custom::<handlers::Display>(replacement).into_dynamic(),
+                ),
+            }
+        }
+
+        let children = core::mem::replace(self.children_mut(), ReportCollection::new());
+        for child in children {
+            let child = match child.try_into_mutable() {
+                Ok(mut child) => {
+                    child.redact_in_place(f);
+                    child.into_cloneable()
+                }
+                Err(shared_child) => {
+                    redact_shared(shared_child.as_ref(), f).unwrap_or(shared_child)
+                }
+            };
+            self.children_mut().push(child);
+        }
+    }
+
+    /// Replaces every attachment in the entire report tree with the output of
+    /// the closure.
+    ///
+    /// The closure is called for every attachment in the report and its
+    /// descendants, in the same order as [`Report::iter_reports`] visits the
+    /// reports. Since attachments of different types are stored together, the
+    /// closure receives and returns type-erased attachments, which can be
+    /// inspected using [`ReportAttachment::downcast_inner`] and replaced with
+    /// new attachments of any type.
+    ///
+    /// Children that are shared with other reports, meaning that
+    /// [`strong_count`](ReportRef::strong_count) is larger than 1, cannot be
+    /// modified, and are left unchanged together with their descendants. Use
+    /// [`Report::redact`] to remove sensitive data, which also covers shared
+    /// children.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_attachment::ReportAttachment};
+    /// let report: Report = report!("request failed")
+    ///     .attach("user_id: 42")
+    ///     .context("Failed to sync")
+    ///     .attach("retry 3 of 3")
+    ///     .into_dynamic();
+    ///
+    /// // Prefix every string attachment
+    /// let report = report.map_attachments(|attachment| match attachment.downcast_inner::<&str>() {
+    ///     Some(text) => ReportAttachment::new(format!("note: {text}")).into_dynamic(),
+    ///     None => attachment,
+    /// });
+    /// assert!(report.to_string().contains("note: user_id: 42"));
+    /// assert!(report.to_string().contains("note: retry 3 of 3"));
+    /// ```
+    #[must_use]
+    pub fn map_attachments<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(ReportAttachment<Dynamic, T>) -> ReportAttachment<Dynamic, T>,
+    {
+        self.rewrite_attachments_in_place(&mut |attachment| Some(f(attachment)));
+        self
+    }
+
+    /// Replaces or removes every attachment in the report tree, skipping the
+    /// children that are shared with other reports.
+    fn rewrite_attachments_in_place<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(ReportAttachment<Dynamic, T>) -> Option<ReportAttachment<Dynamic, T>>,
+    {
+        let mut rewrite = |mut report: ReportMut<'_, Dynamic, T>| {
+            let attachments =
+                core::mem::replace(report.attachments_mut(), ReportAttachments::new());
+            for attachment in attachments {
+                if let Some(attachment) = f(attachment) {
+                    report.attachments_mut().push(attachment);
+                }
+            }
+        };
+        rewrite(self.as_mut().into_dynamic());
+        self.as_mut().for_each_unshared_descendant_mut(&mut rewrite);
+    }
+
+    /// Collapses chains of child reports that only repeat the context of
+    /// their own child.
+    ///
+    /// A child report is replaced by its own child when it has exactly one
+    /// child, a context that is formatted the same way as the context of that
+    /// child, and no attachments other than the [`Location`] added by the
+    /// default report creation hook. This removes the redundant nodes that are
+    /// left behind by repeatedly wrapping an error in the same context, while
+    /// keeping the more informative node.
+    ///
+    /// The report itself is never collapsed, since its context type is part
+    /// of the type of the report. Use [`Report::normalize_by`] to decide
+    /// which reports are collapsed using a custom predicate.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused")
+    ///     .attach("port: 5432")
+    ///     .context("database unavailable")
+    ///     .context("database unavailable")
+    ///     .context("failed to load user");
+    /// assert_eq!(report.iter_reports().count(), 4);
+    ///
+    /// let report = report.normalize();
+    /// assert_eq!(report.iter_reports().count(), 3);
+    /// ```
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        self.normalize_by(|report| {
+            let Some(child) = report.children().get(0) else {
+                return false;
+            };
+            report
+                .attachments()
+                .iter()
+                .all(|attachment| attachment.downcast_inner::<Location>().is_some())
+                && report.format_current_context().to_string()
+                    == child.format_current_context().to_string()
+        })
+    }
+
+    /// Collapses chains of child reports using a custom predicate.
+    ///
+    /// The predicate is called for every descendant of this report that has
+    /// exactly one child. When it returns `true`, the descendant is replaced
+    /// by its child, together with its context and attachments, and the
+    /// predicate is called again for that child.
+    ///
+    /// Children that are shared with other reports, meaning that
+    /// [`strong_count`](ReportRef::strong_count) is larger than 1, can still
+    /// be collapsed, since that only replaces them in this report. Their own
+    /// descendants cannot be modified and are left unchanged, as in
+    /// [`Report::map_attachments`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// struct Retrying;
+    ///
+    /// let report = report!("connection refused")
+    ///     .context_custom::<handlers::Any, _>(Retrying)
+    ///     .context("failed to load user");
+    ///
+    /// let report =
+    ///     report.normalize_by(|report| report.downcast_current_context::<Retrying>().is_some());
+    /// assert_eq!(report.iter_reports().count(), 2);
+    /// ```
+    #[must_use]
+    pub fn normalize_by<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(ReportRef<'_, Dynamic, Cloneable, T>) -> bool,
+    {
+        self.normalize_in_place(&mut f);
+        self
+    }
+
+    /// Collapses the children of this report that satisfy the predicate,
+    /// skipping the descendants of the children that are shared with other
+    /// reports.
+    fn normalize_in_place<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(ReportRef<'_, Dynamic, Cloneable, T>) -> bool,
+    {
+        let children = core::mem::replace(self.children_mut(), ReportCollection::new());
+        for mut child in children {
+            while child.children().len() == 1
+                && f(child.as_ref())
+                && let Some(grandchild) = child.children().get(0)
+            {
+                child = grandchild.to_report();
+            }
+
+            let child = match child.try_into_mutable() {
+                Ok(mut child) => {
+                    child.normalize_in_place(f);
+                    child.into_cloneable()
+                }
+                Err(shared_child) => shared_child,
+            };
+            self.children_mut().push(child);
+        }
+    }
+}
+
+impl<C: ?Sized, O, T> Report<C, O, T> {
+    /// Creates a new [`Report`] with the given context and sets the current
+    /// report as a child of the new report.
+    ///
+    /// The new context will use the [`handlers::Display`] handler to format the
+    /// context.
+    ///
+    /// This is a convenience method used for chaining method calls; it consumes
+    /// the [`Report`] and returns it.
+    ///
+    /// If you want a different context handler, you can use
+    /// [`Report::context_custom`].
+    ///
+    /// If you want to more directly control the allocation of the new report,
+    /// you can use [`Report::from_parts`], which is the underlying method
+    /// used to implement this method.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report: Report = report!("initial error");
+    /// let contextual_report: Report<&str> = report.context("additional context");
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn context<D>(self, context: D) -> Report<D, Mutable, T>
+    where
+        D: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        self.context_custom::<handlers::Display, _>(context)
+    }
+
+    /// Creates a new [`Report`] with the given context and sets the current
+    /// report as a child of the new report.
+    ///
+    /// This is a convenience method used for chaining method calls; it consumes
+    /// the [`Report`] and returns it.
+    ///
+    /// If you want to more directly control the allocation of the new report,
+    /// you can use [`Report::from_parts`], which is the underlying method
+    /// used to implement this method.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report: Report = report!("initial error");
+    /// let contextual_report: Report<&str> = report.context_custom::<handlers::Debug, _>("context");
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn context_custom<H, D>(self, context: D) -> Report<D, Mutable, T>
+    where
+        D: markers::ObjectMarkerFor<T>,
+        H: ContextHandler<D>,
+    {
+        Report::from_parts::<H>(
+            context,
+            ReportCollection::from([self.into_dynamic().into_cloneable()]),
+            ReportAttachments::<T>::new(),
+        )
+    }
+
+    /// Creates a new [`Report`] with the given context and sets the current
+    /// report and `other` as the children of the new report.
+    ///
+    /// This is useful when two independent failures occurred and neither is
+    /// the cause of the other, so both should be reported under a shared
+    /// context. The new context will use the [`handlers::Display`] handler to
+    /// format the context.
+    ///
+    /// To combine more than two reports, use [`Report::join_all`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let primary: Report = report!("primary database unavailable");
+    /// let replica: Report = report!("replica database unavailable");
+    ///
+    /// let report: Report<&str> = primary.join(replica, "Failed to read the user");
+    /// assert_eq!(report.children().len(), 2);
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn join<D, C2: ?Sized, O2>(
+        self,
+        other: Report<C2, O2, T>,
+        context: D,
+    ) -> Report<D, Mutable, T>
+    where
+        D: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        ReportCollection::from([
+            self.into_dynamic().into_cloneable(),
+            other.into_dynamic().into_cloneable(),
+        ])
+        .context(context)
+    }
+
+    /// Converts this report to a different context type using
+    /// [`ReportConversion`].
+    ///
+    /// Implement [`ReportConversion`] once to define conversion logic, then use
+    /// [`context_to`](Self::context_to) at call sites. Useful for consistent
+    /// error type coercion across your codebase, especially when integrating
+    /// with external libraries. You typically need to specify the target type
+    /// (`::<Type>`).
+    ///
+    /// See also: [`context_transform`](Self::context_transform) for direct
+    /// conversion, `context_transform_nested` (in the
+    /// [`rootcause-preformat`](https://docs.rs/rootcause-preformat) crate) for
+    /// wrapping, [`examples/thiserror_interop.rs`] for integration patterns.
+    ///
+    /// [`examples/thiserror_interop.rs`]: https://github.com/rootcause-rs/rootcause/blob/main/examples/thiserror_interop.rs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rootcause::{ReportConversion, markers::Mutable, prelude::*};
+    /// # #[derive(Debug)]
+    /// enum AppError { Parse }
+    /// # impl std::fmt::Display for AppError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "error") }
+    /// # }
+    /// impl<T> ReportConversion<std::num::ParseIntError, Mutable, T> for AppError
+    ///   where AppError: markers::ObjectMarkerFor<T>
+    /// {
+    ///     fn convert_report(report: Report<std::num::ParseIntError, Mutable, T>) -> Report<Self, Mutable, T>
+    ///     {
+    ///         report.context(AppError::Parse)
+    ///     }
+    /// }
+    /// // After implementing ReportConversion, use at call sites:
+    /// let result: Result<i32, Report<AppError>> = "abc".parse::<i32>().context_to();
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn context_to<D: ReportConversion<C, O, T>>(self) -> Report<D, Mutable, T> {
+        D::convert_report(self)
+    }
+
+    /// Returns a reference to the child reports.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_collection::ReportCollection};
+    /// let report: Report = report!("error message");
+    /// let children: &ReportCollection = report.children();
+    /// assert_eq!(children.len(), 0); // The report has just been created, so it has no children
+    /// ```
+    #[must_use]
+    pub fn children(&self) -> &ReportCollection<Dynamic, T> {
+        self.as_uncloneable_ref().children()
+    }
+
+    /// Returns a reference to the attachments.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_attachments::ReportAttachments};
+    /// let report: Report = report!("error message");
+    /// let attachments: &ReportAttachments = report.attachments();
+    /// ```
+    #[must_use]
+    pub fn attachments(&self) -> &ReportAttachments<T> {
+        self.as_uncloneable_ref().attachments()
+    }
+
+    /// Returns the [`Location`] where this report was created.
+    ///
+    /// Returns [`None`] if the report has no location attachment, for instance
+    /// because the hooks were installed with
+    /// [`Hooks::new_without_locations`]. See [`ReportRef::location`] for
+    /// details.
+    ///
+    /// [`Hooks::new_without_locations`]: crate::hooks::Hooks::new_without_locations
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let line = line!() + 1;
+    /// let report: Report = report!("error message");
+    ///
+    /// let location = report.location().unwrap();
+    /// assert_eq!(location.file, file!());
+    /// assert_eq!(location.line, line);
+    /// ```
+    #[must_use]
+    pub fn location(&self) -> Option<&Location> {
+        self.as_uncloneable_ref().location()
+    }
+
+    /// Changes the context type of the [`Report`] to [`Dynamic`].
+    ///
+    /// Calling this method is equivalent to calling `report.into()`, however
+    /// this method has been restricted to only change the context mode to
+    /// [`Dynamic`].
+    ///
+    /// This method can be useful to help with type inference or to improve code
+    /// readability, as it more clearly communicates intent.
+    ///
+    /// This method does not actually modify the report in any way. It only has
+    /// the effect of "forgetting" that the context actually has the type
+    /// `C`.
+    ///
+    /// To get back the report with a concrete `C` you can use the method
+    /// [`Report::downcast_report`].
     ///
     /// # Examples
     /// ```
@@ -906,13 +2131,62 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
         // 3. This is guaranteed by the invariants of `self`.
         // 4. This is guaranteed by the invariants of `self`.
         // 5. `O=Cloneable`, so this is trivially true.
-        // 6. If the ownership of `self` is already `Cloneable`, then this is guaranteed
-        //    by the invariants of `self`. If the ownership of `self` is `Mutable`, then
-        //    the invariants of `self` guarantee that we are the only owner and we are
-        //    consuming `self` in this method, so there are no other owners.
-        // 7. This is guaranteed by the invariants of `self`.
-        // 8. This is guaranteed by the invariants of `self`.
-        unsafe { Report::<C, Cloneable, T>::from_raw(raw) }
+        // 6. If the ownership of `self` is already `Cloneable`, then this is guaranteed
+        //    by the invariants of `self`. If the ownership of `self` is `Mutable`, then
+        //    the invariants of `self` guarantee that we are the only owner and we are
+        //    consuming `self` in this method, so there are no other owners.
+        // 7. This is guaranteed by the invariants of `self`.
+        // 8. This is guaranteed by the invariants of `self`.
+        unsafe { Report::<C, Cloneable, T>::from_raw(raw) }
+    }
+
+    /// Converts the [`Report`] into a [`Report<Dynamic, Cloneable, SendSync>`].
+    ///
+    /// This is the same as calling `.into_dynamic().into_cloneable()`, but
+    /// additionally requires the report to be [`SendSync`]. The resulting
+    /// reports are cheap to clone and can be shared between threads, which
+    /// makes them suitable for storing in shared collections such as
+    /// `Arc<[Report<Dynamic, Cloneable>]>`.
+    ///
+    /// Calling this method on a [`Local`] report fails to compile.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use rootcause::{prelude::*, markers::{Cloneable, Dynamic}};
+    /// let failures: Arc<[Report<Dynamic, Cloneable>]> = [
+    ///     report!("connection refused").into_shared(),
+    ///     report!("timed out").context("failed to load user").into_shared(),
+    /// ]
+    /// .into();
+    /// let cloned = failures[0].clone();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use rootcause::prelude::*;
+    /// let report = report!("error").into_local().into_shared();
+    /// ```
+    #[must_use]
+    pub fn into_shared(self) -> Report<Dynamic, Cloneable, SendSync>
+    where
+        T: markers::SharedReportMarker,
+    {
+        let raw = self.into_dynamic().into_cloneable().into_raw();
+
+        // SAFETY:
+        // 1. `C=Dynamic`, so this is trivially true.
+        // 2. `O=Cloneable`, so this is trivially true.
+        // 3. `T=SendSync`, so this is trivially true.
+        // 4. `C=Dynamic`, so this is trivially true.
+        // 5. `O=Cloneable`, so this is trivially true.
+        // 6. This is guaranteed by the invariants of the cloneable report.
+        // 7. This is guaranteed by the invariants of the cloneable report.
+        // 8. `SharedReportMarker` is sealed and only implemented for `SendSync`, so the
+        //    original report was `SendSync` and this is guaranteed by its invariants.
+        unsafe {
+            // @add-unsafe-context: Dynamic
+            Report::<Dynamic, Cloneable, SendSync>::from_raw(raw)
+        }
     }
 
     /// Changes the thread safety mode of the [`Report`] to [`Local`].
@@ -955,6 +2229,39 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
         unsafe { Report::<C, O, Local>::from_raw(raw) }
     }
 
+    /// Asserts at compile time that the [`Report`] is [`Send`] and [`Sync`],
+    /// and returns it unchanged.
+    ///
+    /// This is useful in async code, where a report that is held across an
+    /// `.await` makes the future `Send` only if the report is. Calling this
+    /// method on a [`Local`] report fails to compile at the call site, instead
+    /// of in a distant `tokio::spawn` or similar.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// async fn load() -> Result<(), Report> {
+    ///     let report = report!("connection refused").send_check();
+    ///     std::future::ready(()).await;
+    ///     Err(report)
+    /// }
+    ///
+    /// fn require_send<F: Send>(_: F) {}
+    /// require_send(load());
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use rootcause::prelude::*;
+    /// let report = report!(std::rc::Rc::new("not thread-safe")).send_check();
+    /// ```
+    #[must_use]
+    pub fn send_check(self) -> Self
+    where
+        Self: Send + Sync,
+    {
+        self
+    }
+
     /// Checks if there is only a single unique owner of the root node of the
     /// [`Report`].
     ///
@@ -978,7 +2285,7 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
     /// }
     /// ```
     pub fn try_into_mutable(self) -> Result<Report<C, Mutable, T>, Report<C, O, T>> {
-        if self.strong_count() == 1 {
+        if self.is_unique() {
             let raw = self.into_raw();
 
             // SAFETY:
@@ -1163,8 +2470,386 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
         self.as_uncloneable_ref().iter_sub_reports()
     }
 
+    /// Returns an iterator over the leaves of the report hierarchy.
+    ///
+    /// See [`ReportRef::leaves`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_collection::ReportCollection};
+    /// let causes: ReportCollection = [
+    ///     report!("disk full").into_cloneable(),
+    ///     report!("timed out").into_cloneable(),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// let report = causes.context("2 jobs failed");
+    ///
+    /// assert_eq!(report.leaves().count(), 2);
+    /// ```
+    pub fn leaves(&self) -> impl Iterator<Item = ReportRef<'_, Dynamic, O::RefMarker, T>>
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().leaves()
+    }
+
+    /// Returns the number of reports in the hierarchy that have no children.
+    ///
+    /// See [`ReportRef::leaf_count`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("timed out").join(report!("disk full"), "2 jobs failed");
+    /// assert_eq!(report.leaf_count(), 2);
+    /// ```
+    #[must_use]
+    pub fn leaf_count(&self) -> usize
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().leaf_count()
+    }
+
+    /// Returns the number of distinct causes among the leaves of the report
+    /// hierarchy.
+    ///
+    /// See [`ReportRef::distinct_cause_count`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("timed out").join(report!("timed out"), "2 jobs failed");
+    /// assert_eq!(report.leaf_count(), 2);
+    /// assert_eq!(report.distinct_cause_count(), 1);
+    /// ```
+    #[must_use]
+    pub fn distinct_cause_count(&self) -> usize
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().distinct_cause_count()
+    }
+
+    /// Returns the highest [`Severity`] attached anywhere in the report
+    /// hierarchy, or `None` if no report has a severity attached.
+    ///
+    /// See [`ReportRef::max_severity`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, severity::Severity};
+    /// let report = report!("database unreachable").attach_severity(Severity::Critical);
+    /// assert_eq!(report.max_severity(), Some(Severity::Critical));
+    /// ```
+    #[must_use]
+    pub fn max_severity(&self) -> Option<Severity>
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().max_severity()
+    }
+
+    /// Returns the first [`ErrorCode`] attached in the report hierarchy, or
+    /// `None` if no report has an error code attached.
+    ///
+    /// See [`ReportRef::error_code`] for more information.
+    ///
+    /// [`ErrorCode`]: crate::error_code::ErrorCode
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("user not found").attach_error_code("E1234");
+    /// assert_eq!(report.error_code(), Some("E1234"));
+    /// ```
+    #[must_use]
+    pub fn error_code(&self) -> Option<&str>
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().error_code()
+    }
+
+    /// Returns the headers of all attachments in the report hierarchy that
+    /// are formatted with a header, without duplicates.
+    ///
+    /// See [`ReportRef::attachment_headers`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("error").attach("plain attachment");
+    /// assert_eq!(report.attachment_headers().count(), 0);
+    /// ```
+    pub fn attachment_headers(&self) -> impl Iterator<Item = &'static str>
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().attachment_headers()
+    }
+
+    /// Returns an iterator over the primary chain of reports, starting with
+    /// this report and following the first child of each report.
+    ///
+    /// See [`ReportRef::iter_primary_chain`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused").context("failed to load user");
+    /// assert_eq!(report.iter_primary_chain().count(), 2);
+    /// ```
+    pub fn iter_primary_chain(
+        &self,
+    ) -> impl Iterator<Item = ReportRef<'_, Dynamic, O::RefMarker, T>>
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().iter_primary_chain()
+    }
+
+    /// Returns `true` if this report has no children and no attachments other
+    /// than [`Location`]s.
+    ///
+    /// See [`ReportRef::is_empty_tree`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// assert!(report!("connection refused").is_empty_tree());
+    /// assert!(!report!("connection refused").attach("port 5432").is_empty_tree());
+    /// ```
+    #[must_use]
+    pub fn is_empty_tree(&self) -> bool
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().is_empty_tree()
+    }
+
+    /// Returns the context of this report as a single [`String`], ignoring
+    /// its children and attachments.
+    ///
+    /// See [`ReportRef::summary_line`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused").context("failed to load user");
+    /// assert_eq!(report.summary_line(), "failed to load user");
+    /// ```
+    #[must_use]
+    pub fn summary_line(&self) -> String
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().summary_line()
+    }
+
+    /// Returns the contexts of the primary chain of reports as strings,
+    /// starting with this report and following the first child of each
+    /// report.
+    ///
+    /// See [`ReportRef::context_chain_strings`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused").context("failed to load user");
+    /// assert_eq!(
+    ///     report.context_chain_strings(),
+    ///     ["failed to load user", "connection refused"]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn context_chain_strings(&self) -> Vec<String>
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().context_chain_strings()
+    }
+
+    /// Returns an iterator over the report hierarchy, where each report is
+    /// paired with its depth in the tree.
+    ///
+    /// See [`ReportRef::walk`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused").context("failed to load user");
+    /// let depths: Vec<usize> = report.walk().map(|(depth, _)| depth).collect();
+    /// assert_eq!(depths, [0, 1]);
+    /// ```
+    pub fn walk(&self) -> impl Iterator<Item = (usize, ReportRef<'_, Dynamic, O::RefMarker, T>)>
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().walk()
+    }
+
+    /// Returns an iterator over the report hierarchy that visits the children
+    /// of each report before the report itself.
+    ///
+    /// See [`ReportRef::iter_post_order`] for the exact ordering guarantees.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused").context("failed to load user");
+    ///
+    /// let root = report.iter_post_order().last().unwrap();
+    /// assert_eq!(root.format_current_context().to_string(), "failed to load user");
+    /// ```
+    pub fn iter_post_order(&self) -> impl Iterator<Item = ReportRef<'_, Dynamic, O::RefMarker, T>>
+    where
+        O: markers::ReportOwnershipMarker,
+    {
+        self.as_ref().iter_post_order()
+    }
+
+    /// Searches the report hierarchy depth-first and returns the first context
+    /// of type `D`, including the context of this report.
+    ///
+    /// See [`ReportRef::find_context`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!(std::io::Error::other("disk full")).context("Failed to save");
+    /// let io_error = report.find_context::<std::io::Error>().unwrap();
+    /// assert_eq!(io_error.to_string(), "disk full");
+    /// ```
+    #[must_use]
+    pub fn find_context<D>(&self) -> Option<&D>
+    where
+        O: ReportOwnershipMarker,
+        D: 'static,
+    {
+        self.as_ref().find_context()
+    }
+
+    /// Searches the report hierarchy depth-first and returns an iterator over
+    /// all contexts of type `D`, including the context of this report.
+    ///
+    /// See [`ReportRef::find_contexts`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("inner").context("middle").context("outer");
+    /// assert_eq!(report.find_contexts::<&str>().count(), 3);
+    /// ```
+    pub fn find_contexts<D>(&self) -> DowncastIterator<'_, D, O::RefMarker, T>
+    where
+        O: ReportOwnershipMarker,
+        D: 'static,
+    {
+        self.as_ref().find_contexts()
+    }
+
+    /// Returns `true` if the context of this report is of type `D`.
+    ///
+    /// See [`ReportRef::has_context`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!(std::io::Error::other("disk full")).context("Failed to save");
+    /// assert!(report.has_context::<&str>());
+    /// ```
+    #[must_use]
+    pub fn has_context<D>(&self) -> bool
+    where
+        D: 'static,
+    {
+        self.as_uncloneable_ref().has_context::<D>()
+    }
+
+    /// Returns `true` if this report or any report below it has a context of
+    /// type `D`.
+    ///
+    /// See [`ReportRef::contains_context`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!(std::io::Error::other("disk full")).context("Failed to save");
+    /// assert!(report.contains_context::<std::io::Error>());
+    /// ```
+    #[must_use]
+    pub fn contains_context<D>(&self) -> bool
+    where
+        D: 'static,
+    {
+        self.as_uncloneable_ref().contains_context::<D>()
+    }
+
+    /// Returns `true` if this report has an attachment of type `A`.
+    ///
+    /// See [`ReportRef::has_attachment`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("request failed").attach(404u16);
+    /// assert!(report.has_attachment::<u16>());
+    /// ```
+    #[must_use]
+    pub fn has_attachment<A>(&self) -> bool
+    where
+        A: 'static,
+    {
+        self.as_uncloneable_ref().has_attachment::<A>()
+    }
+
+    /// Returns `true` if this report or any report below it has an attachment
+    /// of type `A`.
+    ///
+    /// See [`ReportRef::contains_attachment`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("request failed").attach(404u16).context("Failed to load page");
+    /// assert!(report.contains_attachment::<u16>());
+    /// ```
+    #[must_use]
+    pub fn contains_attachment<A>(&self) -> bool
+    where
+        A: 'static,
+    {
+        self.as_uncloneable_ref().contains_attachment::<A>()
+    }
+
+    /// Returns `true` if this report has the same structure as another
+    /// report.
+    ///
+    /// See [`ReportRef::structural_eq`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("disk full").context("Failed to save");
+    /// assert!(report.structural_eq(&report!("disk full").context("Failed to save")));
+    /// assert!(!report.structural_eq(&report!("disk full")));
+    /// ```
+    #[must_use]
+    pub fn structural_eq<C2: ?Sized, O2, T2>(&self, other: &Report<C2, O2, T2>) -> bool {
+        self.as_uncloneable_ref()
+            .structural_eq(other.as_uncloneable_ref())
+    }
+
     /// Returns the [`TypeId`] of the current context.
     ///
+    /// This also works for [`Dynamic`] reports, which makes it useful for
+    /// bucketing errors by their context type without having to downcast to
+    /// every known type.
+    ///
+    /// [`Dynamic`]: crate::markers::Dynamic
+    ///
     /// # Examples
     /// ```
     /// # use rootcause::{prelude::*, markers::Dynamic};
@@ -1178,6 +2863,7 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
     /// let type_id = report.current_context_type_id();
     /// assert_eq!(type_id, TypeId::of::<MyError>());
     /// ```
+    #[doc(alias = "context_type_id")]
     #[must_use]
     pub fn current_context_type_id(&self) -> TypeId {
         self.as_uncloneable_ref().current_context_type_id()
@@ -1185,6 +2871,12 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
 
     /// Returns the [`core::any::type_name`] of the current context.
     ///
+    /// The name is recorded when the report is created, so it remains
+    /// available after the report has been converted to [`Dynamic`]. This
+    /// makes it suitable as a human-readable error category in logs.
+    ///
+    /// [`Dynamic`]: crate::markers::Dynamic
+    ///
     /// # Examples
     /// ```
     /// # use rootcause::{prelude::*, markers::Dynamic};
@@ -1199,6 +2891,7 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
     /// let type_name = report.current_context_type_name();
     /// assert_eq!(type_name, core::any::type_name::<MyError>());
     /// ```
+    #[doc(alias = "context_type_name")]
     #[must_use]
     pub fn current_context_type_name(&self) -> &'static str {
         self.as_uncloneable_ref().current_context_type_name()
@@ -1307,6 +3000,121 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
         self.as_uncloneable_ref().format_current_context_unhooked()
     }
 
+    /// Returns the current context as a string slice, if it is a string.
+    ///
+    /// See [`ReportRef::context_str`] for details.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused");
+    /// assert_eq!(report.context_str(), Some("connection refused"));
+    /// ```
+    #[must_use]
+    pub fn context_str(&self) -> Option<&str> {
+        self.as_uncloneable_ref().context_str()
+    }
+
+    /// Formats the report as a single-line summary of its primary context
+    /// chain.
+    ///
+    /// See [`ReportRef::format_compact`] for details.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused")
+    ///     .attach("port 5432")
+    ///     .context("failed to load user");
+    /// assert_eq!(
+    ///     report.format_compact().to_string(),
+    ///     "failed to load user: connection refused (+1 attachment)"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn format_compact(&self) -> impl core::fmt::Display + core::fmt::Debug {
+        self.as_uncloneable_ref().format_compact()
+    }
+
+    /// Converts the report into a NUL-terminated C string, for handing the
+    /// error message to callers of a C API.
+    ///
+    /// The string contains the [`Display`](core::fmt::Display) output of the
+    /// report, with any interior NUL bytes removed. It is owned by the caller,
+    /// and must be released using [`rootcause_free_error_string`], see the
+    /// [`ffi`](crate::ffi) module.
+    ///
+    /// [`rootcause_free_error_string`]: crate::ffi::rootcause_free_error_string
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{ffi, prelude::*};
+    /// let message = report!("out of memory").into_c_string();
+    /// assert!(!message.is_null());
+    ///
+    /// // SAFETY: The pointer was returned by `into_c_string`, and is freed only
+    /// // once.
+    /// unsafe { ffi::rootcause_free_error_string(message) };
+    /// ```
+    #[cfg(feature = "ffi")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+    #[must_use]
+    pub fn into_c_string(self) -> *mut core::ffi::c_char {
+        crate::ffi::to_c_string(self).into_raw()
+    }
+
+    /// Returns a [`Debug`](core::fmt::Debug) view of the internal structure of
+    /// the report tree, showing the types and handlers of the contexts and
+    /// attachments.
+    ///
+    /// See [`ReportRef::debug_structure`] for details.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("error message").attach(42);
+    /// println!("{:#?}", report.debug_structure());
+    /// ```
+    #[must_use]
+    pub fn debug_structure(&self) -> impl core::fmt::Debug {
+        self.as_uncloneable_ref().debug_structure()
+    }
+
+    /// Writes the entire report tree as JSON to `writer`, without using the
+    /// installed hooks.
+    ///
+    /// See [`ReportRef::write_json`] for the format of the output.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused").context("failed to load user");
+    ///
+    /// let mut body = String::from(r#"{"error":"#);
+    /// report.write_json(&mut body).unwrap();
+    /// body.push('}');
+    /// assert!(body.starts_with(r#"{"error":{"context":"failed to load user""#));
+    /// ```
+    pub fn write_json<W: core::fmt::Write>(&self, writer: &mut W) -> core::fmt::Result {
+        self.as_uncloneable_ref().write_json(writer)
+    }
+
+    /// Returns the entire report tree as a JSON string, without using the
+    /// installed hooks.
+    ///
+    /// See [`ReportRef::write_json`] for the format of the output.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused");
+    /// assert!(report.to_json_string().starts_with(r#"{"context":"connection refused""#));
+    /// ```
+    #[must_use]
+    pub fn to_json_string(&self) -> String {
+        self.as_uncloneable_ref().to_json_string()
+    }
+
     /// Formats the entire report using a specific report formatting hook.
     ///
     /// This method allows you to format a report with a custom formatter
@@ -1331,14 +3139,99 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
     /// let formatted = report.format_with(&DefaultReportFormatter::ASCII);
     /// println!("{}", formatted);
     /// ```
+    ///
+    /// The formatter can also be chosen at runtime as a
+    /// `&dyn ReportFormatter`:
+    ///
+    /// ```
+    /// use rootcause::{
+    ///     hooks::{
+    ///         builtin_hooks::report_formatter::DefaultReportFormatter,
+    ///         report_formatter::ReportFormatter,
+    ///     },
+    ///     prelude::*,
+    /// };
+    ///
+    /// let plain_output = true;
+    /// let formatter: &dyn ReportFormatter = if plain_output {
+    ///     &DefaultReportFormatter::ASCII
+    /// } else {
+    ///     &DefaultReportFormatter::UNICODE_COLORS
+    /// };
+    ///
+    /// let report = report!("error message");
+    /// let output = report.format_with(formatter).to_string();
+    /// assert!(output.contains("o  error message"));
+    /// ```
     #[must_use]
     pub fn format_with<H>(&self, hook: &H) -> impl core::fmt::Display + core::fmt::Debug
     where
-        H: crate::hooks::report_formatter::ReportFormatter,
+        H: crate::hooks::report_formatter::ReportFormatter + ?Sized,
     {
         self.as_uncloneable_ref().format_with(hook)
     }
 
+    /// Writes the formatted report to a [`fmt::Write`](core::fmt::Write)
+    /// target.
+    ///
+    /// This produces the same output as the [`Display`](core::fmt::Display)
+    /// implementation, but it is written to `writer` as it is produced,
+    /// instead of first being collected into a [`String`] as with
+    /// `report.to_string()`. This avoids a large allocation when formatting
+    /// very large reports.
+    ///
+    /// With the `std` feature, `Report::write_to_io` can be used to write to an
+    /// `std::io::Write` target such as a file instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let report = report!("error message");
+    ///
+    /// let mut output = String::new();
+    /// report.write_to(&mut output).unwrap();
+    /// assert_eq!(output, report.to_string());
+    /// ```
+    pub fn write_to<W>(&self, writer: &mut W) -> core::fmt::Result
+    where
+        W: core::fmt::Write + ?Sized,
+    {
+        write!(writer, "{self}")
+    }
+
+    /// Writes the formatted report to an [`io::Write`](std::io::Write) target.
+    ///
+    /// This produces the same output as the [`Display`](core::fmt::Display)
+    /// implementation, but it is written to `writer` as it is produced,
+    /// without collecting the entire output in memory first. This is useful
+    /// when writing large reports to a file or socket.
+    ///
+    /// The writer is not flushed. Wrap unbuffered writers such as
+    /// [`File`](std::fs::File) in a [`BufWriter`](std::io::BufWriter) to
+    /// avoid issuing a separate write for every piece of the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let report = report!("error message");
+    ///
+    /// let mut output: Vec<u8> = Vec::new();
+    /// report.write_to_io(&mut output).unwrap();
+    /// assert_eq!(String::from_utf8(output).unwrap(), report.to_string());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_to_io<W>(&self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: std::io::Write + ?Sized,
+    {
+        write!(writer, "{self}")
+    }
+
     /// Gets the preferred formatting style for the context with hook
     /// processing.
     ///
@@ -1405,16 +3298,49 @@ impl<C: ?Sized, O, T> Report<C, O, T> {
 
     /// Returns the number of references to this report.
     ///
+    /// The count is only a snapshot: for [`Cloneable`] reports with the
+    /// [`SendSync`] marker, clones on other threads may be created or dropped
+    /// concurrently, so the count may already be outdated when it is returned.
+    ///
     /// # Examples
     /// ```
     /// # use rootcause::prelude::*;
     /// let report: Report = report!("error message");
     /// assert_eq!(report.strong_count(), 1); // We just created the report so it has a single owner
+    ///
+    /// let report = report.into_cloneable();
+    /// let clone = report.clone();
+    /// assert_eq!(report.strong_count(), 2);
     /// ```
     #[must_use]
     pub fn strong_count(&self) -> usize {
         self.as_uncloneable_ref().strong_count()
     }
+
+    /// Returns `true` if this is the only reference to the report.
+    ///
+    /// For [`Cloneable`] reports, this tells whether
+    /// [`try_into_mutable`](Report::try_into_mutable) would succeed. Like
+    /// [`strong_count`](Self::strong_count), the result is only a snapshot,
+    /// and may change concurrently for reports with the [`SendSync`] marker.
+    /// For [`Mutable`] reports, this always returns `true`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("error message").into_cloneable();
+    /// assert!(report.is_unique());
+    ///
+    /// let clone = report.clone();
+    /// assert!(!report.is_unique());
+    ///
+    /// drop(clone);
+    /// assert!(report.is_unique());
+    /// ```
+    #[must_use]
+    pub fn is_unique(&self) -> bool {
+        self.as_uncloneable_ref().is_unique()
+    }
 }
 
 impl<C: Sized, O, T> Report<C, O, T> {
@@ -1434,6 +3360,29 @@ impl<C: Sized, O, T> Report<C, O, T> {
     }
 }
 
+impl<C: Sized + Send + Sync, O> Report<C, O, SendSync> {
+    /// Returns a `Send + Sync` [`&dyn Any`](Any) view of the current context.
+    ///
+    /// Unlike the [`&dyn Any`](Any) returned by
+    /// [`current_context_as_any`](Self::current_context_as_any), the returned
+    /// reference is [`Send`], so it can be held across an `.await` in a
+    /// future that needs to be [`Send`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// # use core::any::Any;
+    /// # struct MyError;
+    /// let report: Report<MyError> = report!(MyError);
+    /// let any: &(dyn Any + Send + Sync) = report.current_context_as_any_send_sync();
+    /// assert!(any.is::<MyError>());
+    /// ```
+    #[must_use]
+    pub fn current_context_as_any_send_sync(&self) -> &(dyn Any + Send + Sync + 'static) {
+        self.current_context()
+    }
+}
+
 impl<O, T> Report<Dynamic, O, T> {
     /// Attempts to downcast the current context to a specific type.
     ///
@@ -1610,9 +3559,78 @@ impl<O, T> Report<Dynamic, O, T> {
         O: ReportOwnershipMarker,
         D: 'static,
     {
-        DowncastIterator {
-            iter: self.iter_reports(),
-            _phantom: PhantomData,
+        self.as_ref().iter_downcast_context()
+    }
+}
+
+impl<T> Report<Dynamic, Mutable, T> {
+    /// Downcasts the report to a specific context type, or wraps it in a new
+    /// report with the given context if the current context has a different
+    /// type.
+    ///
+    /// Unlike [`downcast_report`](Self::downcast_report), this always returns
+    /// a [`Report<C>`], which makes it convenient for functions returning a
+    /// typed report that call functions returning a dynamic one. When the
+    /// types differ, the original report is kept as the single child of the
+    /// new report, as with [`context`](Self::context).
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum DomainError {
+    ///     NotFound,
+    ///     Internal,
+    /// }
+    /// # impl std::fmt::Display for DomainError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{self:?}") }
+    /// # }
+    ///
+    /// // The context already has the right type, so it is kept as it is
+    /// let report: Report = report!(DomainError::NotFound).into_dynamic();
+    /// let report: Report<DomainError> = report.downcast_or_context(DomainError::Internal);
+    /// assert_eq!(report.current_context(), &DomainError::NotFound);
+    /// assert_eq!(report.children().len(), 0);
+    ///
+    /// // Otherwise the report is wrapped in a new one
+    /// let report: Report = report!("connection reset");
+    /// let report: Report<DomainError> = report.downcast_or_context(DomainError::Internal);
+    /// assert_eq!(report.current_context(), &DomainError::Internal);
+    /// assert_eq!(report.children().len(), 1);
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn downcast_or_context<C>(self, context: C) -> Report<C, Mutable, T>
+    where
+        C: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        self.downcast_or_context_with(|| context)
+    }
+
+    /// Downcasts the report to a specific context type, or wraps it in a new
+    /// report with a context generated by the provided closure if the current
+    /// context has a different type.
+    ///
+    /// This is the lazy version of
+    /// [`downcast_or_context`](Self::downcast_or_context).
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report: Report = report!("connection reset");
+    /// let report: Report<String> = report.downcast_or_context_with(|| format!("request {} failed", 7));
+    /// assert_eq!(report.current_context(), "request 7 failed");
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn downcast_or_context_with<C, F>(self, context: F) -> Report<C, Mutable, T>
+    where
+        F: FnOnce() -> C,
+        C: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        match self.downcast_report::<C>() {
+            Ok(report) => report,
+            Err(report) => report.context(context()),
         }
     }
 }
@@ -1715,6 +3733,32 @@ impl<C: Sized> Report<C, Mutable, Local> {
     {
         Self::new_custom::<H>(context)
     }
+
+    /// Creates a new [`Local`] report like [`Report::from_parts`], but
+    /// records the context as `Send + Sync`, so that
+    /// [`Report::try_into_sendsync`] can succeed for the report.
+    #[track_caller]
+    #[must_use]
+    pub(crate) fn from_parts_sendsync_context<H>(
+        context: C,
+        children: ReportCollection<Dynamic, Local>,
+        attachments: ReportAttachments<Local>,
+    ) -> Self
+    where
+        C: markers::ObjectMarkerFor<SendSync>,
+        H: ContextHandler<C>,
+    {
+        let mut report = Report::<C, Mutable, SendSync>::from_parts_unhooked::<H>(
+            context,
+            ReportCollection::new(),
+            ReportAttachments::new(),
+        )
+        .into_local();
+        *report.children_mut() = children;
+        *report.attachments_mut() = attachments;
+        <C as markers::ObjectMarkerFor<Local>>::run_creation_hooks(report.as_mut().into_dynamic());
+        report
+    }
 }
 
 impl<C: ?Sized, O> Report<C, O, SendSync> {
@@ -1764,6 +3808,84 @@ impl<C: ?Sized, O> Report<C, O, Local> {
     pub fn as_dyn_error(&self) -> &(dyn core::error::Error + 'static) {
         ErrorNoSourceWrapper::new(self)
     }
+
+    /// Converts this report back to a [`SendSync`] report, if all of its
+    /// objects are known to be [`Send`] and [`Sync`].
+    ///
+    /// Every context and attachment records whether it was created for a
+    /// [`SendSync`] report. This method checks these records for the whole
+    /// report tree, and returns the report unchanged as the [`Err`] variant
+    /// if any of them is missing.
+    ///
+    /// The records are kept when a report is converted using
+    /// [`Report::into_local`]. Objects added to a [`Local`] report are
+    /// recorded as `Send + Sync` when this is known:
+    ///
+    /// - the contexts of reports created by [`report!`](crate::report!),
+    ///   including with a `source` that is a [`Local`] report,
+    /// - the attachments collected by the built-in report creation hooks,
+    ///   such as the [`Location`] of the report.
+    ///
+    /// Whether a type is [`Send`] and [`Sync`] cannot be detected in generic
+    /// code, so the contexts and attachments added to a [`Local`] report by
+    /// methods such as [`Report::context`] and [`Report::attach`] are always
+    /// treated as not thread-safe.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use rootcause::{prelude::*, markers::{Local, SendSync}};
+    /// let report: Report<_, _, Local> = report!("thread-safe error").into_local();
+    /// let report: Report<_, _, SendSync> = report.try_into_sendsync().unwrap();
+    ///
+    /// let local = report!(Rc::new("not thread-safe"));
+    /// let report = report!("failed to parse", source: local);
+    /// // The `Rc` in the child is not thread-safe
+    /// let report = report.try_into_sendsync().unwrap_err();
+    ///
+    /// let child = report.children().get(0).unwrap().clone_arc();
+    /// let mut report: Report<_, _, Local> = report!("thread-safe parent").into_local();
+    /// report.children_mut().push(child);
+    /// assert!(report.try_into_sendsync().is_err());
+    /// ```
+    ///
+    /// [`Location`]: crate::hooks::builtin_hooks::location::Location
+    pub fn try_into_sendsync(self) -> Result<Report<C, O, SendSync>, Self> {
+        if !is_send_sync_tree(self.as_raw_ref()) {
+            return Err(self);
+        }
+
+        let raw = self.into_raw();
+
+        // SAFETY:
+        // 1. This is guaranteed by the invariants of this type.
+        // 2. This is guaranteed by the invariants of this type.
+        // 3. `T=SendSync`, so this is trivially upheld.
+        // 4. This is guaranteed by the invariants of this type.
+        // 5. This is guaranteed by the invariants of this type.
+        // 6. This is guaranteed by the invariants of this type.
+        // 7. This is guaranteed by the invariants of this type.
+        // 8. We just checked that every context and attachment in the report tree
+        //    was created using `RawReport::new_send_sync` or
+        //    `RawAttachment::new_send_sync`, which require the objects to be
+        //    `Send+Sync`.
+        let report = unsafe { Report::<C, O, SendSync>::from_raw(raw) };
+        Ok(report)
+    }
+}
+
+/// Returns whether every context and attachment in the report tree was
+/// recorded as `Send + Sync` when it was created.
+fn is_send_sync_tree(report: RawReportRef<'_>) -> bool {
+    report.is_send_sync()
+        && report
+            .attachments()
+            .iter()
+            .all(|attachment| attachment.as_ref().is_send_sync())
+        && report
+            .children()
+            .iter()
+            .all(|child| is_send_sync_tree(child.as_ref()))
 }
 
 // SAFETY: The `SendSync` marker indicates that all objects in the report are
@@ -1816,6 +3938,40 @@ where
     }
 }
 
+/// Downcasts a [`Dynamic`] report to a report with context type `C`.
+///
+/// This is the same as [`Report::downcast_report`], and returns the original
+/// report as the error if the current context is not of type `C`. On success,
+/// only the context type marker changes, the report itself is not moved or
+/// reallocated.
+///
+/// # Examples
+/// ```
+/// # use rootcause::prelude::*;
+/// # #[derive(Debug)]
+/// # struct MyError;
+/// let report: Report = report!(MyError).into_dynamic();
+/// let typed: Report<MyError> = report.try_into().unwrap();
+///
+/// let report: Report = report!("not a MyError").into_dynamic();
+/// let result: Result<Report<MyError>, Report> = report.try_into();
+/// assert!(result.is_err());
+/// ```
+impl<C: Sized + 'static, O, T> TryFrom<Report<Dynamic, O, T>> for Report<C, O, T> {
+    type Error = Report<Dynamic, O, T>;
+
+    fn try_from(report: Report<Dynamic, O, T>) -> Result<Self, Self::Error> {
+        report.downcast_report()
+    }
+}
+
+/// Cloning a [`Cloneable`] report is cheap: it increments the reference count
+/// of the underlying [`triomphe::Arc`] instead of copying the report tree.
+///
+/// The clone shares the same report tree as the original. If `T` is
+/// [`SendSync`], the clones can be sent to and used from other threads, since
+/// the tree cannot be mutated while it is shared. For [`Local`] reports, the
+/// clones stay on the current thread.
 impl<C: ?Sized, T> Clone for Report<C, Cloneable, T> {
     fn clone(&self) -> Self {
         self.as_ref().clone_arc()
@@ -1893,9 +4049,64 @@ from_impls!(
     <>:  Dynamic => Dynamic, Cloneable => Cloneable, SendSync => Local, [into_local],
 );
 
+/// Redacts a shared report and its descendants as described in
+/// [`Report::redact`].
+///
+/// Returns [`None`] if nothing in the subtree was removed or replaced, and a
+/// copy of the report with the redactions applied otherwise.
+fn redact_shared<T, F>(
+    report: ReportRef<'_, Dynamic, Cloneable, T>,
+    f: &mut F,
+) -> Option<Report<Dynamic, Cloneable, T>>
+where
+    F: FnMut(ReportAttachmentRef<'_, Dynamic>) -> RedactAction,
+    String: markers::ObjectMarkerFor<T>,
+{
+    let actions: Vec<RedactAction> = report.attachments().iter().map(&mut *f).collect();
+    let redacted_children: Vec<Option<Report<Dynamic, Cloneable, T>>> = report
+        .children()
+        .iter()
+        .map(|child| redact_shared(child, f))
+        .collect();
+    if actions.iter().all(|action| *action == RedactAction::Keep)
+        && redacted_children.iter().all(Option::is_none)
+    {
+        return None;
+    }
+
+    let mut attachments = ReportAttachments::new();
+    for (attachment, action) in report.attachments().iter().zip(actions) {
+        let text = match action {
+            RedactAction::Keep => {
+                let style =
+                    attachment.preferred_formatting_style_unhooked(FormattingFunction::Display);
+                if let AttachmentFormattingPlacement::Hidden = style.placement {
+                    continue;
+                }
+                attachment.format_inner_unhooked().to_string()
+            }
+            RedactAction::Remove => continue,
+            RedactAction::Replace(replacement) => replacement,
+        };
+        attachments.push(ReportAttachment::new_custom::<handlers::Display>(text).into_dynamic());
+    }
+
+    let mut children = ReportCollection::new();
+    for (child, redacted_child) in report.children().iter().zip(redacted_children) {
+        children.push(redacted_child.unwrap_or_else(|| child.clone_arc()));
+    }
+
+    let copy = Report::<String, Mutable, T>::from_parts_unhooked::<handlers::Display>(
+        report.format_current_context_unhooked().to_string(),
+        children,
+        attachments,
+    );
+    Some(copy.into_dynamic().into_cloneable())
+}
+
 #[cfg(test)]
 mod tests {
-    use alloc::string::String;
+    use alloc::{format, string::String};
 
     use super::*;
 
@@ -1924,6 +4135,57 @@ mod tests {
         static_assertions::assert_not_impl_any!(Report<Dynamic, Cloneable, Local>: Send, Sync);
     }
 
+    fn require_send<F: Send>(_: F) {}
+
+    async fn yield_now() {}
+
+    #[test]
+    fn test_report_send_across_await() {
+        require_send(async {
+            let report: Report =
+                Report::new_sendsync_custom::<handlers::Display>("error").into_dynamic();
+            yield_now().await;
+            drop(report);
+        });
+        require_send(async {
+            let report: Report<String, Cloneable> =
+                Report::new_sendsync_custom::<handlers::Display>(String::from("error"))
+                    .into_cloneable();
+            let context = report.current_context();
+            yield_now().await;
+            assert_eq!(context, "error");
+        });
+        require_send(async {
+            let report: Report =
+                Report::new_sendsync_custom::<handlers::Display>("error").into_dynamic();
+            for report in report.iter_reports() {
+                let context = report.downcast_current_context::<&str>();
+                yield_now().await;
+                assert!(context.is_some());
+            }
+        });
+        require_send(async {
+            let report: Report =
+                Report::new_sendsync_custom::<handlers::Display>("error").into_dynamic();
+            let error = report.as_dyn_error();
+            yield_now().await;
+            assert_eq!(error.to_string(), "error");
+        });
+        require_send(async {
+            let report: Report<&str> = Report::new_sendsync_custom::<handlers::Display>("error");
+            let context = report.current_context_as_any_send_sync();
+            yield_now().await;
+            assert!(context.is::<&str>());
+        });
+        require_send(async {
+            let report: Report<&str, Cloneable> =
+                Report::new_sendsync_custom::<handlers::Display>("error").into_cloneable();
+            let context = report.as_ref().current_context_as_any_send_sync();
+            yield_now().await;
+            assert!(context.is::<&str>());
+        });
+    }
+
     #[test]
     fn test_report_unpin() {
         static_assertions::assert_impl_all!(Report<(), Mutable, SendSync>: Unpin);
@@ -1974,4 +4236,183 @@ mod tests {
         static_assertions::assert_not_impl_any!(Report<Dynamic, Cloneable, SendSync>: Copy);
         static_assertions::assert_not_impl_any!(Report<Dynamic, Cloneable, Local>: Copy);
     }
+
+    #[test]
+    fn test_try_from_dynamic_keeps_allocation() {
+        let report: Report =
+            Report::new_sendsync_custom::<handlers::Display>(String::from("error")).into_dynamic();
+        let context = report.downcast_current_context::<String>().unwrap() as *const String;
+
+        let typed: Report<String> = report.try_into().unwrap();
+        assert!(core::ptr::eq(typed.current_context(), context));
+
+        let report = typed.into_dynamic();
+        let result: Result<Report<&str>, Report> = report.try_into();
+        let report = result.unwrap_err();
+        assert!(core::ptr::eq(
+            report.downcast_current_context::<String>().unwrap(),
+            context
+        ));
+    }
+
+    #[test]
+    fn test_try_into_sendsync_checks_children() {
+        let child: Report<_, _, Local> = crate::report!("child").into_local();
+        let report = child.context("parent");
+        // The parent context was added to a local report
+        assert!(report.try_into_sendsync().is_err());
+
+        let report = crate::report!("child").context("parent").into_local();
+        let report = report.try_into_sendsync().unwrap();
+
+        let local_child: Report<_, _, Local> =
+            Report::new_local_custom::<handlers::Display>("local child");
+        let mut report = report.into_local();
+        report
+            .children_mut()
+            .push(local_child.into_dynamic().into_cloneable());
+        assert!(report.try_into_sendsync().is_err());
+    }
+
+    #[test]
+    fn test_try_into_sendsync_for_report_macro_with_local_source() {
+        let source: Report<_, _, Local> = crate::report!("child").into_local();
+        let report: Report<_, _, Local> = crate::report!("parent", source: source);
+        assert!(report.try_into_sendsync().is_ok());
+
+        let source: Report<_, _, Local> = crate::report!("child").into_local();
+        let report: Report<_, _, Local> = crate::report!(String::from("parent"), source: source);
+        assert!(report.try_into_sendsync().is_ok());
+
+        let source = crate::report!(alloc::rc::Rc::new("child"));
+        let report = crate::report!(String::from("parent"), source: source);
+        assert!(report.try_into_sendsync().is_err());
+    }
+
+    #[test]
+    fn test_clone_root_with_keeps_handler_and_shares_children() {
+        let report = crate::report!("child")
+            .context_custom::<handlers::Debug, _>(String::from("parent"))
+            .attach("detail");
+        let copy = report.clone_root_with(|_| None);
+
+        assert_eq!(
+            copy.format_current_context().to_string(),
+            "Context of type `alloc::string::String`"
+        );
+        assert!(copy.attachments().is_empty());
+        assert_eq!(report.children().get(0).unwrap().strong_count(), 2);
+        drop(report);
+        assert_eq!(copy.children().get(0).unwrap().strong_count(), 1);
+    }
+
+    #[test]
+    fn test_clone_root_with_keeps_downcastable_attachments() {
+        let report = crate::report!("child")
+            .context(String::from("parent"))
+            .attach("detail")
+            .attach_kv("user", 7)
+            .into_local();
+        let copy = report.clone_root_with(|attachment| {
+            let key_value = attachment.downcast_inner::<crate::key_value::KeyValue>()?;
+            Some(ReportAttachment::new(key_value.clone()).into_dynamic())
+        });
+
+        assert_eq!(copy.attachments().len(), 1);
+        let key_value = copy
+            .attachments()
+            .get(0)
+            .unwrap()
+            .downcast_inner::<crate::key_value::KeyValue>()
+            .unwrap();
+        assert_eq!(key_value.key, "user");
+        assert_eq!(copy.children().len(), 1);
+    }
+
+    #[test]
+    fn test_redact_copies_shared_children() {
+        let shared = crate::report!("shared child")
+            .attach("password: hunter2")
+            .attach("user: alice")
+            .into_cloneable();
+        let mut report = crate::report!("parent");
+        report.children_mut().push(shared.clone().into_dynamic());
+
+        let redacted = report.redact(|attachment| {
+            if attachment
+                .format_inner_unhooked()
+                .to_string()
+                .starts_with("password")
+            {
+                RedactAction::Replace(String::from("password: <redacted>"))
+            } else {
+                RedactAction::Keep
+            }
+        });
+
+        let output = redacted.to_string();
+        assert!(!output.contains("hunter2"));
+        assert!(output.contains("password: <redacted>"));
+        assert!(output.contains("user: alice"));
+        assert!(output.contains("shared child"));
+        assert!(shared.to_string().contains("hunter2"));
+    }
+
+    #[test]
+    fn test_redact_keeps_unchanged_shared_children() {
+        let shared = crate::report!("shared child").into_cloneable();
+        let mut report = crate::report!("parent");
+        report.children_mut().push(shared.clone().into_dynamic());
+
+        let redacted = report.redact(|_| RedactAction::Keep);
+        assert_eq!(shared.strong_count(), 2);
+        drop(redacted);
+        assert_eq!(shared.strong_count(), 1);
+    }
+
+    #[test]
+    fn test_redact_keeps_typed_attachments_of_unchanged_shared_children() {
+        struct RequestId(u32);
+
+        let untouched = crate::report!("untouched child")
+            .attach_custom::<handlers::Any, _>(RequestId(7))
+            .into_cloneable();
+        let mut changed = crate::report!("changed child").attach("password: hunter2");
+        changed
+            .children_mut()
+            .push(untouched.clone().into_dynamic());
+        let changed = changed.into_cloneable();
+        let mut report = crate::report!("parent");
+        report.children_mut().push(untouched.clone().into_dynamic());
+        report.children_mut().push(changed.clone().into_dynamic());
+
+        let redacted = report.redact(|attachment| {
+            if attachment.downcast_inner::<&str>().is_some() {
+                RedactAction::Remove
+            } else {
+                RedactAction::Keep
+            }
+        });
+
+        let children = redacted.children();
+        let kept = children.get(0).unwrap();
+        assert!(kept.attachments().iter().any(|attachment| {
+            attachment
+                .downcast_inner::<RequestId>()
+                .is_some_and(|id| id.0 == 7)
+        }));
+        let copied = children.get(1).unwrap();
+        assert!(!format!("{copied}").contains("hunter2"));
+        let nested = copied.children().get(0).unwrap();
+        assert!(
+            nested
+                .attachments()
+                .iter()
+                .any(|attachment| { attachment.downcast_inner::<RequestId>().is_some() })
+        );
+        // `untouched` itself, the original `changed` and the redacted report
+        // each hold it directly or through the copy
+        assert_eq!(untouched.strong_count(), 4);
+        assert!(format!("{changed}").contains("hunter2"));
+    }
 }
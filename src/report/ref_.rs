@@ -1,13 +1,30 @@
-use alloc::vec;
-use core::any::{Any, TypeId};
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+};
 
-use rootcause_internals::handlers::{ContextFormattingStyle, FormattingFunction};
+use rootcause_internals::handlers::{
+    AttachmentFormattingPlacement, ContextFormattingStyle, FormattingFunction, JsonSink,
+};
 
 use crate::{
     Report, ReportIter,
+    error_code::ErrorCode,
+    handlers::JsonWriter,
+    hooks::builtin_hooks::location::Location,
     markers::{Cloneable, Dynamic, Local, SendSync, Uncloneable},
+    report::iter::DowncastIterator,
+    report_attachment::ReportAttachmentRef,
     report_attachments::ReportAttachments,
     report_collection::ReportCollection,
+    report_testing::ReportShape,
+    severity::Severity,
     util::{ErrorNoSourceWrapper, format_helper},
 };
 
@@ -262,6 +279,30 @@ impl<'a, C: Sized, O, T> ReportRef<'a, C, O, T> {
     }
 }
 
+impl<'a, C: Sized + Send + Sync, O> ReportRef<'a, C, O, SendSync> {
+    /// Returns a `Send + Sync` [`&dyn Any`](Any) view of the current context.
+    ///
+    /// Unlike the [`&dyn Any`](Any) returned by
+    /// [`current_context_as_any`](Self::current_context_as_any), the returned
+    /// reference is [`Send`], so it can be held across an `.await` in a
+    /// future that needs to be [`Send`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportRef};
+    /// # use core::any::Any;
+    /// # struct MyError;
+    /// # let report = report!(MyError).into_cloneable();
+    /// let report_ref: ReportRef<'_, MyError> = report.as_ref();
+    /// let any: &(dyn Any + Send + Sync) = report_ref.current_context_as_any_send_sync();
+    /// assert!(any.is::<MyError>());
+    /// ```
+    #[must_use]
+    pub fn current_context_as_any_send_sync(self) -> &'a (dyn Any + Send + Sync + 'static) {
+        self.current_context()
+    }
+}
+
 impl<'a, C: ?Sized, O, T> ReportRef<'a, C, O, T> {
     /// Maps a Cloneable report reference to a report reference with any
     /// ownership
@@ -336,6 +377,40 @@ impl<'a, C: ?Sized, O, T> ReportRef<'a, C, O, T> {
         unsafe { ReportAttachments::<T>::from_raw_ref(raw) }
     }
 
+    /// Returns the [`Location`] where this report was created.
+    ///
+    /// The location is captured by the [`LocationHook`] that is installed by
+    /// default, and stored as an attachment of the report. This returns the
+    /// first [`Location`] attachment of this report, or [`None`] if the report
+    /// has none, for instance because the hooks were installed with
+    /// [`Hooks::new_without_locations`].
+    ///
+    /// Only the attachments of this report are searched. Use
+    /// [`LocationTrail::from_report`] to collect the locations of the child
+    /// reports as well.
+    ///
+    /// [`LocationHook`]: crate::hooks::builtin_hooks::location::LocationHook
+    /// [`Hooks::new_without_locations`]: crate::hooks::Hooks::new_without_locations
+    /// [`LocationTrail::from_report`]: crate::hooks::builtin_hooks::location_trail::LocationTrail::from_report
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportRef};
+    /// let line = line!() + 1;
+    /// let report = report!("error message").into_cloneable();
+    /// let report_ref: ReportRef<'_> = report.as_ref();
+    ///
+    /// let location = report_ref.location().unwrap();
+    /// assert_eq!(location.file, file!());
+    /// assert_eq!(location.line, line);
+    /// ```
+    #[must_use]
+    pub fn location(self) -> Option<&'a Location> {
+        self.attachments()
+            .iter()
+            .find_map(|attachment| attachment.downcast_inner::<Location>())
+    }
+
     /// Changes the context type of the [`ReportRef`] to [`Dynamic`].
     ///
     /// Calling this method is equivalent to calling `report.into()`, however
@@ -564,6 +639,644 @@ impl<'a, C: ?Sized, O, T> ReportRef<'a, C, O, T> {
         ReportIter::from_raw(stack)
     }
 
+    /// Returns an iterator over the leaves of the report hierarchy.
+    ///
+    /// A leaf is a report without any children, which usually means it is
+    /// one of the underlying causes of the error. The leaves are visited in
+    /// the same depth-first order as [`ReportRef::iter_reports`]. If this
+    /// report has no children, it is the only leaf.
+    ///
+    /// This is useful for summarizing a report that aggregates multiple
+    /// failures down to its underlying causes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_collection::ReportCollection};
+    /// let causes: ReportCollection = [
+    ///     report!("disk full").context("write failed").into_dynamic().into_cloneable(),
+    ///     report!("timed out").into_cloneable(),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// let report: Report = causes.context("2 jobs failed").into_dynamic();
+    ///
+    /// let leaves: Vec<String> = report
+    ///     .as_ref()
+    ///     .leaves()
+    ///     .map(|leaf| leaf.format_current_context().to_string())
+    ///     .collect();
+    /// assert_eq!(leaves, ["disk full", "timed out"]);
+    /// ```
+    pub fn leaves(self) -> impl Iterator<Item = ReportRef<'a, Dynamic, O, T>> {
+        self.iter_reports()
+            .filter(|report| report.children().is_empty())
+    }
+
+    /// Returns the number of reports in the hierarchy that have no children.
+    ///
+    /// This is the number of items returned by [`ReportRef::leaves`]. A
+    /// report without children counts as its own single leaf.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("timed out")
+    ///     .join(report!("timed out"), "2 jobs failed")
+    ///     .join(report!("disk full"), "3 jobs failed");
+    /// assert_eq!(report.as_ref().leaf_count(), 3);
+    /// ```
+    #[must_use]
+    pub fn leaf_count(self) -> usize {
+        self.leaves().count()
+    }
+
+    /// Returns the number of distinct causes among the leaves of the report
+    /// hierarchy.
+    ///
+    /// Two leaves are considered the same cause when their contexts have the
+    /// same type and the same [`Display`](core::fmt::Display) output. This
+    /// can be used to tell a report aggregating many identical failures apart
+    /// from one aggregating many different ones.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("timed out")
+    ///     .join(report!("timed out"), "2 jobs failed")
+    ///     .join(report!("disk full"), "3 jobs failed");
+    /// assert_eq!(report.as_ref().leaf_count(), 3);
+    /// assert_eq!(report.as_ref().distinct_cause_count(), 2);
+    /// ```
+    #[must_use]
+    pub fn distinct_cause_count(self) -> usize {
+        self.leaves()
+            .map(|leaf| {
+                (
+                    leaf.current_context_type_id(),
+                    leaf.format_current_context().to_string(),
+                )
+            })
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    /// Returns the highest [`Severity`] attached anywhere in the report
+    /// hierarchy, or `None` if no report has a severity attached.
+    ///
+    /// Both this report and all of its descendants are searched.
+    ///
+    /// [`Severity`]: crate::severity::Severity
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, severity::Severity};
+    /// let report = report!("disk almost full")
+    ///     .attach_severity(Severity::Warning)
+    ///     .context("health check failed")
+    ///     .attach_severity(Severity::Error);
+    /// assert_eq!(report.as_ref().max_severity(), Some(Severity::Error));
+    /// assert_eq!(report!("no severity").as_ref().max_severity(), None);
+    /// ```
+    #[must_use]
+    pub fn max_severity(self) -> Option<Severity> {
+        self.iter_reports()
+            .flat_map(|report| report.attachments().iter())
+            .filter_map(|attachment| attachment.downcast_inner::<Severity>())
+            .copied()
+            .max()
+    }
+
+    /// Returns the first [`ErrorCode`] attached in the report hierarchy, or
+    /// `None` if no report has an error code attached.
+    ///
+    /// The reports are searched in the same depth-first order as
+    /// [`ReportRef::iter_reports`], so a code attached to an outer report takes
+    /// precedence over the codes of its causes.
+    ///
+    /// [`ErrorCode`]: crate::error_code::ErrorCode
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("user not found")
+    ///     .attach_error_code("E1234")
+    ///     .context("failed to load profile");
+    /// assert_eq!(report.as_ref().error_code(), Some("E1234"));
+    ///
+    /// let report = report.context("request failed").attach_error_code("HTTP404");
+    /// assert_eq!(report.as_ref().error_code(), Some("HTTP404"));
+    /// ```
+    #[must_use]
+    pub fn error_code(self) -> Option<&'a str> {
+        self.iter_reports()
+            .flat_map(|report| report.attachments().iter())
+            .find_map(|attachment| attachment.downcast_inner::<ErrorCode>())
+            .map(ErrorCode::as_str)
+    }
+
+    /// Returns the headers of all attachments in the report hierarchy that
+    /// are formatted with a header, without duplicates.
+    ///
+    /// This includes the `header` of attachments placed
+    /// [`InlineWithHeader`](AttachmentFormattingPlacement::InlineWithHeader)
+    /// or [`Collapsed`](AttachmentFormattingPlacement::Collapsed), and the
+    /// `appendix_name` of attachments placed in an
+    /// [`Appendix`](AttachmentFormattingPlacement::Appendix), as chosen by
+    /// their preferred formatting style when displayed. Both this report and
+    /// all of its descendants are searched, and the headers are returned in
+    /// the order in which they are first found.
+    ///
+    /// This gives a quick summary of the kinds of data carried by a report,
+    /// without formatting it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, handlers::*};
+    /// struct Request(&'static str);
+    ///
+    /// struct RequestHandler;
+    /// impl AttachmentHandler<Request> for RequestHandler {
+    ///     fn display(value: &Request, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         f.write_str(value.0)
+    ///     }
+    ///
+    ///     fn debug(value: &Request, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         f.write_str(value.0)
+    ///     }
+    ///
+    ///     fn preferred_formatting_style(
+    ///         _value: &Request,
+    ///         _report_formatting_function: FormattingFunction,
+    ///     ) -> AttachmentFormattingStyle {
+    ///         AttachmentFormattingStyle {
+    ///             placement: AttachmentFormattingPlacement::InlineWithHeader { header: "Request" },
+    ///             function: FormattingFunction::Display,
+    ///             priority: 0,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let report = report!("timeout")
+    ///     .attach_custom::<RequestHandler, _>(Request("GET /users"))
+    ///     .context("request failed")
+    ///     .attach_custom::<RequestHandler, _>(Request("GET /orders"))
+    ///     .attach("retrying");
+    ///
+    /// let headers: Vec<&str> = report.as_ref().attachment_headers().collect();
+    /// assert_eq!(headers, ["Request"]);
+    /// ```
+    pub fn attachment_headers(self) -> impl Iterator<Item = &'static str> {
+        let mut headers = Vec::new();
+        for report in self.iter_reports() {
+            for attachment in report.attachments().iter() {
+                let header = match attachment
+                    .preferred_formatting_style(FormattingFunction::Display)
+                    .placement
+                {
+                    AttachmentFormattingPlacement::InlineWithHeader { header }
+                    | AttachmentFormattingPlacement::Collapsed { header, .. } => header,
+                    AttachmentFormattingPlacement::Appendix { appendix_name } => appendix_name,
+                    _ => continue,
+                };
+                if !headers.contains(&header) {
+                    headers.push(header);
+                }
+            }
+        }
+        headers.into_iter()
+    }
+
+    /// Returns an iterator over the primary chain of reports, starting with
+    /// this report.
+    ///
+    /// The primary chain follows the first child of each report, which is
+    /// usually the main cause of the error, and ends at the first report
+    /// without children. When a report has multiple children, only the first
+    /// one is followed, and the others and their descendants are skipped. Use
+    /// [`ReportRef::iter_reports`] to visit the full tree instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused")
+    ///     .context("failed to load user")
+    ///     .join(report!("cache unavailable"), "request failed");
+    ///
+    /// let chain: Vec<String> = report
+    ///     .as_ref()
+    ///     .iter_primary_chain()
+    ///     .map(|report| report.format_current_context().to_string())
+    ///     .collect();
+    /// assert_eq!(chain, ["request failed", "failed to load user", "connection refused"]);
+    /// ```
+    pub fn iter_primary_chain(self) -> impl Iterator<Item = ReportRef<'a, Dynamic, O, T>> {
+        core::iter::successors(Some(self.into_dynamic()), |report| {
+            let child = report.children().get(0)?;
+
+            // SAFETY:
+            // 1. We have an instance of `ReportRef<'a, Dynamic, O, T>`, so by its safety
+            //    invariants `O` is either `Cloneable` or `Uncloneable`.
+            Some(unsafe {
+                // @add-unsafe-context: Dynamic
+                ReportRef::<Dynamic, O, T>::from_cloneable(child)
+            })
+        })
+    }
+
+    /// Returns `true` if this report has no children and no attachments, so
+    /// it consists of just its context.
+    ///
+    /// The [`Location`] attachments captured when reports are created are not
+    /// counted, like in [`ReportRef::format_compact`], since almost every
+    /// report has one. This is useful to decide whether a report needs more
+    /// detail before it is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused");
+    /// assert!(report.as_ref().is_empty_tree());
+    ///
+    /// let report = report.attach("port 5432");
+    /// assert!(!report.as_ref().is_empty_tree());
+    ///
+    /// let report = report!("connection refused").context("failed to load user");
+    /// assert!(!report.as_ref().is_empty_tree());
+    /// ```
+    #[must_use]
+    pub fn is_empty_tree(self) -> bool {
+        self.children().is_empty()
+            && self
+                .attachments()
+                .iter()
+                .all(|attachment| attachment.downcast_inner::<Location>().is_some())
+    }
+
+    /// Returns the context of this report as a single [`String`].
+    ///
+    /// The context is formatted with hook processing, like
+    /// [`format_current_context`](Self::format_current_context). The children
+    /// and attachments of the report are ignored entirely; use
+    /// [`ReportRef::format_compact`] for a single line that also covers the
+    /// causes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused")
+    ///     .context("failed to load user")
+    ///     .attach("user_id: 42");
+    ///
+    /// assert_eq!(report.as_ref().summary_line(), "failed to load user");
+    /// ```
+    #[must_use]
+    pub fn summary_line(self) -> String {
+        self.format_current_context().to_string()
+    }
+
+    /// Returns the contexts of the primary chain of reports as strings,
+    /// starting with this report.
+    ///
+    /// The chain is the same as for [`ReportRef::iter_primary_chain`]: when a
+    /// report has multiple children, only the first one is followed, matching
+    /// [`ReportRef::format_compact`]. Each context is formatted with hook
+    /// processing, like
+    /// [`format_current_context`](Self::format_current_context). This is
+    /// useful for user interfaces that render the causes of an error as a list
+    /// instead of a tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused")
+    ///     .context("failed to load user")
+    ///     .join(report!("cache unavailable"), "request failed");
+    ///
+    /// assert_eq!(
+    ///     report.as_ref().context_chain_strings(),
+    ///     ["request failed", "failed to load user", "connection refused"]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn context_chain_strings(self) -> Vec<String> {
+        self.iter_primary_chain()
+            .map(|report| report.format_current_context().to_string())
+            .collect()
+    }
+
+    /// Returns an iterator over the report hierarchy, where each report is
+    /// paired with its depth in the tree.
+    ///
+    /// The reports are visited in the same depth-first pre-order as
+    /// [`ReportRef::iter_reports`]. This report has depth 0, its children
+    /// have depth 1, and so on. This is useful for custom formatters and
+    /// exporters that need to indent each report according to its position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused")
+    ///     .context("failed to load user")
+    ///     .join(report!("cache unavailable"), "request failed");
+    ///
+    /// let lines: Vec<String> = report
+    ///     .as_ref()
+    ///     .walk()
+    ///     .map(|(depth, report)| format!("{:1$}{2}", "", depth * 2, report.format_current_context()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     lines,
+    ///     [
+    ///         "request failed",
+    ///         "  failed to load user",
+    ///         "    connection refused",
+    ///         "  cache unavailable",
+    ///     ]
+    /// );
+    /// ```
+    pub fn walk(self) -> impl Iterator<Item = (usize, ReportRef<'a, Dynamic, O, T>)> {
+        let mut stack = vec![(0, self.into_dynamic())];
+        core::iter::from_fn(move || {
+            let (depth, report) = stack.pop()?;
+            stack.extend(report.children().iter().rev().map(|child| {
+                // SAFETY:
+                // 1. We have an instance of `ReportRef<'a, Dynamic, O, T>`, so by its
+                //    safety invariants `O` is either `Cloneable` or `Uncloneable`.
+                let child = unsafe {
+                    // @add-unsafe-context: Dynamic
+                    ReportRef::<Dynamic, O, T>::from_cloneable(child)
+                };
+                (depth + 1, child)
+            }));
+            Some((depth, report))
+        })
+    }
+
+    /// Returns an iterator over the report hierarchy that visits the children
+    /// of each report before the report itself.
+    ///
+    /// The reports are visited in depth-first post-order:
+    ///
+    /// - Every report is yielded after all of its descendants.
+    /// - The children of a report are visited in order, and all descendants of
+    ///   a child are yielded before its next sibling.
+    /// - This report is always yielded last.
+    ///
+    /// Note that this is not the reverse of [`ReportRef::iter_reports`], which
+    /// would also visit the siblings in reverse order. Like
+    /// [`ReportRef::iter_reports`], a report that is shared by several parents
+    /// is visited once for every parent.
+    ///
+    /// This is useful for aggregations where the result for a report depends
+    /// on the results for its children.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused")
+    ///     .context("failed to load user")
+    ///     .join(report!("cache unavailable"), "request failed");
+    ///
+    /// let contexts: Vec<String> = report
+    ///     .as_ref()
+    ///     .iter_post_order()
+    ///     .map(|report| report.format_current_context().to_string())
+    ///     .collect();
+    /// assert_eq!(
+    ///     contexts,
+    ///     [
+    ///         "connection refused",
+    ///         "failed to load user",
+    ///         "cache unavailable",
+    ///         "request failed",
+    ///     ]
+    /// );
+    /// ```
+    pub fn iter_post_order(self) -> impl Iterator<Item = ReportRef<'a, Dynamic, O, T>> {
+        // Each entry holds a report and the index of its next unvisited child
+        let mut stack = vec![(self.into_dynamic(), 0)];
+        core::iter::from_fn(move || {
+            loop {
+                let (report, next_child) = stack.last_mut()?;
+                let report = *report;
+                if let Some(child) = report.children().get(*next_child) {
+                    *next_child += 1;
+                    // SAFETY:
+                    // 1. We have an instance of `ReportRef<'a, Dynamic, O, T>`, so by its
+                    //    safety invariants `O` is either `Cloneable` or `Uncloneable`.
+                    let child = unsafe {
+                        // @add-unsafe-context: Dynamic
+                        ReportRef::<Dynamic, O, T>::from_cloneable(child)
+                    };
+                    stack.push((child, 0));
+                } else {
+                    stack.pop();
+                    return Some(report);
+                }
+            }
+        })
+    }
+
+    /// Returns an iterator over all contexts in the report hierarchy that can
+    /// be downcast to the specified type `D`.
+    ///
+    /// This is the same as [`Report::iter_downcast_context`], and is also
+    /// available under the name [`ReportRef::find_contexts`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let child = report!("inner").into_dynamic();
+    /// let mut root = report!("outer").into_dynamic();
+    /// root.children_mut().push(child.into_cloneable());
+    ///
+    /// let contexts: Vec<&&str> = root.as_ref().iter_downcast_context::<&str>().collect();
+    /// assert_eq!(contexts, [&"outer", &"inner"]);
+    /// ```
+    pub fn iter_downcast_context<D>(self) -> DowncastIterator<'a, D, O, T>
+    where
+        D: 'static,
+    {
+        DowncastIterator {
+            iter: self.iter_reports(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Searches the report hierarchy depth-first and returns the first context
+    /// of type `D`, including the context of this report.
+    ///
+    /// This is useful for inspecting aggregated reports, for example to decide
+    /// how to recover from an error based on whether any of its causes is of a
+    /// specific type.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// #[derive(Debug)]
+    /// struct RateLimited {
+    ///     retry_after_secs: u64,
+    /// }
+    /// # impl std::fmt::Display for RateLimited {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "rate limited") }
+    /// # }
+    ///
+    /// let report = report!(RateLimited { retry_after_secs: 30 })
+    ///     .context("Failed to fetch orders")
+    ///     .context("Failed to render dashboard");
+    ///
+    /// let rate_limited = report.as_ref().find_context::<RateLimited>();
+    /// assert_eq!(rate_limited.map(|e| e.retry_after_secs), Some(30));
+    /// assert!(report.as_ref().find_context::<std::io::Error>().is_none());
+    /// ```
+    #[must_use]
+    pub fn find_context<D>(self) -> Option<&'a D>
+    where
+        D: 'static,
+    {
+        self.iter_downcast_context().next()
+    }
+
+    /// Searches the report hierarchy depth-first and returns an iterator over
+    /// all contexts of type `D`, including the context of this report.
+    ///
+    /// This is an alias for [`ReportRef::iter_downcast_context`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, report_collection::ReportCollection};
+    /// #[derive(Debug)]
+    /// struct HttpError(u16);
+    /// # impl std::fmt::Display for HttpError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "HTTP {}", self.0) }
+    /// # }
+    ///
+    /// let causes: ReportCollection = [
+    ///     report!(HttpError(503)).into_dynamic().into_cloneable(),
+    ///     report!("timed out").into_cloneable(),
+    ///     report!(HttpError(404)).into_dynamic().into_cloneable(),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// let report = causes.context("3 requests failed");
+    ///
+    /// let statuses: Vec<u16> = report.as_ref().find_contexts::<HttpError>().map(|e| e.0).collect();
+    /// assert_eq!(statuses, [503, 404]);
+    /// ```
+    pub fn find_contexts<D>(self) -> DowncastIterator<'a, D, O, T>
+    where
+        D: 'static,
+    {
+        self.iter_downcast_context()
+    }
+
+    /// Returns `true` if the context of this report is of type `D`.
+    ///
+    /// Only this report is checked. Use
+    /// [`contains_context`](Self::contains_context) to search the whole report
+    /// hierarchy.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!(std::io::Error::other("disk full")).context("Failed to save");
+    /// assert!(report.as_ref().has_context::<&str>());
+    /// assert!(!report.as_ref().has_context::<std::io::Error>());
+    /// ```
+    #[must_use]
+    pub fn has_context<D>(self) -> bool
+    where
+        D: 'static,
+    {
+        self.current_context_type_id() == TypeId::of::<D>()
+    }
+
+    /// Returns `true` if this report or any report below it has a context of
+    /// type `D`.
+    ///
+    /// The hierarchy is searched depth-first, stopping at the first match.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!(std::io::Error::other("disk full")).context("Failed to save");
+    /// assert!(report.as_ref().contains_context::<std::io::Error>());
+    /// assert!(!report.as_ref().contains_context::<core::fmt::Error>());
+    /// ```
+    #[must_use]
+    pub fn contains_context<D>(self) -> bool
+    where
+        D: 'static,
+    {
+        self.iter_reports().any(|report| report.has_context::<D>())
+    }
+
+    /// Returns `true` if this report has an attachment of type `A`.
+    ///
+    /// Only the attachments of this report are checked. Use
+    /// [`contains_attachment`](Self::contains_attachment) to search the whole
+    /// report hierarchy.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("request failed").attach(404u16).context("Failed to load page");
+    /// assert!(!report.as_ref().has_attachment::<u16>());
+    /// assert!(report.children().get(0).unwrap().has_attachment::<u16>());
+    /// ```
+    #[must_use]
+    pub fn has_attachment<A>(self) -> bool
+    where
+        A: 'static,
+    {
+        self.attachments()
+            .iter()
+            .any(|attachment| attachment.inner_type_id() == TypeId::of::<A>())
+    }
+
+    /// Returns `true` if this report or any report below it has an attachment
+    /// of type `A`.
+    ///
+    /// The hierarchy is searched depth-first, stopping at the first match.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("request failed").attach(404u16).context("Failed to load page");
+    /// assert!(report.as_ref().contains_attachment::<u16>());
+    /// assert!(!report.as_ref().contains_attachment::<u32>());
+    /// ```
+    #[must_use]
+    pub fn contains_attachment<A>(self) -> bool
+    where
+        A: 'static,
+    {
+        self.iter_reports()
+            .any(|report| report.has_attachment::<A>())
+    }
+
+    /// Returns `true` if this report has the same structure as another
+    /// report.
+    ///
+    /// Two reports have the same structure when their [`ReportShape`]s are
+    /// equal, meaning that their contexts and attachments are formatted the
+    /// same way and their children have the same structure. This is meant for
+    /// tests; see the [`report_testing`](crate::report_testing) module for
+    /// details.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("disk full").context("Failed to save");
+    /// let other = report!("disk full").context("Failed to save");
+    /// assert!(report.as_ref().structural_eq(other.as_ref()));
+    /// assert!(!report.as_ref().structural_eq(report.children().get(0).unwrap()));
+    /// ```
+    #[must_use]
+    pub fn structural_eq<C2: ?Sized, O2, T2>(self, other: ReportRef<'_, C2, O2, T2>) -> bool {
+        ReportShape::from(self) == ReportShape::from(other)
+    }
+
     /// Returns the [`TypeId`] of the current context.
     ///
     /// # Examples
@@ -580,6 +1293,7 @@ impl<'a, C: ?Sized, O, T> ReportRef<'a, C, O, T> {
     /// let type_id = report_ref.current_context_type_id();
     /// assert_eq!(type_id, TypeId::of::<MyError>());
     /// ```
+    #[doc(alias = "context_type_id")]
     #[must_use]
     pub fn current_context_type_id(self) -> TypeId {
         self.as_raw_ref().context_type_id()
@@ -601,6 +1315,7 @@ impl<'a, C: ?Sized, O, T> ReportRef<'a, C, O, T> {
     /// let type_name = report_ref.current_context_type_name();
     /// assert_eq!(type_name, core::any::type_name::<MyError>());
     /// ```
+    #[doc(alias = "context_type_name")]
     #[must_use]
     pub fn current_context_type_name(self) -> &'static str {
         self.as_raw_ref().context_type_name()
@@ -653,6 +1368,23 @@ impl<'a, C: ?Sized, O, T> ReportRef<'a, C, O, T> {
         self.as_raw_ref().context_handler_type_id()
     }
 
+    /// Returns the type name of the handler used for the current context.
+    ///
+    /// Like [`core::any::type_name`], the returned name is only meant for
+    /// debugging and its exact contents are not guaranteed to be stable.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = Report::new_sendsync_custom::<handlers::Debug>("error message");
+    /// let handler_name = report.as_ref().current_context_handler_type_name();
+    /// assert!(handler_name.ends_with("Debug"));
+    /// ```
+    #[must_use]
+    pub fn current_context_handler_type_name(self) -> &'static str {
+        self.as_raw_ref().context_handler_type_name()
+    }
+
     /// Returns the error source if the context implements [`Error`].
     ///
     /// [`Error`]: core::error::Error
@@ -707,6 +1439,197 @@ impl<'a, C: ?Sized, O, T> ReportRef<'a, C, O, T> {
         )
     }
 
+    /// Returns the current context as a string slice, if it is a string.
+    ///
+    /// If the current context is a `&'static str` or a [`String`], such as
+    /// the contexts created by [`report!`](crate::report!) from a message,
+    /// the string is borrowed directly instead of being formatted. This avoids
+    /// an allocation when only the message text is needed. Returns `None` for
+    /// all other context types.
+    ///
+    /// Unlike [`format_current_context`](Self::format_current_context), the
+    /// string is returned as-is, without hook processing. For reports
+    /// preformatted with the companion `rootcause-preformat` crate, the
+    /// `PreformattedContext::display_str` method serves the same purpose.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused");
+    /// assert_eq!(report.as_ref().context_str(), Some("connection refused"));
+    ///
+    /// let port = 8080;
+    /// let report = report!("port {port} is in use");
+    /// assert_eq!(report.as_ref().context_str(), Some("port 8080 is in use"));
+    ///
+    /// let report = report!(std::io::Error::other("disk full"));
+    /// assert_eq!(report.as_ref().context_str(), None);
+    /// ```
+    #[must_use]
+    pub fn context_str(self) -> Option<&'a str> {
+        let report = self.into_dynamic();
+        if let Some(context) = report.downcast_current_context::<&'static str>() {
+            Some(context)
+        } else {
+            report
+                .downcast_current_context::<String>()
+                .map(String::as_str)
+        }
+    }
+
+    /// Formats the report as a single-line summary.
+    ///
+    /// The summary follows the primary context chain, which starts at this
+    /// report and continues with the first child at each level. The contexts
+    /// in the chain are separated by `: `, and are formatted with hook
+    /// processing, like [`format_current_context`](Self::format_current_context).
+    ///
+    /// Everything else is omitted, and the number of omitted sibling reports
+    /// and attachments along the chain is appended in parentheses, for
+    /// instance `(+1 sibling, +2 attachments)`. The [`Location`] attachments
+    /// captured when the reports were created and attachments hidden from the
+    /// regular output are not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let leaf = report!("connection refused").attach("port 5432");
+    /// let other = report!("cache unavailable");
+    /// let report = leaf.join(other, "failed to load user").into_cloneable();
+    ///
+    /// assert_eq!(
+    ///     report.as_ref().format_compact().to_string(),
+    ///     "failed to load user: connection refused (+1 sibling, +1 attachment)"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn format_compact(self) -> impl core::fmt::Display + core::fmt::Debug {
+        format_helper(
+            self.into_dynamic().into_uncloneable().into_local(),
+            |report, formatter| write_compact(report, formatter, FormattingFunction::Display),
+            |report, formatter| write_compact(report, formatter, FormattingFunction::Debug),
+        )
+    }
+
+    /// Returns a [`Debug`](core::fmt::Debug) view of the internal structure of
+    /// the report tree, meant for developers debugging handlers and hooks.
+    ///
+    /// For each report in the tree, the output shows the type name and
+    /// [`TypeId`] of the context, the type name of its handler and its
+    /// preferred formatting style. For each attachment, it shows the type
+    /// name, [`TypeId`] and handler of the attachment, together with its
+    /// preferred placement and priority. Formatting hooks are taken into
+    /// account for the formatting styles, which are those used when the report
+    /// is formatted using [`Display`](core::fmt::Display).
+    ///
+    /// The contexts and attachments themselves are not formatted. Like
+    /// [`core::any::type_name`], the exact output is not guaranteed to be
+    /// stable.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("error message").attach(42);
+    /// let structure = format!("{:#?}", report.as_ref().debug_structure());
+    /// assert!(structure.contains("context_type: \"&str\""));
+    /// assert!(structure.contains("attachment_type: \"i32\""));
+    /// ```
+    #[must_use]
+    pub fn debug_structure(self) -> impl core::fmt::Debug {
+        DebugStructure(self.into_dynamic().into_uncloneable().into_local())
+    }
+
+    /// Emits the structured representation of the current context to a
+    /// [`JsonSink`], using the [`ContextHandler::json`] method of its handler.
+    ///
+    /// Unless the handler overrides [`ContextHandler::json`], this emits the
+    /// display output of the context as a JSON string.
+    ///
+    /// [`ContextHandler::json`]: crate::handlers::ContextHandler::json
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, handlers::JsonWriter};
+    /// let report = report!("error message");
+    /// let mut writer = JsonWriter::new(String::new());
+    /// report.as_ref().write_current_context_json(&mut writer).unwrap();
+    /// assert_eq!(writer.into_inner(), r#""error message""#);
+    /// ```
+    pub fn write_current_context_json(self, sink: &mut dyn JsonSink) -> core::fmt::Result {
+        self.as_raw_ref().context_json(sink)
+    }
+
+    /// Writes the entire report tree as JSON to `writer`.
+    ///
+    /// Each report is written as an object with these fields:
+    ///
+    /// - `context`: the structured representation of the context, as emitted
+    ///   by [`write_current_context_json`](Self::write_current_context_json)
+    /// - `context_type`: the type name of the context
+    /// - `attachments`: the structured representations of the attachments, as
+    ///   emitted by [`ReportAttachmentRef::write_inner_json`]
+    /// - `children`: the child reports, as objects of the same shape
+    ///
+    /// Handlers that implement [`ContextHandler::json`] or
+    /// [`AttachmentHandler::json`] emit real JSON values, and all other
+    /// contexts and attachments are written as strings of their display
+    /// output. Attachments whose preferred placement is
+    /// [`Hidden`](AttachmentFormattingPlacement::Hidden) are left out.
+    ///
+    /// The output does not depend on the installed hooks: neither the report
+    /// formatter nor the context and attachment formatter hooks are used. This
+    /// makes it suitable for code paths that always need JSON, such as the
+    /// error body of an API response.
+    ///
+    /// [`ContextHandler::json`]: crate::handlers::ContextHandler::json
+    /// [`AttachmentHandler::json`]: crate::handlers::AttachmentHandler::json
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, hooks::Hooks};
+    /// # Hooks::new_without_locations().install().unwrap();
+    /// let report = report!("connection refused")
+    ///     .attach_kv("port", "5432")
+    ///     .context("failed to load user");
+    ///
+    /// let mut json = String::new();
+    /// report.as_ref().write_json(&mut json).unwrap();
+    /// assert_eq!(
+    ///     json,
+    ///     concat!(
+    ///         r#"{"context":"failed to load user","context_type":"&str","attachments":[],"#,
+    ///         r#""children":[{"context":"connection refused","context_type":"&str","#,
+    ///         r#""attachments":[{"port":"5432"}],"children":[]}]}"#,
+    ///     )
+    /// );
+    /// ```
+    pub fn write_json<W: core::fmt::Write>(self, writer: &mut W) -> core::fmt::Result {
+        let mut writer = JsonWriter::new(writer);
+        write_json_node(
+            self.into_dynamic().into_uncloneable().into_local(),
+            &mut writer,
+        )
+    }
+
+    /// Returns the entire report tree as a JSON string.
+    ///
+    /// See [`ReportRef::write_json`] for the format of the output.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::prelude::*;
+    /// let report = report!("connection refused").context("failed to load user");
+    /// let json = report.as_ref().to_json_string();
+    /// assert!(json.starts_with(r#"{"context":"failed to load user""#));
+    /// ```
+    #[must_use]
+    pub fn to_json_string(self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json)
+            .expect("writing to a String does not fail");
+        json
+    }
+
     /// Formats the entire report using a specific report formatting hook.
     ///
     /// This method allows you to format a report with a custom formatter
@@ -733,7 +1656,7 @@ impl<'a, C: ?Sized, O, T> ReportRef<'a, C, O, T> {
     /// println!("{}", formatted);
     /// ```
     #[must_use]
-    pub fn format_with<H: crate::hooks::report_formatter::ReportFormatter>(
+    pub fn format_with<H: crate::hooks::report_formatter::ReportFormatter + ?Sized>(
         self,
         hook: &H,
     ) -> impl core::fmt::Display + core::fmt::Debug {
@@ -822,6 +1745,25 @@ impl<'a, C: ?Sized, O, T> ReportRef<'a, C, O, T> {
     pub fn strong_count(self) -> usize {
         self.as_raw_ref().strong_count()
     }
+
+    /// Returns `true` if there is only a single owner of this report.
+    ///
+    /// The result is only a snapshot, and may change concurrently for
+    /// reports with the [`SendSync`] marker.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{prelude::*, ReportRef};
+    /// let report = report!("error message").into_cloneable();
+    /// assert!(report.as_ref().is_unique());
+    ///
+    /// let clone = report.clone();
+    /// assert!(!report.as_ref().is_unique());
+    /// ```
+    #[must_use]
+    pub fn is_unique(self) -> bool {
+        self.strong_count() == 1
+    }
 }
 
 impl<'a, O, T> ReportRef<'a, Dynamic, O, T> {
@@ -1007,6 +1949,34 @@ impl<'a, C: ?Sized, T> ReportRef<'a, C, Cloneable, T> {
         // 8. This is guaranteed by our own safety invariants.
         unsafe { Report::<C, Cloneable, T>::from_raw(cloned_raw) }
     }
+
+    /// Returns an owned [`Report`] that references the same report as this
+    /// [`ReportRef`].
+    ///
+    /// This is the same as [`ReportRef::clone_arc`], and is useful for keeping
+    /// a child report while iterating over the children of a report, which
+    /// are always [`Cloneable`]. The report tree is not copied; only the
+    /// reference count of the underlying [`triomphe::Arc`] is incremented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{markers::Cloneable, prelude::*};
+    ///
+    /// let report = report!("parent error").context("context");
+    ///
+    /// // Keep the first child around after the parent is dropped
+    /// let child: Report<_, Cloneable> = report.children().iter().next().unwrap().to_report();
+    /// drop(report);
+    ///
+    /// assert_eq!(child.format_current_context().to_string(), "parent error");
+    /// ```
+    ///
+    /// [`Cloneable`]: crate::markers::Cloneable
+    #[must_use]
+    pub fn to_report(self) -> Report<C, Cloneable, T> {
+        self.clone_arc()
+    }
 }
 
 impl<'a, C: ?Sized, T> From<ReportRef<'a, C, Cloneable, T>> for Report<C, Cloneable, T> {
@@ -1141,9 +2111,174 @@ from_impls!(
     <>:  Dynamic => Dynamic, Uncloneable => Uncloneable, SendSync => Local, [into_local],
 );
 
+/// Writes the single-line summary created by [`ReportRef::format_compact`].
+fn write_compact(
+    report: ReportRef<'_, Dynamic, Uncloneable, Local>,
+    formatter: &mut core::fmt::Formatter<'_>,
+    function: FormattingFunction,
+) -> core::fmt::Result {
+    let mut omitted_siblings = 0;
+    let mut omitted_attachments = 0;
+
+    for (index, report) in report.iter_primary_chain().enumerate() {
+        if index > 0 {
+            formatter.write_str(": ")?;
+        }
+
+        match function {
+            FormattingFunction::Debug => {
+                crate::hooks::context_formatter::debug_context(report, formatter)?;
+            }
+            _ => crate::hooks::context_formatter::display_context(report, formatter)?,
+        }
+
+        omitted_attachments += report
+            .attachments()
+            .iter()
+            .filter(|attachment| {
+                attachment.downcast_inner::<Location>().is_none()
+                    && !matches!(
+                        attachment.preferred_formatting_style(function).placement,
+                        AttachmentFormattingPlacement::Hidden
+                    )
+            })
+            .count();
+        omitted_siblings += report.children().len().saturating_sub(1);
+    }
+
+    let mut separator = " (";
+    if omitted_siblings > 0 {
+        let noun = if omitted_siblings == 1 {
+            "sibling"
+        } else {
+            "siblings"
+        };
+        write!(formatter, "{separator}+{omitted_siblings} {noun}")?;
+        separator = ", ";
+    }
+    if omitted_attachments > 0 {
+        let noun = if omitted_attachments == 1 {
+            "attachment"
+        } else {
+            "attachments"
+        };
+        write!(formatter, "{separator}+{omitted_attachments} {noun}")?;
+        separator = ", ";
+    }
+    if separator == ", " {
+        formatter.write_str(")")?;
+    }
+    Ok(())
+}
+
+/// Writes a report and its descendants as JSON objects, as described in
+/// [`ReportRef::write_json`].
+fn write_json_node(
+    report: ReportRef<'_, Dynamic, Uncloneable, Local>,
+    sink: &mut dyn JsonSink,
+) -> core::fmt::Result {
+    sink.begin_object()?;
+    sink.key("context")?;
+    report.write_current_context_json(sink)?;
+    sink.key("context_type")?;
+    sink.str(report.current_context_type_name())?;
+
+    sink.key("attachments")?;
+    sink.begin_array()?;
+    for attachment in report.attachments().iter() {
+        let style = attachment.preferred_formatting_style_unhooked(FormattingFunction::Json);
+        if let AttachmentFormattingPlacement::Hidden = style.placement {
+            continue;
+        }
+        attachment.write_inner_json(sink)?;
+    }
+    sink.end_array()?;
+
+    sink.key("children")?;
+    sink.begin_array()?;
+    for child in report.children().iter() {
+        write_json_node(child.into_uncloneable(), sink)?;
+    }
+    sink.end_array()?;
+    sink.end_object()
+}
+
+/// The developer view of a report tree created by
+/// [`ReportRef::debug_structure`].
+struct DebugStructure<'a>(ReportRef<'a, Dynamic, Uncloneable, Local>);
+
+impl core::fmt::Debug for DebugStructure<'_> {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let report = self.0;
+        formatter
+            .debug_struct("Report")
+            .field("context_type", &report.current_context_type_name())
+            .field("context_type_id", &report.current_context_type_id())
+            .field(
+                "context_handler",
+                &report.current_context_handler_type_name(),
+            )
+            .field(
+                "context_formatting_style",
+                &report.preferred_context_formatting_style(FormattingFunction::Display),
+            )
+            .field("attachments", &DebugAttachments(report))
+            .field("children", &DebugChildren(report))
+            .finish()
+    }
+}
+
+/// A single attachment in the output of [`ReportRef::debug_structure`].
+struct DebugAttachmentStructure<'a>(ReportAttachmentRef<'a, Dynamic>);
+
+impl core::fmt::Debug for DebugAttachmentStructure<'_> {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let attachment = self.0;
+        let style = attachment.preferred_formatting_style(FormattingFunction::Display);
+        formatter
+            .debug_struct("Attachment")
+            .field("attachment_type", &attachment.inner_type_name())
+            .field("attachment_type_id", &attachment.inner_type_id())
+            .field("attachment_handler", &attachment.inner_handler_type_name())
+            .field("placement", &style.placement)
+            .field("priority", &style.priority)
+            .finish()
+    }
+}
+
+/// The attachments of a report in the output of
+/// [`ReportRef::debug_structure`].
+struct DebugAttachments<'a>(ReportRef<'a, Dynamic, Uncloneable, Local>);
+
+impl core::fmt::Debug for DebugAttachments<'_> {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter
+            .debug_list()
+            .entries(self.0.attachments().iter().map(DebugAttachmentStructure))
+            .finish()
+    }
+}
+
+/// The children of a report in the output of [`ReportRef::debug_structure`].
+struct DebugChildren<'a>(ReportRef<'a, Dynamic, Uncloneable, Local>);
+
+impl core::fmt::Debug for DebugChildren<'_> {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter
+            .debug_list()
+            .entries(
+                self.0
+                    .children()
+                    .iter()
+                    .map(|child| DebugStructure(child.into_uncloneable().into_local())),
+            )
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use alloc::string::String;
+    use alloc::string::{String, ToString};
 
     use super::*;
     use crate::markers::{Mutable, Uncloneable};
@@ -1234,4 +2369,208 @@ mod tests {
         static_assertions::assert_not_impl_any!(Report<Dynamic, Mutable, Local>: From<ReportRef<'static, Dynamic, Uncloneable, Local>>);
         static_assertions::assert_not_impl_any!(Report<Dynamic, Mutable, SendSync>: From<ReportRef<'static, Dynamic, Uncloneable, SendSync>>);
     }
+
+    #[test]
+    fn test_format_compact() {
+        let report: crate::Report = report!("leaf")
+            .context("middle")
+            .context("root")
+            .into_dynamic();
+        assert_eq!(report.format_compact().to_string(), "root: middle: leaf");
+
+        let report = report!("first")
+            .attach("first attachment")
+            .join(report!("second").attach("second attachment"), "parent")
+            .attach("parent attachment")
+            .into_dynamic();
+        assert_eq!(
+            report.format_compact().to_string(),
+            "parent: first (+1 sibling, +2 attachments)"
+        );
+    }
+
+    #[test]
+    fn test_write_json_skips_hidden_attachments() {
+        struct Secret;
+        struct SecretHandler;
+        impl crate::handlers::AttachmentHandler<Secret> for SecretHandler {
+            fn display(_value: &Secret, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("secret")
+            }
+
+            fn debug(_value: &Secret, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("secret")
+            }
+
+            fn preferred_formatting_style(
+                _value: &Secret,
+                _report_formatting_function: FormattingFunction,
+            ) -> crate::handlers::AttachmentFormattingStyle {
+                crate::handlers::AttachmentFormattingStyle {
+                    placement: AttachmentFormattingPlacement::Hidden,
+                    ..Default::default()
+                }
+            }
+        }
+
+        let report = report!("inner")
+            .attach_custom::<SecretHandler, _>(Secret)
+            .attach("quote \" inside")
+            .context("outer");
+        let json = report.to_json_string();
+        assert!(!json.contains("secret"));
+        assert!(json.contains(r#""quote \" inside""#));
+        assert_eq!(json.matches(r#""children":[]"#).count(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_json_ignores_attachment_formatter_hooks() {
+        use crate::{
+            handlers::AttachmentFormattingStyle,
+            hooks::{Hooks, attachment_formatter::AttachmentFormatterHook},
+            report_attachment::ReportAttachmentRef,
+        };
+
+        #[derive(Debug)]
+        struct Note;
+        struct HideNotes;
+        impl AttachmentFormatterHook<Note> for HideNotes {
+            fn preferred_formatting_style(
+                &self,
+                _attachment: ReportAttachmentRef<'_, Note>,
+                _report_formatting_function: FormattingFunction,
+            ) -> AttachmentFormattingStyle {
+                AttachmentFormattingStyle {
+                    placement: AttachmentFormattingPlacement::Hidden,
+                    ..Default::default()
+                }
+            }
+        }
+
+        let report = report!("failed").attach_custom::<crate::handlers::Debug, _>(Note);
+        let json = Hooks::new()
+            .attachment_formatter::<Note, _>(HideNotes)
+            .scope(|| report.to_json_string());
+        assert!(json.contains("Note"), "{json}");
+    }
+
+    #[test]
+    fn test_is_empty_tree_after_removing_attachments() {
+        let mut report = report!("bare").attach("detail");
+        assert!(!report.as_ref().is_empty_tree());
+        report.attachments_mut().pop();
+        assert!(report.as_ref().is_empty_tree());
+        assert_eq!(report.as_ref().summary_line(), "bare");
+    }
+
+    #[test]
+    fn test_context_chain_strings_matches_format_compact() {
+        let report = report!("first")
+            .join(report!("second"), "parent")
+            .context("root")
+            .into_dynamic();
+        let chain = report.context_chain_strings();
+        assert_eq!(chain, ["root", "parent", "first"]);
+        assert!(
+            report
+                .format_compact()
+                .to_string()
+                .starts_with(&chain.join(": "))
+        );
+    }
+
+    #[test]
+    fn test_distinct_cause_count_groups_by_type_and_display() {
+        let children: ReportCollection = (0..50)
+            .map(|_| report!("timed out").into_dynamic().into_cloneable())
+            .chain([report!(String::from("timed out"))
+                .into_dynamic()
+                .into_cloneable()])
+            .collect();
+        let report = children.context("51 jobs failed");
+        assert_eq!(report.leaf_count(), 51);
+        assert_eq!(report.distinct_cause_count(), 2);
+
+        let report = report!("timed out");
+        assert_eq!(report.leaf_count(), 1);
+        assert_eq!(report.distinct_cause_count(), 1);
+    }
+
+    #[test]
+    fn test_debug_structure() {
+        let report = report!("leaf")
+            .attach(42i32)
+            .context(String::from("root"))
+            .into_dynamic();
+        let structure = alloc::format!("{:?}", report.debug_structure());
+
+        assert!(structure.starts_with("Report { context_type: \"alloc::string::String\""));
+        assert!(structure.contains("context_type: \"&str\""));
+        assert!(structure.contains(&alloc::format!(
+            "context_handler: {:?}",
+            core::any::type_name::<crate::handlers::Display>()
+        )));
+        assert!(structure.contains(&alloc::format!(
+            "attachment_type: \"i32\", attachment_type_id: {:?}",
+            TypeId::of::<i32>()
+        )));
+        assert!(structure.contains("placement: Inline"));
+    }
+
+    #[test]
+    fn test_attachment_headers_includes_appendices() {
+        use crate::handlers::{AttachmentFormattingStyle, AttachmentHandler};
+
+        struct Dump;
+
+        struct DumpHandler;
+        impl AttachmentHandler<Dump> for DumpHandler {
+            fn display(_value: &Dump, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("dump")
+            }
+
+            fn debug(_value: &Dump, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("Dump")
+            }
+
+            fn preferred_formatting_style(
+                _value: &Dump,
+                _report_formatting_function: FormattingFunction,
+            ) -> AttachmentFormattingStyle {
+                AttachmentFormattingStyle {
+                    placement: AttachmentFormattingPlacement::Appendix {
+                        appendix_name: "Memory dump",
+                    },
+                    function: FormattingFunction::Display,
+                    priority: 0,
+                }
+            }
+        }
+
+        let report = report!("leaf")
+            .attach_custom::<DumpHandler, _>(Dump)
+            .context("root")
+            .attach_custom::<DumpHandler, _>(Dump);
+        let headers: Vec<&str> = report.attachment_headers().collect();
+        assert_eq!(headers, ["Memory dump"]);
+    }
+
+    #[test]
+    fn test_iter_post_order_visits_shared_reports_per_parent() {
+        let shared = report!("shared").into_cloneable();
+        let mut report = report!("root").into_dynamic();
+        for name in ["first", "second"] {
+            let mut parent = report!(name).into_dynamic();
+            parent.children_mut().push(shared.clone());
+            report.children_mut().push(parent.into_cloneable());
+        }
+
+        let contexts: Vec<String> = report
+            .iter_post_order()
+            .map(|report| report.format_current_context().to_string())
+            .collect();
+        assert_eq!(contexts, ["shared", "first", "shared", "second", "root"]);
+        assert_eq!(contexts.len(), report.iter_reports().count());
+    }
 }
@@ -1,9 +1,6 @@
 use core::any::{Any, TypeId};
 
-use rootcause_internals::{
-    RawAttachment,
-    handlers::{AttachmentFormattingStyle, FormattingFunction},
-};
+use rootcause_internals::handlers::{AttachmentFormattingStyle, FormattingFunction};
 
 use crate::{
     handlers::{self, AttachmentHandler},
@@ -167,6 +164,51 @@ impl<A: Sized, T> ReportAttachment<A, T> {
         Self::new_custom::<handlers::Display>(attachment)
     }
 
+    /// Allocates a new [`ReportAttachment`] that is formatted using its
+    /// [`Display`](core::fmt::Display) implementation.
+    ///
+    /// This is the same as [`ReportAttachment::new`], and is a shorthand for
+    /// `ReportAttachment::new_custom::<handlers::Display>`. It can be used to
+    /// make the choice of handler explicit at the call site.
+    ///
+    /// # Examples
+    /// ```
+    /// use rootcause::report_attachment::ReportAttachment;
+    ///
+    /// let attachment: ReportAttachment<u16> = ReportAttachment::new_display(404);
+    /// assert_eq!(attachment.format_inner_unhooked().to_string(), "404");
+    /// ```
+    #[must_use]
+    pub fn new_display(attachment: A) -> Self
+    where
+        A: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        Self::new_custom::<handlers::Display>(attachment)
+    }
+
+    /// Allocates a new [`ReportAttachment`] that is formatted using the
+    /// [`handlers::Debug`] handler.
+    ///
+    /// This is a shorthand for
+    /// `ReportAttachment::new_custom::<handlers::Debug>`. The
+    /// [`Debug`](core::fmt::Debug) output of the attachment is only shown when
+    /// the report is debug-formatted; see [`handlers::Debug`] for details.
+    ///
+    /// # Examples
+    /// ```
+    /// use rootcause::report_attachment::ReportAttachment;
+    ///
+    /// let attachment: ReportAttachment<&str> = ReportAttachment::new_debug("api-key");
+    /// assert_eq!(format!("{:?}", attachment.format_inner_unhooked()), r#""api-key""#);
+    /// ```
+    #[must_use]
+    pub fn new_debug(attachment: A) -> Self
+    where
+        A: markers::ObjectMarkerFor<T> + core::fmt::Debug,
+    {
+        Self::new_custom::<handlers::Debug>(attachment)
+    }
+
     /// Allocates a new [`ReportAttachment`] with the given attachment as the
     /// data and the given handler to format it.
     ///
@@ -190,7 +232,7 @@ impl<A: Sized, T> ReportAttachment<A, T> {
         A: markers::ObjectMarkerFor<T>,
         H: AttachmentHandler<A>,
     {
-        let raw = RawAttachment::new::<A, H>(attachment);
+        let raw = attachment.new_raw_attachment::<H>();
 
         // SAFETY:
         // 1. `A` is bounded by `Sized` in this impl, so this is trivially true.
@@ -1,6 +1,9 @@
+use alloc::vec::Vec;
 use core::any::{Any, TypeId};
 
-use rootcause_internals::handlers::{AttachmentFormattingStyle, FormattingFunction};
+use rootcause_internals::handlers::{
+    AttachmentFormattingStyle, FormattingFunction, JsonSink, StackFrame,
+};
 
 use crate::{markers::Dynamic, util::format_helper};
 
@@ -201,6 +204,25 @@ impl<'a, A: ?Sized> ReportAttachmentRef<'a, A> {
         self.as_raw_ref().attachment_handler_type_id()
     }
 
+    /// Returns the type name of the handler used to format the inner
+    /// attachment.
+    ///
+    /// Like [`core::any::type_name`], the returned name is only meant for
+    /// debugging and its exact contents are not guaranteed to be stable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rootcause::{prelude::*, report_attachment::ReportAttachment};
+    /// let attachment = ReportAttachment::new_sendsync(42i32);
+    /// let reference = attachment.as_ref();
+    /// assert!(reference.inner_handler_type_name().ends_with("Display"));
+    /// ```
+    #[must_use]
+    pub fn inner_handler_type_name(self) -> &'static str {
+        self.as_raw_ref().attachment_handler_type_name()
+    }
+
     /// Returns a [`&dyn Any`](Any) view of the inner attachment.
     ///
     /// This is the most general accessor for the inner attachment: it works
@@ -300,6 +322,50 @@ impl<'a, A: ?Sized> ReportAttachmentRef<'a, A> {
         )
     }
 
+    /// Emits the structured representation of the inner attachment data to a
+    /// [`JsonSink`], using the [`AttachmentHandler::json`] method of its
+    /// handler.
+    ///
+    /// Unless the handler overrides [`AttachmentHandler::json`], this emits
+    /// the display output of the attachment as a JSON string.
+    ///
+    /// [`AttachmentHandler::json`]: crate::handlers::AttachmentHandler::json
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rootcause::{handlers::JsonWriter, report_attachment::ReportAttachment};
+    /// let attachment = ReportAttachment::new_sendsync(42i32);
+    /// let mut writer = JsonWriter::new(String::new());
+    /// attachment.as_ref().write_inner_json(&mut writer).unwrap();
+    /// assert_eq!(writer.into_inner(), r#""42""#);
+    /// ```
+    pub fn write_inner_json(self, sink: &mut dyn JsonSink) -> core::fmt::Result {
+        self.as_raw_ref().attachment_json(sink)
+    }
+
+    /// Returns the stack frames of the inner attachment data if it is a
+    /// backtrace, using the [`AttachmentHandler::stack_frames`] method of its
+    /// handler.
+    ///
+    /// The frames are ordered from the innermost frame to the outermost one.
+    /// Unless the handler overrides [`AttachmentHandler::stack_frames`], this
+    /// returns `None`.
+    ///
+    /// [`AttachmentHandler::stack_frames`]: crate::handlers::AttachmentHandler::stack_frames
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rootcause::report_attachment::ReportAttachment;
+    /// let attachment = ReportAttachment::new_sendsync(42i32);
+    /// assert!(attachment.as_ref().stack_frames().is_none());
+    /// ```
+    #[must_use]
+    pub fn stack_frames(self) -> Option<Vec<StackFrame<'a>>> {
+        self.as_raw_ref().attachment_stack_frames()
+    }
+
     /// Changes the inner attachment type of the [`ReportAttachmentRef`] to
     /// [`Dynamic`].
     ///
@@ -432,6 +498,9 @@ impl<'a> ReportAttachmentRef<'a, Dynamic> {
     /// This method is most useful when going from a [`Dynamic`] to a concrete
     /// `A`.
     ///
+    /// If you only need the attachment data, [`downcast`](Self::downcast)
+    /// returns a `&A` directly.
+    ///
     /// # Examples
     /// ```
     /// use rootcause::{
@@ -457,7 +526,7 @@ impl<'a> ReportAttachmentRef<'a, Dynamic> {
     where
         A: Sized + 'static,
     {
-        if TypeId::of::<A>() == self.inner_type_id() {
+        if self.is::<A>() {
             // SAFETY:
             // 1. We just checked that the types match
             let attachment = unsafe { self.downcast_attachment_unchecked() };
@@ -590,6 +659,65 @@ impl<'a> ReportAttachmentRef<'a, Dynamic> {
         // 1. Guaranteed by the caller
         unsafe { raw.attachment_downcast_unchecked() }
     }
+
+    /// Returns `true` if the inner attachment data is of type `A`.
+    ///
+    /// This mirrors `<dyn Any>::is`, and is equivalent to
+    /// comparing [`inner_type_id`](ReportAttachmentRef::inner_type_id) with
+    /// the [`TypeId`] of `A`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rootcause::{markers::Dynamic, report_attachment::ReportAttachment};
+    ///
+    /// let attachment: ReportAttachment<Dynamic> = ReportAttachment::new("text data").into_dynamic();
+    /// assert!(attachment.as_ref().is::<&str>());
+    /// assert!(!attachment.as_ref().is::<i32>());
+    /// ```
+    #[must_use]
+    pub fn is<A>(self) -> bool
+    where
+        A: Sized + 'static,
+    {
+        TypeId::of::<A>() == self.inner_type_id()
+    }
+
+    /// Attempts to downcast the inner attachment data to a reference of type
+    /// `A`.
+    ///
+    /// This mirrors `<dyn Any>::downcast_ref`, and is the
+    /// same as [`downcast_inner`](Self::downcast_inner). Use it when you only
+    /// need the value itself. When you also need the attachment's handler or
+    /// formatting, use [`downcast_attachment`](Self::downcast_attachment)
+    /// instead, which returns a typed [`ReportAttachmentRef`] whose
+    /// [`inner`](ReportAttachmentRef::inner) is the same value.
+    ///
+    /// # Examples
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct RetryMetadata {
+    ///     attempt: u32,
+    /// }
+    ///
+    /// let report = report!("request failed").attach_custom::<handlers::Debug, _>(RetryMetadata { attempt: 3 });
+    ///
+    /// let attempts: Vec<u32> = report
+    ///     .attachments()
+    ///     .iter()
+    ///     .filter_map(|attachment| attachment.downcast::<RetryMetadata>())
+    ///     .map(|metadata| metadata.attempt)
+    ///     .collect();
+    /// assert_eq!(attempts, [3]);
+    /// ```
+    #[must_use]
+    pub fn downcast<A>(self) -> Option<&'a A>
+    where
+        A: Sized + 'static,
+    {
+        self.downcast_inner()
+    }
 }
 
 impl<'a, A: ?Sized> core::fmt::Display for ReportAttachmentRef<'a, A> {
@@ -15,6 +15,9 @@
 //! - Iterating over attachments with [`iter`]
 //! - Converting between thread safety markers with [`into_local`]
 //!
+//! It also provides [`RedactAction`], which is used with [`Report::redact`] to
+//! remove or replace sensitive attachments in an entire report tree.
+//!
 //! # Examples
 //!
 //! ```
@@ -35,11 +38,14 @@
 //! [`pop`]: ReportAttachments::pop
 //! [`iter`]: ReportAttachments::iter
 //! [`into_local`]: ReportAttachments::into_local
+//! [`Report::redact`]: crate::Report::redact
 
 mod iter;
 mod owned;
+mod redact;
 
 pub use self::{
     iter::{ReportAttachmentsIntoIter, ReportAttachmentsIter, ReportAttachmentsIterMut},
     owned::ReportAttachments,
+    redact::RedactAction,
 };
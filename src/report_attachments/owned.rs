@@ -1,6 +1,7 @@
 use alloc::vec::Vec;
 
 use crate::{
+    handlers::FormattingFunction,
     markers::{Dynamic, Local, SendSync},
     report_attachment::{ReportAttachment, ReportAttachmentMut, ReportAttachmentRef},
     report_attachments::{
@@ -284,6 +285,108 @@ impl<T> ReportAttachments<T> {
         self.as_raw().len()
     }
 
+    /// Returns the total number of attachments the collection can hold without
+    /// reallocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report_attachments::ReportAttachments;
+    ///
+    /// let mut attachments = ReportAttachments::new_sendsync();
+    /// attachments.reserve(5);
+    /// assert!(attachments.capacity() >= 5);
+    /// ```
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.as_raw().capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more attachments to be
+    /// inserted in the collection.
+    ///
+    /// The collection may reserve more space to avoid frequent reallocations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report_attachments::ReportAttachments;
+    ///
+    /// let mut attachments = ReportAttachments::new_sendsync();
+    /// attachments.reserve(10);
+    /// assert!(attachments.capacity() >= 10);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        // SAFETY:
+        // 1. Reserving space does not add attachments, so if the collection is empty,
+        //    it stays empty. Otherwise `T` is already either `Local` or `SendSync`.
+        // 2. Reserving space does not add or modify attachments, so if `T = SendSync`,
+        //    all attachments remain `Send + Sync`.
+        let raw = unsafe { self.as_raw_mut() };
+
+        raw.reserve(additional);
+    }
+
+    /// Sorts the attachments by their priority, from highest to lowest.
+    ///
+    /// The priority of an attachment is the
+    /// [`priority`](crate::handlers::AttachmentFormattingStyle::priority) of
+    /// its [`preferred_formatting_style`] for [`Display`] formatting, including
+    /// any installed attachment formatting hooks. This is the same order the
+    /// default report formatter uses when printing the attachments, but
+    /// sorting the stored attachments makes the order deterministic for
+    /// other consumers as well, such as serializers or snapshot tests.
+    ///
+    /// The sort is stable, so attachments with the same priority keep their
+    /// insertion order.
+    ///
+    /// [`preferred_formatting_style`]: ReportAttachmentRef::preferred_formatting_style
+    /// [`Display`]: core::fmt::Display
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{
+    ///     hooks::builtin_hooks::location::{Location, LocationHandler},
+    ///     report_attachment::ReportAttachment,
+    ///     report_attachments::ReportAttachments,
+    /// };
+    ///
+    /// let mut attachments = ReportAttachments::new_sendsync();
+    /// attachments.push(ReportAttachment::new("first").into_dynamic());
+    /// attachments.push(
+    ///     ReportAttachment::new_custom::<LocationHandler>(Location { file: "main.rs", line: 7 })
+    ///         .into_dynamic(),
+    /// );
+    /// attachments.push(ReportAttachment::new("second").into_dynamic());
+    ///
+    /// attachments.sort_by_priority();
+    ///
+    /// assert!(attachments.get(0).unwrap().downcast_inner::<Location>().is_some());
+    /// assert_eq!(attachments.get(1).unwrap().downcast_inner::<&str>(), Some(&"first"));
+    /// assert_eq!(attachments.get(2).unwrap().downcast_inner::<&str>(), Some(&"second"));
+    /// ```
+    pub fn sort_by_priority(&mut self) {
+        // SAFETY:
+        // 1. Reordering the attachments does not change whether the collection is
+        //    empty. If it is non-empty, `T` is already either `Local` or `SendSync`.
+        // 2. Reordering the attachments does not add or modify attachments, so if `T =
+        //    SendSync`, all attachments remain `Send + Sync`.
+        let raw = unsafe { self.as_raw_mut() };
+
+        raw.sort_by_cached_key(|attachment| {
+            // SAFETY:
+            // 1. `A=Dynamic`, so this is trivially true.
+            // 2. `A=Dynamic`, so this is trivially true.
+            let attachment = unsafe {
+                // @add-unsafe-context: Dynamic
+                ReportAttachmentRef::<Dynamic>::from_raw(attachment.as_ref())
+            };
+            let style = attachment.preferred_formatting_style(FormattingFunction::Display);
+            core::cmp::Reverse(style.priority)
+        });
+    }
+
     /// Returns a reference to the attachment at the given index.
     ///
     /// Returns [`None`] if the index is out of bounds.
@@ -615,6 +718,8 @@ impl<'a, T> IntoIterator for &'a mut ReportAttachments<T> {
 
 impl<A: ?Sized, T> Extend<ReportAttachment<A, T>> for ReportAttachments<T> {
     fn extend<I: IntoIterator<Item = ReportAttachment<A, T>>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
         for report in iter {
             self.push(report.into_dynamic());
         }
@@ -651,4 +756,30 @@ mod tests {
         static_assertions::assert_not_impl_any!(ReportAttachments<SendSync>: Copy, Clone);
         static_assertions::assert_not_impl_any!(ReportAttachments<Local>: Copy, Clone);
     }
+
+    #[test]
+    fn test_attachments_from_iterator() {
+        let mut attachments: ReportAttachments<SendSync> =
+            (1..=2).map(ReportAttachment::new).collect();
+        attachments.extend([ReportAttachment::new("three").into_dynamic()]);
+        attachments.extend(core::iter::once(ReportAttachment::new(4u8)));
+
+        assert_eq!(attachments.len(), 4);
+        assert_eq!(
+            attachments.get(0).unwrap().downcast_inner::<i32>(),
+            Some(&1)
+        );
+        assert_eq!(
+            attachments.get(1).unwrap().downcast_inner::<i32>(),
+            Some(&2)
+        );
+        assert_eq!(
+            attachments.get(2).unwrap().downcast_inner::<&str>(),
+            Some(&"three")
+        );
+        assert_eq!(attachments.get(3).unwrap().downcast_inner::<u8>(), Some(&4));
+
+        let erased: ReportAttachments<SendSync> = attachments.into_iter().collect();
+        assert_eq!(erased.len(), 4);
+    }
 }
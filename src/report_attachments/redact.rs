@@ -0,0 +1,39 @@
+use alloc::string::String;
+
+/// The action to take for an attachment when redacting a report using
+/// [`Report::redact`].
+///
+/// [`Report::redact`]: crate::Report::redact
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{prelude::*, report_attachments::RedactAction};
+///
+/// let report = report!("login failed")
+///     .attach("user: alice")
+///     .attach("password: hunter2")
+///     .redact(|attachment| {
+///         let text = attachment.format_inner_unhooked().to_string();
+///         if text.starts_with("password") {
+///             RedactAction::Replace("password: <redacted>".to_string())
+///         } else {
+///             RedactAction::Keep
+///         }
+///     });
+///
+/// let output = report.to_string();
+/// assert!(output.contains("user: alice"));
+/// assert!(output.contains("password: <redacted>"));
+/// assert!(!output.contains("hunter2"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RedactAction {
+    /// Keep the attachment unchanged.
+    Keep,
+    /// Remove the attachment from the report.
+    Remove,
+    /// Replace the attachment with a string attachment containing the given
+    /// text.
+    Replace(String),
+}
@@ -373,6 +373,244 @@ impl<C: ?Sized, T> ReportCollection<C, T> {
         Some(report)
     }
 
+    /// Reverses the order of the reports in the collection, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{report, report_collection::ReportCollection};
+    ///
+    /// let mut collection = ReportCollection::new();
+    /// collection.push(report!("First error").into_cloneable());
+    /// collection.push(report!("Second error").into_cloneable());
+    ///
+    /// collection.reverse();
+    /// let first = collection.get(0).unwrap();
+    /// assert_eq!(first.format_current_context().to_string(), "Second error");
+    /// ```
+    pub fn reverse(&mut self) {
+        // SAFETY:
+        // 1. If the collection is already non-empty, `C` is already valid. Otherwise
+        //    reordering the elements will not modify it to become non-empty.
+        // 2. If the collection is already non-empty, `T` is already valid. Otherwise
+        //    reordering the elements will not modify it to become non-empty.
+        // 3. Reordering the elements does not change the types of their contexts.
+        // 4. Reordering the elements does not invalidate their shared ownership
+        //    properties.
+        // 5. Reordering the elements does not cause them to stop being `Send +
+        //    Sync`.
+        let raw = unsafe { self.as_raw_mut() };
+
+        raw.reverse();
+    }
+
+    /// Sorts the collection by the key computed for each report.
+    ///
+    /// The sort is stable, so reports with equal keys keep their relative
+    /// order. The key is computed once per report, like
+    /// [`slice::sort_by_cached_key`], so it can be expensive to compute, for
+    /// instance by formatting the context of the report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{report, report_collection::ReportCollection};
+    ///
+    /// let mut collection = ReportCollection::new();
+    /// collection.push(report!("timed out").into_cloneable());
+    /// collection.push(report!("disk full").into_cloneable());
+    /// collection.push(report!("timed out").into_cloneable());
+    ///
+    /// collection.sort_by_key(|report| report.format_current_context().to_string());
+    /// let contexts: Vec<String> = collection
+    ///     .iter()
+    ///     .map(|report| report.format_current_context().to_string())
+    ///     .collect();
+    /// assert_eq!(contexts, ["disk full", "timed out", "timed out"]);
+    /// ```
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(ReportRef<'_, C, Cloneable, T>) -> K,
+    {
+        // SAFETY:
+        // 1. If the collection is already non-empty, `C` is already valid. Otherwise
+        //    reordering the elements will not modify it to become non-empty.
+        // 2. If the collection is already non-empty, `T` is already valid. Otherwise
+        //    reordering the elements will not modify it to become non-empty.
+        // 3. Reordering the elements does not change the types of their contexts.
+        // 4. Reordering the elements does not invalidate their shared ownership
+        //    properties.
+        // 5. Reordering the elements does not cause them to stop being `Send +
+        //    Sync`.
+        let raw = unsafe { self.as_raw_mut() };
+
+        raw.sort_by_cached_key(|raw_report| {
+            // SAFETY:
+            // 1. Guaranteed by the invariants of the collection.
+            // 2. `O=Cloneable`, so this is trivially true.
+            // 3. Guaranteed by the invariants of the collection.
+            // 4. If `C` is a `Sized` type: Guaranteed by the invariants of the
+            //    collection.
+            // 5. Guaranteed by the invariants of the collection.
+            // 6. Guaranteed by the invariants of the collection.
+            // 7. If `T = SendSync`: Guaranteed by the invariants of the collection.
+            let report = unsafe { ReportRef::<C, Cloneable, T>::from_raw(raw_report.as_ref()) };
+            f(report)
+        });
+    }
+
+    /// Splits the collection into two at the given index.
+    ///
+    /// Returns a new collection containing the reports in the range
+    /// `[at, len)`, while `self` keeps the reports in the range `[0, at)`. The
+    /// order of the reports is preserved in both collections.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`, like [`Vec::split_off`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{report, report_collection::ReportCollection};
+    ///
+    /// let mut collection = ReportCollection::new();
+    /// collection.push(report!("Error 1").into_cloneable());
+    /// collection.push(report!("Error 2").into_cloneable());
+    /// collection.push(report!("Error 3").into_cloneable());
+    ///
+    /// let tail = collection.split_off(1);
+    /// assert_eq!(collection.len(), 1);
+    /// assert_eq!(tail.len(), 2);
+    /// let first = tail.get(0).unwrap();
+    /// assert_eq!(first.format_current_context().to_string(), "Error 2");
+    /// ```
+    #[must_use]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        // SAFETY:
+        // 1. If the collection is already non-empty, `C` is already valid. Otherwise
+        //    this will not modify it to become non-empty.
+        // 2. If the collection is already non-empty, `T` is already valid. Otherwise
+        //    this will not modify it to become non-empty.
+        // 3. Removing elements does not change the types of contexts in the remaining
+        //    elements.
+        // 4. Removing elements does not invalidate the shared ownership properties of
+        //    the remaining elements.
+        // 5. Removing elements does not cause the remaining elements to stop being
+        //    `Send + Sync`.
+        let raw = unsafe { self.as_raw_mut() };
+
+        let tail = raw.split_off(at);
+
+        // SAFETY: The reports were taken from this collection, which has the same `C`
+        // and `T`:
+        // 1. Guaranteed by the invariants of the collection.
+        // 2. Guaranteed by the invariants of the collection.
+        // 3. Guaranteed by the invariants of the collection.
+        // 4. Guaranteed by the invariants of the collection.
+        // 5. Guaranteed by the invariants of the collection.
+        unsafe { Self::from_raw(tail) }
+    }
+
+    /// Moves all the reports of `other` to the end of this collection, leaving
+    /// `other` empty.
+    ///
+    /// The order of the reports is preserved, like [`Vec::append`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{report, report_collection::ReportCollection};
+    ///
+    /// let mut collection = ReportCollection::new();
+    /// collection.push(report!("Error 1").into_cloneable());
+    ///
+    /// let mut other = ReportCollection::new();
+    /// other.push(report!("Error 2").into_cloneable());
+    ///
+    /// collection.append(&mut other);
+    /// assert_eq!(collection.len(), 2);
+    /// assert!(other.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        // SAFETY:
+        // 1. If `other` is non-empty, `C` is valid by the invariants of `other`.
+        //    Otherwise `self` does not change.
+        // 2. If `other` is non-empty, `T` is valid by the invariants of `other`.
+        //    Otherwise `self` does not change.
+        // 3. If `C` is a `Sized` type: The invariants of `other` guarantee that the
+        //    added contexts are of type `C`.
+        // 4. The invariants of `other` guarantee this for the added elements.
+        // 5. If `T = SendSync`: The invariants of `other` guarantee this for the added
+        //    elements.
+        let raw = unsafe { self.as_raw_mut() };
+
+        // SAFETY:
+        // 1. Removing all elements makes the collection empty.
+        // 2. Removing all elements makes the collection empty.
+        // 3. An empty collection contains no contexts.
+        // 4. An empty collection contains no references.
+        // 5. An empty collection contains no contexts or attachments.
+        let other_raw = unsafe { other.as_raw_mut() };
+
+        raw.append(other_raw);
+    }
+
+    /// Replaces every report in the collection with the output of a closure.
+    ///
+    /// The collection is consumed and rebuilt in a single pass, with `f`
+    /// called once for each report, in order.
+    ///
+    /// # Shared Reports
+    ///
+    /// The reports in a collection are [`Cloneable`], so other collections or
+    /// reports may hold references to the same report. Mapping never modifies
+    /// a report that is visible elsewhere. Instead, `f` works on a
+    /// copy-on-write basis:
+    ///
+    /// - If this collection holds the only reference to a report,
+    ///   [`Report::try_into_mutable`] succeeds without copying anything, and
+    ///   the report can be edited in place, for instance by adding
+    ///   attachments.
+    /// - Otherwise, `f` has to return a new report, for instance by wrapping
+    ///   the shared report with [`Report::context`]. This allocates a new root
+    ///   node, but the shared report itself is not copied, and the other
+    ///   holders keep seeing the original report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::{prelude::*, report_collection::ReportCollection};
+    ///
+    /// let shared = report!("timeout").into_dynamic().into_cloneable();
+    /// let collection: ReportCollection = [
+    ///     report!("connection refused").into_dynamic().into_cloneable(),
+    ///     shared.clone(),
+    /// ]
+    /// .into();
+    ///
+    /// let collection = collection.map(|report| match report.try_into_mutable() {
+    ///     Ok(unique) => unique.attach("attempt 2").into_cloneable(),
+    ///     Err(shared) => shared.context("retried").into_dynamic().into_cloneable(),
+    /// });
+    ///
+    /// let first = collection.get(0).unwrap();
+    /// assert_eq!(first.attachments().len(), 2);
+    /// let second = collection.get(1).unwrap();
+    /// assert_eq!(second.format_current_context().to_string(), "retried");
+    ///
+    /// // The shared report is left untouched
+    /// assert_eq!(shared.attachments().len(), 1);
+    /// ```
+    #[must_use]
+    pub fn map<F>(self, f: F) -> Self
+    where
+        F: FnMut(Report<C, Cloneable, T>) -> Report<C, Cloneable, T>,
+    {
+        self.into_iter().map(f).collect()
+    }
+
     /// Returns the number of reports in the collection.
     ///
     /// # Examples
@@ -559,7 +797,7 @@ impl<C: ?Sized, T> ReportCollection<C, T> {
     #[must_use]
     pub fn format_with<H>(&self, hook: &H) -> impl core::fmt::Display + core::fmt::Debug
     where
-        H: crate::hooks::report_formatter::ReportFormatter,
+        H: crate::hooks::report_formatter::ReportFormatter + ?Sized,
     {
         let raw = self.as_raw();
 
@@ -799,6 +1037,102 @@ impl<C: ?Sized, T> ReportCollection<C, T> {
     {
         Report::from_parts::<H>(context, self.into_dynamic(), ReportAttachments::new())
     }
+
+    /// Creates a new [`Report`] with a context generated by the provided
+    /// closure and sets the current report collection as the children of the
+    /// new report.
+    ///
+    /// This is the lazy version of [`ReportCollection::context`]. The new
+    /// context will use the [`handlers::Display`] handler to format the
+    /// context.
+    ///
+    /// See also [`ReportCollection::into_report_if_nonempty`], which only
+    /// calls the closure if the collection contains any reports.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{Report, report_collection::ReportCollection, report};
+    /// let report_collection: ReportCollection = [report!("error A"), report!("error B")]
+    ///     .into_iter()
+    ///     .collect();
+    /// let failed_count = report_collection.len();
+    /// let report: Report<String> =
+    ///     report_collection.context_with(|| format!("{failed_count} tasks failed"));
+    /// assert_eq!(report.children().len(), 2);
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn context_with<D, F>(self, context: F) -> Report<D, Mutable, T>
+    where
+        F: FnOnce() -> D,
+        D: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        self.context_custom::<handlers::Display, _>(context())
+    }
+
+    /// Creates a new [`Report`] with a context generated by the provided
+    /// closure and sets the current report collection as the children of the
+    /// new report.
+    ///
+    /// This is the lazy version of [`ReportCollection::context_custom`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{Report, report_collection::ReportCollection, report, handlers};
+    /// let report_collection: ReportCollection = [report!("error A"), report!("error B")]
+    ///     .into_iter()
+    ///     .collect();
+    /// let report: Report<String> = report_collection
+    ///     .context_custom_with::<handlers::Debug, _, _>(|| String::from("context"));
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn context_custom_with<H, D, F>(self, context: F) -> Report<D, Mutable, T>
+    where
+        F: FnOnce() -> D,
+        D: markers::ObjectMarkerFor<T>,
+        H: ContextHandler<D>,
+    {
+        self.context_custom::<H, _>(context())
+    }
+
+    /// Creates a new [`Report`] with the current report collection as its
+    /// children, or returns `None` if the collection is empty.
+    ///
+    /// The context is only generated by the closure if the collection
+    /// contains any reports, so callers can skip both building the context
+    /// and wrapping the collection when nothing failed. The new context will
+    /// use the [`handlers::Display`] handler to format the context.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rootcause::{Report, report_collection::ReportCollection, report};
+    /// let empty: ReportCollection = ReportCollection::new();
+    /// let report: Option<Report<String>> =
+    ///     empty.into_report_if_nonempty(|| unreachable!("not called for empty collections"));
+    /// assert!(report.is_none());
+    ///
+    /// let report_collection: ReportCollection = [report!("error A"), report!("error B")]
+    ///     .into_iter()
+    ///     .collect();
+    /// let report = report_collection
+    ///     .into_report_if_nonempty(|| String::from("2 tasks failed"))
+    ///     .unwrap();
+    /// assert_eq!(report.children().len(), 2);
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn into_report_if_nonempty<D, F>(self, context: F) -> Option<Report<D, Mutable, T>>
+    where
+        F: FnOnce() -> D,
+        D: markers::ObjectMarkerFor<T> + core::fmt::Display + core::fmt::Debug,
+    {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.context_with(context))
+        }
+    }
 }
 
 impl<C: ?Sized> ReportCollection<C, SendSync> {
@@ -1131,4 +1465,58 @@ mod tests {
         static_assertions::assert_not_impl_any!(ReportCollection<NonSend, Local>: Copy);
         static_assertions::assert_not_impl_any!(ReportCollection<Dynamic, Local>: Copy);
     }
+
+    fn contexts(collection: &ReportCollection<&'static str>) -> Vec<&'static str> {
+        collection
+            .iter()
+            .map(|report| *report.current_context())
+            .collect()
+    }
+
+    fn collection(contexts: &[&'static str]) -> ReportCollection<&'static str> {
+        contexts
+            .iter()
+            .map(|context| Report::new_sendsync_custom::<handlers::Display>(*context))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_off_and_append_preserve_order() {
+        let mut head = collection(&["a", "b", "c", "d"]);
+
+        let mut tail = head.split_off(1);
+        assert_eq!(contexts(&head), ["a"]);
+        assert_eq!(contexts(&tail), ["b", "c", "d"]);
+
+        let end = tail.split_off(tail.len());
+        assert!(end.is_empty());
+
+        head.append(&mut collection(&["e"]));
+        head.append(&mut tail);
+        assert_eq!(contexts(&head), ["a", "e", "b", "c", "d"]);
+        assert!(tail.is_empty());
+
+        let all = head.split_off(0);
+        assert!(head.is_empty());
+        assert_eq!(contexts(&all), ["a", "e", "b", "c", "d"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_off_out_of_bounds() {
+        let mut collection = collection(&["a"]);
+        let _ = collection.split_off(2);
+    }
+
+    #[test]
+    fn test_sort_by_key_is_stable() {
+        let mut collection = collection(&["b1", "a1", "b2", "a2", "c1"]);
+        let mut calls = 0;
+        collection.sort_by_key(|report| {
+            calls += 1;
+            report.current_context().as_bytes()[0]
+        });
+        assert_eq!(contexts(&collection), ["a1", "a2", "b1", "b2", "c1"]);
+        assert_eq!(calls, 5);
+    }
 }
@@ -0,0 +1,261 @@
+//! Helpers for asserting the structure of reports in tests.
+//!
+//! Contexts and attachments are type-erased, so reports cannot implement
+//! [`PartialEq`]. Instead, a [`ReportShape`] captures the parts of a report
+//! that tests usually care about: the formatted context, the formatted
+//! attachments, and the shapes of the child reports. Shapes can be compared
+//! with each other, and the [`assert_report_matches!`] macro compares a
+//! report with an expected shape and prints both trees when they differ.
+//!
+//! [`Location`] attachments are left out of the shape, since they depend on
+//! the line that created the report.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{assert_report_matches, prelude::*, report_testing::ReportShape};
+//!
+//! let report = report!("connection refused")
+//!     .attach("port: 5432")
+//!     .context("failed to load user");
+//!
+//! assert_report_matches!(
+//!     report,
+//!     ReportShape::new("failed to load user")
+//!         .child(ReportShape::new("connection refused").attachment("port: 5432"))
+//! );
+//! ```
+//!
+//! [`Location`]: crate::hooks::builtin_hooks::location::Location
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{Report, ReportRef, hooks::builtin_hooks::location::Location, markers::Uncloneable};
+
+/// The structure of a report, used to compare reports in tests.
+///
+/// See the [module-level documentation](self) for more information.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{prelude::*, report_testing::ReportShape};
+///
+/// let report = report!("disk full").context("failed to save");
+/// let shape = ReportShape::from(&report);
+///
+/// assert_eq!(shape.context(), "failed to save");
+/// assert_eq!(shape.children()[0].context(), "disk full");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReportShape {
+    context: String,
+    attachments: Vec<String>,
+    children: Vec<ReportShape>,
+}
+
+impl ReportShape {
+    /// Creates a shape with the given context and no attachments or
+    /// children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report_testing::ReportShape;
+    ///
+    /// let shape = ReportShape::new("disk full");
+    /// assert_eq!(shape.context(), "disk full");
+    /// assert!(shape.attachments().is_empty());
+    /// ```
+    pub fn new(context: impl Into<String>) -> Self {
+        Self {
+            context: context.into(),
+            attachments: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds an attachment with the given formatted value to the shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report_testing::ReportShape;
+    ///
+    /// let shape = ReportShape::new("disk full").attachment("path: /tmp");
+    /// assert_eq!(shape.attachments(), ["path: /tmp"]);
+    /// ```
+    #[must_use]
+    pub fn attachment(mut self, attachment: impl Into<String>) -> Self {
+        self.attachments.push(attachment.into());
+        self
+    }
+
+    /// Adds a child shape to the shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report_testing::ReportShape;
+    ///
+    /// let shape = ReportShape::new("failed to save").child(ReportShape::new("disk full"));
+    /// assert_eq!(shape.children().len(), 1);
+    /// ```
+    #[must_use]
+    pub fn child(mut self, child: ReportShape) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Returns the formatted context of the shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report_testing::ReportShape;
+    ///
+    /// assert_eq!(ReportShape::new("disk full").context(), "disk full");
+    /// ```
+    #[must_use]
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+
+    /// Returns the formatted attachments of the shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report_testing::ReportShape;
+    ///
+    /// let shape = ReportShape::new("disk full").attachment("path: /tmp");
+    /// assert_eq!(shape.attachments(), ["path: /tmp"]);
+    /// ```
+    #[must_use]
+    pub fn attachments(&self) -> &[String] {
+        &self.attachments
+    }
+
+    /// Returns the shapes of the children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report_testing::ReportShape;
+    ///
+    /// let shape = ReportShape::new("failed to save").child(ReportShape::new("disk full"));
+    /// assert_eq!(shape.children()[0].context(), "disk full");
+    /// ```
+    #[must_use]
+    pub fn children(&self) -> &[ReportShape] {
+        &self.children
+    }
+
+    /// Describes the first difference between this shape and another one, or
+    /// returns `None` if they are equal.
+    ///
+    /// The description names the path to the differing report, where `root`
+    /// is the report itself and `root.children[1]` is its second child.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::report_testing::ReportShape;
+    ///
+    /// let expected = ReportShape::new("failed to save").child(ReportShape::new("disk full"));
+    /// let actual = ReportShape::new("failed to save").child(ReportShape::new("out of memory"));
+    ///
+    /// assert_eq!(
+    ///     expected.difference(&actual).unwrap(),
+    ///     r#"at root.children[0]: expected context "disk full", found "out of memory""#
+    /// );
+    /// assert_eq!(expected.difference(&expected), None);
+    /// ```
+    #[must_use]
+    pub fn difference(&self, actual: &ReportShape) -> Option<String> {
+        self.difference_at(actual, &mut String::from("root"))
+    }
+
+    fn difference_at(&self, actual: &ReportShape, path: &mut String) -> Option<String> {
+        if self.context != actual.context {
+            return Some(format!(
+                "at {path}: expected context {:?}, found {:?}",
+                self.context, actual.context
+            ));
+        }
+        if self.attachments != actual.attachments {
+            return Some(format!(
+                "at {path}: expected attachments {:?}, found {:?}",
+                self.attachments, actual.attachments
+            ));
+        }
+        if self.children.len() != actual.children.len() {
+            return Some(format!(
+                "at {path}: expected {} children, found {}",
+                self.children.len(),
+                actual.children.len()
+            ));
+        }
+        for (index, (expected, actual)) in self.children.iter().zip(&actual.children).enumerate() {
+            let len_before = path.len();
+            path.push_str(&format!(".children[{index}]"));
+            if let Some(difference) = expected.difference_at(actual, path) {
+                return Some(difference);
+            }
+            path.truncate(len_before);
+        }
+        None
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        writeln!(f, "{:indent$}- {}", "", self.context)?;
+        for attachment in &self.attachments {
+            writeln!(f, "{:indent$}  | {attachment}", "")?;
+        }
+        for child in &self.children {
+            child.fmt_indented(f, indent + 2)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the shape as an indented tree, with one line per context and
+/// attachment.
+impl fmt::Display for ReportShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl<'a, C: ?Sized, O, T> From<ReportRef<'a, C, O, T>> for ReportShape {
+    fn from(report: ReportRef<'a, C, O, T>) -> Self {
+        Self {
+            context: report.format_current_context_unhooked().to_string(),
+            attachments: report
+                .attachments()
+                .iter()
+                .filter(|attachment| attachment.downcast_inner::<Location>().is_none())
+                .map(|attachment| attachment.format_inner_unhooked().to_string())
+                .collect(),
+            children: report.children().iter().map(ReportShape::from).collect(),
+        }
+    }
+}
+
+impl<'a, C: ?Sized, O, T> From<&ReportRef<'a, C, O, T>> for ReportShape {
+    fn from(report: &ReportRef<'a, C, O, T>) -> Self {
+        Self::from(*report)
+    }
+}
+
+impl<C: ?Sized, O, T> From<&Report<C, O, T>> for ReportShape {
+    fn from(report: &Report<C, O, T>) -> Self {
+        let report: ReportRef<'_, C, Uncloneable, T> = report.as_uncloneable_ref();
+        Self::from(report)
+    }
+}
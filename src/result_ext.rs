@@ -1,7 +1,10 @@
 use rootcause_internals::handlers;
 
+use alloc::string::String;
+
 use crate::{
     IntoReport, Report, ReportConversion,
+    help::{Note, NoteHandler, Suggestion, SuggestionHandler},
     into_report::IntoReportCollection,
     markers::{Local, Mutable, SendSync},
 };
@@ -19,7 +22,8 @@ use crate::{
 ///   converts the error into a [`Report`]
 /// - **Adding context**: [`context`](ResultExt::context),
 ///   [`context_with`](ResultExt::context_with), and variants add a new context
-///   layer to the error
+///   layer to the error, and [`context_attach`](ResultExt::context_attach)
+///   also adds an attachment to the new layer
 /// - **Transforming contexts**: [`context_to`](ResultExt::context_to)
 ///   converts the context via the [`ReportConversion`] trait, and
 ///   [`context_transform`](ResultExt::context_transform) transforms it with
@@ -27,6 +31,9 @@ use crate::{
 /// - **Adding attachments**: [`attach`](ResultExt::attach),
 ///   [`attach_with`](ResultExt::attach_with), and variants add supplementary
 ///   data to the error
+/// - **Adding help text**: [`suggestion`](ResultExt::suggestion) and
+///   [`note`](ResultExt::note) add help text that is rendered in a footer
+///   section after the report
 ///
 /// Each method has a `local_*` variant for working with types that are not
 /// `Send + Sync`.
@@ -309,6 +316,43 @@ pub trait ResultExt<V, E> {
         F: FnOnce(E::Context) -> C,
         C: Send + Sync + core::fmt::Display + core::fmt::Debug;
 
+    /// Converts the error into a new [`Report`] using the provided context,
+    /// and adds the provided attachment to the new [`Report`].
+    ///
+    /// This is a shorthand for calling [`context`](ResultExt::context)
+    /// followed by [`Report::attach`] on the error. The current error is set
+    /// as a child of the new [`Report`], and the attachment is added next to
+    /// the new context rather than to the original error.
+    ///
+    /// See also [`local_context_attach`](ResultExt::local_context_attach) for
+    /// a non-thread-safe version that works with types that are not
+    /// `Send + Sync`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use rootcause::prelude::*;
+    ///
+    /// let report: Report<&str> = std::fs::read("/nonexistent/user_data.bz2")
+    ///     .context_attach("Failed to fetch user data", "path: /nonexistent/user_data.bz2")
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(report.children().len(), 1);
+    /// assert!(report.to_string().contains("path: /nonexistent/user_data.bz2"));
+    /// ```
+    #[track_caller]
+    fn context_attach<C, A>(
+        self,
+        context: C,
+        attachment: A,
+    ) -> Result<V, Report<C, Mutable, SendSync>>
+    where
+        E: IntoReportCollection<SendSync>,
+        C: Send + Sync + core::fmt::Display + core::fmt::Debug,
+        A: 'static + Send + Sync + core::fmt::Display + core::fmt::Debug;
+
     /// Converts the error into a [`Report`] and adds the provided attachment to
     /// the [`Report`].
     ///
@@ -330,6 +374,19 @@ pub trait ResultExt<V, E> {
     /// let result: Result<Vec<u8>, Report<io::Error>> =
     ///     std::fs::read("user_data.bz2").attach("while reading user_data.bz2");
     /// ```
+    ///
+    /// If the error is already a [`Report`], the attachment is added to the
+    /// existing report rather than wrapping it in a new one:
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let result: Result<(), Report> = Err(report!("request failed"));
+    /// let report = result.attach("request_id=42").unwrap_err();
+    ///
+    /// assert!(report.children().is_empty());
+    /// assert_eq!(report.attachments().len(), 2); // The location and the attachment
+    /// ```
     #[track_caller]
     fn attach<A>(self, attachment: A) -> Result<V, Report<E::Context, Mutable, SendSync>>
     where
@@ -455,6 +512,56 @@ pub trait ResultExt<V, E> {
         A: 'static + Send + Sync,
         H: handlers::AttachmentHandler<A>;
 
+    /// Converts the error into a [`Report`] and adds a [`Suggestion`] for how
+    /// to fix the error.
+    ///
+    /// Suggestions are rendered in a footer section after the report by the
+    /// default formatter, prefixed with `Suggestion:`.
+    ///
+    /// [`Suggestion`]: crate::help::Suggestion
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let result: Result<String, Report<std::io::Error>> =
+    ///     std::fs::read_to_string("/nonexistent/config.toml")
+    ///         .suggestion("Run `app init` to create a default config file");
+    /// let output = result.unwrap_err().to_string();
+    /// assert!(output.contains("Suggestion: Run `app init` to create a default config file"));
+    /// ```
+    #[track_caller]
+    fn suggestion<S>(self, suggestion: S) -> Result<V, Report<E::Context, Mutable, SendSync>>
+    where
+        E: IntoReport<SendSync, Ownership = Mutable>,
+        S: Into<String>;
+
+    /// Converts the error into a [`Report`] and adds a [`Note`] with additional
+    /// information about the error.
+    ///
+    /// Notes are rendered in a footer section after the report by the default
+    /// formatter, prefixed with `Note:`.
+    ///
+    /// [`Note`]: crate::help::Note
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::prelude::*;
+    ///
+    /// let result: Result<String, Report<std::io::Error>> =
+    ///     std::fs::read_to_string("/nonexistent/config.toml")
+    ///         .note("The config file is created on first start");
+    /// let output = result.unwrap_err().to_string();
+    /// assert!(output.contains("Note: The config file is created on first start"));
+    /// ```
+    #[track_caller]
+    fn note<S>(self, note: S) -> Result<V, Report<E::Context, Mutable, SendSync>>
+    where
+        E: IntoReport<SendSync, Ownership = Mutable>,
+        S: Into<String>;
+
     /// Converts the error into a local (non-thread-safe) [`Report`].
     ///
     /// If the result is `Ok`, returns the value unchanged. If the result is
@@ -751,6 +858,37 @@ pub trait ResultExt<V, E> {
         F: FnOnce(E::Context) -> C,
         C: core::fmt::Display + core::fmt::Debug;
 
+    /// Converts the error into a new local (non-thread-safe) [`Report`] using
+    /// the provided context, and adds the provided attachment to the new
+    /// [`Report`].
+    ///
+    /// This is the non-thread-safe version of
+    /// [`context_attach`](ResultExt::context_attach).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    ///
+    /// use rootcause::{markers, prelude::*};
+    ///
+    /// let report: Report<Rc<&str>, _, markers::Local> = std::fs::read("/nonexistent/user_data.bz2")
+    ///     .local_context_attach(Rc::new("Failed to fetch user data"), Rc::new("retrying later"))
+    ///     .unwrap_err();
+    ///
+    /// assert!(report.to_string().contains("retrying later"));
+    /// ```
+    #[track_caller]
+    fn local_context_attach<C, A>(
+        self,
+        context: C,
+        attachment: A,
+    ) -> Result<V, Report<C, Mutable, Local>>
+    where
+        E: IntoReportCollection<Local>,
+        C: core::fmt::Display + core::fmt::Debug,
+        A: 'static + core::fmt::Display + core::fmt::Debug;
+
     /// Converts the error into a local (non-thread-safe) [`Report`] and adds
     /// the provided attachment to the [`Report`].
     ///
@@ -910,6 +1048,61 @@ pub trait ResultExt<V, E> {
         E: IntoReport<Local, Ownership = Mutable>,
         F: FnOnce() -> A,
         H: handlers::AttachmentHandler<A>;
+
+    /// Converts the error into a local (non-thread-safe) [`Report`] and adds a
+    /// [`Suggestion`] for how to fix the error.
+    ///
+    /// See also [`suggestion`](ResultExt::suggestion) for a thread-safe
+    /// version that returns a [`Report`] that can be sent across thread
+    /// boundaries.
+    ///
+    /// [`Suggestion`]: crate::help::Suggestion
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    ///
+    /// use rootcause::prelude::*;
+    ///
+    /// let result: Result<(), Report<Rc<&str>, _, markers::Local>> =
+    ///     Err(report!(Rc::new("config not found")))
+    ///         .local_suggestion("Run `app init` to create a default config file");
+    /// let output = result.unwrap_err().to_string();
+    /// assert!(output.contains("Suggestion: Run `app init` to create a default config file"));
+    /// ```
+    #[track_caller]
+    fn local_suggestion<S>(self, suggestion: S) -> Result<V, Report<E::Context, Mutable, Local>>
+    where
+        E: IntoReport<Local, Ownership = Mutable>,
+        S: Into<String>;
+
+    /// Converts the error into a local (non-thread-safe) [`Report`] and adds a
+    /// [`Note`] with additional information about the error.
+    ///
+    /// See also [`note`](ResultExt::note) for a thread-safe version that
+    /// returns a [`Report`] that can be sent across thread boundaries.
+    ///
+    /// [`Note`]: crate::help::Note
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    ///
+    /// use rootcause::prelude::*;
+    ///
+    /// let result: Result<(), Report<Rc<&str>, _, markers::Local>> =
+    ///     Err(report!(Rc::new("config not found")))
+    ///         .local_note("The config file is created on first start");
+    /// let output = result.unwrap_err().to_string();
+    /// assert!(output.contains("Note: The config file is created on first start"));
+    /// ```
+    #[track_caller]
+    fn local_note<S>(self, note: S) -> Result<V, Report<E::Context, Mutable, Local>>
+    where
+        E: IntoReport<Local, Ownership = Mutable>,
+        S: Into<String>;
 }
 
 impl<V, E> ResultExt<V, E> for Result<V, E> {
@@ -1002,6 +1195,26 @@ impl<V, E> ResultExt<V, E> for Result<V, E> {
         }
     }
 
+    #[inline]
+    fn context_attach<C, A>(
+        self,
+        context: C,
+        attachment: A,
+    ) -> Result<V, Report<C, Mutable, SendSync>>
+    where
+        E: IntoReportCollection<SendSync>,
+        C: Send + Sync + core::fmt::Display + core::fmt::Debug,
+        A: 'static + Send + Sync + core::fmt::Display + core::fmt::Debug,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e
+                .into_report_collection()
+                .context(context)
+                .attach(attachment)),
+        }
+    }
+
     #[inline]
     fn attach<A>(self, attachment: A) -> Result<V, Report<E::Context, Mutable, SendSync>>
     where
@@ -1057,6 +1270,34 @@ impl<V, E> ResultExt<V, E> for Result<V, E> {
         }
     }
 
+    #[inline]
+    fn suggestion<S>(self, suggestion: S) -> Result<V, Report<E::Context, Mutable, SendSync>>
+    where
+        E: IntoReport<SendSync, Ownership = Mutable>,
+        S: Into<String>,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e
+                .into_report()
+                .attach_custom::<SuggestionHandler, _>(Suggestion::new(suggestion))),
+        }
+    }
+
+    #[inline]
+    fn note<S>(self, note: S) -> Result<V, Report<E::Context, Mutable, SendSync>>
+    where
+        E: IntoReport<SendSync, Ownership = Mutable>,
+        S: Into<String>,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e
+                .into_report()
+                .attach_custom::<NoteHandler, _>(Note::new(note))),
+        }
+    }
+
     #[inline]
     fn local_into_report(self) -> Result<V, Report<E::Context, E::Ownership, Local>>
     where
@@ -1144,6 +1385,26 @@ impl<V, E> ResultExt<V, E> for Result<V, E> {
         }
     }
 
+    #[inline]
+    fn local_context_attach<C, A>(
+        self,
+        context: C,
+        attachment: A,
+    ) -> Result<V, Report<C, Mutable, Local>>
+    where
+        E: IntoReportCollection<Local>,
+        C: core::fmt::Display + core::fmt::Debug,
+        A: 'static + core::fmt::Display + core::fmt::Debug,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e
+                .into_report_collection()
+                .context(context)
+                .attach(attachment)),
+        }
+    }
+
     #[inline]
     fn local_attach<A>(self, attachment: A) -> Result<V, Report<<E>::Context, Mutable, Local>>
     where
@@ -1204,4 +1465,32 @@ impl<V, E> ResultExt<V, E> for Result<V, E> {
             Err(e) => Err(e.into_report().attach_custom::<H, _>(attachment())),
         }
     }
+
+    #[inline]
+    fn local_suggestion<S>(self, suggestion: S) -> Result<V, Report<E::Context, Mutable, Local>>
+    where
+        E: IntoReport<Local, Ownership = Mutable>,
+        S: Into<String>,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e
+                .into_report()
+                .attach_custom::<SuggestionHandler, _>(Suggestion::new(suggestion))),
+        }
+    }
+
+    #[inline]
+    fn local_note<S>(self, note: S) -> Result<V, Report<E::Context, Mutable, Local>>
+    where
+        E: IntoReport<Local, Ownership = Mutable>,
+        S: Into<String>,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e
+                .into_report()
+                .attach_custom::<NoteHandler, _>(Note::new(note))),
+        }
+    }
 }
@@ -0,0 +1,164 @@
+//! Severity attachments for classifying errors.
+//!
+//! A [`Severity`] can be attached to any node of a report tree, usually using
+//! [`Report::attach_severity`]. The highest severity in the whole tree can then
+//! be found with [`Report::max_severity`], for instance to decide whether an
+//! error should trigger an alert.
+//!
+//! The default formatter renders the severity as a badge in front of the
+//! context of the report it is attached to.
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{prelude::*, severity::Severity};
+//!
+//! let disk = report!("disk almost full").attach_severity(Severity::Warning);
+//! let database = report!("database unreachable").attach_severity(Severity::Critical);
+//! let report = disk.join(database, "health check failed");
+//!
+//! assert_eq!(report.max_severity(), Some(Severity::Critical));
+//! assert!(report.to_string().contains("[critical] database unreachable"));
+//! ```
+//!
+//! [`Report::attach_severity`]: crate::Report::attach_severity
+//! [`Report::max_severity`]: crate::Report::max_severity
+
+use core::fmt;
+
+use crate::handlers::{
+    AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler,
+    FormattingFunction, JsonSink,
+};
+
+/// The severity of an error.
+///
+/// Severities are ordered from the least to the most severe, so the highest
+/// severity of several errors is their maximum.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::severity::Severity;
+///
+/// assert!(Severity::Warning < Severity::Error);
+/// assert!(Severity::Error < Severity::Critical);
+/// assert_eq!(Severity::Critical.to_string(), "critical");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A problem that does not prevent the operation from completing
+    Warning,
+    /// A failure of the operation
+    Error,
+    /// A failure that needs immediate attention
+    Critical,
+}
+
+impl Severity {
+    /// Returns the lowercase name of the severity, such as `"warning"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::severity::Severity;
+    ///
+    /// assert_eq!(Severity::Warning.as_str(), "warning");
+    /// ```
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Handler for formatting [`Severity`] attachments.
+///
+/// The severity is displayed inline as `Severity: critical`. The default
+/// formatter instead renders it as a badge in front of the context of the
+/// report, see
+/// [`AttachmentBadge::severity`](crate::hooks::builtin_hooks::report_formatter::AttachmentBadge::severity).
+/// When emitted as JSON, it is written as a string such as `"critical"`.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     prelude::*,
+///     severity::{Severity, SeverityHandler},
+/// };
+///
+/// let report = report!("error").attach_custom::<SeverityHandler, _>(Severity::Error);
+/// ```
+#[derive(Copy, Clone)]
+pub struct SeverityHandler;
+
+impl AttachmentHandler<Severity> for SeverityHandler {
+    fn display(value: &Severity, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Severity: {value}")
+    }
+
+    fn debug(value: &Severity, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, f)
+    }
+
+    fn json(value: &Severity, sink: &mut dyn JsonSink) -> fmt::Result {
+        sink.str(value.as_str())
+    }
+
+    fn preferred_formatting_style(
+        _value: &Severity,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::Inline,
+            function: FormattingFunction::Display,
+            priority: 100,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use super::*;
+    use crate::{
+        handlers::JsonWriter, hooks::builtin_hooks::report_formatter::DefaultReportFormatter,
+    };
+
+    #[test]
+    fn test_severity_json() {
+        let mut writer = JsonWriter::new(String::new());
+        SeverityHandler::json(&Severity::Critical, &mut writer).unwrap();
+        assert_eq!(writer.into_inner(), r#""critical""#);
+    }
+
+    #[test]
+    fn test_severity_badge() {
+        let report = crate::report!("disk almost full")
+            .attach_severity(Severity::Warning)
+            .attach_severity(Severity::Error);
+        let formatter = DefaultReportFormatter::ASCII;
+        let output = report.format_with(&formatter).to_string();
+        assert!(output.contains("[error] disk almost full"));
+        assert!(!output.contains("Severity:"));
+
+        let formatter = DefaultReportFormatter {
+            attachment_badges: &[],
+            ..DefaultReportFormatter::ASCII
+        };
+        let output = report.format_with(&formatter).to_string();
+        assert!(!output.contains("[error]"));
+        assert!(output.contains("Severity: warning"));
+        assert!(output.contains("Severity: error"));
+    }
+}
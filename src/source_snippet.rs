@@ -0,0 +1,344 @@
+//! Source snippet attachments for compiler-like errors.
+//!
+//! Interpreters, parsers and configuration loaders often want to point at the
+//! part of the input that caused an error. A [`SourceSnippet`] stores the
+//! lines of source code around a span, together with the file name and the
+//! position of the span, and is displayed with the span underlined, in the
+//! same style as `rustc`:
+//!
+//! ```text
+//!  --> main.dsl:2:9
+//!   |
+//! 2 | let x = foo(1, 2;
+//!   |         ^^^^^^^^
+//! ```
+//!
+//! Snippets are usually added using [`Report::attach_snippet`], which finds
+//! the line and column of a byte span in a source file.
+//!
+//! [`Report::attach_snippet`]: crate::Report::attach_snippet
+//!
+//! # Examples
+//!
+//! ```
+//! use rootcause::{prelude::*, source_snippet::SourceSnippet};
+//!
+//! let source = "let a = 1;\nlet x = foo(1, 2;\n";
+//! let report = report!("expected `)`").attach_snippet("main.dsl", source, 19..27);
+//!
+//! let output = report.to_string();
+//! assert!(output.contains("--> main.dsl:2:9"));
+//! assert!(output.contains("2 | let x = foo(1, 2;"));
+//! assert!(output.contains("  |         ^^^^^^^^"));
+//!
+//! let snippet = report
+//!     .attachments()
+//!     .iter()
+//!     .find_map(|attachment| attachment.downcast_inner::<SourceSnippet>())
+//!     .unwrap();
+//! assert_eq!((snippet.line, snippet.column), (2, 9));
+//! ```
+
+use alloc::string::String;
+use core::{fmt, ops::Range};
+
+use crate::handlers::{
+    AttachmentFormattingPlacement, AttachmentFormattingStyle, AttachmentHandler,
+    FormattingFunction, JsonSink,
+};
+
+/// An attachment pointing at a span of source code.
+///
+/// The [`text`](Self::text) holds the source lines containing the span,
+/// starting at the beginning of line [`line`](Self::line) of the file. The
+/// [`span`](Self::span) is a byte range into the text, and may cover several
+/// lines.
+///
+/// When displayed, the snippet is rendered with a `--> file:line:column`
+/// header, followed by the lines of the text with their line numbers, each
+/// with the part of the span on that line underlined with `^`.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::source_snippet::SourceSnippet;
+///
+/// let snippet = SourceSnippet {
+///     filename: "main.dsl".into(),
+///     line: 7,
+///     column: 5,
+///     span: 4..7,
+///     text: "let foo = 1;".into(),
+/// };
+/// assert_eq!(
+///     snippet.to_string(),
+///     " --> main.dsl:7:5\n  |\n7 | let foo = 1;\n  |     ^^^"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceSnippet {
+    /// The name of the source file.
+    pub filename: String,
+    /// The 1-based line number of the first line of the
+    /// [`text`](Self::text).
+    pub line: usize,
+    /// The 1-based column of the start of the [`span`](Self::span), counted
+    /// in characters.
+    pub column: usize,
+    /// The byte range of the [`text`](Self::text) to underline.
+    pub span: Range<usize>,
+    /// The source lines containing the [`span`](Self::span).
+    pub text: String,
+}
+
+impl SourceSnippet {
+    /// Creates a snippet for a byte span of a complete source file.
+    ///
+    /// The line and column of the span are computed from the source, and only
+    /// the lines containing the span are kept in the snippet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span is out of bounds of the source, if its start is
+    /// after its end, or if it does not lie on character boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rootcause::source_snippet::SourceSnippet;
+    ///
+    /// let source = "fn main() {\n    undefined();\n}\n";
+    /// let snippet = SourceSnippet::new("main.rs", source, 16..25);
+    /// assert_eq!(snippet.line, 2);
+    /// assert_eq!(snippet.column, 5);
+    /// assert_eq!(snippet.text, "    undefined();");
+    /// assert_eq!(&snippet.text[snippet.span.clone()], "undefined");
+    /// ```
+    #[track_caller]
+    pub fn new(filename: impl Into<String>, source: &str, span: Range<usize>) -> Self {
+        let spanned = &source[span.clone()];
+
+        let line_start = source[..span.start]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let last_position = if spanned.ends_with('\n') {
+            span.end - 1
+        } else {
+            span.end
+        };
+        let line_end = source[last_position..]
+            .find('\n')
+            .map_or(source.len(), |index| last_position + index);
+
+        Self {
+            filename: filename.into(),
+            line: source[..line_start].matches('\n').count() + 1,
+            column: source[line_start..span.start].chars().count() + 1,
+            span: span.start - line_start..span.end - line_start,
+            text: String::from(&source[line_start..line_end]),
+        }
+    }
+}
+
+/// Renders the snippet with its span underlined, in the same style as
+/// `rustc`.
+impl fmt::Display for SourceSnippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_count = self.text.split('\n').count();
+        let width = digit_count(self.line + line_count - 1);
+
+        write!(
+            f,
+            "{:width$}--> {}:{}:{}\n{:width$} |",
+            "", self.filename, self.line, self.column, ""
+        )?;
+
+        let mut line_start = 0;
+        for (index, line) in self.text.split('\n').enumerate() {
+            let line_end = line_start + line.len();
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            write!(f, "\n{:>width$} |", self.line + index)?;
+            if !line.is_empty() {
+                write!(f, " {line}")?;
+            }
+
+            let is_first_line = (line_start..=line_end).contains(&self.span.start);
+            let is_covered =
+                is_first_line || (self.span.start < line_start && self.span.end > line_start);
+            if is_covered {
+                let indentation = line.len() - line.trim_start().len();
+                let start = if is_first_line {
+                    self.span.start - line_start
+                } else {
+                    indentation
+                };
+                let end = self
+                    .span
+                    .end
+                    .min(line_end)
+                    .saturating_sub(line_start)
+                    .max(start);
+
+                if is_first_line || end > start {
+                    write!(f, "\n{:width$} | ", "")?;
+                    for character in line.get(..start).unwrap_or(line).chars() {
+                        f.write_str(if character == '\t' { "\t" } else { " " })?;
+                    }
+                    let underline = line.get(start..end).map_or(0, |part| part.chars().count());
+                    for _ in 0..underline.max(1) {
+                        f.write_str("^")?;
+                    }
+                }
+            }
+
+            line_start = line_end + 1;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the number of decimal digits of a line number.
+fn digit_count(number: usize) -> usize {
+    number
+        .checked_ilog10()
+        .map_or(1, |digits| digits as usize + 1)
+}
+
+/// Handler for formatting [`SourceSnippet`] attachments.
+///
+/// The snippet is displayed inline, before the
+/// [`Location`](crate::hooks::builtin_hooks::location::Location) of the report
+/// and any other attachments with the default priority. When emitted as JSON,
+/// it is written as an object with the fields of the snippet.
+///
+/// # Examples
+///
+/// ```
+/// use rootcause::{
+///     prelude::*,
+///     source_snippet::{SourceSnippet, SourceSnippetHandler},
+/// };
+///
+/// let snippet = SourceSnippet::new("query.sql", "SELECT * FORM users", 9..13);
+/// let report = report!("syntax error").attach_custom::<SourceSnippetHandler, _>(snippet);
+/// ```
+#[derive(Copy, Clone)]
+pub struct SourceSnippetHandler;
+
+impl AttachmentHandler<SourceSnippet> for SourceSnippetHandler {
+    fn display(value: &SourceSnippet, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(value, f)
+    }
+
+    fn debug(value: &SourceSnippet, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(value, f)
+    }
+
+    fn json(value: &SourceSnippet, sink: &mut dyn JsonSink) -> fmt::Result {
+        sink.begin_object()?;
+        sink.key("filename")?;
+        sink.str(&value.filename)?;
+        sink.key("line")?;
+        sink.u64(value.line as u64)?;
+        sink.key("column")?;
+        sink.u64(value.column as u64)?;
+        sink.key("span")?;
+        sink.begin_array()?;
+        sink.u64(value.span.start as u64)?;
+        sink.u64(value.span.end as u64)?;
+        sink.end_array()?;
+        sink.key("text")?;
+        sink.str(&value.text)?;
+        sink.end_object()
+    }
+
+    fn preferred_formatting_style(
+        _value: &SourceSnippet,
+        _report_formatting_function: FormattingFunction,
+    ) -> AttachmentFormattingStyle {
+        AttachmentFormattingStyle {
+            placement: AttachmentFormattingPlacement::Inline,
+            function: FormattingFunction::Display,
+            priority: 30,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::handlers::JsonWriter;
+
+    #[test]
+    fn test_new_single_line() {
+        let source = "a = 1\nb = oops\nc = 3";
+        let snippet = SourceSnippet::new("test.txt", source, 10..14);
+        assert_eq!(snippet.line, 2);
+        assert_eq!(snippet.column, 5);
+        assert_eq!(snippet.span, 4..8);
+        assert_eq!(snippet.text, "b = oops");
+    }
+
+    #[test]
+    fn test_display_multi_line() {
+        let source = "fn main() {\n    foo(1,\n        2);\n}\n";
+        let snippet = SourceSnippet::new("main.rs", source, 16..33);
+        assert_eq!(
+            snippet.to_string(),
+            concat!(
+                " --> main.rs:2:5\n",
+                "  |\n",
+                "2 |     foo(1,\n",
+                "  |     ^^^^^^\n",
+                "3 |         2);\n",
+                "  |         ^^",
+            )
+        );
+    }
+
+    #[test]
+    fn test_display_empty_span() {
+        let snippet = SourceSnippet::new("main.rs", "foo(", 4..4);
+        assert_eq!(
+            snippet.to_string(),
+            " --> main.rs:1:5\n  |\n1 | foo(\n  |     ^"
+        );
+    }
+
+    #[test]
+    fn test_display_line_number_width() {
+        let snippet = SourceSnippet {
+            filename: "main.rs".into(),
+            line: 99,
+            column: 1,
+            span: 0..5,
+            text: "a\nb\ncd".into(),
+        };
+        assert_eq!(
+            snippet.to_string(),
+            concat!(
+                "   --> main.rs:99:1\n",
+                "    |\n",
+                " 99 | a\n",
+                "    | ^\n",
+                "100 | b\n",
+                "    | ^\n",
+                "101 | cd\n",
+                "    | ^",
+            )
+        );
+    }
+
+    #[test]
+    fn test_source_snippet_json() {
+        let snippet = SourceSnippet::new("a.txt", "x = \"y\"", 4..7);
+        let mut writer = JsonWriter::new(String::new());
+        SourceSnippetHandler::json(&snippet, &mut writer).unwrap();
+        assert_eq!(
+            writer.into_inner(),
+            r#"{"filename":"a.txt","line":1,"column":5,"span":[4,7],"text":"x = \"y\""}"#
+        );
+    }
+}
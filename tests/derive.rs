@@ -0,0 +1,104 @@
+//! Tests for `#[derive(RootcauseContext)]`.
+
+use std::{any::TypeId, rc::Rc};
+
+use rootcause::{
+    handlers::{self, RootcauseContext},
+    markers::Local,
+    prelude::*,
+};
+
+#[derive(Debug, thiserror::Error, RootcauseContext)]
+#[error("failed to read config")]
+struct ConfigError {
+    #[source]
+    source: std::io::Error,
+}
+
+#[derive(Debug, RootcauseContext)]
+#[rootcause(display = "user {user_id} not found in {table}")]
+struct UserNotFound {
+    user_id: u64,
+    table: &'static str,
+}
+
+#[derive(Debug, RootcauseContext)]
+#[rootcause(display = "service unavailable")]
+struct Unavailable;
+
+#[derive(Debug, RootcauseContext)]
+#[rootcause(handler = handlers::Debug)]
+struct Opaque {
+    retries: Rc<u32>,
+}
+
+#[derive(Debug, RootcauseContext)]
+#[rootcause(display = "invalid value {value:?}")]
+struct InvalidValue<T: std::fmt::Debug> {
+    value: T,
+}
+
+#[test]
+fn test_default_handler_is_error() {
+    let report = report!(ConfigError {
+        source: std::io::Error::other("disk full"),
+    });
+    assert_eq!(
+        report.current_context_handler_type_id(),
+        TypeId::of::<handlers::Error>()
+    );
+    assert_eq!(
+        report.format_current_context().to_string(),
+        "failed to read config"
+    );
+}
+
+#[test]
+fn test_display_attribute() {
+    let report = report!(UserNotFound {
+        user_id: 42,
+        table: "users",
+    });
+    assert_eq!(
+        report.format_current_context().to_string(),
+        "user 42 not found in users"
+    );
+
+    let report = report!(Unavailable);
+    assert_eq!(
+        report.format_current_context().to_string(),
+        "service unavailable"
+    );
+
+    let report = report!(InvalidValue { value: "abc" });
+    assert_eq!(
+        report.format_current_context().to_string(),
+        "invalid value \"abc\""
+    );
+}
+
+#[test]
+fn test_handler_attribute() {
+    let report: Report<Opaque, _, Local> = report!(Opaque {
+        retries: Rc::new(3),
+    });
+    assert_eq!(
+        report.current_context_handler_type_id(),
+        TypeId::of::<handlers::Debug>()
+    );
+    assert_eq!(
+        report.format_current_context().to_string(),
+        format!("Context of type `{}`", core::any::type_name::<Opaque>())
+    );
+    assert_eq!(*report.current_context().retries, 3);
+}
+
+#[test]
+fn test_source_argument() {
+    let report = report!(Unavailable, source: std::io::Error::other("connection refused"));
+    assert_eq!(
+        report.format_current_context().to_string(),
+        "service unavailable"
+    );
+    assert_eq!(report.children().len(), 1);
+}
@@ -0,0 +1,13 @@
+//! Compile-fail tests checking the compiler diagnostics of rootcause.
+//!
+//! The exact compiler output changes between Rust versions, so these tests
+//! are ignored by default. They are run on the stable toolchain in CI with
+//! `cargo test --test ui -- --ignored`. Use `TRYBUILD=overwrite` to update the
+//! expected output after a toolchain upgrade.
+
+#[test]
+#[ignore = "compiler diagnostics differ between Rust versions"]
+fn ui() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,10 @@
+use rootcause::prelude::*;
+
+fn check() -> Result<(), Report> {
+    ensure!();
+    Ok(())
+}
+
+fn main() {
+    let _ = check();
+}
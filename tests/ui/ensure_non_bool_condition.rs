@@ -0,0 +1,10 @@
+use rootcause::prelude::*;
+
+fn check(retries: u32) -> Result<(), Report> {
+    ensure!(retries, "too many retries");
+    Ok(())
+}
+
+fn main() {
+    let _ = check(3);
+}
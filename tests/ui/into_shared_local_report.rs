@@ -0,0 +1,8 @@
+use std::rc::Rc;
+
+use rootcause::prelude::*;
+
+fn main() {
+    let report = report!(Rc::new("not thread-safe"));
+    let _shared = report.into_shared();
+}
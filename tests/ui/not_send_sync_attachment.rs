@@ -0,0 +1,7 @@
+use std::rc::Rc;
+
+use rootcause::prelude::*;
+
+fn main() {
+    let _report: Report = report!("request failed").attach(Rc::new("shared"));
+}